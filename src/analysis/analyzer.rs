@@ -1,24 +1,53 @@
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::growth::{growth_iter, species_growth};
 use crate::analysis::{
-    compute_stand_metrics, project_growth, DiameterDistribution, GrowthModel, GrowthProjection,
-    SamplingStatistics, StandMetrics,
+    compute_stand_metrics_with, harvest_summary, plot_qa_with, DiameterDistribution,
+    DistributionOptions, GrowthModel, GrowthProjection, HarvestSummary, PlotQaFlag,
+    RecruitmentModel, SamplingStatistics, SpeciesGrowthProjection, StandMetrics,
 };
 use crate::error::ForestError;
-use crate::models::ForestInventory;
+use crate::models::{ForestInventory, VolumeEquation};
 
 /// Unified analysis API that groups all analysis operations on an inventory.
 pub struct Analyzer<'a> {
     inventory: &'a ForestInventory,
+    volume_equation: VolumeEquation,
+    recruitment: Option<RecruitmentModel>,
 }
 
 impl<'a> Analyzer<'a> {
-    /// Create a new Analyzer for the given inventory.
+    /// Create a new Analyzer for the given inventory, using the default volume equation.
     pub fn new(inventory: &'a ForestInventory) -> Self {
-        Self { inventory }
+        Self {
+            inventory,
+            volume_equation: VolumeEquation::default(),
+            recruitment: None,
+        }
+    }
+
+    /// Use custom volume equation coefficients for stand metrics and growth projections.
+    pub fn with_volume_equation(mut self, volume_equation: VolumeEquation) -> Self {
+        self.volume_equation = volume_equation;
+        self
+    }
+
+    /// Add ingrowth to growth projections.
+    pub fn with_recruitment(mut self, recruitment: RecruitmentModel) -> Self {
+        self.recruitment = Some(recruitment);
+        self
+    }
+
+    /// Wrap this analyzer in a [`CachedAnalyzer`] that memoizes its results.
+    pub fn cached(self) -> CachedAnalyzer<'a> {
+        CachedAnalyzer::new(self)
     }
 
     /// Compute stand-level metrics (TPA, BA, volume, QMD, species composition).
     pub fn stand_metrics(&self) -> StandMetrics {
-        compute_stand_metrics(self.inventory)
+        compute_stand_metrics_with(self.inventory, &self.volume_equation)
     }
 
     /// Compute sampling statistics at the given confidence level (e.g. 0.95).
@@ -31,19 +60,128 @@ impl<'a> Analyzer<'a> {
         DiameterDistribution::from_inventory(self.inventory, class_width)
     }
 
-    /// Project stand growth over the given number of years using the specified model.
+    /// Build a diameter distribution for a chosen tree status and minimum DBH.
+    pub fn diameter_distribution_with(
+        &self,
+        options: &DistributionOptions,
+    ) -> Result<DiameterDistribution, ForestError> {
+        DiameterDistribution::with_options(self.inventory, options)
+    }
+
+    /// Flag problem plots, treating basal area beyond `k` standard deviations as an outlier.
+    pub fn plot_qa(&self, k: f64) -> Vec<PlotQaFlag> {
+        plot_qa_with(self.inventory, k)
+    }
+
+    /// Summarize per-acre removals of trees tagged as cut.
+    pub fn harvest_summary(&self) -> HarvestSummary {
+        harvest_summary(self.inventory)
+    }
+
+    /// Project stand growth over the given number of years using the specified model,
+    /// plus any recruitment set with [`Analyzer::with_recruitment`].
     pub fn project_growth(
         &self,
         model: &GrowthModel,
         years: u32,
     ) -> Result<Vec<GrowthProjection>, ForestError> {
-        project_growth(self.inventory, model, years)
+        Ok(growth_iter(
+            self.inventory,
+            model,
+            self.recruitment.as_ref(),
+            &[],
+            years,
+            &self.volume_equation,
+        )?
+        .collect())
+    }
+
+    /// Project growth per species, applying the model to each species independently.
+    pub fn project_growth_by_species(
+        &self,
+        model: &GrowthModel,
+        years: u32,
+    ) -> Result<Vec<SpeciesGrowthProjection>, ForestError> {
+        species_growth(
+            self.inventory,
+            model,
+            &HashMap::new(),
+            years,
+            &self.volume_equation,
+        )
+    }
+}
+
+/// An [`Analyzer`] that memoizes stand metrics, diameter distributions (per
+/// class width) and sampling statistics (per confidence level).
+///
+/// Opt-in for callers that ask for the same results repeatedly, e.g. once per
+/// growth year or per web request: only the first call with given parameters
+/// computes anything, and later calls return the cached value. The inventory
+/// is borrowed immutably, so the cache never needs invalidating. Errors are
+/// not cached. Other analyses are available uncached through
+/// [`CachedAnalyzer::analyzer`].
+pub struct CachedAnalyzer<'a> {
+    analyzer: Analyzer<'a>,
+    stand_metrics: OnceCell<StandMetrics>,
+    /// Keyed by the bits of the class width
+    distributions: RefCell<HashMap<u64, Rc<DiameterDistribution>>>,
+    /// Keyed by the bits of the confidence level
+    statistics: RefCell<HashMap<u64, Rc<SamplingStatistics>>>,
+}
+
+impl<'a> CachedAnalyzer<'a> {
+    /// Cache the results of `analyzer`, keeping its volume equation.
+    pub fn new(analyzer: Analyzer<'a>) -> Self {
+        Self {
+            analyzer,
+            stand_metrics: OnceCell::new(),
+            distributions: RefCell::new(HashMap::new()),
+            statistics: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// The wrapped analyzer, for analyses that are not cached.
+    pub fn analyzer(&self) -> &Analyzer<'a> {
+        &self.analyzer
+    }
+
+    /// Stand-level metrics, computed on the first call.
+    pub fn stand_metrics(&self) -> &StandMetrics {
+        self.stand_metrics
+            .get_or_init(|| self.analyzer.stand_metrics())
+    }
+
+    /// Diameter distribution with the given class width in inches, computed
+    /// on the first call with that width.
+    pub fn diameter_distribution(&self, class_width: f64) -> Rc<DiameterDistribution> {
+        self.distributions
+            .borrow_mut()
+            .entry(class_width.to_bits())
+            .or_insert_with(|| Rc::new(self.analyzer.diameter_distribution(class_width)))
+            .clone()
+    }
+
+    /// Sampling statistics at the given confidence level, computed on the
+    /// first successful call with that level.
+    pub fn sampling_statistics(
+        &self,
+        confidence: f64,
+    ) -> Result<Rc<SamplingStatistics>, ForestError> {
+        let key = confidence.to_bits();
+        if let Some(stats) = self.statistics.borrow().get(&key) {
+            return Ok(Rc::clone(stats));
+        }
+        let stats = Rc::new(self.analyzer.sampling_statistics(confidence)?);
+        self.statistics.borrow_mut().insert(key, Rc::clone(&stats));
+        Ok(stats)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::{compute_stand_metrics, project_growth};
     use crate::models::{Plot, Species, Tree, TreeStatus};
 
     fn make_tree(plot_id: u32, dbh: f64) -> Tree {
@@ -61,6 +199,7 @@ mod tests {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -73,6 +212,11 @@ mod tests {
             elevation_ft: None,
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
@@ -119,6 +263,21 @@ mod tests {
         assert_eq!(from_analyzer.class_width, from_standalone.class_width);
     }
 
+    #[test]
+    fn test_diameter_distribution_with_options() {
+        let mut inv = sample_inventory();
+        inv.plots[0].trees[0].status = TreeStatus::Dead;
+        let analyzer = Analyzer::new(&inv);
+        let options = DistributionOptions {
+            status_filter: TreeStatus::Dead,
+            ..Default::default()
+        };
+        let dist = analyzer.diameter_distribution_with(&options).unwrap();
+        assert_eq!(dist.classes.len(), 1);
+        assert_eq!(dist.classes[0].lower, 14.0);
+        assert!((dist.classes[0].tpa - 2.5).abs() < 0.001);
+    }
+
     #[test]
     fn test_project_growth_matches_standalone() {
         let inv = sample_inventory();
@@ -133,6 +292,55 @@ mod tests {
         assert!((from_analyzer[10].basal_area - from_standalone[10].basal_area).abs() < 0.001);
     }
 
+    #[test]
+    fn test_volume_equation_applies_to_metrics_and_growth() {
+        let inv = sample_inventory();
+        let eq = VolumeEquation {
+            cuft_b1: VolumeEquation::default().cuft_b1 * 2.0,
+            ..VolumeEquation::default()
+        };
+        let default = Analyzer::new(&inv);
+        let custom = Analyzer::new(&inv).with_volume_equation(eq);
+
+        let default_cuft = default.stand_metrics().total_volume_cuft;
+        assert!((custom.stand_metrics().total_volume_cuft - 2.0 * default_cuft).abs() < 1e-6);
+        assert!(
+            (custom.stand_metrics().total_volume_bdft - default.stand_metrics().total_volume_bdft)
+                .abs()
+                < 1e-9
+        );
+
+        let model = GrowthModel::Linear {
+            annual_increment: 1.0,
+            mortality_rate: 0.0,
+        };
+        let projection = custom.project_growth(&model, 1).unwrap();
+        assert!((projection[0].volume_cuft - 2.0 * default_cuft).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_volume_equation_applies_to_growth_by_species() {
+        let inv = sample_inventory();
+        let eq = VolumeEquation {
+            cuft_b1: VolumeEquation::default().cuft_b1 * 2.0,
+            ..VolumeEquation::default()
+        };
+        let model = GrowthModel::Linear {
+            annual_increment: 1.0,
+            mortality_rate: 0.0,
+        };
+        let default = Analyzer::new(&inv)
+            .project_growth_by_species(&model, 1)
+            .unwrap();
+        let custom = Analyzer::new(&inv)
+            .with_volume_equation(eq)
+            .project_growth_by_species(&model, 1)
+            .unwrap();
+        let default_cuft = default[0].total.volume_cuft;
+        assert!(default_cuft > 0.0);
+        assert!((custom[0].total.volume_cuft - 2.0 * default_cuft).abs() < 1e-6);
+    }
+
     #[test]
     fn test_analyzer_empty_inventory() {
         let inv = ForestInventory::new("Empty");
@@ -141,4 +349,27 @@ mod tests {
         assert_eq!(metrics.total_tpa, 0.0);
         assert!(analyzer.sampling_statistics(0.95).is_err());
     }
+
+    #[test]
+    fn test_cached_analyzer_returns_cached_results() {
+        let inv = sample_inventory();
+        let cached = Analyzer::new(&inv).cached();
+
+        let first = cached.stand_metrics();
+        let second = cached.stand_metrics();
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(first.total_tpa, compute_stand_metrics(&inv).total_tpa);
+
+        let dist = cached.diameter_distribution(2.0);
+        assert!(Rc::ptr_eq(&dist, &cached.diameter_distribution(2.0)));
+        assert!(!Rc::ptr_eq(&dist, &cached.diameter_distribution(4.0)));
+        assert_eq!(dist.class_width, 2.0);
+
+        let stats = cached.sampling_statistics(0.95).unwrap();
+        let again = cached.sampling_statistics(0.95).unwrap();
+        assert!(Rc::ptr_eq(&stats, &again));
+        let stats_90 = cached.sampling_statistics(0.90).unwrap();
+        assert!(stats_90.basal_area.upper < stats.basal_area.upper);
+        assert!(cached.sampling_statistics(1.5).is_err());
+    }
 }
@@ -1,6 +1,80 @@
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
+use statrs::function::gamma::gamma;
+
+use super::statistics::{ci_from_t, t_critical, ConfidenceInterval};
+use crate::error::ForestError;
+use crate::models::{ForestInventory, Tree, TreeStatus};
+
+/// A validated diameter class width in inches.
+///
+/// Must be finite and at least [`DiameterClassWidth::MIN`]; narrower classes
+/// are below DBH measurement precision and would generate an unbounded number
+/// of classes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct DiameterClassWidth(f64);
+
+impl DiameterClassWidth {
+    /// Narrowest accepted class width, in inches.
+    pub const MIN: f64 = 0.1;
+
+    /// Validate a class width in inches.
+    pub fn new(width: f64) -> Result<Self, ForestError> {
+        if width.is_finite() && width >= Self::MIN {
+            Ok(Self(width))
+        } else {
+            Err(ForestError::ValidationError(format!(
+                "diameter class width must be at least {} inches, got {width}",
+                Self::MIN
+            )))
+        }
+    }
+
+    /// The width in inches.
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl Default for DiameterClassWidth {
+    fn default() -> Self {
+        Self(2.0)
+    }
+}
+
+impl TryFrom<f64> for DiameterClassWidth {
+    type Error = ForestError;
+
+    fn try_from(width: f64) -> Result<Self, Self::Error> {
+        Self::new(width)
+    }
+}
+
+impl From<DiameterClassWidth> for f64 {
+    fn from(width: DiameterClassWidth) -> Self {
+        width.0
+    }
+}
+
+impl FromStr for DiameterClassWidth {
+    type Err = ForestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let width: f64 = s.trim().parse().map_err(|_| {
+            ForestError::ParseError(format!("'{s}' is not a valid diameter class width"))
+        })?;
+        Self::new(width)
+    }
+}
 
-use crate::models::ForestInventory;
+impl fmt::Display for DiameterClassWidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 /// A single diameter class in the distribution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,102 +91,292 @@ pub struct DiameterClass {
     pub basal_area: f64,
     /// Number of measured trees in this class
     pub tree_count: usize,
+    /// Confidence interval of the class TPA across plots; only set by
+    /// [`DiameterDistribution::with_confidence`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tpa_ci: Option<ConfidenceInterval>,
+}
+
+/// Which trees a diameter distribution covers.
+///
+/// The default matches [`DiameterDistribution::from_inventory`]: live trees in
+/// 2-inch classes starting at the smallest tree. Each class always carries both
+/// TPA and basal area, so the choice of metric is left to the renderer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionOptions {
+    /// Width of each diameter class
+    pub class_width: DiameterClassWidth,
+    /// Smallest DBH in inches to include; the first class starts here.
+    /// `None` includes every tree and aligns classes to multiples of the width.
+    pub min_dbh: Option<f64>,
+    /// Status of the trees to include
+    pub status_filter: TreeStatus,
+    /// Add a leading "ingrowth" class from 0 to `min_dbh` holding the trees
+    /// below the threshold, which are otherwise left out. Trees exactly at
+    /// the threshold stay in the first full class. Has no effect without a
+    /// `min_dbh`.
+    pub include_ingrowth_class: bool,
+}
+
+impl Default for DistributionOptions {
+    fn default() -> Self {
+        Self {
+            class_width: DiameterClassWidth::default(),
+            min_dbh: None,
+            status_filter: TreeStatus::Live,
+            include_ingrowth_class: false,
+        }
+    }
 }
 
 /// Diameter distribution for the stand.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiameterDistribution {
-    /// Width of each diameter class in inches
+    /// Width of each diameter class in inches, or 0 when the classes come
+    /// from explicit breaks ([`DiameterDistribution::from_breaks`])
     pub class_width: f64,
     /// The diameter classes
     pub classes: Vec<DiameterClass>,
 }
 
+/// A Weibull distribution of DBH fitted by [`DiameterDistribution::fit_weibull`].
+///
+/// The density is `c/b · ((x − a)/b)^(c−1) · exp(−((x − a)/b)^c)` for
+/// `x > a`, where `a` is the location, `b` the scale and `c` the shape.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeibullParams {
+    /// Location `a` in inches, the DBH below which the density is zero
+    pub location: f64,
+    /// Scale `b` in inches
+    pub scale: f64,
+    /// Shape `c`: below 1 a reverse-J curve, near 3.6 symmetric
+    pub shape: f64,
+}
+
+impl WeibullParams {
+    /// Probability density at `dbh` (inches), per inch of DBH.
+    ///
+    /// Multiply by the stand's TPA and a class width to get the fitted TPA
+    /// of a class.
+    pub fn pdf(&self, dbh: f64) -> f64 {
+        let x = (dbh - self.location) / self.scale;
+        if x <= 0.0 {
+            return 0.0;
+        }
+        self.shape / self.scale * x.powf(self.shape - 1.0) * (-x.powf(self.shape)).exp()
+    }
+}
+
 impl DiameterDistribution {
     /// Build a diameter distribution from the inventory.
     ///
     /// # Arguments
     /// * `inventory` - The forest inventory data
     /// * `class_width` - Width of each diameter class in inches (commonly 2)
+    ///
+    /// A width that is not a valid [`DiameterClassWidth`] (non-positive,
+    /// non-finite or below the minimum) yields an empty distribution; use
+    /// [`DiameterDistribution::try_from_inventory`] to get an error instead.
     pub fn from_inventory(inventory: &ForestInventory, class_width: f64) -> Self {
-        if class_width <= 0.0 || !class_width.is_finite() {
-            return DiameterDistribution {
+        match DiameterClassWidth::new(class_width) {
+            Ok(width) => Self::with_width(inventory, width),
+            Err(_) => DiameterDistribution {
                 class_width,
                 classes: Vec::new(),
-            };
+            },
         }
+    }
 
-        let num_plots = inventory.num_plots() as f64;
-        if num_plots == 0.0 {
-            return DiameterDistribution {
-                class_width,
-                classes: Vec::new(),
-            };
+    /// Build a diameter distribution, rejecting an invalid class width with
+    /// `ForestError::ValidationError`.
+    pub fn try_from_inventory(
+        inventory: &ForestInventory,
+        class_width: f64,
+    ) -> Result<Self, ForestError> {
+        Ok(Self::with_width(inventory, DiameterClassWidth::new(class_width)?))
+    }
+
+    /// Build a diameter distribution with an already-validated class width.
+    pub fn with_width(inventory: &ForestInventory, class_width: DiameterClassWidth) -> Self {
+        Self::build(inventory, class_width.get(), None, &TreeStatus::Live, false)
+    }
+
+    /// Build a live-tree distribution whose classes each carry a confidence
+    /// interval of their TPA, from the class TPA on every plot.
+    ///
+    /// Plots without trees in a class count as zero, so classes found on few
+    /// plots get wide intervals. The interval means equal the class TPA.
+    /// Returns `ForestError::AnalysisError` if `confidence` is not strictly
+    /// between 0 and 1, or `ForestError::InsufficientData` with fewer than 2
+    /// plots.
+    pub fn with_confidence(
+        inventory: &ForestInventory,
+        class_width: DiameterClassWidth,
+        confidence: f64,
+    ) -> Result<Self, ForestError> {
+        let n = inventory.num_plots();
+        if n < 2 {
+            return Err(ForestError::InsufficientData(format!(
+                "class confidence intervals need at least 2 plots, got {n}"
+            )));
+        }
+        let t_value = t_critical((n - 1) as f64, confidence)?;
+        let mut dist = Self::with_width(inventory, class_width);
+        let inventory = &*inventory.to_per_acre();
+        for class in &mut dist.classes {
+            let plot_tpa: Vec<f64> = inventory
+                .plots
+                .iter()
+                .map(|plot| {
+                    plot.live_trees()
+                        .into_iter()
+                        .filter(|t| t.dbh >= class.lower && t.dbh < class.upper)
+                        .map(|t| t.expansion_factor)
+                        .sum()
+                })
+                .collect();
+            class.tpa_ci = Some(ci_from_t(&plot_tpa, confidence, t_value)?);
+        }
+        Ok(dist)
+    }
+
+    /// Build a diameter distribution for the trees selected by `options`.
+    ///
+    /// Returns `ForestError::ValidationError` if `options.min_dbh` is negative
+    /// or not finite.
+    pub fn with_options(
+        inventory: &ForestInventory,
+        options: &DistributionOptions,
+    ) -> Result<Self, ForestError> {
+        if let Some(min_dbh) = options.min_dbh {
+            if !min_dbh.is_finite() || min_dbh < 0.0 {
+                return Err(ForestError::ValidationError(format!(
+                    "min_dbh must be a non-negative number of inches, got {min_dbh}"
+                )));
+            }
+        }
+        Ok(Self::build(
+            inventory,
+            options.class_width.get(),
+            options.min_dbh,
+            &options.status_filter,
+            options.include_ingrowth_class,
+        ))
+    }
+
+    /// Build a distribution of live trees over non-uniform classes, e.g.
+    /// product breaks `[1.0, 5.0, 11.0, 21.0]` for the classes 1–5", 5–11" and
+    /// 11–21".
+    ///
+    /// Every class is returned, including empty ones. Trees below the first
+    /// break are left out; if any tree is at or above the last break, the last
+    /// class is extended to the next whole inch above the largest tree.
+    /// Returns `ForestError::ValidationError` unless there are at least two
+    /// breaks, all finite, non-negative and strictly ascending.
+    pub fn from_breaks(inventory: &ForestInventory, breaks: &[f64]) -> Result<Self, ForestError> {
+        if breaks.len() < 2 {
+            return Err(ForestError::ValidationError(format!(
+                "diameter class breaks need at least 2 values, got {}",
+                breaks.len()
+            )));
+        }
+        if breaks.iter().any(|b| !b.is_finite() || *b < 0.0)
+            || breaks.windows(2).any(|w| w[0] >= w[1])
+        {
+            return Err(ForestError::ValidationError(format!(
+                "diameter class breaks must be non-negative and strictly ascending, got {breaks:?}"
+            )));
         }
 
-        // Find DBH range
-        let all_live_dbh: Vec<(f64, f64)> = inventory
+        let inventory = &*inventory.to_per_acre();
+        let num_plots = inventory.num_plots() as f64;
+        let trees: Vec<&Tree> = inventory
             .plots
             .iter()
-            .flat_map(|p| {
-                p.live_trees()
-                    .into_iter()
-                    .map(|t| (t.dbh, t.expansion_factor))
-            })
+            .flat_map(|p| p.live_trees())
             .collect();
+        let mut bounds = breaks.to_vec();
+        let largest = trees
+            .iter()
+            .map(|t| t.dbh)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let last = bounds.last_mut().expect("at least two breaks");
+        if largest >= *last {
+            *last = largest.floor() + 1.0;
+        }
+
+        let classes = if num_plots == 0.0 {
+            Vec::new()
+        } else {
+            bounds
+                .windows(2)
+                .map(|w| {
+                    tally_class(&trees, w[0], w[1], num_plots).unwrap_or(DiameterClass {
+                        lower: w[0],
+                        upper: w[1],
+                        midpoint: (w[0] + w[1]) / 2.0,
+                        tpa: 0.0,
+                        basal_area: 0.0,
+                        tree_count: 0,
+                        tpa_ci: None,
+                    })
+                })
+                .collect()
+        };
 
-        if all_live_dbh.is_empty() {
+        Ok(DiameterDistribution {
+            class_width: 0.0,
+            classes,
+        })
+    }
+
+    fn build(
+        inventory: &ForestInventory,
+        class_width: f64,
+        min_dbh: Option<f64>,
+        status: &TreeStatus,
+        include_ingrowth: bool,
+    ) -> Self {
+        let inventory = &*inventory.to_per_acre();
+        let num_plots = inventory.num_plots() as f64;
+        let threshold = min_dbh.unwrap_or(f64::NEG_INFINITY);
+        let (trees, below): (Vec<&Tree>, Vec<&Tree>) = inventory
+            .plots
+            .iter()
+            .flat_map(|p| p.trees_with_status(status))
+            .partition(|t| t.dbh >= threshold);
+
+        let mut classes = Vec::new();
+        if num_plots == 0.0 {
             return DiameterDistribution {
                 class_width,
-                classes: Vec::new(),
+                classes,
             };
         }
 
-        let min_dbh = all_live_dbh
-            .iter()
-            .map(|(d, _)| *d)
-            .fold(f64::INFINITY, f64::min);
-        let max_dbh = all_live_dbh
-            .iter()
-            .map(|(d, _)| *d)
-            .fold(f64::NEG_INFINITY, f64::max);
+        if let (Some(min_dbh), true) = (min_dbh, include_ingrowth) {
+            classes.extend(tally_class(&below, 0.0, min_dbh, num_plots));
+        }
 
-        // Build classes starting from the lower bound
-        let start = (min_dbh / class_width).floor() * class_width;
-        let end = ((max_dbh / class_width).floor() + 1.0) * class_width;
+        if !trees.is_empty() {
+            // Find DBH range
+            let smallest = trees.iter().map(|t| t.dbh).fold(f64::INFINITY, f64::min);
+            let largest = trees
+                .iter()
+                .map(|t| t.dbh)
+                .fold(f64::NEG_INFINITY, f64::max);
 
-        let mut classes = Vec::new();
-        let mut lower = start;
-        while lower < end {
-            let upper = lower + class_width;
-            let midpoint = lower + class_width / 2.0;
-
-            let mut tpa_sum = 0.0;
-            let mut ba_sum = 0.0;
-            let mut count = 0usize;
-
-            for plot in &inventory.plots {
-                for tree in plot.live_trees() {
-                    if tree.dbh >= lower && tree.dbh < upper {
-                        tpa_sum += tree.expansion_factor;
-                        ba_sum += tree.basal_area_per_acre();
-                        count += 1;
-                    }
-                }
-            }
+            // Build classes starting from the threshold, or from the class
+            // boundary below the smallest tree
+            let start = min_dbh.unwrap_or((smallest / class_width).floor() * class_width);
+            let end = start + (((largest - start) / class_width).floor() + 1.0) * class_width;
 
-            if count > 0 {
-                classes.push(DiameterClass {
-                    lower,
-                    upper,
-                    midpoint,
-                    tpa: tpa_sum / num_plots,
-                    basal_area: ba_sum / num_plots,
-                    tree_count: count,
-                });
+            let mut lower = start;
+            while lower < end {
+                let upper = lower + class_width;
+                classes.extend(tally_class(&trees, lower, upper, num_plots));
+                lower = upper;
             }
-
-            lower = upper;
         }
 
         DiameterDistribution {
@@ -120,6 +384,140 @@ impl DiameterDistribution {
             classes,
         }
     }
+
+    /// Cumulative (ogive) distribution as `(upper bound, cumulative TPA, cumulative BA)`.
+    ///
+    /// Each entry accumulates every class up to and including the one ending at
+    /// `upper`, so the last entry equals the stand's live TPA and basal area.
+    pub fn cumulative(&self) -> Vec<(f64, f64, f64)> {
+        self.classes
+            .iter()
+            .scan((0.0, 0.0), |(tpa, ba), class| {
+                *tpa += class.tpa;
+                *ba += class.basal_area;
+                Some((class.upper, *tpa, *ba))
+            })
+            .collect()
+    }
+
+    /// Fit a three-parameter Weibull distribution to the classes by the
+    /// method of moments.
+    ///
+    /// Each class counts as its TPA at its midpoint. The shape is solved from
+    /// the skewness, then the scale from the variance and the location from
+    /// the mean. A fit whose location would be a negative DBH is replaced by
+    /// a two-parameter fit (location 0) to the mean and coefficient of
+    /// variation. Returns `None` with fewer than three classes holding trees,
+    /// or when the skewness is beyond what a Weibull can take.
+    pub fn fit_weibull(&self) -> Option<WeibullParams> {
+        let classes: Vec<&DiameterClass> = self.classes.iter().filter(|c| c.tpa > 0.0).collect();
+        if classes.len() < 3 {
+            return None;
+        }
+
+        let total: f64 = classes.iter().map(|c| c.tpa).sum();
+        let mean = classes.iter().map(|c| c.tpa * c.midpoint).sum::<f64>() / total;
+        let central_moment = |k: i32| {
+            classes
+                .iter()
+                .map(|c| c.tpa * (c.midpoint - mean).powi(k))
+                .sum::<f64>()
+                / total
+        };
+        let variance = central_moment(2);
+        if variance <= 0.0 {
+            return None;
+        }
+        let skewness = central_moment(3) / variance.powf(1.5);
+
+        let shape = solve_weibull_shape(weibull_skewness, skewness)?;
+        let (g1, g2) = weibull_gammas(shape);
+        let scale = (variance / (g2 - g1 * g1)).sqrt();
+        let location = mean - scale * g1;
+        if location >= 0.0 {
+            return Some(WeibullParams {
+                location,
+                scale,
+                shape,
+            });
+        }
+
+        let cv_squared = variance / (mean * mean);
+        let shape = solve_weibull_shape(weibull_cv_squared, cv_squared)?;
+        let (g1, _) = weibull_gammas(shape);
+        Some(WeibullParams {
+            location: 0.0,
+            scale: mean / g1,
+            shape,
+        })
+    }
+}
+
+/// Range of Weibull shapes searched by [`solve_weibull_shape`].
+const WEIBULL_SHAPE_RANGE: (f64, f64) = (0.1, 100.0);
+
+/// Γ(1 + 1/c) and Γ(1 + 2/c) for shape `c`.
+fn weibull_gammas(shape: f64) -> (f64, f64) {
+    (gamma(1.0 + 1.0 / shape), gamma(1.0 + 2.0 / shape))
+}
+
+/// Skewness of a Weibull distribution with the given shape.
+fn weibull_skewness(shape: f64) -> f64 {
+    let (g1, g2) = weibull_gammas(shape);
+    let g3 = gamma(1.0 + 3.0 / shape);
+    (g3 - 3.0 * g1 * g2 + 2.0 * g1.powi(3)) / (g2 - g1 * g1).powf(1.5)
+}
+
+/// Squared coefficient of variation of a two-parameter Weibull distribution
+/// with the given shape.
+fn weibull_cv_squared(shape: f64) -> f64 {
+    let (g1, g2) = weibull_gammas(shape);
+    g2 / (g1 * g1) - 1.0
+}
+
+/// The shape at which `moment`, which decreases with the shape, equals
+/// `target`, by bisection on the log of the shape; `None` if `target` is
+/// outside the range [`WEIBULL_SHAPE_RANGE`] covers.
+fn solve_weibull_shape(moment: fn(f64) -> f64, target: f64) -> Option<f64> {
+    let (mut lo, mut hi) = (WEIBULL_SHAPE_RANGE.0.ln(), WEIBULL_SHAPE_RANGE.1.ln());
+    if !(moment(hi.exp())..=moment(lo.exp())).contains(&target) {
+        return None;
+    }
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.0;
+        if moment(mid.exp()) > target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(((lo + hi) / 2.0).exp())
+}
+
+/// Per-acre totals of the `trees` with `lower <= dbh < upper`, or `None` if
+/// there are none.
+fn tally_class(trees: &[&Tree], lower: f64, upper: f64, num_plots: f64) -> Option<DiameterClass> {
+    let mut tpa_sum = 0.0;
+    let mut ba_sum = 0.0;
+    let mut count = 0usize;
+
+    for tree in trees {
+        if tree.dbh >= lower && tree.dbh < upper {
+            tpa_sum += tree.expansion_factor;
+            ba_sum += tree.basal_area_per_acre();
+            count += 1;
+        }
+    }
+
+    (count > 0).then(|| DiameterClass {
+        lower,
+        upper,
+        midpoint: (lower + upper) / 2.0,
+        tpa: tpa_sum / num_plots,
+        basal_area: ba_sum / num_plots,
+        tree_count: count,
+        tpa_ci: None,
+    })
 }
 
 #[cfg(test)]
@@ -142,6 +540,7 @@ mod tests {
             expansion_factor: ef,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -160,6 +559,7 @@ mod tests {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -172,9 +572,72 @@ mod tests {
             elevation_ft: None,
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
+    /// DBHs at evenly spaced quantiles of a Weibull with location 0, so the
+    /// sample follows the distribution without random noise.
+    fn weibull_sample(scale: f64, shape: f64, n: usize) -> Vec<Tree> {
+        (0..n)
+            .map(|i| {
+                let u = (i as f64 + 0.5) / n as f64;
+                make_tree(1, scale * (-(1.0 - u).ln()).powf(1.0 / shape), 5.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_weibull_recovers_shape() {
+        let mut inv = ForestInventory::new("Weibull");
+        let sample = weibull_sample(12.0, 2.5, 2000);
+        inv.plots.push(make_plot(1, sample));
+        let dist = DiameterDistribution::from_inventory(&inv, 1.0);
+        let fit = dist.fit_weibull().unwrap();
+        assert!((fit.shape - 2.5).abs() / 2.5 < 0.1, "{fit:?}");
+
+        // The fitted density integrates to about one over the sampled range
+        let area: f64 = (0..400).map(|i| fit.pdf(i as f64 * 0.1 + 0.05) * 0.1).sum();
+        assert!((area - 1.0).abs() < 0.02, "{area}");
+        assert_eq!(fit.pdf(fit.location - 1.0), 0.0);
+
+        // A sample shifted away from zero gets a location
+        let mut shifted = weibull_sample(12.0, 2.5, 2000);
+        shifted.iter_mut().for_each(|t| t.dbh += 4.0);
+        let mut inv = ForestInventory::new("Shifted");
+        inv.plots.push(make_plot(1, shifted));
+        let shifted_fit = DiameterDistribution::from_inventory(&inv, 1.0)
+            .fit_weibull()
+            .unwrap();
+        assert!(
+            (shifted_fit.shape - 2.5).abs() / 2.5 < 0.1,
+            "{shifted_fit:?}"
+        );
+        assert!((shifted_fit.location - 4.0).abs() < 1.0, "{shifted_fit:?}");
+
+        let json = serde_json::to_string(&fit).unwrap();
+        let back: WeibullParams = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, fit);
+    }
+
+    #[test]
+    fn test_fit_weibull_needs_three_classes() {
+        let mut inv = ForestInventory::new("Two");
+        inv.plots.push(make_plot(
+            1,
+            vec![make_tree(1, 8.0, 5.0), make_tree(1, 11.0, 5.0)],
+        ));
+        let dist = DiameterDistribution::from_inventory(&inv, 2.0);
+        assert_eq!(dist.classes.len(), 2);
+        assert!(dist.fit_weibull().is_none());
+        let empty = DiameterDistribution::from_inventory(&ForestInventory::new("Empty"), 2.0);
+        assert!(empty.fit_weibull().is_none());
+    }
+
     #[test]
     fn test_empty_inventory() {
         let inv = ForestInventory::new("Empty");
@@ -314,6 +777,41 @@ mod tests {
         assert_eq!(deserialized.class_width, dist.class_width);
     }
 
+    #[test]
+    fn test_cumulative_ends_at_stand_total() {
+        let mut inv = ForestInventory::new("Ogive");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, 4.0, 10.0),
+                make_tree(1, 12.0, 5.0),
+                make_dead_tree(1, 14.0),
+            ],
+        ));
+        inv.plots.push(make_plot(
+            2,
+            vec![make_tree(2, 24.0, 3.0), make_tree(2, 36.0, 1.0)],
+        ));
+        let dist = DiameterDistribution::from_inventory(&inv, 2.0);
+        let ogive = dist.cumulative();
+        assert_eq!(ogive.len(), dist.classes.len());
+
+        let (upper, tpa, ba) = *ogive.last().unwrap();
+        assert_eq!(upper, dist.classes.last().unwrap().upper);
+        assert!((tpa - inv.mean_tpa()).abs() < 1e-9);
+        assert!((ba - inv.mean_basal_area()).abs() < 1e-9);
+        for pair in ogive.windows(2) {
+            assert!(pair[1].1 >= pair[0].1);
+            assert!(pair[1].2 >= pair[0].2);
+        }
+    }
+
+    #[test]
+    fn test_cumulative_empty() {
+        let dist = DiameterDistribution::from_inventory(&ForestInventory::new("Empty"), 2.0);
+        assert!(dist.cumulative().is_empty());
+    }
+
     #[test]
     fn test_zero_class_width_returns_empty() {
         let mut inv = ForestInventory::new("Zero Width");
@@ -322,6 +820,31 @@ mod tests {
         assert!(dist.classes.is_empty());
     }
 
+    #[test]
+    fn test_zero_class_width_does_not_hang() {
+        let mut inv = ForestInventory::new("Zero Width");
+        inv.plots.push(make_plot(1, vec![make_tree(1, 12.0, 5.0)]));
+        for width in [0.0, 1e-12, f64::NAN, f64::INFINITY] {
+            let err = DiameterDistribution::try_from_inventory(&inv, width).unwrap_err();
+            assert!(matches!(err, ForestError::ValidationError(_)));
+            assert!(err.to_string().contains("diameter class width"));
+            assert!(DiameterDistribution::from_inventory(&inv, width)
+                .classes
+                .is_empty());
+        }
+        assert!(DiameterDistribution::try_from_inventory(&inv, 2.0).is_ok());
+    }
+
+    #[test]
+    fn test_class_width_parse() {
+        assert_eq!("2".parse::<DiameterClassWidth>().unwrap().get(), 2.0);
+        assert!("0".parse::<DiameterClassWidth>().is_err());
+        assert!("-1".parse::<DiameterClassWidth>().is_err());
+        assert!("abc".parse::<DiameterClassWidth>().is_err());
+        assert_eq!(DiameterClassWidth::default().get(), 2.0);
+        assert!(serde_json::from_str::<DiameterClassWidth>("0.0").is_err());
+    }
+
     #[test]
     fn test_negative_class_width_returns_empty() {
         let mut inv = ForestInventory::new("Negative Width");
@@ -329,4 +852,260 @@ mod tests {
         let dist = DiameterDistribution::from_inventory(&inv, -2.0);
         assert!(dist.classes.is_empty());
     }
+
+    #[test]
+    fn test_dead_only_distribution() {
+        let mut inv = ForestInventory::new("Snags");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, 14.0, 5.0),
+                make_dead_tree(1, 12.5),
+                make_dead_tree(1, 17.0),
+            ],
+        ));
+        let options = DistributionOptions {
+            status_filter: TreeStatus::Dead,
+            ..Default::default()
+        };
+        let dist = DiameterDistribution::with_options(&inv, &options).unwrap();
+        assert_eq!(dist.classes.len(), 2);
+        assert_eq!(dist.classes[0].lower, 12.0);
+        assert_eq!(dist.classes[1].lower, 16.0);
+        assert_eq!(dist.classes.iter().map(|c| c.tree_count).sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn test_from_breaks_uses_exact_boundaries() {
+        let mut inv = ForestInventory::new("Products");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, 0.5, 50.0),
+                make_tree(1, 3.0, 20.0),
+                make_tree(1, 5.0, 10.0),
+                make_tree(1, 10.9, 10.0),
+                make_tree(1, 18.0, 5.0),
+            ],
+        ));
+        inv.plots.push(make_plot(2, vec![make_tree(2, 4.0, 20.0)]));
+
+        let dist = DiameterDistribution::from_breaks(&inv, &[1.0, 5.0, 11.0, 21.0]).unwrap();
+        let bounds: Vec<(f64, f64)> = dist.classes.iter().map(|c| (c.lower, c.upper)).collect();
+        assert_eq!(bounds, vec![(1.0, 5.0), (5.0, 11.0), (11.0, 21.0)]);
+        let counts: Vec<usize> = dist.classes.iter().map(|c| c.tree_count).collect();
+        assert_eq!(counts, vec![2, 2, 1]);
+        // (20 + 20) / 2 plots; the 0.5" tree is below the first break
+        assert!((dist.classes[0].tpa - 20.0).abs() < 1e-9);
+        assert_eq!(dist.classes[1].midpoint, 8.0);
+        assert_eq!(dist.class_width, 0.0);
+
+        // Empty classes are kept
+        let dist = DiameterDistribution::from_breaks(&inv, &[1.0, 5.0, 11.0, 15.0, 30.0]).unwrap();
+        assert_eq!(dist.classes[2].tree_count, 0);
+        assert_eq!(dist.classes[2].tpa, 0.0);
+    }
+
+    #[test]
+    fn test_from_breaks_extends_last_class() {
+        let mut inv = ForestInventory::new("Big");
+        inv.plots.push(make_plot(
+            1,
+            vec![make_tree(1, 8.0, 5.0), make_tree(1, 32.4, 5.0)],
+        ));
+        let dist = DiameterDistribution::from_breaks(&inv, &[5.0, 11.0, 21.0]).unwrap();
+        let last = dist.classes.last().unwrap();
+        assert_eq!((last.lower, last.upper), (11.0, 33.0));
+        assert_eq!(last.tree_count, 1);
+    }
+
+    #[test]
+    fn test_from_breaks_rejects_bad_breaks() {
+        let inv = ForestInventory::new("Empty");
+        for breaks in [
+            &[][..],
+            &[5.0],
+            &[5.0, 5.0],
+            &[11.0, 5.0],
+            &[-1.0, 5.0],
+            &[1.0, f64::NAN],
+        ] {
+            assert!(
+                matches!(
+                    DiameterDistribution::from_breaks(&inv, breaks),
+                    Err(ForestError::ValidationError(_))
+                ),
+                "{breaks:?}"
+            );
+        }
+        assert!(DiameterDistribution::from_breaks(&inv, &[1.0, 5.0])
+            .unwrap()
+            .classes
+            .is_empty());
+    }
+
+    #[test]
+    fn test_with_confidence_brackets_and_reconciles() {
+        let mut inv = ForestInventory::new("CI");
+        inv.plots.push(make_plot(
+            1,
+            vec![make_tree(1, 9.0, 5.0), make_tree(1, 14.0, 5.0)],
+        ));
+        inv.plots.push(make_plot(
+            2,
+            vec![make_tree(2, 9.5, 5.0), make_tree(2, 10.5, 5.0)],
+        ));
+        inv.plots.push(make_plot(3, vec![make_tree(3, 15.0, 5.0)]));
+        inv.plots.push(make_plot(4, vec![make_tree(4, 8.5, 5.0)]));
+        let width = DiameterClassWidth::default();
+
+        let dist = DiameterDistribution::with_confidence(&inv, width, 0.95).unwrap();
+        let point = DiameterDistribution::with_width(&inv, width);
+        assert_eq!(dist.classes.len(), point.classes.len());
+        for (class, expected) in dist.classes.iter().zip(&point.classes) {
+            let ci = class.tpa_ci.as_ref().unwrap();
+            assert!(ci.lower <= ci.mean && ci.mean <= ci.upper);
+            assert!((ci.mean - expected.tpa).abs() < 1e-9);
+            assert!((class.tpa - expected.tpa).abs() < 1e-9);
+            assert_eq!(ci.sample_size, 4);
+        }
+
+        let total: f64 = dist
+            .classes
+            .iter()
+            .map(|c| c.tpa_ci.as_ref().unwrap().mean)
+            .sum();
+        assert!((total - inv.mean_tpa()).abs() < 1e-9);
+
+        // 8-10" is on three of four plots, 14-16" on only two
+        let se = |lower: f64| {
+            let class = dist.classes.iter().find(|c| c.lower == lower).unwrap();
+            class.tpa_ci.as_ref().unwrap().sampling_error_percent
+        };
+        assert!(se(14.0) > se(8.0));
+    }
+
+    #[test]
+    fn test_with_confidence_errors() {
+        let width = DiameterClassWidth::default();
+        let mut inv = ForestInventory::new("One");
+        inv.plots.push(make_plot(1, vec![make_tree(1, 9.0, 5.0)]));
+        assert!(matches!(
+            DiameterDistribution::with_confidence(&inv, width, 0.95),
+            Err(ForestError::InsufficientData(_))
+        ));
+
+        inv.plots.push(make_plot(2, vec![make_tree(2, 11.0, 5.0)]));
+        assert!(matches!(
+            DiameterDistribution::with_confidence(&inv, width, 1.0),
+            Err(ForestError::AnalysisError(_))
+        ));
+    }
+
+    #[test]
+    fn test_min_dbh_truncates_and_starts_classes() {
+        let mut inv = ForestInventory::new("Merch");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, 3.0, 50.0),
+                make_tree(1, 4.9, 50.0),
+                make_tree(1, 5.0, 5.0),
+                make_tree(1, 8.2, 5.0),
+            ],
+        ));
+        let options = DistributionOptions {
+            min_dbh: Some(5.0),
+            ..Default::default()
+        };
+        let dist = DiameterDistribution::with_options(&inv, &options).unwrap();
+        let lowers: Vec<f64> = dist.classes.iter().map(|c| c.lower).collect();
+        assert_eq!(lowers, vec![5.0, 7.0]);
+        assert!((dist.classes.iter().map(|c| c.tpa).sum::<f64>() - 10.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_ingrowth_class_holds_sub_threshold_stems() {
+        let mut inv = ForestInventory::new("Ingrowth");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, 1.5, 100.0),
+                make_tree(1, 4.9, 50.0),
+                make_tree(1, 5.0, 5.0),
+                make_tree(1, 8.2, 5.0),
+            ],
+        ));
+        let options = DistributionOptions {
+            min_dbh: Some(5.0),
+            include_ingrowth_class: true,
+            ..Default::default()
+        };
+        let dist = DiameterDistribution::with_options(&inv, &options).unwrap();
+        let bounds: Vec<(f64, f64)> = dist.classes.iter().map(|c| (c.lower, c.upper)).collect();
+        assert_eq!(bounds, vec![(0.0, 5.0), (5.0, 7.0), (7.0, 9.0)]);
+        assert_eq!(dist.classes[0].tree_count, 2);
+        assert!((dist.classes[0].tpa - 150.0).abs() < 0.001);
+        // The tree exactly at the threshold stays in the first full class
+        assert_eq!(dist.classes[1].tree_count, 1);
+    }
+
+    #[test]
+    fn test_ingrowth_class_absent_by_default() {
+        let mut inv = ForestInventory::new("Ingrowth");
+        inv.plots.push(make_plot(
+            1,
+            vec![make_tree(1, 3.0, 50.0), make_tree(1, 6.0, 5.0)],
+        ));
+        assert!(!DistributionOptions::default().include_ingrowth_class);
+        let options = DistributionOptions {
+            min_dbh: Some(5.0),
+            ..Default::default()
+        };
+        let dist = DiameterDistribution::with_options(&inv, &options).unwrap();
+        assert_eq!(dist.classes.len(), 1);
+        assert_eq!(dist.classes[0].lower, 5.0);
+
+        // Without a threshold there is nothing to split off
+        let options = DistributionOptions {
+            include_ingrowth_class: true,
+            ..Default::default()
+        };
+        let dist = DiameterDistribution::with_options(&inv, &options).unwrap();
+        assert_eq!(dist.classes[0].lower, 2.0);
+        assert_eq!(dist.classes.iter().map(|c| c.tree_count).sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn test_default_options_match_from_inventory() {
+        let mut inv = ForestInventory::new("Default");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, 9.0, 5.0),
+                make_tree(1, 15.0, 5.0),
+                make_dead_tree(1, 20.0),
+            ],
+        ));
+        let with =
+            DiameterDistribution::with_options(&inv, &DistributionOptions::default()).unwrap();
+        let plain = DiameterDistribution::from_inventory(&inv, 2.0);
+        let bounds = |d: &DiameterDistribution| -> Vec<(f64, f64)> {
+            d.classes.iter().map(|c| (c.lower, c.tpa)).collect()
+        };
+        assert_eq!(bounds(&with), bounds(&plain));
+    }
+
+    #[test]
+    fn test_invalid_min_dbh_rejected() {
+        let inv = ForestInventory::new("Empty");
+        for min_dbh in [-1.0, f64::NAN] {
+            let options = DistributionOptions {
+                min_dbh: Some(min_dbh),
+                ..Default::default()
+            };
+            let err = DiameterDistribution::with_options(&inv, &options).unwrap_err();
+            assert!(matches!(err, ForestError::ValidationError(_)));
+        }
+    }
 }
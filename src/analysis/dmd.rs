@@ -0,0 +1,191 @@
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use super::StandMetrics;
+use crate::error::ForestError;
+
+/// Slope of Reineke's self-thinning line on log-log axes.
+pub const REINEKE_EXPONENT: f64 = 1.605;
+
+/// Points computed along each isoline, evenly spaced in log(TPA).
+pub const ISOLINE_POINTS: usize = 25;
+
+/// A stand's position on a density management diagram.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DmdPoint {
+    /// Live trees per acre
+    pub tpa: f64,
+    /// Quadratic mean diameter in inches
+    pub qmd: f64,
+    /// Reineke stand density index: TPA × (QMD / 10)^1.605
+    pub sdi: f64,
+    /// Curtis relative density: basal area / √QMD
+    pub rd: f64,
+}
+
+/// One (TPA, QMD) vertex of an isoline.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IsolinePoint {
+    pub tpa: f64,
+    pub qmd: f64,
+}
+
+/// A line of constant SDI on a density management diagram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Isoline {
+    pub sdi: f64,
+    /// Vertices ordered by increasing TPA (and so decreasing QMD)
+    pub points: Vec<IsolinePoint>,
+}
+
+/// Place a stand on a density management diagram.
+///
+/// Uses the live-tree TPA, QMD and basal area from `metrics`. A stand with
+/// no live trees sits at the origin with zero SDI and relative density.
+pub fn dmd_point(metrics: &StandMetrics) -> DmdPoint {
+    let tpa = metrics.total_tpa;
+    let qmd = metrics.quadratic_mean_diameter;
+    let rd = if qmd > 0.0 {
+        metrics.total_basal_area / qmd.sqrt()
+    } else {
+        0.0
+    };
+    DmdPoint {
+        tpa,
+        qmd,
+        sdi: metrics.stand_density_index,
+        rd,
+    }
+}
+
+/// Curves of constant SDI across `tpa_range`, for drawing management zones
+/// (e.g. 25%, 35% and 60% of a species' maximum SDI) behind [`dmd_point`].
+///
+/// Each SDI value yields one [`Isoline`] of [`ISOLINE_POINTS`] vertices with
+/// QMD = 10 × (SDI / TPA)^(1 / 1.605). Returns `ForestError::ValidationError`
+/// if the range is not positive and increasing or an SDI value is not positive.
+pub fn dmd_isolines(
+    sdi_values: &[f64],
+    tpa_range: RangeInclusive<f64>,
+) -> Result<Vec<Isoline>, ForestError> {
+    let (min_tpa, max_tpa) = (*tpa_range.start(), *tpa_range.end());
+    if !(min_tpa.is_finite() && max_tpa.is_finite() && min_tpa > 0.0 && min_tpa < max_tpa) {
+        return Err(ForestError::ValidationError(format!(
+            "TPA range must be positive and increasing, got {min_tpa}..={max_tpa}"
+        )));
+    }
+    if let Some(bad) = sdi_values.iter().find(|s| !(s.is_finite() && **s > 0.0)) {
+        return Err(ForestError::ValidationError(format!(
+            "SDI values must be positive, got {bad}"
+        )));
+    }
+
+    let (log_min, log_max) = (min_tpa.ln(), max_tpa.ln());
+    let step = (log_max - log_min) / (ISOLINE_POINTS - 1) as f64;
+    Ok(sdi_values
+        .iter()
+        .map(|&sdi| Isoline {
+            sdi,
+            points: (0..ISOLINE_POINTS)
+                .map(|i| {
+                    let tpa = (log_min + step * i as f64).exp();
+                    IsolinePoint {
+                        tpa,
+                        qmd: 10.0 * (sdi / tpa).powf(1.0 / REINEKE_EXPONENT),
+                    }
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::compute_stand_metrics;
+    use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus};
+
+    fn make_tree(dbh: f64) -> Tree {
+        Tree {
+            tree_id: 1,
+            plot_id: 1,
+            species: Species {
+                common_name: "Douglas Fir".to_string(),
+                code: "DF".to_string(),
+            },
+            dbh,
+            height: Some(90.0),
+            crown_ratio: Some(0.5),
+            status: TreeStatus::Live,
+            expansion_factor: 100.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    #[test]
+    fn test_dmd_point_sdi_and_rd() {
+        let mut inv = ForestInventory::new("DMD");
+        inv.plots.push(Plot {
+            plot_id: 1,
+            plot_size_acres: 0.2,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees: vec![make_tree(10.0), make_tree(10.0)],
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        });
+        let metrics = compute_stand_metrics(&inv);
+        let point = dmd_point(&metrics);
+
+        // 200 TPA at exactly 10" QMD: SDI equals TPA by definition
+        assert!((point.tpa - 200.0).abs() < 1e-9);
+        assert!((point.qmd - 10.0).abs() < 1e-9);
+        assert!((point.sdi - 200.0).abs() < 1e-9);
+        assert!((point.rd - metrics.total_basal_area / 10.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dmd_point_empty_stand() {
+        let metrics = compute_stand_metrics(&ForestInventory::new("Empty"));
+        let point = dmd_point(&metrics);
+        assert_eq!(point.sdi, 0.0);
+        assert_eq!(point.rd, 0.0);
+    }
+
+    #[test]
+    fn test_isolines_monotonic_and_constant_sdi() {
+        let isolines = dmd_isolines(&[150.0, 300.0], 50.0..=1000.0).unwrap();
+        assert_eq!(isolines.len(), 2);
+        for line in &isolines {
+            assert_eq!(line.points.len(), ISOLINE_POINTS);
+            assert!((line.points[0].tpa - 50.0).abs() < 1e-9);
+            assert!((line.points[ISOLINE_POINTS - 1].tpa - 1000.0).abs() < 1e-6);
+            for pair in line.points.windows(2) {
+                assert!(pair[1].tpa > pair[0].tpa);
+                assert!(pair[1].qmd < pair[0].qmd);
+            }
+            for p in &line.points {
+                let sdi = p.tpa * (p.qmd / 10.0).powf(REINEKE_EXPONENT);
+                assert!((sdi - line.sdi).abs() < 1e-6);
+            }
+        }
+        // Higher SDI lies above at the same TPA
+        assert!(isolines[1].points[0].qmd > isolines[0].points[0].qmd);
+    }
+
+    #[test]
+    fn test_isolines_reject_bad_input() {
+        assert!(dmd_isolines(&[100.0], 0.0..=500.0).is_err());
+        assert!(dmd_isolines(&[100.0], 500.0..=100.0).is_err());
+        assert!(dmd_isolines(&[-5.0], 10.0..=500.0).is_err());
+        assert!(dmd_isolines(&[], 10.0..=500.0).unwrap().is_empty());
+    }
+}
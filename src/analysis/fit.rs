@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::growth::project_state;
+use super::{GrowthModel, StandState};
+use crate::error::ForestError;
+use crate::models::{ForestInventory, Plot};
+
+/// How closely a growth model reproduces an observed re-measurement.
+///
+/// Errors are projected minus observed per-acre values over the plots
+/// measured both times.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelFit {
+    /// Root-mean-square basal area error in sq ft per acre
+    pub rmse_ba: f64,
+    /// Root-mean-square gross cubic-foot volume error in cu ft per acre
+    pub rmse_volume: f64,
+    /// Mean basal area error in sq ft per acre; positive when the model
+    /// over-projects growth
+    pub bias: f64,
+    /// Number of plots compared
+    pub num_plots: usize,
+}
+
+/// Goodness of fit of `model` against an observed re-measurement.
+///
+/// Each plot of `before` is projected `years` ahead (possibly fractional)
+/// and compared with the plot of the same `plot_id` in `after`; plots
+/// measured only once are skipped. Use it to choose between models, e.g.
+/// exponential vs logistic, on your own data: the lower the RMSE the better.
+/// Each inventory's expansion factors are read according to its own
+/// `expansion_kind`. Returns `ForestError::ValidationError` unless `years` is positive and
+/// finite or if the model is invalid, and `ForestError::InsufficientData` if
+/// no plot appears in both inventories.
+pub fn model_fit(
+    model: &GrowthModel,
+    before: &ForestInventory,
+    after: &ForestInventory,
+    years: f64,
+) -> Result<ModelFit, ForestError> {
+    if !(years.is_finite() && years > 0.0) {
+        return Err(ForestError::ValidationError(format!(
+            "Re-measurement interval must be positive, got {years} years"
+        )));
+    }
+
+    let before = &*before.to_per_acre();
+    let after = &*after.to_per_acre();
+    let observed: HashMap<u32, &Plot> = after.plots.iter().map(|p| (p.plot_id, p)).collect();
+    let mut ba_errors = Vec::new();
+    let mut volume_errors = Vec::new();
+    for plot in &before.plots {
+        let Some(remeasured) = observed.get(&plot.plot_id) else {
+            continue;
+        };
+        let projected = project_state(plot_state(plot), model, years)?;
+        ba_errors.push(projected.basal_area - remeasured.basal_area_per_acre());
+        volume_errors.push(projected.volume_cuft - remeasured.volume_cuft_per_acre());
+    }
+
+    if ba_errors.is_empty() {
+        return Err(ForestError::InsufficientData(
+            "No plot appears in both measurements".to_string(),
+        ));
+    }
+
+    let n = ba_errors.len() as f64;
+    let rmse = |errors: &[f64]| (errors.iter().map(|e| e * e).sum::<f64>() / n).sqrt();
+    Ok(ModelFit {
+        rmse_ba: rmse(&ba_errors),
+        rmse_volume: rmse(&volume_errors),
+        bias: ba_errors.iter().sum::<f64>() / n,
+        num_plots: ba_errors.len(),
+    })
+}
+
+fn plot_state(plot: &Plot) -> StandState {
+    StandState {
+        tpa: plot.trees_per_acre(),
+        basal_area: plot.basal_area_per_acre(),
+        volume_cuft: plot.volume_cuft_per_acre(),
+        volume_bdft: plot.volume_bdft_per_acre(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ExpansionKind, Species, Tree, TreeStatus, DEFAULT_PLOT_SIZE_ACRES};
+
+    /// Three plots with different diameters, every diameter scaled by `scale`.
+    fn stand(scale: f64) -> ForestInventory {
+        let trees = [10.0, 12.0, 16.0]
+            .into_iter()
+            .zip(1..)
+            .map(|(dbh, plot_id)| Tree {
+                tree_id: 1,
+                plot_id,
+                species: Species {
+                    common_name: "Douglas Fir".to_string(),
+                    code: "DF".to_string(),
+                },
+                dbh: dbh * scale,
+                height: Some(100.0),
+                crown_ratio: Some(0.5),
+                status: TreeStatus::Live,
+                expansion_factor: 50.0,
+                age: None,
+                defect: None,
+                decay_class: None,
+            });
+        ForestInventory::from_trees("Stand", trees)
+    }
+
+    #[test]
+    fn test_matching_model_fits_better_than_wrong_model() {
+        // Basal area and cubic volume both scale with DBH², so scaling every
+        // DBH by e^(rt/2) is exactly exponential growth at rate r
+        let (rate, years) = (0.02, 10.0);
+        let before = stand(1.0);
+        let after = stand((rate * years / 2.0_f64).exp());
+
+        let exponential = GrowthModel::Exponential {
+            annual_rate: rate,
+            mortality_rate: 0.0,
+        };
+        let fit = model_fit(&exponential, &before, &after, years).unwrap();
+        assert_eq!(fit.num_plots, 3);
+        assert!(fit.rmse_ba < 1e-9, "{fit:?}");
+        assert!(fit.rmse_volume < 1e-9, "{fit:?}");
+        assert!(fit.bias.abs() < 1e-9);
+
+        let linear = GrowthModel::Linear {
+            annual_increment: 5.0,
+            mortality_rate: 0.0,
+        };
+        let wrong = model_fit(&linear, &before, &after, years).unwrap();
+        assert!(wrong.rmse_ba > 1.0, "{wrong:?}");
+        assert!(wrong.rmse_volume > fit.rmse_volume);
+        assert!(wrong.bias > 0.0);
+    }
+
+    #[test]
+    fn test_skips_plots_measured_once() {
+        let before = stand(1.0);
+        let mut after = stand(1.0);
+        after.plots.retain(|p| p.plot_id != 2);
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.0,
+            mortality_rate: 0.0,
+        };
+        let fit = model_fit(&model, &before, &after, 1.0).unwrap();
+        assert_eq!(fit.num_plots, 2);
+        assert!(fit.rmse_ba < 1e-9);
+
+        after.plots.clear();
+        assert!(matches!(
+            model_fit(&model, &before, &after, 1.0),
+            Err(ForestError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_count_based_measurements_are_converted_to_per_acre() {
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.0,
+            mortality_rate: 0.0,
+        };
+        // The same trees recorded as counts on the 0.2-acre plots
+        let mut counted = stand(1.0);
+        counted.expansion_kind = ExpansionKind::CountOnPlot;
+        for tree in counted.plots.iter_mut().flat_map(|p| &mut p.trees) {
+            tree.expansion_factor *= DEFAULT_PLOT_SIZE_ACRES;
+        }
+
+        for (before, after) in [(&counted, &stand(1.0)), (&stand(1.0), &counted)] {
+            let fit = model_fit(&model, before, after, 1.0).unwrap();
+            assert_eq!(fit.num_plots, 3);
+            assert!(fit.rmse_ba < 1e-9, "{fit:?}");
+            assert!(fit.rmse_volume < 1e-9, "{fit:?}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_non_positive_years() {
+        let inv = stand(1.0);
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.02,
+            mortality_rate: 0.0,
+        };
+        for years in [0.0, -1.0, f64::NAN] {
+            assert!(matches!(
+                model_fit(&model, &inv, &inv, years),
+                Err(ForestError::ValidationError(_))
+            ));
+        }
+    }
+}
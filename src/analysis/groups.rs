@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+#[cfg(feature = "config")]
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "config")]
+use crate::error::ForestError;
+use crate::models::ForestInventory;
+
+/// Group label for species codes that have no entry in [`SpeciesGroups`].
+pub const OTHER_GROUP: &str = "Other";
+
+/// Mapping from species code to a reporting group, e.g. conifer/hardwood or
+/// commercial/non-commercial.
+///
+/// Deserializes from a plain table, in TOML (or JSON for `.json` files):
+///
+/// ```toml
+/// DF = "Conifer"
+/// WH = "Conifer"
+/// RA = "Hardwood"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SpeciesGroups {
+    groups: BTreeMap<String, String>,
+}
+
+impl SpeciesGroups {
+    /// Create an empty mapping; every species falls into [`OTHER_GROUP`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assign a species code to a group.
+    pub fn with_group(mut self, code: impl Into<String>, group: impl Into<String>) -> Self {
+        self.groups.insert(code.into(), group.into());
+        self
+    }
+
+    /// Group label for a species code, or [`OTHER_GROUP`] if unmapped.
+    pub fn group_for(&self, code: &str) -> &str {
+        self.groups.get(code).map_or(OTHER_GROUP, String::as_str)
+    }
+
+    /// Load a mapping from a TOML file, or a JSON file if the extension is `.json`.
+    #[cfg(feature = "config")]
+    pub fn load(path: &Path) -> Result<Self, ForestError> {
+        let content = std::fs::read_to_string(path)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            toml::from_str(&content).map_err(|e| {
+                ForestError::ParseError(format!("Failed to parse species groups file: {e}"))
+            })
+        }
+    }
+}
+
+/// Per-acre values for one species group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupComposition {
+    pub group: String,
+    /// Codes of the species present in this group, sorted
+    pub species: Vec<String>,
+    pub tpa: f64,
+    pub basal_area: f64,
+    pub volume_cuft: f64,
+    pub volume_bdft: f64,
+    pub percent_tpa: f64,
+    pub percent_basal_area: f64,
+}
+
+/// Roll live-tree species composition up into `groups`.
+///
+/// Values are averaged over every plot, like [`crate::analysis::StandMetrics`],
+/// so a group's TPA and basal area equal the sum of its species' entries in
+/// `species_composition`. Species without a group are reported under
+/// [`OTHER_GROUP`]. Groups are ordered by descending basal area.
+pub fn compute_stand_metrics_grouped(
+    inventory: &ForestInventory,
+    groups: &SpeciesGroups,
+) -> Vec<GroupComposition> {
+    let inventory = &*inventory.to_per_acre();
+    let n = inventory.num_plots().max(1) as f64;
+
+    struct GroupAccum {
+        species: BTreeSet<String>,
+        tpa: f64,
+        basal_area: f64,
+        volume_cuft: f64,
+        volume_bdft: f64,
+    }
+
+    let mut by_group: HashMap<&str, GroupAccum> = HashMap::new();
+    for tree in inventory.plots.iter().flat_map(|p| p.live_trees()) {
+        let entry = by_group
+            .entry(groups.group_for(&tree.species.code))
+            .or_insert_with(|| GroupAccum {
+                species: BTreeSet::new(),
+                tpa: 0.0,
+                basal_area: 0.0,
+                volume_cuft: 0.0,
+                volume_bdft: 0.0,
+            });
+        entry.species.insert(tree.species.code.clone());
+        entry.tpa += tree.expansion_factor;
+        entry.basal_area += tree.basal_area_per_acre();
+        entry.volume_cuft += tree.volume_cuft().unwrap_or(0.0) * tree.expansion_factor;
+        entry.volume_bdft += tree.volume_bdft().unwrap_or(0.0) * tree.expansion_factor;
+    }
+
+    let total_tpa: f64 = by_group.values().map(|g| g.tpa).sum();
+    let total_ba: f64 = by_group.values().map(|g| g.basal_area).sum();
+    let percent = |part: f64, total: f64| {
+        if total > 0.0 {
+            part / total * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    let mut result: Vec<GroupComposition> = by_group
+        .into_iter()
+        .map(|(group, acc)| GroupComposition {
+            group: group.to_string(),
+            species: acc.species.into_iter().collect(),
+            tpa: acc.tpa / n,
+            basal_area: acc.basal_area / n,
+            volume_cuft: acc.volume_cuft / n,
+            volume_bdft: acc.volume_bdft / n,
+            percent_tpa: percent(acc.tpa, total_tpa),
+            percent_basal_area: percent(acc.basal_area, total_ba),
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        b.basal_area
+            .partial_cmp(&a.basal_area)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.group.cmp(&b.group))
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::compute_stand_metrics;
+    use crate::models::{Plot, Species, Tree, TreeStatus};
+
+    fn make_tree(code: &str, dbh: f64, status: TreeStatus) -> Tree {
+        Tree {
+            tree_id: 1,
+            plot_id: 1,
+            species: Species {
+                common_name: code.to_string(),
+                code: code.to_string(),
+            },
+            dbh,
+            height: Some(90.0),
+            crown_ratio: Some(0.5),
+            status,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    fn sample_inventory() -> ForestInventory {
+        let mut inv = ForestInventory::new("Groups");
+        inv.plots.push(Plot {
+            plot_id: 1,
+            plot_size_acres: 0.2,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees: vec![
+                make_tree("DF", 18.0, TreeStatus::Live),
+                make_tree("WH", 12.0, TreeStatus::Live),
+                make_tree("RA", 10.0, TreeStatus::Live),
+                make_tree("BM", 8.0, TreeStatus::Live),
+                make_tree("DF", 20.0, TreeStatus::Dead),
+            ],
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        });
+        inv
+    }
+
+    #[test]
+    fn test_conifers_collapse_into_one_group() {
+        let inv = sample_inventory();
+        let groups = SpeciesGroups::new()
+            .with_group("DF", "Conifer")
+            .with_group("WH", "Conifer")
+            .with_group("RA", "Hardwood");
+        let grouped = compute_stand_metrics_grouped(&inv, &groups);
+        let metrics = compute_stand_metrics(&inv);
+        let share = |code: &str| {
+            metrics
+                .species_composition
+                .iter()
+                .find(|sc| sc.species.code == code)
+                .unwrap()
+                .clone()
+        };
+
+        assert_eq!(grouped.len(), 3);
+        let conifer = &grouped[0];
+        assert_eq!(conifer.group, "Conifer");
+        assert_eq!(conifer.species, vec!["DF", "WH"]);
+        let (df, wh) = (share("DF"), share("WH"));
+        assert!((conifer.tpa - (df.tpa + wh.tpa)).abs() < 1e-9);
+        assert!((conifer.basal_area - (df.basal_area + wh.basal_area)).abs() < 1e-9);
+        assert!((conifer.percent_tpa - (df.percent_tpa + wh.percent_tpa)).abs() < 1e-9);
+        let ba_pct = df.percent_basal_area + wh.percent_basal_area;
+        assert!((conifer.percent_basal_area - ba_pct).abs() < 1e-9);
+
+        // BM has no group
+        let other = grouped.iter().find(|g| g.group == OTHER_GROUP).unwrap();
+        assert_eq!(other.species, vec!["BM"]);
+
+        let total_pct: f64 = grouped.iter().map(|g| g.percent_basal_area).sum();
+        assert!((total_pct - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_groups_put_everything_in_other() {
+        let grouped = compute_stand_metrics_grouped(&sample_inventory(), &SpeciesGroups::new());
+        assert_eq!(grouped.len(), 1);
+        assert_eq!(grouped[0].group, OTHER_GROUP);
+        assert!((grouped[0].percent_tpa - 100.0).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_load_toml_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("groups.toml");
+        std::fs::write(&toml_path, "DF = \"Conifer\"\nRA = \"Hardwood\"\n").unwrap();
+        let groups = SpeciesGroups::load(&toml_path).unwrap();
+        assert_eq!(groups.group_for("DF"), "Conifer");
+        assert_eq!(groups.group_for("XX"), OTHER_GROUP);
+
+        let json_path = dir.path().join("groups.json");
+        std::fs::write(&json_path, r#"{"RA": "Hardwood"}"#).unwrap();
+        assert_eq!(
+            SpeciesGroups::load(&json_path).unwrap().group_for("RA"),
+            "Hardwood"
+        );
+
+        std::fs::write(&toml_path, "DF = [").unwrap();
+        assert!(SpeciesGroups::load(&toml_path).is_err());
+    }
+}
@@ -1,32 +1,56 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::ForestError;
-use crate::models::ForestInventory;
+use crate::models::{ForestInventory, Species, VolumeEquation};
 
 /// Growth model type.
+///
+/// Every model can describe a declining stand (e.g. after drought or insect
+/// attack): use a negative `annual_rate` for [`GrowthModel::Exponential`], a
+/// negative `annual_increment` for [`GrowthModel::Linear`], or a
+/// `carrying_capacity` (or `asymptote`) below current basal area for
+/// [`GrowthModel::Logistic`] (or [`GrowthModel::ChapmanRichards`]).
+/// Projected values are floored at zero, and a stand whose TPA or basal area
+/// reaches zero is projected as empty.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GrowthModel {
-    /// Simple exponential growth: V(t) = V0 * e^(r*t)
+    /// Simple exponential growth: V(t) = V0 * e^(r*t). A negative `r` models decline.
     Exponential {
         annual_rate: f64,
         /// Annual mortality rate as a proportion (e.g. 0.005 = 0.5%)
         mortality_rate: f64,
     },
-    /// Logistic growth with carrying capacity: V(t) = K / (1 + ((K - V0)/V0) * e^(-r*t))
+    /// Logistic growth with carrying capacity: V(t) = K / (1 + ((K - V0)/V0) * e^(-r*t)).
+    /// When V0 > K the stand declines toward K.
     Logistic {
         annual_rate: f64,
         carrying_capacity: f64,
         /// Annual mortality rate as a proportion (e.g. 0.005 = 0.5%)
         mortality_rate: f64,
     },
-    /// Linear growth: V(t) = V0 + r*t
+    /// Linear growth: V(t) = V0 + r*t. A negative `r` models decline.
     Linear {
         annual_increment: f64,
         /// Annual TPA mortality (absolute, e.g. 0.5 TPA/year)
         mortality_rate: f64,
     },
+    /// Chapman-Richards growth: Y(t) = A * (1 - e^(-k*t))^p, a sigmoid with an
+    /// inflection when `shape` > 1, as for basal area in even-aged stands.
+    /// The curve is rescaled to start from current basal area, giving
+    /// V(t) = V0 + (A - V0) * (1 - e^(-k*t))^p; volumes scale with basal area.
+    ChapmanRichards {
+        /// Basal area the stand approaches, sq ft/acre (A)
+        asymptote: f64,
+        /// Rate parameter (k)
+        rate: f64,
+        /// Shape parameter (p); 1 gives a curve with no inflection
+        shape: f64,
+        /// Annual mortality rate as a proportion (e.g. 0.005 = 0.5%)
+        mortality_rate: f64,
+    },
 }
 
 impl FromStr for GrowthModel {
@@ -38,6 +62,8 @@ impl FromStr for GrowthModel {
     /// - `"exponential"` / `"exp"` — Exponential growth (rate=0.03, mortality=0.005)
     /// - `"logistic"` / `"log"` — Logistic growth (rate=0.03, capacity=300.0, mortality=0.005)
     /// - `"linear"` / `"lin"` — Linear growth (increment=2.0, mortality=0.5)
+    /// - `"chapman-richards"` / `"chapman"` / `"cr"` — Chapman-Richards growth
+    ///   (asymptote=300.0, rate=0.03, shape=3.0, mortality=0.005)
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "exponential" | "exp" => Ok(GrowthModel::Exponential {
@@ -53,14 +79,120 @@ impl FromStr for GrowthModel {
                 annual_increment: 2.0,
                 mortality_rate: 0.5,
             }),
+            "chapman-richards" | "chapman" | "cr" => Ok(GrowthModel::ChapmanRichards {
+                asymptote: 300.0,
+                rate: 0.03,
+                shape: 3.0,
+                mortality_rate: 0.005,
+            }),
             _ => Err(ForestError::ParseError(format!(
-                "Unknown growth model: '{}'. Use: exponential, logistic, or linear",
+                "Unknown growth model: '{}'. Use: exponential, logistic, linear, or chapman",
                 s
             ))),
         }
     }
 }
 
+/// Ingrowth of new stems into the smallest diameter class.
+///
+/// Without recruitment a projection only grows and kills existing stems, so
+/// TPA can never increase. Recruited stems enter at `dbh`, add their basal
+/// area to the stand, and are not grown or killed further by the
+/// [`GrowthModel`]. They contribute no volume, since ingrowth is normally
+/// below merchantable size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RecruitmentModel {
+    /// A fixed number of trees per acre recruited every year.
+    Constant {
+        /// New trees per acre per year
+        annual_tpa: f64,
+        /// DBH of recruited trees in inches
+        dbh: f64,
+    },
+}
+
+impl RecruitmentModel {
+    /// Cumulative recruited TPA after `t` years.
+    fn recruited_tpa(&self, t: f64) -> f64 {
+        match self {
+            RecruitmentModel::Constant { annual_tpa, .. } => annual_tpa * t,
+        }
+    }
+
+    /// Basal area per acre of `tpa` recruited stems.
+    fn basal_area(&self, tpa: f64) -> f64 {
+        match self {
+            RecruitmentModel::Constant { dbh, .. } => {
+                std::f64::consts::PI * (dbh / 2.0).powi(2) / 144.0 * tpa
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), ForestError> {
+        match self {
+            RecruitmentModel::Constant { annual_tpa, dbh } => {
+                if !(annual_tpa.is_finite() && *annual_tpa >= 0.0) {
+                    return Err(ForestError::ValidationError(format!(
+                        "recruitment annual_tpa must be non-negative, got {annual_tpa}"
+                    )));
+                }
+                if !(dbh.is_finite() && *dbh > 0.0) {
+                    return Err(ForestError::ValidationError(format!(
+                        "recruitment dbh must be positive, got {dbh}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A thinning partway through a projection.
+///
+/// At `year` the projected TPA, basal area and volumes are all multiplied by
+/// `residual_ba_fraction`, and later years grow from that reduced stand as if
+/// the projection had started there. Recruited ingrowth is not removed, as a
+/// commercial thinning takes merchantable stems.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Thinning {
+    /// Projection year of the thinning; its row shows the residual stand
+    pub year: u32,
+    /// Share of the stand left after the thinning, in (0, 1]
+    pub residual_ba_fraction: f64,
+}
+
+impl Thinning {
+    fn validate(&self) -> Result<(), ForestError> {
+        let fraction = self.residual_ba_fraction;
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(ForestError::ValidationError(format!(
+                "thinning residual fraction must be above 0 and at most 1, got {fraction}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Thinning {
+    type Err = ForestError;
+
+    /// Parse `YEAR:FRACTION`, e.g. `15:0.6` to leave 60% of the stand at year 15.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = || {
+            ForestError::ParseError(format!(
+                "Invalid thinning: '{s}'. Use YEAR:FRACTION, e.g. 15:0.6"
+            ))
+        };
+        let (year, fraction) = s.split_once(':').ok_or_else(parse_error)?;
+        let thinning = Thinning {
+            year: year.trim().parse().map_err(|_| parse_error())?,
+            residual_ba_fraction: fraction.trim().parse().map_err(|_| parse_error())?,
+        };
+        thinning.validate()?;
+        Ok(thinning)
+    }
+}
+
 /// A single year's growth projection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrowthProjection {
@@ -69,6 +201,61 @@ pub struct GrowthProjection {
     pub basal_area: f64,
     pub volume_cuft: f64,
     pub volume_bdft: f64,
+    /// Trees per acre recruited since year 0, included in `tpa`
+    #[serde(default)]
+    pub recruited_tpa: f64,
+}
+
+/// Per-acre starting point of a growth projection.
+///
+/// Usually derived from an inventory with [`StandState::from_inventory`], but
+/// can be set directly to project a hypothetical stand with
+/// [`project_growth_from`], e.g. "what if we start at 120 sq ft/ac?".
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StandState {
+    pub tpa: f64,
+    pub basal_area: f64,
+    pub volume_cuft: f64,
+    pub volume_bdft: f64,
+}
+
+impl StandState {
+    /// Mean per-acre values of an inventory's live trees, with the default
+    /// volume equation.
+    pub fn from_inventory(inventory: &ForestInventory) -> Self {
+        Self::from_inventory_with(inventory, &VolumeEquation::default())
+    }
+
+    /// Mean per-acre values of an inventory's live trees, with custom volume
+    /// equation coefficients.
+    pub fn from_inventory_with(
+        inventory: &ForestInventory,
+        volume_equation: &VolumeEquation,
+    ) -> Self {
+        Self {
+            tpa: inventory.mean_tpa(),
+            basal_area: inventory.mean_basal_area(),
+            volume_cuft: inventory.mean_volume_cuft_with(volume_equation),
+            volume_bdft: inventory.mean_volume_bdft_with(volume_equation),
+        }
+    }
+
+    /// Check that every value is finite and non-negative.
+    fn validate(&self) -> Result<(), ForestError> {
+        for (name, value) in [
+            ("tpa", self.tpa),
+            ("basal_area", self.basal_area),
+            ("volume_cuft", self.volume_cuft),
+            ("volume_bdft", self.volume_bdft),
+        ] {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(ForestError::ValidationError(format!(
+                    "Initial {name} must be finite and non-negative, got {value}"
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Project stand growth over a number of years.
@@ -77,21 +264,401 @@ pub fn project_growth(
     model: &GrowthModel,
     years: u32,
 ) -> Result<Vec<GrowthProjection>, ForestError> {
+    project_growth_with_equation(inventory, model, years, &VolumeEquation::default())
+}
+
+/// Project stand growth with ingrowth from `recruitment` added each year.
+///
+/// Recruited stems increase TPA and basal area on top of the model's
+/// trajectory, offsetting mortality; see [`RecruitmentModel`].
+pub fn project_growth_with_recruitment(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    recruitment: &RecruitmentModel,
+    years: u32,
+) -> Result<Vec<GrowthProjection>, ForestError> {
+    Ok(growth_iter(
+        inventory,
+        model,
+        Some(recruitment),
+        &[],
+        years,
+        &VolumeEquation::default(),
+    )?
+    .collect())
+}
+
+/// Project stand growth with the stand reduced by each of `thinnings`, and
+/// ingrowth from `recruitment` if given.
+///
+/// Thinnings may be in any order; two in the same year compound. Thinnings
+/// after `years` have no effect. Returns `ForestError::ValidationError` for
+/// a residual fraction outside (0, 1]; see [`Thinning`].
+pub fn project_growth_with_thinning(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    thinnings: &[Thinning],
+    recruitment: Option<&RecruitmentModel>,
+    years: u32,
+) -> Result<Vec<GrowthProjection>, ForestError> {
+    Ok(growth_iter(
+        inventory,
+        model,
+        recruitment,
+        thinnings,
+        years,
+        &VolumeEquation::default(),
+    )?
+    .collect())
+}
+
+/// Project stand growth, computing the starting volumes with custom volume
+/// equation coefficients.
+pub fn project_growth_with_equation(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    years: u32,
+    volume_equation: &VolumeEquation,
+) -> Result<Vec<GrowthProjection>, ForestError> {
+    Ok(growth_iter(inventory, model, None, &[], years, volume_equation)?.collect())
+}
+
+/// Project growth from a given starting state rather than an inventory.
+///
+/// [`project_growth`] is this with the state derived from the inventory by
+/// [`StandState::from_inventory`]. Returns `ForestError::ValidationError` if
+/// a starting value is negative or not finite.
+pub fn project_growth_from(
+    initial: StandState,
+    model: &GrowthModel,
+    years: u32,
+) -> Result<Vec<GrowthProjection>, ForestError> {
+    Ok(state_growth_iter(initial, model, None, &[], years)?.collect())
+}
+
+/// A single state `t` years after `initial`, which may be fractional.
+///
+/// The same projection [`project_growth_from`] makes for whole years, without
+/// recruitment.
+pub(super) fn project_state(
+    initial: StandState,
+    model: &GrowthModel,
+    t: f64,
+) -> Result<StandState, ForestError> {
+    initial.validate()?;
+    validate_model(model)?;
+    let (tpa, basal_area, volume_cuft, volume_bdft) = project_values(
+        model,
+        (
+            initial.tpa,
+            initial.basal_area,
+            initial.volume_cuft,
+            initial.volume_bdft,
+        ),
+        t,
+    );
+    Ok(StandState {
+        tpa,
+        basal_area,
+        volume_cuft,
+        volume_bdft,
+    })
+}
+
+/// Project stand growth lazily, computing each year only when it is requested.
+///
+/// Yields the same sequence as [`project_growth`] (year 0 through `years`)
+/// without allocating it, for callers that stream projections to a writer.
+/// The inventory and model are checked up front, so errors are returned here
+/// rather than mid-iteration.
+pub fn project_growth_iter(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    years: u32,
+) -> Result<impl Iterator<Item = GrowthProjection>, ForestError> {
+    growth_iter(
+        inventory,
+        model,
+        None,
+        &[],
+        years,
+        &VolumeEquation::default(),
+    )
+}
+
+pub(super) fn growth_iter(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    recruitment: Option<&RecruitmentModel>,
+    thinnings: &[Thinning],
+    years: u32,
+    volume_equation: &VolumeEquation,
+) -> Result<impl Iterator<Item = GrowthProjection>, ForestError> {
     if inventory.num_plots() == 0 {
         return Err(ForestError::InsufficientData(
             "No plots available for growth projection".to_string(),
         ));
     }
 
-    // Validate model parameters
+    state_growth_iter(
+        StandState::from_inventory_with(inventory, volume_equation),
+        model,
+        recruitment,
+        thinnings,
+        years,
+    )
+}
+
+/// Project from `initial`, one year per item from year 0 through `years`.
+fn state_growth_iter(
+    initial: StandState,
+    model: &GrowthModel,
+    recruitment: Option<&RecruitmentModel>,
+    thinnings: &[Thinning],
+    years: u32,
+) -> Result<impl Iterator<Item = GrowthProjection>, ForestError> {
+    initial.validate()?;
+    validate_model(model)?;
+    if let Some(recruitment) = recruitment {
+        recruitment.validate()?;
+    }
+    for thinning in thinnings {
+        thinning.validate()?;
+    }
+
+    let initial = (
+        initial.tpa,
+        initial.basal_area,
+        initial.volume_cuft,
+        initial.volume_bdft,
+    );
+    let model = model.clone();
+    let recruitment = recruitment.cloned();
+    let segments = thinned_segments(&model, initial, thinnings);
+
+    Ok((0..=years).map(move |year| {
+        // Each thinning restarts the model from the residual stand; a
+        // segment's first year is its starting state, later years are
+        // floored at zero
+        let (start, state) = segments
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= year)
+            .copied()
+            .unwrap_or((0, initial));
+        let (tpa, basal_area, volume_cuft, volume_bdft) = if year == start {
+            state
+        } else {
+            project_values(&model, state, (year - start) as f64)
+        };
+        let (recruited_tpa, recruited_ba) = recruitment.as_ref().map_or((0.0, 0.0), |r| {
+            let recruited = r.recruited_tpa(year as f64);
+            (recruited, r.basal_area(recruited))
+        });
+        GrowthProjection {
+            year,
+            tpa: tpa + recruited_tpa,
+            basal_area: basal_area + recruited_ba,
+            volume_cuft,
+            volume_bdft,
+            recruited_tpa,
+        }
+    }))
+}
+
+/// One species' projected per-acre values in a given year.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciesGrowth {
+    pub species: Species,
+    pub tpa: f64,
+    pub basal_area: f64,
+    pub volume_cuft: f64,
+    pub volume_bdft: f64,
+}
+
+/// A single year's growth projection broken down by species.
+///
+/// `total` is always the sum of the per-species values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciesGrowthProjection {
+    pub year: u32,
+    pub species: Vec<SpeciesGrowth>,
+    pub total: GrowthProjection,
+}
+
+/// Project growth for each species independently using a single model.
+///
+/// See [`project_growth_by_species_with`] for how stand-scale parameters are
+/// apportioned between species.
+pub fn project_growth_by_species(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    years: u32,
+) -> Result<Vec<SpeciesGrowthProjection>, ForestError> {
+    project_growth_by_species_with(inventory, model, &HashMap::new(), years)
+}
+
+/// Project growth for each species independently, with optional per-species
+/// models keyed by species code. Species without an override use `model`.
+///
+/// Model parameters are interpreted at stand scale. Quantities with absolute
+/// units (logistic carrying capacity, linear increment and linear mortality)
+/// are apportioned by each species' initial share of basal area (or TPA for
+/// mortality), so applying one model to every species reproduces the
+/// stand-level [`project_growth`] trajectory. Species are ordered by initial
+/// basal area, largest first.
+pub fn project_growth_by_species_with(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    species_models: &HashMap<String, GrowthModel>,
+    years: u32,
+) -> Result<Vec<SpeciesGrowthProjection>, ForestError> {
+    species_growth(
+        inventory,
+        model,
+        species_models,
+        years,
+        &VolumeEquation::default(),
+    )
+}
+
+pub(super) fn species_growth(
+    inventory: &ForestInventory,
+    model: &GrowthModel,
+    species_models: &HashMap<String, GrowthModel>,
+    years: u32,
+    volume_equation: &VolumeEquation,
+) -> Result<Vec<SpeciesGrowthProjection>, ForestError> {
+    let inventory = &*inventory.to_per_acre();
+    let num_plots = inventory.num_plots();
+    if num_plots == 0 {
+        return Err(ForestError::InsufficientData(
+            "No plots available for growth projection".to_string(),
+        ));
+    }
+
+    validate_model(model)?;
+    for species_model in species_models.values() {
+        validate_model(species_model)?;
+    }
+
+    // Per-species per-acre means, accumulated the same way as the plot methods
+    let mut initial: HashMap<String, (Species, f64, f64, f64, f64)> = HashMap::new();
+    for tree in inventory.plots.iter().flat_map(|p| p.live_trees()) {
+        let entry = initial
+            .entry(tree.species.code.clone())
+            .or_insert_with(|| (tree.species.clone(), 0.0, 0.0, 0.0, 0.0));
+        entry.1 += tree.expansion_factor;
+        entry.2 += tree.basal_area_per_acre();
+        let cuft = tree.volume_cuft_with(volume_equation);
+        let bdft = tree.volume_bdft_with(volume_equation);
+        entry.3 += cuft.map_or(0.0, |v| v * tree.expansion_factor);
+        entry.4 += bdft.map_or(0.0, |v| v * tree.expansion_factor);
+    }
+    let n = num_plots as f64;
+    let mut initial: Vec<(Species, f64, f64, f64, f64)> = initial
+        .into_values()
+        .map(|(sp, tpa, ba, vc, vb)| (sp, tpa / n, ba / n, vc / n, vb / n))
+        .collect();
+    initial.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let stand_tpa: f64 = initial.iter().map(|s| s.1).sum();
+    let stand_ba: f64 = initial.iter().map(|s| s.2).sum();
+
+    let apportioned: Vec<GrowthModel> = initial
+        .iter()
+        .map(|(sp, tpa, ba, _, _)| {
+            let base = species_models.get(&sp.code).unwrap_or(model);
+            let ba_share = if stand_ba > 0.0 { ba / stand_ba } else { 0.0 };
+            let tpa_share = if stand_tpa > 0.0 { tpa / stand_tpa } else { 0.0 };
+            apportion_model(base, ba_share, tpa_share)
+        })
+        .collect();
+
+    let mut projections = Vec::with_capacity(years as usize + 1);
+    for year in 0..=years {
+        let t = year as f64;
+        let species: Vec<SpeciesGrowth> = initial
+            .iter()
+            .zip(&apportioned)
+            .map(|((sp, tpa, ba, vc, vb), species_model)| {
+                let (tpa, ba, vc, vb) = if year == 0 {
+                    (*tpa, *ba, *vc, *vb)
+                } else {
+                    project_values(species_model, (*tpa, *ba, *vc, *vb), t)
+                };
+                SpeciesGrowth {
+                    species: sp.clone(),
+                    tpa: tpa.max(0.0),
+                    basal_area: ba.max(0.0),
+                    volume_cuft: vc.max(0.0),
+                    volume_bdft: vb.max(0.0),
+                }
+            })
+            .collect();
+
+        let total = GrowthProjection {
+            year,
+            tpa: species.iter().map(|s| s.tpa).sum(),
+            basal_area: species.iter().map(|s| s.basal_area).sum(),
+            volume_cuft: species.iter().map(|s| s.volume_cuft).sum(),
+            volume_bdft: species.iter().map(|s| s.volume_bdft).sum(),
+            recruited_tpa: 0.0,
+        };
+        projections.push(SpeciesGrowthProjection {
+            year,
+            species,
+            total,
+        });
+    }
+
+    Ok(projections)
+}
+
+/// Scale a stand-level model's absolute parameters to one species' share.
+fn apportion_model(model: &GrowthModel, ba_share: f64, tpa_share: f64) -> GrowthModel {
+    match model {
+        GrowthModel::Exponential { .. } => model.clone(),
+        GrowthModel::Logistic {
+            annual_rate,
+            carrying_capacity,
+            mortality_rate,
+        } => GrowthModel::Logistic {
+            annual_rate: *annual_rate,
+            carrying_capacity: carrying_capacity * ba_share,
+            mortality_rate: *mortality_rate,
+        },
+        GrowthModel::Linear {
+            annual_increment,
+            mortality_rate,
+        } => GrowthModel::Linear {
+            annual_increment: annual_increment * ba_share,
+            mortality_rate: mortality_rate * tpa_share,
+        },
+        GrowthModel::ChapmanRichards {
+            asymptote,
+            rate,
+            shape,
+            mortality_rate,
+        } => GrowthModel::ChapmanRichards {
+            asymptote: asymptote * ba_share,
+            rate: *rate,
+            shape: *shape,
+            mortality_rate: *mortality_rate,
+        },
+    }
+}
+
+/// Check growth model parameters, returning `ValidationError` for invalid values.
+fn validate_model(model: &GrowthModel) -> Result<(), ForestError> {
     match model {
         GrowthModel::Exponential {
             annual_rate,
             mortality_rate,
         } => {
-            if *annual_rate < 0.0 {
+            if !annual_rate.is_finite() {
                 return Err(ForestError::ValidationError(format!(
-                    "annual_rate must be non-negative, got {annual_rate}"
+                    "annual_rate must be finite, got {annual_rate}"
                 )));
             }
             if *mortality_rate < 0.0 || *mortality_rate >= 1.0 {
@@ -125,9 +692,9 @@ pub fn project_growth(
             annual_increment,
             mortality_rate,
         } => {
-            if *annual_increment < 0.0 {
+            if !annual_increment.is_finite() {
                 return Err(ForestError::ValidationError(format!(
-                    "annual_increment must be non-negative, got {annual_increment}"
+                    "annual_increment must be finite, got {annual_increment}"
                 )));
             }
             if *mortality_rate < 0.0 {
@@ -136,86 +703,159 @@ pub fn project_growth(
                 )));
             }
         }
+        GrowthModel::ChapmanRichards {
+            asymptote,
+            rate,
+            shape,
+            mortality_rate,
+        } => {
+            if !(asymptote.is_finite() && *asymptote > 0.0) {
+                return Err(ForestError::ValidationError(format!(
+                    "asymptote must be positive, got {asymptote}"
+                )));
+            }
+            if !(rate.is_finite() && *rate >= 0.0) {
+                return Err(ForestError::ValidationError(format!(
+                    "rate must be non-negative, got {rate}"
+                )));
+            }
+            if !(shape.is_finite() && *shape > 0.0) {
+                return Err(ForestError::ValidationError(format!(
+                    "shape must be positive, got {shape}"
+                )));
+            }
+            if *mortality_rate < 0.0 || *mortality_rate >= 1.0 {
+                return Err(ForestError::ValidationError(format!(
+                    "mortality_rate must be in [0.0, 1.0), got {mortality_rate}"
+                )));
+            }
+        }
     }
+    Ok(())
+}
 
-    let initial_tpa = inventory.mean_tpa();
-    let initial_ba = inventory.mean_basal_area();
-    let initial_vol_cuft = inventory.mean_volume_cuft();
-    let initial_vol_bdft = inventory.mean_volume_bdft();
-
-    let mut projections = Vec::with_capacity(years as usize + 1);
-
-    // Year 0 = current conditions
-    projections.push(GrowthProjection {
-        year: 0,
-        tpa: initial_tpa,
-        basal_area: initial_ba,
-        volume_cuft: initial_vol_cuft,
-        volume_bdft: initial_vol_bdft,
-    });
-
-    for year in 1..=years {
-        let t = year as f64;
+/// The (start year, starting values) of each stretch of a projection between
+/// thinnings, in year order, beginning with year 0 and `initial`.
+fn thinned_segments(
+    model: &GrowthModel,
+    initial: (f64, f64, f64, f64),
+    thinnings: &[Thinning],
+) -> Vec<(u32, (f64, f64, f64, f64))> {
+    let mut thinnings = thinnings.to_vec();
+    thinnings.sort_by_key(|t| t.year);
 
-        let (tpa, ba, vol_cuft, vol_bdft) = match model {
-            GrowthModel::Exponential {
-                annual_rate,
-                mortality_rate,
-            } => {
-                let factor = (annual_rate * t).exp();
-                let tpa_factor = (-mortality_rate * t).exp();
-                (
-                    initial_tpa * tpa_factor,
-                    initial_ba * factor,
-                    initial_vol_cuft * factor,
-                    initial_vol_bdft * factor,
-                )
-            }
-            GrowthModel::Logistic {
-                annual_rate,
-                carrying_capacity,
-                mortality_rate,
-            } => {
-                let apply_logistic = |v0: f64, k: f64| -> f64 {
-                    if v0 <= 0.0 {
-                        return 0.0;
-                    }
-                    k / (1.0 + ((k - v0) / v0) * (-annual_rate * t).exp())
-                };
-                // Scale carrying capacities relative to basal area capacity
-                let ba_ratio = if initial_ba > 0.0 {
-                    *carrying_capacity / initial_ba
-                } else {
-                    1.0
-                };
-                (
-                    initial_tpa * (-mortality_rate * t).exp(),
-                    apply_logistic(initial_ba, *carrying_capacity),
-                    apply_logistic(initial_vol_cuft, initial_vol_cuft * ba_ratio),
-                    apply_logistic(initial_vol_bdft, initial_vol_bdft * ba_ratio),
-                )
-            }
-            GrowthModel::Linear {
-                annual_increment,
-                mortality_rate,
-            } => (
-                (initial_tpa - mortality_rate * t).max(0.0),
-                initial_ba + annual_increment * t,
-                initial_vol_cuft + annual_increment * t * 10.0, // rough volume scaling
-                initial_vol_bdft + annual_increment * t * 50.0,
-            ),
+    let mut segments = vec![(0, initial)];
+    for thinning in thinnings {
+        let (start, state) = segments[segments.len() - 1];
+        let (tpa, ba, vol_cuft, vol_bdft) = if thinning.year == start {
+            state
+        } else {
+            project_values(model, state, (thinning.year - start) as f64)
         };
+        let f = thinning.residual_ba_fraction;
+        let residual = (tpa * f, ba * f, vol_cuft * f, vol_bdft * f);
+        if thinning.year == start {
+            segments.pop();
+        }
+        segments.push((thinning.year, residual));
+    }
+    segments
+}
 
-        projections.push(GrowthProjection {
-            year,
-            tpa: tpa.max(0.0),
-            basal_area: ba.max(0.0),
-            volume_cuft: vol_cuft.max(0.0),
-            volume_bdft: vol_bdft.max(0.0),
-        });
+/// Apply a growth model to initial (TPA, BA, cu ft, bd ft) values at year `t`.
+///
+/// Values are floored at zero. If either TPA or basal area has declined to
+/// zero the stand has no trees left, so every value is zero.
+fn project_values(
+    model: &GrowthModel,
+    initial: (f64, f64, f64, f64),
+    t: f64,
+) -> (f64, f64, f64, f64) {
+    let (tpa, ba, vol_cuft, vol_bdft) = apply_model(model, initial, t);
+    if tpa <= 0.0 || ba <= 0.0 {
+        return (0.0, 0.0, 0.0, 0.0);
     }
+    (tpa, ba, vol_cuft.max(0.0), vol_bdft.max(0.0))
+}
 
-    Ok(projections)
+fn apply_model(
+    model: &GrowthModel,
+    initial: (f64, f64, f64, f64),
+    t: f64,
+) -> (f64, f64, f64, f64) {
+    let (initial_tpa, initial_ba, initial_vol_cuft, initial_vol_bdft) = initial;
+    match model {
+        GrowthModel::Exponential {
+            annual_rate,
+            mortality_rate,
+        } => {
+            let factor = (annual_rate * t).exp();
+            let tpa_factor = (-mortality_rate * t).exp();
+            (
+                initial_tpa * tpa_factor,
+                initial_ba * factor,
+                initial_vol_cuft * factor,
+                initial_vol_bdft * factor,
+            )
+        }
+        GrowthModel::Logistic {
+            annual_rate,
+            carrying_capacity,
+            mortality_rate,
+        } => {
+            let apply_logistic = |v0: f64, k: f64| -> f64 {
+                if v0 <= 0.0 {
+                    return 0.0;
+                }
+                k / (1.0 + ((k - v0) / v0) * (-annual_rate * t).exp())
+            };
+            // Scale carrying capacities relative to basal area capacity
+            let ba_ratio = if initial_ba > 0.0 {
+                *carrying_capacity / initial_ba
+            } else {
+                1.0
+            };
+            (
+                initial_tpa * (-mortality_rate * t).exp(),
+                apply_logistic(initial_ba, *carrying_capacity),
+                apply_logistic(initial_vol_cuft, initial_vol_cuft * ba_ratio),
+                apply_logistic(initial_vol_bdft, initial_vol_bdft * ba_ratio),
+            )
+        }
+        GrowthModel::Linear {
+            annual_increment,
+            mortality_rate,
+        } => (
+            (initial_tpa - mortality_rate * t).max(0.0),
+            initial_ba + annual_increment * t,
+            initial_vol_cuft + annual_increment * t * 10.0, // rough volume scaling
+            initial_vol_bdft + annual_increment * t * 50.0,
+        ),
+        GrowthModel::ChapmanRichards {
+            asymptote,
+            rate,
+            shape,
+            mortality_rate,
+        } => {
+            // The closed form starts from zero at t = 0; rescaling it to run
+            // from the current basal area keeps year 0 equal to the inventory
+            let progress = (1.0 - (-rate * t).exp()).powf(*shape);
+            let ba = initial_ba + (asymptote - initial_ba) * progress;
+            // As for Logistic, volumes approach their capacity in proportion
+            // to basal area
+            let ba_ratio = if initial_ba > 0.0 {
+                ba / initial_ba
+            } else {
+                1.0
+            };
+            (
+                initial_tpa * (-mortality_rate * t).exp(),
+                ba,
+                initial_vol_cuft * ba_ratio,
+                initial_vol_bdft * ba_ratio,
+            )
+        }
+    }
 }
 
 #[cfg(test)]
@@ -238,6 +878,7 @@ mod tests {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -250,6 +891,11 @@ mod tests {
             elevation_ft: None,
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
@@ -298,6 +944,215 @@ mod tests {
         assert_eq!(proj.last().unwrap().year, 20);
     }
 
+    #[test]
+    fn test_recruitment_offsets_mortality() {
+        let inv = sample_inventory();
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.0,
+            mortality_rate: 0.01,
+        };
+        // Replace roughly the 1% of stems lost each year
+        let recruitment = RecruitmentModel::Constant {
+            annual_tpa: 0.01 * inv.mean_tpa(),
+            dbh: 1.0,
+        };
+        let without = project_growth(&inv, &model, 20).unwrap();
+        let with = project_growth_with_recruitment(&inv, &model, &recruitment, 20).unwrap();
+
+        let initial_tpa = inv.mean_tpa();
+        assert!(without[20].tpa < initial_tpa * 0.85);
+        for proj in &with {
+            assert!(
+                (proj.tpa - initial_tpa).abs() / initial_tpa < 0.03,
+                "{proj:?}"
+            );
+        }
+
+        assert_eq!(with[0].recruited_tpa, 0.0);
+        let recruited = with[20].recruited_tpa;
+        assert!((recruited - 0.2 * initial_tpa).abs() < 1e-9);
+        assert!((with[20].tpa - (without[20].tpa + recruited)).abs() < 1e-9);
+        // 1" recruits add a little basal area and no volume
+        let ba_added = with[20].basal_area - without[20].basal_area;
+        assert!((ba_added - recruited * std::f64::consts::PI / 576.0).abs() < 1e-9);
+        assert_eq!(with[20].volume_cuft, without[20].volume_cuft);
+    }
+
+    #[test]
+    fn test_recruitment_rejects_bad_parameters() {
+        let model = GrowthModel::from_str("exponential").unwrap();
+        let inv = sample_inventory();
+        for (annual_tpa, dbh) in [(-1.0, 1.0), (5.0, 0.0), (f64::NAN, 1.0)] {
+            let recruitment = RecruitmentModel::Constant { annual_tpa, dbh };
+            assert!(project_growth_with_recruitment(&inv, &model, &recruitment, 5).is_err());
+        }
+    }
+
+    #[test]
+    fn test_thinning_drops_basal_area_then_regrows() {
+        let inv = sample_inventory();
+        let model = GrowthModel::from_str("exponential").unwrap();
+        let thin = Thinning {
+            year: 10,
+            residual_ba_fraction: 0.6,
+        };
+        let unthinned = project_growth(&inv, &model, 20).unwrap();
+        let thinned = project_growth_with_thinning(&inv, &model, &[thin], None, 20).unwrap();
+
+        assert_eq!(thinned.len(), 21);
+        for year in 0..10 {
+            assert_eq!(thinned[year].basal_area, unthinned[year].basal_area);
+        }
+        assert!((thinned[10].basal_area - 0.6 * unthinned[10].basal_area).abs() < 1e-9);
+        assert!((thinned[10].tpa - 0.6 * unthinned[10].tpa).abs() < 1e-9);
+        assert!(thinned[10].basal_area < thinned[9].basal_area);
+        assert!(thinned[11].basal_area > thinned[10].basal_area);
+        // Exponential growth is memoryless, so the thinned stand keeps a
+        // constant 60% of the unthinned one
+        assert!((thinned[20].volume_cuft - 0.6 * unthinned[20].volume_cuft).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_thinnings_compose() {
+        let inv = sample_inventory();
+        let model = GrowthModel::from_str("logistic").unwrap();
+        let first = Thinning {
+            year: 5,
+            residual_ba_fraction: 0.7,
+        };
+        let second = Thinning {
+            year: 15,
+            residual_ba_fraction: 0.8,
+        };
+        // Order does not matter
+        let both = project_growth_with_thinning(&inv, &model, &[second, first], None, 20).unwrap();
+        let once = project_growth_with_thinning(&inv, &model, &[first], None, 20).unwrap();
+        assert_eq!(both[10].basal_area, once[10].basal_area);
+        assert!((both[15].basal_area - 0.8 * once[15].basal_area).abs() < 1e-9);
+        assert!(both[20].basal_area > both[15].basal_area);
+
+        // Two thinnings in the same year compound
+        let same_year = Thinning {
+            year: 5,
+            residual_ba_fraction: 0.5,
+        };
+        let twice =
+            project_growth_with_thinning(&inv, &model, &[first, same_year], None, 20).unwrap();
+        assert!((twice[5].basal_area - 0.5 * once[5].basal_area).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_thinning_parse_and_validation() {
+        let thin: Thinning = "15:0.6".parse().unwrap();
+        assert_eq!(
+            thin,
+            Thinning {
+                year: 15,
+                residual_ba_fraction: 0.6
+            }
+        );
+        for bad in ["15", "x:0.5", "10:abc", "10:0", "10:1.5"] {
+            assert!(bad.parse::<Thinning>().is_err(), "{bad}");
+        }
+        let model = GrowthModel::from_str("exponential").unwrap();
+        let bad = Thinning {
+            year: 5,
+            residual_ba_fraction: -0.5,
+        };
+        assert!(
+            project_growth_with_thinning(&sample_inventory(), &model, &[bad], None, 10).is_err()
+        );
+    }
+
+    #[test]
+    fn test_iter_matches_collected_projection() {
+        let inv = sample_inventory();
+        let model = GrowthModel::Logistic {
+            annual_rate: 0.05,
+            carrying_capacity: 150.0,
+            mortality_rate: 0.01,
+        };
+        let collected = project_growth(&inv, &model, 40).unwrap();
+        let lazy: Vec<GrowthProjection> = project_growth_iter(&inv, &model, 40).unwrap().collect();
+        assert_eq!(
+            serde_json::to_string(&lazy).unwrap(),
+            serde_json::to_string(&collected).unwrap()
+        );
+
+        // Taking a prefix only computes those years
+        let first_three: Vec<u32> = project_growth_iter(&inv, &model, 1_000_000)
+            .unwrap()
+            .take(3)
+            .map(|p| p.year)
+            .collect();
+        assert_eq!(first_three, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_from_derived_state_matches_inventory_projection() {
+        let inv = sample_inventory();
+        let state = StandState::from_inventory(&inv);
+        for model in [
+            GrowthModel::Exponential {
+                annual_rate: 0.03,
+                mortality_rate: 0.005,
+            },
+            GrowthModel::Logistic {
+                annual_rate: 0.05,
+                carrying_capacity: 150.0,
+                mortality_rate: 0.01,
+            },
+            GrowthModel::Linear {
+                annual_increment: -1.5,
+                mortality_rate: 0.5,
+            },
+        ] {
+            let from_state = project_growth_from(state, &model, 25).unwrap();
+            let from_inventory = project_growth(&inv, &model, 25).unwrap();
+            assert_eq!(
+                serde_json::to_string(&from_state).unwrap(),
+                serde_json::to_string(&from_inventory).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_hypothetical_state() {
+        let state = StandState {
+            tpa: 200.0,
+            basal_area: 120.0,
+            volume_cuft: 3000.0,
+            volume_bdft: 15000.0,
+        };
+        let model = GrowthModel::Linear {
+            annual_increment: 2.0,
+            mortality_rate: 1.0,
+        };
+        let proj = project_growth_from(state, &model, 10).unwrap();
+        assert_eq!(proj[0].basal_area, 120.0);
+        assert!((proj[10].basal_area - 140.0).abs() < 1e-9);
+        assert!((proj[10].tpa - 190.0).abs() < 1e-9);
+
+        let bad = StandState {
+            basal_area: -1.0,
+            ..state
+        };
+        assert!(matches!(
+            project_growth_from(bad, &model, 10),
+            Err(ForestError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_iter_reports_errors_up_front() {
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.03,
+            mortality_rate: 2.0,
+        };
+        assert!(project_growth_iter(&sample_inventory(), &model, 10).is_err());
+        assert!(project_growth_iter(&ForestInventory::new("Empty"), &model, 10).is_err());
+    }
+
     #[test]
     fn test_zero_years() {
         let inv = sample_inventory();
@@ -383,6 +1238,90 @@ mod tests {
         assert!(proj[10].tpa < proj[0].tpa);
     }
 
+    #[test]
+    fn test_chapman_richards_monotonic_and_bounded() {
+        let inv = sample_inventory();
+        let model = GrowthModel::ChapmanRichards {
+            asymptote: 300.0,
+            rate: 0.04,
+            shape: 2.5,
+            mortality_rate: 0.005,
+        };
+        let proj = project_growth(&inv, &model, 200).unwrap();
+        assert!((proj[0].basal_area - inv.mean_basal_area()).abs() < 0.001);
+        for pair in proj.windows(2) {
+            assert!(pair[1].basal_area >= pair[0].basal_area);
+            assert!(pair[1].volume_cuft >= pair[0].volume_cuft);
+            assert!(pair[1].tpa < pair[0].tpa);
+        }
+        assert!(proj.iter().all(|p| p.basal_area <= 300.0));
+        assert!((proj[200].basal_area - 300.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_chapman_richards_has_inflection() {
+        let inv = sample_inventory();
+        let model = GrowthModel::ChapmanRichards {
+            asymptote: 300.0,
+            rate: 0.05,
+            shape: 3.0,
+            mortality_rate: 0.0,
+        };
+        let proj = project_growth(&inv, &model, 100).unwrap();
+        let increments: Vec<f64> = proj
+            .windows(2)
+            .map(|p| p[1].basal_area - p[0].basal_area)
+            .collect();
+        // Growth speeds up before the inflection and slows after it
+        let peak = increments
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert!(peak > 0 && peak < increments.len() - 1);
+        assert!(increments[0] < increments[peak]);
+    }
+
+    #[test]
+    fn test_chapman_richards_declines_toward_asymptote() {
+        let inv = sample_inventory();
+        let asymptote = inv.mean_basal_area() / 2.0;
+        let model = GrowthModel::ChapmanRichards {
+            asymptote,
+            rate: 0.05,
+            shape: 1.0,
+            mortality_rate: 0.0,
+        };
+        let proj = project_growth(&inv, &model, 100).unwrap();
+        assert!(proj.windows(2).all(|p| p[1].basal_area <= p[0].basal_area));
+        assert!(proj.iter().all(|p| p.basal_area >= asymptote));
+    }
+
+    #[test]
+    fn test_chapman_richards_rejects_bad_parameters() {
+        let inv = sample_inventory();
+        let valid = (300.0, 0.03, 3.0, 0.005);
+        for (asymptote, rate, shape, mortality_rate) in [
+            (0.0, valid.1, valid.2, valid.3),
+            (valid.0, -0.01, valid.2, valid.3),
+            (valid.0, valid.1, 0.0, valid.3),
+            (valid.0, valid.1, f64::NAN, valid.3),
+            (valid.0, valid.1, valid.2, 1.0),
+        ] {
+            let model = GrowthModel::ChapmanRichards {
+                asymptote,
+                rate,
+                shape,
+                mortality_rate,
+            };
+            assert!(matches!(
+                project_growth(&inv, &model, 10),
+                Err(ForestError::ValidationError(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_linear_growth() {
         let inv = sample_inventory();
@@ -463,6 +1402,12 @@ mod tests {
                 annual_increment: 1.5,
                 mortality_rate: 0.5,
             },
+            GrowthModel::ChapmanRichards {
+                asymptote: 280.0,
+                rate: 0.04,
+                shape: 2.5,
+                mortality_rate: 0.005,
+            },
         ];
         for model in &models {
             let json = serde_json::to_string(model).unwrap();
@@ -478,6 +1423,7 @@ mod tests {
             basal_area: 150.0,
             volume_cuft: 2000.0,
             volume_bdft: 10000.0,
+            recruited_tpa: 0.0,
         };
         let json = serde_json::to_string(&proj).unwrap();
         let deserialized: GrowthProjection = serde_json::from_str(&json).unwrap();
@@ -611,6 +1557,21 @@ mod tests {
         assert!("LIN".parse::<GrowthModel>().is_ok());
     }
 
+    #[test]
+    fn test_from_str_chapman_richards() {
+        for name in ["chapman-richards", "chapman", "CR"] {
+            match name.parse::<GrowthModel>().unwrap() {
+                GrowthModel::ChapmanRichards {
+                    asymptote, shape, ..
+                } => {
+                    assert_eq!(asymptote, 300.0);
+                    assert_eq!(shape, 3.0);
+                }
+                _ => panic!("Expected ChapmanRichards"),
+            }
+        }
+    }
+
     #[test]
     fn test_from_str_invalid() {
         assert!("unknown".parse::<GrowthModel>().is_err());
@@ -623,13 +1584,55 @@ mod tests {
     #[test]
     fn test_negative_annual_rate_rejected() {
         let inv = sample_inventory();
-        let model = GrowthModel::Exponential {
+        let model = GrowthModel::Logistic {
             annual_rate: -0.01,
+            carrying_capacity: 300.0,
+            mortality_rate: 0.005,
+        };
+        assert!(project_growth(&inv, &model, 10).is_err());
+    }
+
+    #[test]
+    fn test_non_finite_rate_rejected() {
+        let inv = sample_inventory();
+        let model = GrowthModel::Exponential {
+            annual_rate: f64::NAN,
             mortality_rate: 0.005,
         };
         assert!(project_growth(&inv, &model, 10).is_err());
     }
 
+    #[test]
+    fn test_exponential_decline() {
+        let inv = sample_inventory();
+        let model = GrowthModel::Exponential {
+            annual_rate: -0.02,
+            mortality_rate: 0.005,
+        };
+        let proj = project_growth(&inv, &model, 10).unwrap();
+        for pair in proj.windows(2) {
+            assert!(pair[1].basal_area < pair[0].basal_area);
+            assert!(pair[1].volume_cuft < pair[0].volume_cuft);
+            assert!(pair[1].tpa < pair[0].tpa);
+        }
+        let expected_ba = proj[0].basal_area * (-0.2_f64).exp();
+        assert!((proj[10].basal_area - expected_ba).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_logistic_declines_toward_capacity() {
+        let inv = sample_inventory();
+        let initial_ba = inv.mean_basal_area();
+        let model = GrowthModel::Logistic {
+            annual_rate: 0.1,
+            carrying_capacity: initial_ba / 2.0,
+            mortality_rate: 0.0,
+        };
+        let proj = project_growth(&inv, &model, 50).unwrap();
+        assert!(proj[10].basal_area < initial_ba);
+        assert!(proj[50].basal_area >= initial_ba / 2.0);
+    }
+
     #[test]
     fn test_negative_mortality_rate_rejected() {
         let inv = sample_inventory();
@@ -662,13 +1665,21 @@ mod tests {
     }
 
     #[test]
-    fn test_negative_annual_increment_rejected() {
+    fn test_linear_decline_empties_stand() {
         let inv = sample_inventory();
+        let initial_ba = inv.mean_basal_area();
         let model = GrowthModel::Linear {
-            annual_increment: -1.0,
+            annual_increment: -initial_ba / 5.0,
             mortality_rate: 0.5,
         };
-        assert!(project_growth(&inv, &model, 10).is_err());
+        let proj = project_growth(&inv, &model, 10).unwrap();
+        assert!(proj[3].basal_area < initial_ba);
+        assert!(proj[3].tpa > 0.0);
+        // Once basal area is gone, no trees or volume remain
+        let last = proj.last().unwrap();
+        assert_eq!(last.basal_area, 0.0);
+        assert_eq!(last.tpa, 0.0);
+        assert_eq!(last.volume_cuft, 0.0);
     }
 
     #[test]
@@ -680,4 +1691,121 @@ mod tests {
         };
         assert!(project_growth(&inv, &model, 10).is_err());
     }
+
+    fn make_species_tree(plot_id: u32, code: &str, name: &str, dbh: f64) -> Tree {
+        Tree {
+            species: Species {
+                common_name: name.to_string(),
+                code: code.to_string(),
+            },
+            ..make_tree(plot_id, dbh)
+        }
+    }
+
+    fn mixed_inventory() -> ForestInventory {
+        let mut inv = ForestInventory::new("Mixed");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_species_tree(1, "DF", "Douglas Fir", 16.0),
+                make_species_tree(1, "WH", "Western Hemlock", 10.0),
+            ],
+        ));
+        inv.plots.push(make_plot(
+            2,
+            vec![
+                make_species_tree(2, "DF", "Douglas Fir", 18.0),
+                make_species_tree(2, "WH", "Western Hemlock", 12.0),
+            ],
+        ));
+        inv
+    }
+
+    #[test]
+    fn test_species_growth_composition_shifts() {
+        let inv = mixed_inventory();
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.01,
+            mortality_rate: 0.005,
+        };
+        // Shade-tolerant hemlock grows faster
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "WH".to_string(),
+            GrowthModel::Exponential {
+                annual_rate: 0.05,
+                mortality_rate: 0.005,
+            },
+        );
+        let proj = project_growth_by_species_with(&inv, &model, &overrides, 20).unwrap();
+        assert_eq!(proj.len(), 21);
+
+        let share = |p: &SpeciesGrowthProjection, code: &str| {
+            let sp = p.species.iter().find(|s| s.species.code == code).unwrap();
+            sp.basal_area / p.total.basal_area
+        };
+        assert!(share(&proj[20], "WH") > share(&proj[0], "WH"));
+        assert!(share(&proj[20], "DF") < share(&proj[0], "DF"));
+    }
+
+    #[test]
+    fn test_species_growth_totals_equal_sum() {
+        let inv = mixed_inventory();
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.03,
+            mortality_rate: 0.005,
+        };
+        for p in project_growth_by_species(&inv, &model, 10).unwrap() {
+            let ba: f64 = p.species.iter().map(|s| s.basal_area).sum();
+            let vol: f64 = p.species.iter().map(|s| s.volume_cuft).sum();
+            assert!((p.total.basal_area - ba).abs() < 1e-9);
+            assert!((p.total.volume_cuft - vol).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_species_growth_matches_stand_projection() {
+        let inv = mixed_inventory();
+        for model in [
+            GrowthModel::Exponential {
+                annual_rate: 0.03,
+                mortality_rate: 0.005,
+            },
+            GrowthModel::Logistic {
+                annual_rate: 0.05,
+                carrying_capacity: 300.0,
+                mortality_rate: 0.005,
+            },
+            GrowthModel::Linear {
+                annual_increment: 2.0,
+                mortality_rate: 0.5,
+            },
+        ] {
+            let stand = project_growth(&inv, &model, 10).unwrap();
+            let by_species = project_growth_by_species(&inv, &model, 10).unwrap();
+            for (s, sp) in stand.iter().zip(&by_species) {
+                assert!((s.tpa - sp.total.tpa).abs() < 1e-6);
+                assert!((s.basal_area - sp.total.basal_area).abs() < 1e-6);
+                assert!((s.volume_cuft - sp.total.volume_cuft).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_species_growth_invalid_override() {
+        let inv = mixed_inventory();
+        let model = GrowthModel::Exponential {
+            annual_rate: 0.03,
+            mortality_rate: 0.005,
+        };
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "WH".to_string(),
+            GrowthModel::Exponential {
+                annual_rate: 0.03,
+                mortality_rate: 1.5,
+            },
+        );
+        assert!(project_growth_by_species_with(&inv, &model, &overrides, 5).is_err());
+    }
 }
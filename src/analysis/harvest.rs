@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ForestInventory, Species, TreeStatus};
+
+/// Per-acre removals for one species.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciesHarvest {
+    pub species: Species,
+    pub tpa: f64,
+    pub basal_area: f64,
+    pub volume_cuft: f64,
+    pub volume_bdft: f64,
+}
+
+/// Per-acre removals of trees tagged [`TreeStatus::Cut`] in a single inventory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarvestSummary {
+    pub tpa: f64,
+    pub basal_area: f64,
+    pub volume_cuft: f64,
+    pub volume_bdft: f64,
+    /// Cut basal area as a percentage of pre-harvest (live + cut) basal area;
+    /// `None` if there was no pre-harvest basal area
+    pub percent_basal_area_removed: Option<f64>,
+    /// Removals by species, largest basal area first
+    pub species: Vec<SpeciesHarvest>,
+}
+
+/// Summarize per-acre TPA, basal area and volume of cut trees, by species.
+///
+/// Values are averaged over every plot in the inventory, like the live-tree
+/// metrics, so they are directly comparable to [`crate::analysis::StandMetrics`].
+/// Cut trees without a height contribute no volume.
+pub fn harvest_summary(inventory: &ForestInventory) -> HarvestSummary {
+    let inventory = &*inventory.to_per_acre();
+    let num_plots = inventory.num_plots();
+    let mut by_species: HashMap<String, SpeciesHarvest> = HashMap::new();
+    let mut live_ba = 0.0;
+
+    for tree in inventory.plots.iter().flat_map(|p| p.trees.iter()) {
+        match tree.status {
+            TreeStatus::Live => live_ba += tree.basal_area_per_acre(),
+            TreeStatus::Cut => {
+                let entry = by_species
+                    .entry(tree.species.code.clone())
+                    .or_insert_with(|| SpeciesHarvest {
+                        species: tree.species.clone(),
+                        tpa: 0.0,
+                        basal_area: 0.0,
+                        volume_cuft: 0.0,
+                        volume_bdft: 0.0,
+                    });
+                entry.tpa += tree.expansion_factor;
+                entry.basal_area += tree.basal_area_per_acre();
+                entry.volume_cuft += tree.volume_cuft().unwrap_or(0.0) * tree.expansion_factor;
+                entry.volume_bdft += tree.volume_bdft().unwrap_or(0.0) * tree.expansion_factor;
+            }
+            TreeStatus::Dead | TreeStatus::Missing => {}
+        }
+    }
+
+    let n = num_plots.max(1) as f64;
+    let mut species: Vec<SpeciesHarvest> = by_species
+        .into_values()
+        .map(|s| SpeciesHarvest {
+            tpa: s.tpa / n,
+            basal_area: s.basal_area / n,
+            volume_cuft: s.volume_cuft / n,
+            volume_bdft: s.volume_bdft / n,
+            ..s
+        })
+        .collect();
+    species.sort_by(|a, b| {
+        b.basal_area
+            .partial_cmp(&a.basal_area)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.species.code.cmp(&b.species.code))
+    });
+
+    let basal_area: f64 = species.iter().map(|s| s.basal_area).sum();
+    let pre_harvest_ba = live_ba / n + basal_area;
+    HarvestSummary {
+        tpa: species.iter().map(|s| s.tpa).sum(),
+        basal_area,
+        volume_cuft: species.iter().map(|s| s.volume_cuft).sum(),
+        volume_bdft: species.iter().map(|s| s.volume_bdft).sum(),
+        percent_basal_area_removed: (pre_harvest_ba > 0.0)
+            .then(|| basal_area / pre_harvest_ba * 100.0),
+        species,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Plot, Tree};
+
+    fn make_tree(plot_id: u32, code: &str, dbh: f64, status: TreeStatus) -> Tree {
+        Tree {
+            tree_id: 1,
+            plot_id,
+            species: Species {
+                common_name: code.to_string(),
+                code: code.to_string(),
+            },
+            dbh,
+            height: Some(90.0),
+            crown_ratio: Some(0.5),
+            status,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    fn make_plot(plot_id: u32, trees: Vec<Tree>) -> Plot {
+        Plot {
+            plot_id,
+            plot_size_acres: 0.2,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees,
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        }
+    }
+
+    #[test]
+    fn test_harvest_summary_counts_cut_trees_by_species() {
+        let mut inv = ForestInventory::new("Thinned");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, "DF", 16.0, TreeStatus::Live),
+                make_tree(1, "DF", 12.0, TreeStatus::Cut),
+                make_tree(1, "WH", 10.0, TreeStatus::Cut),
+                make_tree(1, "WH", 8.0, TreeStatus::Dead),
+            ],
+        ));
+        inv.plots.push(make_plot(
+            2,
+            vec![
+                make_tree(2, "DF", 18.0, TreeStatus::Live),
+                make_tree(2, "DF", 14.0, TreeStatus::Cut),
+            ],
+        ));
+
+        let summary = harvest_summary(&inv);
+        // Three cut trees at EF 5 over two plots
+        assert!((summary.tpa - 7.5).abs() < 1e-9);
+        assert_eq!(summary.species.len(), 2);
+        assert_eq!(summary.species[0].species.code, "DF");
+        assert!((summary.species[0].tpa - 5.0).abs() < 1e-9);
+        assert!((summary.species[1].tpa - 2.5).abs() < 1e-9);
+
+        let cut_ba = (make_tree(1, "DF", 12.0, TreeStatus::Cut).basal_area_per_acre()
+            + make_tree(1, "DF", 14.0, TreeStatus::Cut).basal_area_per_acre()
+            + make_tree(1, "WH", 10.0, TreeStatus::Cut).basal_area_per_acre())
+            / 2.0;
+        assert!((summary.basal_area - cut_ba).abs() < 1e-9);
+        assert!(summary.volume_bdft > 0.0);
+
+        let pre_harvest = inv.mean_basal_area() + cut_ba;
+        let pct = summary.percent_basal_area_removed.unwrap();
+        assert!((pct - cut_ba / pre_harvest * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_harvest_summary_no_cut_trees() {
+        let mut inv = ForestInventory::new("Uncut");
+        inv.plots.push(make_plot(
+            1,
+            vec![make_tree(1, "DF", 16.0, TreeStatus::Live)],
+        ));
+        let summary = harvest_summary(&inv);
+        assert!(summary.species.is_empty());
+        assert_eq!(summary.tpa, 0.0);
+        assert_eq!(summary.percent_basal_area_removed, Some(0.0));
+
+        let empty = harvest_summary(&ForestInventory::new("Empty"));
+        assert!(empty.percent_basal_area_removed.is_none());
+    }
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::ForestError;
+use crate::models::ForestInventory;
+
+/// Default number of residual standard deviations beyond which a tree's
+/// height is flagged by [`hd_relationship`].
+pub const DEFAULT_HD_OUTLIER_SD: f64 = 3.0;
+
+/// One measured tree in a height-diameter fit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HdPoint {
+    pub plot_id: u32,
+    pub tree_id: u32,
+    /// Diameter at breast height in inches
+    pub dbh: f64,
+    /// Measured height in feet
+    pub height: f64,
+    /// Measured minus fitted height in feet
+    pub residual: f64,
+    /// Whether the residual is beyond the outlier threshold
+    pub outlier: bool,
+}
+
+/// Height-diameter curve `H = a + b·ln(DBH)` fitted by least squares over
+/// live trees with measured heights.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HdFit {
+    /// Intercept in feet
+    pub a: f64,
+    /// Slope in feet per unit of ln(DBH in inches)
+    pub b: f64,
+    /// Residual standard deviation in feet (n − 2 degrees of freedom)
+    pub residual_sd: f64,
+    /// Every tree used in the fit, in inventory order, for charting
+    pub points: Vec<HdPoint>,
+}
+
+impl HdFit {
+    /// Fitted height in feet for a DBH in inches.
+    pub fn predict(&self, dbh: f64) -> f64 {
+        self.a + self.b * dbh.ln()
+    }
+
+    /// Trees whose heights were flagged as outliers.
+    pub fn outliers(&self) -> impl Iterator<Item = &HdPoint> {
+        self.points.iter().filter(|p| p.outlier)
+    }
+}
+
+/// Fit heights against diameters and flag trees more than
+/// [`DEFAULT_HD_OUTLIER_SD`] residual standard deviations off the curve.
+pub fn hd_relationship(inventory: &ForestInventory) -> Result<HdFit, ForestError> {
+    hd_relationship_with(inventory, DEFAULT_HD_OUTLIER_SD)
+}
+
+/// Fit `H = a + b·ln(DBH)` over live trees with a positive DBH and height,
+/// flagging trees whose residual exceeds `k` residual standard deviations.
+///
+/// A bad height or DBH record usually stands out as such an outlier.
+/// Returns `ForestError::ValidationError` unless `k` is positive and finite,
+/// and `ForestError::InsufficientData` with fewer than three measured trees
+/// or when every tree has the same DBH.
+pub fn hd_relationship_with(inventory: &ForestInventory, k: f64) -> Result<HdFit, ForestError> {
+    if !(k.is_finite() && k > 0.0) {
+        return Err(ForestError::ValidationError(format!(
+            "Outlier threshold must be positive, got {k}"
+        )));
+    }
+
+    let measured: Vec<(u32, u32, f64, f64)> = inventory
+        .plots
+        .iter()
+        .flat_map(|p| p.live_trees())
+        .filter_map(|t| {
+            let height = t.height.filter(|&h| h > 0.0)?;
+            (t.dbh > 0.0).then_some((t.plot_id, t.tree_id, t.dbh, height))
+        })
+        .collect();
+    if measured.len() < 3 {
+        return Err(ForestError::InsufficientData(format!(
+            "Height-diameter fit needs at least 3 live trees with heights, found {}",
+            measured.len()
+        )));
+    }
+
+    let n = measured.len() as f64;
+    let mean_x = measured.iter().map(|&(_, _, d, _)| d.ln()).sum::<f64>() / n;
+    let mean_y = measured.iter().map(|&(_, _, _, h)| h).sum::<f64>() / n;
+    let (sxx, sxy) = measured
+        .iter()
+        .fold((0.0, 0.0), |(sxx, sxy), &(_, _, d, h)| {
+            let dx = d.ln() - mean_x;
+            (sxx + dx * dx, sxy + dx * (h - mean_y))
+        });
+    if sxx <= f64::EPSILON {
+        return Err(ForestError::InsufficientData(
+            "Height-diameter fit needs trees of more than one DBH".to_string(),
+        ));
+    }
+    let b = sxy / sxx;
+    let a = mean_y - b * mean_x;
+
+    let residuals: Vec<f64> = measured
+        .iter()
+        .map(|&(_, _, d, h)| h - (a + b * d.ln()))
+        .collect();
+    let sse: f64 = residuals.iter().map(|r| r * r).sum();
+    let residual_sd = (sse / (n - 2.0)).sqrt();
+
+    let points = measured
+        .into_iter()
+        .zip(residuals)
+        .map(|((plot_id, tree_id, dbh, height), residual)| HdPoint {
+            plot_id,
+            tree_id,
+            dbh,
+            height,
+            residual,
+            outlier: residual_sd > 0.0 && residual.abs() > k * residual_sd,
+        })
+        .collect();
+
+    Ok(HdFit {
+        a,
+        b,
+        residual_sd,
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Species, Tree, TreeStatus};
+
+    fn tree(tree_id: u32, dbh: f64, height: Option<f64>) -> Tree {
+        Tree {
+            tree_id,
+            plot_id: 1,
+            species: Species {
+                common_name: "Douglas Fir".to_string(),
+                code: "DF".to_string(),
+            },
+            dbh,
+            height,
+            crown_ratio: None,
+            status: TreeStatus::Live,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    /// Trees on the curve `H = -40 + 45·ln(DBH)`, with small alternating noise.
+    fn clean_stand() -> Vec<Tree> {
+        (1..=20)
+            .map(|i| {
+                let dbh = 4.0 + 1.5 * i as f64;
+                let noise = if i % 2 == 0 { 0.5 } else { -0.5 };
+                tree(i, dbh, Some(-40.0 + 45.0 * dbh.ln() + noise))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_stand_recovers_coefficients() {
+        let inv = ForestInventory::from_trees("HD", clean_stand());
+        let fit = hd_relationship(&inv).unwrap();
+        assert!((fit.a + 40.0).abs() < 1.0, "a = {}", fit.a);
+        assert!((fit.b - 45.0).abs() < 0.5, "b = {}", fit.b);
+        assert!(fit.residual_sd < 1.0);
+        assert_eq!(fit.points.len(), 20);
+        assert_eq!(fit.outliers().count(), 0);
+        assert!((fit.predict(20.0) - (-40.0 + 45.0 * 20.0_f64.ln())).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_bad_height_is_flagged() {
+        let mut trees = clean_stand();
+        // A 16" tree recorded at 16 ft, e.g. a DBH typed into the height column
+        trees.push(tree(21, 16.0, Some(16.0)));
+        let inv = ForestInventory::from_trees("HD", trees);
+        let fit = hd_relationship(&inv).unwrap();
+
+        let outliers: Vec<u32> = fit.outliers().map(|p| p.tree_id).collect();
+        assert_eq!(outliers, vec![21]);
+        assert!(fit.points.last().unwrap().residual < 0.0);
+    }
+
+    #[test]
+    fn test_skips_trees_without_heights_and_dead_trees() {
+        let mut trees = clean_stand();
+        trees.push(tree(21, 30.0, None));
+        trees.push(Tree {
+            status: TreeStatus::Dead,
+            ..tree(22, 30.0, Some(5.0))
+        });
+        let inv = ForestInventory::from_trees("HD", trees);
+        assert_eq!(hd_relationship(&inv).unwrap().points.len(), 20);
+    }
+
+    #[test]
+    fn test_insufficient_data() {
+        let two = ForestInventory::from_trees(
+            "HD",
+            vec![tree(1, 10.0, Some(60.0)), tree(2, 12.0, Some(70.0))],
+        );
+        assert!(matches!(
+            hd_relationship(&two),
+            Err(ForestError::InsufficientData(_))
+        ));
+
+        let same_dbh = ForestInventory::from_trees(
+            "HD",
+            (1..=4).map(|i| tree(i, 10.0, Some(60.0 + i as f64))),
+        );
+        assert!(matches!(
+            hd_relationship(&same_dbh),
+            Err(ForestError::InsufficientData(_))
+        ));
+
+        let inv = ForestInventory::from_trees("HD", clean_stand());
+        assert!(matches!(
+            hd_relationship_with(&inv, 0.0),
+            Err(ForestError::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use super::compute_stand_metrics;
+use crate::error::ForestError;
+use crate::models::ForestInventory;
+
+/// Mean annual change in per-acre stand values between two measurements.
+///
+/// Signed: a stand that lost basal area or volume has negative increments.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnnualIncrement {
+    /// Live trees per acre per year
+    pub tpa_per_year: f64,
+    /// Basal area in sq ft per acre per year
+    pub ba_per_year: f64,
+    /// Gross cubic-foot volume per acre per year
+    pub cuft_per_year: f64,
+    /// Board-foot volume per acre per year
+    pub bdft_per_year: f64,
+}
+
+/// Absolute growth per acre per year between two measurements of a stand,
+/// e.g. "2.1 sq ft/ac/yr of basal area".
+///
+/// Each increment is the difference of the live-tree stand means from
+/// [`compute_stand_metrics`] divided by `years`, the (possibly fractional)
+/// interval between `before` and `after`. This complements percentage rates
+/// such as a compound annual growth rate. Returns
+/// `ForestError::ValidationError` unless `years` is positive and finite.
+pub fn annual_increment(
+    before: &ForestInventory,
+    after: &ForestInventory,
+    years: f64,
+) -> Result<AnnualIncrement, ForestError> {
+    if !(years.is_finite() && years > 0.0) {
+        return Err(ForestError::ValidationError(format!(
+            "Re-measurement interval must be positive, got {years} years"
+        )));
+    }
+
+    let (b, a) = (compute_stand_metrics(before), compute_stand_metrics(after));
+    Ok(AnnualIncrement {
+        tpa_per_year: (a.total_tpa - b.total_tpa) / years,
+        ba_per_year: (a.total_basal_area - b.total_basal_area) / years,
+        cuft_per_year: (a.total_volume_cuft - b.total_volume_cuft) / years,
+        bdft_per_year: (a.total_volume_bdft - b.total_volume_bdft) / years,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Species, Tree, TreeStatus};
+
+    fn stand(dbh: f64, expansion_factor: f64) -> ForestInventory {
+        let trees = (1..=3).map(|plot_id| Tree {
+            tree_id: 1,
+            plot_id,
+            species: Species {
+                common_name: "Douglas Fir".to_string(),
+                code: "DF".to_string(),
+            },
+            dbh,
+            height: Some(100.0),
+            crown_ratio: Some(0.5),
+            status: TreeStatus::Live,
+            expansion_factor,
+            age: None,
+            defect: None,
+            decay_class: None,
+        });
+        ForestInventory::from_trees("Stand", trees)
+    }
+
+    #[test]
+    fn test_recovers_known_increment() {
+        // 100 TPA of 12" trees growing to 14" over 5 years
+        let before = stand(12.0, 100.0);
+        let after = stand(14.0, 100.0);
+        let inc = annual_increment(&before, &after, 5.0).unwrap();
+
+        let ba = |dbh: f64| std::f64::consts::PI * (dbh / 24.0).powi(2) * 100.0;
+        assert!((inc.ba_per_year - (ba(14.0) - ba(12.0)) / 5.0).abs() < 1e-6);
+        assert_eq!(inc.tpa_per_year, 0.0);
+        assert!(inc.cuft_per_year > 0.0);
+        assert!(inc.bdft_per_year > 0.0);
+    }
+
+    #[test]
+    fn test_decline_is_negative() {
+        let before = stand(14.0, 100.0);
+        let after = stand(14.0, 80.0);
+        let inc = annual_increment(&before, &after, 2.5).unwrap();
+        assert!((inc.tpa_per_year + 8.0).abs() < 1e-9);
+        assert!(inc.ba_per_year < 0.0);
+        assert!(inc.cuft_per_year < 0.0);
+    }
+
+    #[test]
+    fn test_rejects_non_positive_years() {
+        let inv = stand(12.0, 100.0);
+        for years in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                annual_increment(&inv, &inv, years),
+                Err(ForestError::ValidationError(_))
+            ));
+        }
+    }
+}
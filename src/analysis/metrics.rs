@@ -1,8 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
-use crate::models::{ForestInventory, Species};
+use super::dmd::REINEKE_EXPONENT;
+use crate::models::{
+    wood_density, AgeSummary, ForestInventory, Species, TreeStatus, UnitSystem, VolumeEquation,
+    VolumeModel, CARBON_FRACTION, DEFAULT_SITE_INDEX_BASE_AGE,
+};
+
+/// DBH (inches) at or above which a live tree counts toward
+/// [`StandMetrics::ba_in_large_trees_percent`].
+pub const LARGE_TREE_DBH: f64 = 21.0;
+
+/// Maximum stand density index that [`StandMetrics::relative_density`] is
+/// relative to unless set with [`StandMetrics::with_max_sdi`]; Reineke's
+/// value for Douglas-fir.
+pub const DEFAULT_MAX_SDI: f64 = 450.0;
+
+/// Pounds in a short ton.
+const LBS_PER_TON: f64 = 2000.0;
 
 /// Per-species composition data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +32,21 @@ pub struct SpeciesComposition {
     pub mean_height: Option<f64>,
 }
 
+/// Standing dead tree (snag) metrics, from trees with status
+/// [`TreeStatus::Dead`] only.
+///
+/// All values are zero for a stand without snags.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnagMetrics {
+    pub snag_tpa: f64,
+    pub snag_basal_area: f64,
+    /// Cubic volume of snags with heights, net of recorded defect but not
+    /// reduced for decay class
+    pub snag_volume_cuft: f64,
+    /// Expansion-factor-weighted mean DBH of snags
+    pub mean_snag_dbh: f64,
+}
+
 /// Overall stand-level metrics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StandMetrics {
@@ -23,14 +54,201 @@ pub struct StandMetrics {
     pub total_basal_area: f64,
     pub total_volume_cuft: f64,
     pub total_volume_bdft: f64,
+    /// Dry stem biomass of live trees in short tons per acre, from cubic
+    /// volume at each species' [`wood_density`]
+    #[serde(default)]
+    pub total_biomass_tons_per_acre: f64,
+    /// Carbon in live trees in short tons per acre, [`CARBON_FRACTION`] of
+    /// the biomass
+    #[serde(default)]
+    pub total_carbon_tons_per_acre: f64,
     pub quadratic_mean_diameter: f64,
     pub mean_height: Option<f64>,
+    /// Expansion-factor-weighted mean height:DBH ratio of live trees with heights
+    pub mean_slenderness: Option<f64>,
+    /// Expansion-factor-weighted mean crown ratio (0-1) of live trees with a crown ratio
+    pub mean_crown_ratio: Option<f64>,
+    /// Share of live basal area (0-100) in trees of at least [`LARGE_TREE_DBH`]
+    #[serde(default)]
+    pub ba_in_large_trees_percent: f64,
+    /// Reineke stand density index of live trees: TPA × (QMD / 10)^1.605
+    #[serde(default)]
+    pub stand_density_index: f64,
+    /// Stand density index as a percentage of the maximum SDI, by default
+    /// [`DEFAULT_MAX_SDI`]
+    #[serde(default)]
+    pub relative_density: f64,
+    /// Shannon diversity index of live species by TPA: −Σ p·ln(p)
+    #[serde(default)]
+    pub shannon_index: f64,
+    /// Simpson diversity index of live species by TPA: 1 − Σ p²
+    #[serde(default)]
+    pub simpson_index: f64,
     pub num_species: usize,
     pub species_composition: Vec<SpeciesComposition>,
+    /// Ages of cored live trees; see [`ForestInventory::age_summary`]
+    #[serde(default)]
+    pub age: Option<AgeSummary>,
+    /// King's Douglas-fir site index at base age
+    /// [`DEFAULT_SITE_INDEX_BASE_AGE`]; see [`ForestInventory::site_index`]
+    #[serde(default)]
+    pub site_index: Option<f64>,
+    /// Snag density, basal area and volume; the other metrics cover live
+    /// trees only
+    #[serde(default)]
+    pub snags: SnagMetrics,
+    /// Units the values are in; imperial unless converted with
+    /// [`to_metric`](Self::to_metric)
+    #[serde(default)]
+    pub units: UnitSystem,
+}
+
+impl StandMetrics {
+    /// Recompute [`relative_density`](Self::relative_density) against
+    /// `max_sdi`, e.g. a species' maximum from a density management guide.
+    /// A `max_sdi` that is not positive gives a relative density of zero.
+    pub fn with_max_sdi(mut self, max_sdi: f64) -> Self {
+        self.relative_density = relative_density(self.stand_density_index, max_sdi);
+        self
+    }
+
+    /// The metrics in metric units: DBH in centimeters, height in meters,
+    /// trees per hectare, basal area in m²/ha, cubic volume in m³/ha and
+    /// biomass and carbon in tonnes/ha.
+    ///
+    /// Board-foot volume has no metric equivalent and is only put on a
+    /// per-hectare basis. The stand density index becomes trees per hectare
+    /// at a 25.4 cm reference diameter; percentages and ratios are unchanged.
+    /// Metrics already in metric units are returned as they are.
+    pub fn to_metric(&self) -> StandMetrics {
+        let units = UnitSystem::Metric;
+        if self.units == units {
+            return self.clone();
+        }
+        StandMetrics {
+            total_tpa: units.per_area(self.total_tpa),
+            total_basal_area: units.basal_area_per_area(self.total_basal_area),
+            total_volume_cuft: units.volume_per_area(self.total_volume_cuft),
+            total_volume_bdft: units.per_area(self.total_volume_bdft),
+            total_biomass_tons_per_acre: units.mass_per_area(self.total_biomass_tons_per_acre),
+            total_carbon_tons_per_acre: units.mass_per_area(self.total_carbon_tons_per_acre),
+            quadratic_mean_diameter: units.diameter(self.quadratic_mean_diameter),
+            mean_height: self.mean_height.map(|h| units.height(h)),
+            site_index: self.site_index.map(|h| units.height(h)),
+            stand_density_index: units.per_area(self.stand_density_index),
+            species_composition: self
+                .species_composition
+                .iter()
+                .map(|sc| SpeciesComposition {
+                    tpa: units.per_area(sc.tpa),
+                    basal_area: units.basal_area_per_area(sc.basal_area),
+                    mean_dbh: units.diameter(sc.mean_dbh),
+                    mean_height: sc.mean_height.map(|h| units.height(h)),
+                    ..sc.clone()
+                })
+                .collect(),
+            snags: SnagMetrics {
+                snag_tpa: units.per_area(self.snags.snag_tpa),
+                snag_basal_area: units.basal_area_per_area(self.snags.snag_basal_area),
+                snag_volume_cuft: units.volume_per_area(self.snags.snag_volume_cuft),
+                mean_snag_dbh: units.diameter(self.snags.mean_snag_dbh),
+            },
+            units,
+            ..self.clone()
+        }
+    }
+
+    /// Flatten the metrics into a map of dotted keys to values, for templating.
+    ///
+    /// Top-level keys use the serialized field names (`total_tpa`,
+    /// `quadratic_mean_diameter`, ...). Per-species values are keyed by
+    /// species code, e.g. `species.DF.percent_basal_area`. Optional values
+    /// that are `None` are omitted.
+    pub fn to_flat_map(&self) -> BTreeMap<String, f64> {
+        let mut map = BTreeMap::new();
+        map.insert("total_tpa".to_string(), self.total_tpa);
+        map.insert("total_basal_area".to_string(), self.total_basal_area);
+        map.insert("total_volume_cuft".to_string(), self.total_volume_cuft);
+        map.insert("total_volume_bdft".to_string(), self.total_volume_bdft);
+        map.insert(
+            "total_biomass_tons_per_acre".to_string(),
+            self.total_biomass_tons_per_acre,
+        );
+        map.insert(
+            "total_carbon_tons_per_acre".to_string(),
+            self.total_carbon_tons_per_acre,
+        );
+        map.insert(
+            "quadratic_mean_diameter".to_string(),
+            self.quadratic_mean_diameter,
+        );
+        if let Some(h) = self.mean_height {
+            map.insert("mean_height".to_string(), h);
+        }
+        if let Some(s) = self.mean_slenderness {
+            map.insert("mean_slenderness".to_string(), s);
+        }
+        if let Some(cr) = self.mean_crown_ratio {
+            map.insert("mean_crown_ratio".to_string(), cr);
+        }
+        map.insert(
+            "ba_in_large_trees_percent".to_string(),
+            self.ba_in_large_trees_percent,
+        );
+        map.insert("stand_density_index".to_string(), self.stand_density_index);
+        map.insert("relative_density".to_string(), self.relative_density);
+        map.insert("shannon_index".to_string(), self.shannon_index);
+        map.insert("simpson_index".to_string(), self.simpson_index);
+        map.insert("num_species".to_string(), self.num_species as f64);
+        if let Some(age) = &self.age {
+            map.insert("age.mean".to_string(), age.mean);
+            map.insert("age.min".to_string(), f64::from(age.min));
+            map.insert("age.max".to_string(), f64::from(age.max));
+            map.insert("age.std_dev".to_string(), age.std_dev);
+        }
+        if let Some(si) = self.site_index {
+            map.insert("site_index".to_string(), si);
+        }
+        map.insert("snags.snag_tpa".to_string(), self.snags.snag_tpa);
+        map.insert(
+            "snags.snag_basal_area".to_string(),
+            self.snags.snag_basal_area,
+        );
+        map.insert(
+            "snags.snag_volume_cuft".to_string(),
+            self.snags.snag_volume_cuft,
+        );
+        map.insert("snags.mean_snag_dbh".to_string(), self.snags.mean_snag_dbh);
+
+        for sc in &self.species_composition {
+            let prefix = format!("species.{}", sc.species.code);
+            map.insert(format!("{prefix}.tpa"), sc.tpa);
+            map.insert(format!("{prefix}.basal_area"), sc.basal_area);
+            map.insert(format!("{prefix}.percent_tpa"), sc.percent_tpa);
+            map.insert(format!("{prefix}.percent_basal_area"), sc.percent_basal_area);
+            map.insert(format!("{prefix}.mean_dbh"), sc.mean_dbh);
+            if let Some(h) = sc.mean_height {
+                map.insert(format!("{prefix}.mean_height"), h);
+            }
+        }
+        map
+    }
 }
 
 /// Compute stand-level metrics from a forest inventory.
 pub fn compute_stand_metrics(inventory: &ForestInventory) -> StandMetrics {
+    compute_stand_metrics_with(inventory, &VolumeEquation::default())
+}
+
+/// Compute stand-level metrics, using `volume_model` (e.g. a
+/// [`VolumeEquation`] with custom coefficients, or a
+/// [`SpeciesVolumeTable`](crate::models::SpeciesVolumeTable) of per-species
+/// equations) for the volume totals.
+pub fn compute_stand_metrics_with(
+    inventory: &ForestInventory,
+    volume_model: &dyn VolumeModel,
+) -> StandMetrics {
+    let inventory = &*inventory.to_per_acre();
     let num_plots = inventory.num_plots() as f64;
     if num_plots == 0.0 {
         return StandMetrics {
@@ -38,10 +256,23 @@ pub fn compute_stand_metrics(inventory: &ForestInventory) -> StandMetrics {
             total_basal_area: 0.0,
             total_volume_cuft: 0.0,
             total_volume_bdft: 0.0,
+            total_biomass_tons_per_acre: 0.0,
+            total_carbon_tons_per_acre: 0.0,
             quadratic_mean_diameter: 0.0,
             mean_height: None,
+            mean_slenderness: None,
+            mean_crown_ratio: None,
+            ba_in_large_trees_percent: 0.0,
+            stand_density_index: 0.0,
+            relative_density: 0.0,
+            shannon_index: 0.0,
+            simpson_index: 0.0,
             num_species: 0,
             species_composition: Vec::new(),
+            age: None,
+            site_index: None,
+            snags: SnagMetrics::default(),
+            units: UnitSystem::Imperial,
         };
     }
 
@@ -53,8 +284,8 @@ pub fn compute_stand_metrics(inventory: &ForestInventory) -> StandMetrics {
             (
                 tpa + plot.trees_per_acre(),
                 ba + plot.basal_area_per_acre(),
-                vc + plot.volume_cuft_per_acre(),
-                vb + plot.volume_bdft_per_acre(),
+                vc + plot.volume_cuft_per_acre_with(volume_model),
+                vb + plot.volume_bdft_per_acre_with(volume_model),
             )
         });
     let total_tpa = sum_tpa / num_plots;
@@ -62,6 +293,18 @@ pub fn compute_stand_metrics(inventory: &ForestInventory) -> StandMetrics {
     let total_vol_cuft = sum_vol_cuft / num_plots;
     let total_vol_bdft = sum_vol_bdft / num_plots;
 
+    // Dry biomass of live trees with heights, at each species' wood density
+    let sum_biomass_lbs: f64 = inventory
+        .plots
+        .iter()
+        .flat_map(|p| p.live_trees())
+        .filter_map(|t| {
+            t.biomass_lbs_with(volume_model, wood_density(&t.species.code))
+                .map(|b| b * t.expansion_factor)
+        })
+        .sum();
+    let total_biomass = sum_biomass_lbs / LBS_PER_TON / num_plots;
+
     // Stand-level QMD: sqrt(Σ(EF × DBH²) / Σ(EF)) across all live trees
     let (sum_ef_dbh_sq, sum_ef) = inventory
         .plots
@@ -91,6 +334,69 @@ pub fn compute_stand_metrics(inventory: &ForestInventory) -> StandMetrics {
         None
     };
 
+    // Mean slenderness (height:DBH) of live trees, weighted by expansion factor
+    let (weighted_slenderness_sum, ef_sum_with_slenderness) = inventory
+        .plots
+        .iter()
+        .flat_map(|p| p.live_trees())
+        .filter_map(|t| t.slenderness().map(|s| (s * t.expansion_factor, t.expansion_factor)))
+        .fold((0.0, 0.0_f64), |(ws, ef), (ws_i, ef_i)| (ws + ws_i, ef + ef_i));
+    let mean_slenderness = if ef_sum_with_slenderness > 0.0 {
+        Some(weighted_slenderness_sum / ef_sum_with_slenderness)
+    } else {
+        None
+    };
+
+    // Mean crown ratio of live trees, weighted by expansion factor
+    let (weighted_cr_sum, ef_sum_with_cr) = inventory
+        .plots
+        .iter()
+        .flat_map(|p| p.live_trees())
+        .filter_map(|t| t.crown_ratio.map(|cr| (cr * t.expansion_factor, t.expansion_factor)))
+        .fold((0.0, 0.0_f64), |(wc, ef), (wc_i, ef_i)| (wc + wc_i, ef + ef_i));
+    let mean_crown_ratio = if ef_sum_with_cr > 0.0 {
+        Some(weighted_cr_sum / ef_sum_with_cr)
+    } else {
+        None
+    };
+
+    // Share of basal area in large live trees
+    let large_ba = inventory
+        .plots
+        .iter()
+        .flat_map(|p| p.live_trees())
+        .filter(|t| t.dbh >= LARGE_TREE_DBH)
+        .fold(0.0, |ba, t| ba + t.basal_area_per_acre());
+    let ba_in_large_trees_percent = if total_ba > 0.0 {
+        large_ba / num_plots / total_ba * 100.0
+    } else {
+        0.0
+    };
+
+    // Snags: dead trees only, averaged over all plots like the live totals
+    let (snag_ef, snag_ba, snag_vol, snag_ef_dbh) = inventory
+        .plots
+        .iter()
+        .flat_map(|p| p.trees_with_status(&TreeStatus::Dead))
+        .fold((0.0, 0.0, 0.0, 0.0), |(ef, ba, vol, dbh), t| {
+            (
+                ef + t.expansion_factor,
+                ba + t.basal_area_per_acre(),
+                vol + volume_model.cuft(t).map_or(0.0, |v| v * t.expansion_factor),
+                dbh + t.dbh * t.expansion_factor,
+            )
+        });
+    let snags = SnagMetrics {
+        snag_tpa: snag_ef / num_plots,
+        snag_basal_area: snag_ba / num_plots,
+        snag_volume_cuft: snag_vol / num_plots,
+        mean_snag_dbh: if snag_ef > 0.0 {
+            snag_ef_dbh / snag_ef
+        } else {
+            0.0
+        },
+    };
+
     // Species composition — accumulate per-species stats across all plots
     struct SpeciesAccum {
         species: Species,
@@ -163,22 +469,108 @@ pub fn compute_stand_metrics(inventory: &ForestInventory) -> StandMetrics {
 
     species_comp.sort_by(|a, b| b.basal_area.partial_cmp(&a.basal_area).unwrap_or(std::cmp::Ordering::Equal));
 
+    let stand_density_index = total_tpa * (qmd / 10.0).powf(REINEKE_EXPONENT);
+    let (shannon_index, simpson_index) = diversity_indices(&species_comp);
     StandMetrics {
         total_tpa,
         total_basal_area: total_ba,
         total_volume_cuft: total_vol_cuft,
         total_volume_bdft: total_vol_bdft,
+        total_biomass_tons_per_acre: total_biomass,
+        total_carbon_tons_per_acre: total_biomass * CARBON_FRACTION,
         quadratic_mean_diameter: qmd,
         mean_height,
+        mean_slenderness,
+        mean_crown_ratio,
+        ba_in_large_trees_percent,
+        stand_density_index,
+        relative_density: relative_density(stand_density_index, DEFAULT_MAX_SDI),
+        shannon_index,
+        simpson_index,
         num_species: species_comp.len(),
         species_composition: species_comp,
+        age: inventory.age_summary(),
+        site_index: inventory.site_index(DEFAULT_SITE_INDEX_BASE_AGE),
+        snags,
+        units: UnitSystem::Imperial,
+    }
+}
+
+/// `sdi` as a percentage of `max_sdi`, or zero if `max_sdi` is not positive.
+fn relative_density(sdi: f64, max_sdi: f64) -> f64 {
+    if max_sdi > 0.0 {
+        sdi / max_sdi * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Shannon and Simpson diversity indices from each species' share of TPA,
+/// both zero for a single species or no live trees.
+fn diversity_indices(species: &[SpeciesComposition]) -> (f64, f64) {
+    let total: f64 = species.iter().map(|sc| sc.tpa).sum();
+    if total <= 0.0 {
+        return (0.0, 0.0);
     }
+    species
+        .iter()
+        .map(|sc| sc.tpa / total)
+        .filter(|&p| p > 0.0)
+        .fold((0.0, 1.0), |(shannon, simpson), p| {
+            (shannon - p * p.ln(), simpson - p * p)
+        })
+}
+
+/// Live trees per acre in one crown ratio class.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrownRatioClass {
+    /// Lower bound of the class (inclusive), as a fraction of total height
+    pub lower: f64,
+    /// Upper bound of the class (exclusive, except 1.0 which is included)
+    pub upper: f64,
+    pub tpa: f64,
+    pub tree_count: usize,
+}
+
+/// Width of each class in [`live_crown_ratio_distribution`].
+pub const CROWN_RATIO_CLASS_WIDTH: f64 = 0.1;
+
+/// Distribution of live-tree TPA across 10%-wide crown ratio classes.
+///
+/// Trees without a crown ratio are skipped. Only classes containing trees are
+/// returned, in ascending order; the result is empty if no live tree has a
+/// crown ratio. TPA is averaged over all plots, like the stand metrics.
+pub fn live_crown_ratio_distribution(inventory: &ForestInventory) -> Vec<CrownRatioClass> {
+    let inventory = &*inventory.to_per_acre();
+    let num_classes = (1.0 / CROWN_RATIO_CLASS_WIDTH).round() as usize;
+    let mut tpa_sums = vec![0.0; num_classes];
+    let mut counts = vec![0usize; num_classes];
+
+    for tree in inventory.plots.iter().flat_map(|p| p.live_trees()) {
+        if let Some(cr) = tree.crown_ratio {
+            // Multiply rather than divide by the width: 0.6 / 0.1 < 6 in floating point
+            let class = ((cr * num_classes as f64).floor() as usize).min(num_classes - 1);
+            tpa_sums[class] += tree.expansion_factor;
+            counts[class] += 1;
+        }
+    }
+
+    let num_plots = inventory.num_plots().max(1) as f64;
+    (0..num_classes)
+        .filter(|&i| counts[i] > 0)
+        .map(|i| CrownRatioClass {
+            lower: i as f64 * CROWN_RATIO_CLASS_WIDTH,
+            upper: (i + 1) as f64 * CROWN_RATIO_CLASS_WIDTH,
+            tpa: tpa_sums[i] / num_plots,
+            tree_count: counts[i],
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Plot, Tree, TreeStatus};
+    use crate::models::{Plot, SpeciesVolumeTable, Tree, TreeStatus};
 
     fn make_species(code: &str, name: &str) -> Species {
         Species {
@@ -205,6 +597,7 @@ mod tests {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -217,6 +610,11 @@ mod tests {
             elevation_ft: None,
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
@@ -243,6 +641,41 @@ mod tests {
         inv
     }
 
+    #[test]
+    fn test_snag_metrics_zero_without_dead_trees() {
+        let mut inv = sample_inventory();
+        inv.plots[0].trees.retain(|t| t.is_live());
+        let metrics = compute_stand_metrics(&inv);
+        assert_eq!(metrics.snags, SnagMetrics::default());
+    }
+
+    #[test]
+    fn test_snag_metrics_partition_dead_trees() {
+        let inv = sample_inventory();
+        let metrics = compute_stand_metrics(&inv);
+        let snags = &metrics.snags;
+
+        // One 10" snag at 5 TPA on one of two plots
+        assert!((snags.snag_tpa - 2.5).abs() < 1e-9);
+        let ba = 0.005454 * 10.0_f64.powi(2) * 5.0 / 2.0;
+        assert!((snags.snag_basal_area - ba).abs() < 1e-3);
+        assert!(snags.snag_volume_cuft > 0.0);
+        assert!((snags.mean_snag_dbh - 10.0).abs() < 1e-9);
+
+        // Live metrics are the same as for the stand without the snag
+        let mut live_only = inv.clone();
+        live_only.plots[0].trees.retain(|t| t.is_live());
+        let live = compute_stand_metrics(&live_only);
+        assert_eq!(metrics.total_tpa, live.total_tpa);
+        assert_eq!(metrics.total_basal_area, live.total_basal_area);
+        assert_eq!(metrics.total_volume_cuft, live.total_volume_cuft);
+        assert_eq!(
+            metrics.quadratic_mean_diameter,
+            live.quadratic_mean_diameter
+        );
+        assert!((metrics.total_tpa + snags.snag_tpa - 12.5).abs() < 1e-9);
+    }
+
     #[test]
     fn test_empty_inventory_metrics() {
         let inv = ForestInventory::new("Empty");
@@ -253,8 +686,94 @@ mod tests {
         assert_eq!(metrics.total_volume_bdft, 0.0);
         assert_eq!(metrics.quadratic_mean_diameter, 0.0);
         assert!(metrics.mean_height.is_none());
+        assert!(metrics.mean_slenderness.is_none());
         assert_eq!(metrics.num_species, 0);
         assert!(metrics.species_composition.is_empty());
+        assert_eq!(metrics.stand_density_index, 0.0);
+        assert_eq!(metrics.relative_density, 0.0);
+        assert_eq!(metrics.shannon_index, 0.0);
+        assert_eq!(metrics.simpson_index, 0.0);
+    }
+
+    #[test]
+    fn test_diversity_indices_even_split() {
+        // Two live Douglas-fir and two live cedar at the same expansion factor
+        let metrics = compute_stand_metrics(&sample_inventory());
+        assert!((metrics.shannon_index - 2.0_f64.ln()).abs() < 1e-12);
+        assert!((metrics.simpson_index - 0.5).abs() < 1e-12);
+        assert_eq!(
+            metrics.to_flat_map()["shannon_index"],
+            metrics.shannon_index
+        );
+    }
+
+    #[test]
+    fn test_biomass_and_carbon_per_acre() {
+        let metrics = compute_stand_metrics(&sample_inventory());
+        assert!(metrics.total_biomass_tons_per_acre > 0.0);
+        assert_eq!(
+            metrics.total_carbon_tons_per_acre,
+            metrics.total_biomass_tons_per_acre * 0.5
+        );
+
+        // Live trees without a height, or with a zero height, add nothing
+        let mut inv = sample_inventory();
+        let df = make_species("DF", "Douglas Fir");
+        let unmeasured = make_tree(1, df.clone(), 20.0, None, TreeStatus::Live);
+        let flat = make_tree(2, df, 20.0, Some(0.0), TreeStatus::Live);
+        inv.plots[0].trees.push(unmeasured);
+        inv.plots[1].trees.push(flat);
+        let with_unmeasured = compute_stand_metrics(&inv);
+        assert_eq!(
+            with_unmeasured.total_biomass_tons_per_acre,
+            metrics.total_biomass_tons_per_acre
+        );
+        assert_eq!(
+            with_unmeasured.total_carbon_tons_per_acre,
+            metrics.total_carbon_tons_per_acre
+        );
+        assert!(with_unmeasured.total_tpa > metrics.total_tpa);
+    }
+
+    #[test]
+    fn test_to_metric() {
+        let mut metrics = compute_stand_metrics(&sample_inventory());
+        metrics.quadratic_mean_diameter = 12.0;
+        let metric = metrics.to_metric();
+        assert_eq!(metric.units, UnitSystem::Metric);
+        assert!((metric.quadratic_mean_diameter - 30.48).abs() < 1e-9);
+        assert!((metric.total_tpa - metrics.total_tpa * 2.471).abs() < 1e-9);
+        assert!((metric.total_basal_area - metrics.total_basal_area * 0.2296).abs() < 1e-9);
+        assert!((metric.total_volume_cuft - metrics.total_volume_cuft * 0.06997).abs() < 1e-9);
+        assert_eq!(metric.relative_density, metrics.relative_density);
+        let sc = &metrics.species_composition[0];
+        let metric_sc = &metric.species_composition[0];
+        assert!((metric_sc.mean_dbh - sc.mean_dbh * 2.54).abs() < 1e-9);
+        assert_eq!(metric_sc.percent_basal_area, sc.percent_basal_area);
+        assert!((metric.snags.snag_tpa - metrics.snags.snag_tpa * 2.471).abs() < 1e-9);
+        assert!((metric.snags.mean_snag_dbh - 25.4).abs() < 1e-9);
+
+        // Converting again leaves the values as they are
+        let twice = metric.to_metric();
+        assert_eq!(
+            twice.quadratic_mean_diameter,
+            metric.quadratic_mean_diameter
+        );
+        assert_eq!(twice.total_basal_area, metric.total_basal_area);
+        assert_eq!(twice.species_composition[0].tpa, metric_sc.tpa);
+    }
+
+    #[test]
+    fn test_stand_density_index() {
+        let inv = sample_inventory();
+        let metrics = compute_stand_metrics(&inv);
+        let expected =
+            metrics.total_tpa * (metrics.quadratic_mean_diameter / 10.0).powf(REINEKE_EXPONENT);
+        assert!((metrics.stand_density_index - expected).abs() < 1e-9);
+        assert!((metrics.relative_density - 100.0 * expected / DEFAULT_MAX_SDI).abs() < 1e-9);
+
+        let metrics = metrics.with_max_sdi(expected);
+        assert!((metrics.relative_density - 100.0).abs() < 1e-9);
     }
 
     #[test]
@@ -268,6 +787,79 @@ mod tests {
         assert!(metrics.quadratic_mean_diameter > 0.0);
     }
 
+    #[test]
+    fn test_custom_volume_model() {
+        // Ten cubic feet per live tree regardless of size; no board feet
+        struct TenCubicFeet;
+        impl VolumeModel for TenCubicFeet {
+            fn cuft(&self, _tree: &Tree) -> Option<f64> {
+                Some(10.0)
+            }
+            fn bdft(&self, _tree: &Tree) -> Option<f64> {
+                None
+            }
+        }
+
+        let inv = sample_inventory();
+        let metrics = compute_stand_metrics_with(&inv, &TenCubicFeet);
+        // Two live trees per plot at EF 5: 2 * 5 * 10 = 100 cu ft/ac per plot
+        assert!((metrics.total_volume_cuft - 100.0).abs() < 1e-9);
+        assert_eq!(metrics.total_volume_bdft, 0.0);
+        assert!(
+            (metrics.total_volume_cuft - compute_stand_metrics(&inv).total_volume_cuft).abs() > 1.0
+        );
+        // Non-volume metrics don't depend on the model
+        assert_eq!(metrics.total_tpa, compute_stand_metrics(&inv).total_tpa);
+    }
+
+    #[test]
+    fn test_species_volume_table() {
+        let inv = sample_inventory();
+        let default = compute_stand_metrics(&inv);
+
+        // WRC gets half the default cubic coefficient; DF has no entry
+        let wrc = VolumeEquation {
+            cuft_b1: VolumeEquation::default().cuft_b1 / 2.0,
+            ..VolumeEquation::default()
+        };
+        let table = SpeciesVolumeTable::default().with_species("WRC", wrc.clone());
+        let metrics = compute_stand_metrics_with(&inv, &table);
+
+        // Live WRC volume per acre: (0.002454 * (12^2 * 80 + 14^2 * 90)) * 5 / 2 plots
+        let wrc_default = 0.002454 * (144.0 * 80.0 + 196.0 * 90.0) * 5.0 / 2.0;
+        assert!(
+            (default.total_volume_cuft - metrics.total_volume_cuft - wrc_default / 2.0).abs()
+                < 1e-9
+        );
+
+        // A table with entries only for codes not in the stand is the default
+        let unknown = SpeciesVolumeTable::default().with_species("XYZ", wrc);
+        let metrics = compute_stand_metrics_with(&inv, &unknown);
+        assert!((metrics.total_volume_cuft - default.total_volume_cuft).abs() < 1e-9);
+        assert!((metrics.total_volume_bdft - default.total_volume_bdft).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_to_flat_map_keys() {
+        let metrics = compute_stand_metrics(&sample_inventory());
+        let map = metrics.to_flat_map();
+        assert_eq!(map["total_tpa"], metrics.total_tpa);
+        assert_eq!(
+            map["quadratic_mean_diameter"],
+            metrics.quadratic_mean_diameter
+        );
+        assert_eq!(map["num_species"], 2.0);
+
+        let df = metrics
+            .species_composition
+            .iter()
+            .find(|sc| sc.species.code == "DF")
+            .unwrap();
+        assert_eq!(map["species.DF.percent_basal_area"], df.percent_basal_area);
+        assert!(map.contains_key("species.WRC.tpa"));
+        assert!(!map.keys().any(|k| k.contains("Douglas Fir")));
+    }
+
     #[test]
     fn test_species_count() {
         let inv = sample_inventory();
@@ -320,6 +912,85 @@ mod tests {
         assert!(metrics.mean_height.is_some());
     }
 
+    #[test]
+    fn test_mean_slenderness_weighted() {
+        let df = make_species("DF", "Douglas Fir");
+        // 100 ft / 10" -> 120; 80 ft / 24" -> 40; equal EF -> mean 80
+        let mut inv = ForestInventory::new("Slenderness");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                make_tree(1, df.clone(), 10.0, Some(100.0), TreeStatus::Live),
+                make_tree(1, df.clone(), 24.0, Some(80.0), TreeStatus::Live),
+                make_tree(1, df, 12.0, None, TreeStatus::Live),
+            ],
+        ));
+        let metrics = compute_stand_metrics(&inv);
+        assert!((metrics.mean_slenderness.unwrap() - 80.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mean_crown_ratio_weighted_by_expansion() {
+        let df = make_species("DF", "Douglas Fir");
+        let tree = |dbh: f64, cr: Option<f64>, ef: f64, status: TreeStatus| Tree {
+            crown_ratio: cr,
+            expansion_factor: ef,
+            ..make_tree(1, df.clone(), dbh, Some(90.0), status)
+        };
+        let mut inv = ForestInventory::new("Crowns");
+        inv.plots.push(make_plot(
+            1,
+            vec![
+                tree(10.0, Some(0.6), 15.0, TreeStatus::Live),
+                tree(20.0, Some(0.2), 5.0, TreeStatus::Live),
+                tree(14.0, None, 20.0, TreeStatus::Live),
+                tree(12.0, Some(0.9), 50.0, TreeStatus::Dead),
+            ],
+        ));
+        let metrics = compute_stand_metrics(&inv);
+        // (0.6 * 15 + 0.2 * 5) / 20; the tree without a crown ratio and the dead tree are skipped
+        assert!((metrics.mean_crown_ratio.unwrap() - 0.5).abs() < 1e-9);
+        assert!((metrics.to_flat_map()["mean_crown_ratio"] - 0.5).abs() < 1e-9);
+
+        let dist = live_crown_ratio_distribution(&inv);
+        assert_eq!(dist.len(), 2);
+        assert!((dist[0].lower - 0.2).abs() < 1e-9);
+        assert!((dist[0].tpa - 5.0).abs() < 1e-9);
+        assert!((dist[1].lower - 0.6).abs() < 1e-9);
+        assert!((dist[1].tpa - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mean_crown_ratio_none_without_crown_ratios() {
+        let df = make_species("DF", "Douglas Fir");
+        let mut inv = ForestInventory::new("No Crowns");
+        inv.plots.push(make_plot(
+            1,
+            vec![Tree {
+                crown_ratio: None,
+                ..make_tree(1, df, 12.0, Some(80.0), TreeStatus::Live)
+            }],
+        ));
+        assert!(compute_stand_metrics(&inv).mean_crown_ratio.is_none());
+        assert!(live_crown_ratio_distribution(&inv).is_empty());
+    }
+
+    #[test]
+    fn test_full_crown_in_top_class() {
+        let df = make_species("DF", "Douglas Fir");
+        let mut inv = ForestInventory::new("Open Grown");
+        inv.plots.push(make_plot(
+            1,
+            vec![Tree {
+                crown_ratio: Some(1.0),
+                ..make_tree(1, df, 12.0, Some(40.0), TreeStatus::Live)
+            }],
+        ));
+        let dist = live_crown_ratio_distribution(&inv);
+        assert_eq!(dist.len(), 1);
+        assert!((dist[0].upper - 1.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_mean_height_none_when_no_heights() {
         let df = make_species("DF", "Douglas Fir");
@@ -375,6 +1046,8 @@ mod tests {
         assert_eq!(metrics.num_species, 1);
         assert!((metrics.species_composition[0].percent_tpa - 100.0).abs() < 0.1);
         assert!((metrics.species_composition[0].percent_basal_area - 100.0).abs() < 0.1);
+        assert_eq!(metrics.shannon_index, 0.0);
+        assert_eq!(metrics.simpson_index, 0.0);
     }
 
     #[test]
@@ -392,6 +1065,8 @@ mod tests {
         assert_eq!(metrics.total_tpa, 0.0);
         assert_eq!(metrics.total_basal_area, 0.0);
         assert_eq!(metrics.num_species, 0);
+        assert_eq!(metrics.snags.snag_tpa, 10.0);
+        assert_eq!(metrics.snags.mean_snag_dbh, 15.0);
     }
 
     #[test]
@@ -403,4 +1078,20 @@ mod tests {
         assert_eq!(deserialized.num_species, metrics.num_species);
         assert!((deserialized.total_tpa - metrics.total_tpa).abs() < 0.001);
     }
+
+    #[test]
+    fn test_count_on_plot_metrics_scale_by_plot_size() {
+        let per_acre = compute_stand_metrics(&sample_inventory());
+        let mut inv = sample_inventory();
+        inv.expansion_kind = crate::models::ExpansionKind::CountOnPlot;
+        let count = compute_stand_metrics(&inv);
+
+        // 0.2-acre plots: per-acre values are 5x the per-plot counts
+        assert!((count.total_tpa - 5.0 * per_acre.total_tpa).abs() < 1e-9);
+        assert!((count.total_basal_area - 5.0 * per_acre.total_basal_area).abs() < 1e-9);
+        assert_eq!(
+            count.quadratic_mean_diameter,
+            per_acre.quadratic_mean_diameter
+        );
+    }
 }
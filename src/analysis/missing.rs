@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ForestInventory, Plot, Species, TreeStatus};
+
+/// Share of a plot's tree records marked Missing above which plot QA warns
+/// of a possible relocation error (wrong plot center or tree tags).
+pub const HIGH_MISSING_FRACTION: f64 = 0.25;
+
+/// Missing-tree counts for one plot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlotMissing {
+    pub plot_id: u32,
+    /// Tree records with status Missing
+    pub missing: usize,
+    /// All tree records on the plot
+    pub total: usize,
+    /// `missing / total`, 0-1
+    pub fraction: f64,
+}
+
+/// Missing-tree count for one species.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesMissing {
+    pub species: Species,
+    pub missing: usize,
+}
+
+/// Trees that could not be relocated on remeasured permanent plots.
+///
+/// Counts are tree records, not expansion-factor-weighted: a missing tree is
+/// a bookkeeping problem, whatever it represents per acre.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MissingTreeReport {
+    pub total_missing: usize,
+    pub total_trees: usize,
+    /// `total_missing / total_trees`, 0-1; zero for an empty inventory
+    pub missing_fraction: f64,
+    /// Plots with at least one missing tree, in inventory order
+    pub by_plot: Vec<PlotMissing>,
+    /// Species with at least one missing tree, most missing first, then by code
+    pub by_species: Vec<SpeciesMissing>,
+}
+
+/// Count trees with [`TreeStatus::Missing`] by plot and species.
+///
+/// Missing trees are excluded from live-tree metrics like dead and cut
+/// trees, but unlike them they are not confirmed gone, so a high count
+/// usually means plot or tree relocation trouble rather than mortality.
+pub fn missing_tree_report(inventory: &ForestInventory) -> MissingTreeReport {
+    let mut by_plot = Vec::new();
+    let mut by_species: BTreeMap<String, SpeciesMissing> = BTreeMap::new();
+    for plot in &inventory.plots {
+        let missing = plot.trees_with_status(&TreeStatus::Missing);
+        for tree in &missing {
+            by_species
+                .entry(tree.species.code.clone())
+                .or_insert_with(|| SpeciesMissing {
+                    species: tree.species.clone(),
+                    missing: 0,
+                })
+                .missing += 1;
+        }
+        if !missing.is_empty() {
+            by_plot.push(PlotMissing {
+                plot_id: plot.plot_id,
+                missing: missing.len(),
+                total: plot.trees.len(),
+                fraction: missing_fraction(plot),
+            });
+        }
+    }
+
+    let total_missing: usize = by_plot.iter().map(|p| p.missing).sum();
+    let total_trees = inventory.num_trees();
+    let mut by_species: Vec<SpeciesMissing> = by_species.into_values().collect();
+    by_species.sort_by_key(|s| std::cmp::Reverse(s.missing));
+    MissingTreeReport {
+        total_missing,
+        total_trees,
+        missing_fraction: if total_trees > 0 {
+            total_missing as f64 / total_trees as f64
+        } else {
+            0.0
+        },
+        by_plot,
+        by_species,
+    }
+}
+
+/// Share of a plot's tree records marked Missing, 0-1.
+pub(super) fn missing_fraction(plot: &Plot) -> f64 {
+    if plot.trees.is_empty() {
+        return 0.0;
+    }
+    plot.trees_with_status(&TreeStatus::Missing).len() as f64 / plot.trees.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Tree;
+
+    fn make_tree(plot_id: u32, code: &str, status: TreeStatus) -> Tree {
+        Tree {
+            tree_id: 1,
+            plot_id,
+            species: Species {
+                common_name: code.to_string(),
+                code: code.to_string(),
+            },
+            dbh: 12.0,
+            height: Some(80.0),
+            crown_ratio: Some(0.5),
+            status,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    #[test]
+    fn test_report_counts_by_plot_and_species() {
+        use TreeStatus::{Dead, Live, Missing};
+        let inv = ForestInventory::from_trees(
+            "Remeasured",
+            vec![
+                make_tree(1, "DF", Live),
+                make_tree(1, "DF", Live),
+                make_tree(1, "DF", Missing),
+                make_tree(1, "DF", Dead),
+                make_tree(2, "WH", Missing),
+                make_tree(2, "DF", Missing),
+                make_tree(2, "WH", Missing),
+                make_tree(3, "DF", Live),
+            ],
+        );
+        let report = missing_tree_report(&inv);
+
+        assert_eq!(report.total_missing, 4);
+        assert_eq!(report.total_trees, 8);
+        assert!((report.missing_fraction - 0.5).abs() < 1e-9);
+
+        assert_eq!(report.by_plot.len(), 2);
+        assert_eq!(
+            (report.by_plot[0].plot_id, report.by_plot[0].missing),
+            (1, 1)
+        );
+        assert!((report.by_plot[0].fraction - 0.25).abs() < 1e-9);
+        assert_eq!(report.by_plot[1].total, 3);
+        assert!((report.by_plot[1].fraction - 1.0).abs() < 1e-9);
+
+        let species: Vec<(&str, usize)> = report
+            .by_species
+            .iter()
+            .map(|s| (s.species.code.as_str(), s.missing))
+            .collect();
+        assert_eq!(species, vec![("DF", 2), ("WH", 2)]);
+    }
+
+    #[test]
+    fn test_report_empty_inventory() {
+        let report = missing_tree_report(&ForestInventory::new("Empty"));
+        assert_eq!(report.total_missing, 0);
+        assert_eq!(report.missing_fraction, 0.0);
+        assert!(report.by_plot.is_empty());
+    }
+}
@@ -6,12 +6,59 @@
 
 mod analyzer;
 mod diameter_distribution;
+mod dmd;
+mod fit;
+mod groups;
 mod growth;
+mod harvest;
+mod height_diameter;
+mod increment;
 mod metrics;
+mod missing;
+mod plot_qa;
+mod rng;
 mod statistics;
+mod structure;
 
-pub use analyzer::Analyzer;
-pub use diameter_distribution::{DiameterClass, DiameterDistribution};
-pub use growth::{project_growth, GrowthModel, GrowthProjection};
-pub use metrics::{compute_stand_metrics, SpeciesComposition, StandMetrics};
-pub use statistics::{ConfidenceInterval, SamplingStatistics};
+pub use analyzer::{Analyzer, CachedAnalyzer};
+pub use diameter_distribution::{
+    DiameterClass, DiameterClassWidth, DiameterDistribution, DistributionOptions, WeibullParams,
+};
+pub use dmd::{
+    dmd_isolines, dmd_point, DmdPoint, Isoline, IsolinePoint, ISOLINE_POINTS, REINEKE_EXPONENT,
+};
+pub use fit::{model_fit, ModelFit};
+pub use groups::{compute_stand_metrics_grouped, GroupComposition, SpeciesGroups, OTHER_GROUP};
+pub use growth::{
+    project_growth, project_growth_by_species, project_growth_by_species_with, project_growth_from,
+    project_growth_iter, project_growth_with_equation, project_growth_with_recruitment,
+    project_growth_with_thinning, GrowthModel, GrowthProjection, RecruitmentModel, SpeciesGrowth,
+    SpeciesGrowthProjection, StandState, Thinning,
+};
+pub use harvest::{harvest_summary, HarvestSummary, SpeciesHarvest};
+pub use height_diameter::{
+    hd_relationship, hd_relationship_with, HdFit, HdPoint, DEFAULT_HD_OUTLIER_SD,
+};
+pub use increment::{annual_increment, AnnualIncrement};
+pub use metrics::{
+    compute_stand_metrics, compute_stand_metrics_with, live_crown_ratio_distribution,
+    CrownRatioClass, SnagMetrics, SpeciesComposition, StandMetrics, CROWN_RATIO_CLASS_WIDTH,
+    DEFAULT_MAX_SDI, LARGE_TREE_DBH,
+};
+pub use missing::{
+    missing_tree_report, MissingTreeReport, PlotMissing, SpeciesMissing, HIGH_MISSING_FRACTION,
+};
+pub use plot_qa::{plot_qa, plot_qa_with, PlotQaFlag, PlotQaReason, DEFAULT_OUTLIER_SD};
+pub use rng::RngSeed;
+pub use statistics::{
+    bootstrap_mean_ci, t_critical, ConfidenceInterval, IncrementalStats, SamplingStatistics,
+    StratifiedStatistics, StratumStatistics, TrimmedStatistics, DEFAULT_BOOTSTRAP_ITERATIONS,
+    DEFAULT_MAX_SAMPLING_ERROR, UNSTRATIFIED,
+};
+pub use structure::{
+    structural_stage, StructuralStage, OLD_GROWTH_MIN_LARGE_BA_PERCENT, STAND_INITIATION_MAX_QMD,
+    STEM_EXCLUSION_MIN_TPA,
+};
+
+#[cfg(any(test, feature = "testing"))]
+pub(crate) use rng::rng_from_seed;
@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+
+use super::missing::{missing_fraction, HIGH_MISSING_FRACTION};
+use crate::models::ForestInventory;
+
+/// Default number of standard deviations from the stand mean basal area
+/// beyond which a plot is flagged as an outlier.
+pub const DEFAULT_OUTLIER_SD: f64 = 2.0;
+
+/// Why a plot was flagged during QA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlotQaReason {
+    /// The plot has no live trees
+    NoLiveTrees,
+    /// The plot has exactly one live tree, which dominates its per-acre values
+    SingleLiveTree,
+    /// Per-acre basal area is far from the stand mean
+    BasalAreaOutlier,
+    /// More than [`HIGH_MISSING_FRACTION`] of the plot's trees are Missing,
+    /// which suggests the plot or its trees were not relocated correctly
+    HighMissingFraction,
+}
+
+impl std::fmt::Display for PlotQaReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlotQaReason::NoLiveTrees => write!(f, "No live trees"),
+            PlotQaReason::SingleLiveTree => write!(f, "Single live tree"),
+            PlotQaReason::BasalAreaOutlier => write!(f, "Basal area outlier"),
+            PlotQaReason::HighMissingFraction => write!(f, "Many missing trees"),
+        }
+    }
+}
+
+/// A QA flag raised for a single plot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlotQaFlag {
+    pub plot_id: u32,
+    pub reason: PlotQaReason,
+    /// The value that triggered the flag: live tree count for tree-count
+    /// flags, basal area (sq ft/acre) for outliers, percent of trees
+    /// Missing for missing-tree flags
+    pub value: f64,
+    /// Standard deviations from the stand mean basal area (outliers only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub z_score: Option<f64>,
+}
+
+/// Flag problem plots using [`DEFAULT_OUTLIER_SD`] as the outlier threshold.
+pub fn plot_qa(inventory: &ForestInventory) -> Vec<PlotQaFlag> {
+    plot_qa_with(inventory, DEFAULT_OUTLIER_SD)
+}
+
+/// Flag plots with no live trees, a single live tree, per-acre basal area
+/// more than `k` standard deviations from the stand mean, or more than
+/// [`HIGH_MISSING_FRACTION`] of their trees Missing.
+///
+/// Outliers use the sample standard deviation across all plots and require
+/// at least three plots. Flags are ordered by plot, in inventory order.
+pub fn plot_qa_with(inventory: &ForestInventory, k: f64) -> Vec<PlotQaFlag> {
+    let inventory = &*inventory.to_per_acre();
+    let n = inventory.num_plots();
+    let ba_values: Vec<f64> = inventory
+        .plots
+        .iter()
+        .map(|p| p.basal_area_per_acre())
+        .collect();
+
+    let (mean, sd) = if n >= 3 {
+        let mean = ba_values.iter().sum::<f64>() / n as f64;
+        let variance =
+            ba_values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        (mean, variance.sqrt())
+    } else {
+        (0.0, 0.0)
+    };
+
+    let mut flags = Vec::new();
+    for (plot, &ba) in inventory.plots.iter().zip(&ba_values) {
+        let live = plot.live_trees().len();
+        match live {
+            0 => flags.push(PlotQaFlag {
+                plot_id: plot.plot_id,
+                reason: PlotQaReason::NoLiveTrees,
+                value: 0.0,
+                z_score: None,
+            }),
+            1 => flags.push(PlotQaFlag {
+                plot_id: plot.plot_id,
+                reason: PlotQaReason::SingleLiveTree,
+                value: 1.0,
+                z_score: None,
+            }),
+            _ => {}
+        }
+
+        let missing = missing_fraction(plot);
+        if missing > HIGH_MISSING_FRACTION {
+            flags.push(PlotQaFlag {
+                plot_id: plot.plot_id,
+                reason: PlotQaReason::HighMissingFraction,
+                value: missing * 100.0,
+                z_score: None,
+            });
+        }
+
+        if sd > 0.0 {
+            let z = (ba - mean) / sd;
+            if z.abs() > k {
+                flags.push(PlotQaFlag {
+                    plot_id: plot.plot_id,
+                    reason: PlotQaReason::BasalAreaOutlier,
+                    value: ba,
+                    z_score: Some(z),
+                });
+            }
+        }
+    }
+
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Plot, Species, Tree, TreeStatus};
+
+    fn make_tree(plot_id: u32, tree_id: u32, dbh: f64, status: TreeStatus) -> Tree {
+        Tree {
+            tree_id,
+            plot_id,
+            species: Species {
+                common_name: "Douglas Fir".to_string(),
+                code: "DF".to_string(),
+            },
+            dbh,
+            height: Some(100.0),
+            crown_ratio: Some(0.5),
+            status,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    fn make_plot(plot_id: u32, trees: Vec<Tree>) -> Plot {
+        Plot {
+            plot_id,
+            plot_size_acres: 0.2,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees,
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        }
+    }
+
+    fn typical_plot(plot_id: u32) -> Plot {
+        make_plot(
+            plot_id,
+            vec![
+                make_tree(plot_id, 1, 12.0, TreeStatus::Live),
+                make_tree(plot_id, 2, 14.0, TreeStatus::Live),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_plot_qa_flags_empty_and_extreme_plots() {
+        let mut inv = ForestInventory::new("QA");
+        for id in 1..=8 {
+            inv.plots.push(typical_plot(id));
+        }
+        // Only a dead tree -> no live trees
+        inv.plots
+            .push(make_plot(9, vec![make_tree(9, 1, 10.0, TreeStatus::Dead)]));
+        // Many large trees -> extreme basal area
+        inv.plots.push(make_plot(
+            10,
+            (1..=6)
+                .map(|i| make_tree(10, i, 40.0, TreeStatus::Live))
+                .collect(),
+        ));
+
+        let flags = plot_qa(&inv);
+        assert!(flags
+            .iter()
+            .any(|f| f.plot_id == 9 && f.reason == PlotQaReason::NoLiveTrees));
+        let outlier = flags
+            .iter()
+            .find(|f| f.plot_id == 10 && f.reason == PlotQaReason::BasalAreaOutlier)
+            .expect("extreme plot should be flagged");
+        assert!(outlier.z_score.unwrap() > DEFAULT_OUTLIER_SD);
+        assert!(flags.iter().all(|f| f.plot_id >= 9));
+    }
+
+    #[test]
+    fn test_plot_qa_single_live_tree() {
+        let mut inv = ForestInventory::new("QA");
+        inv.plots.push(typical_plot(1));
+        inv.plots.push(make_plot(
+            2,
+            vec![
+                make_tree(2, 1, 12.0, TreeStatus::Live),
+                make_tree(2, 2, 12.0, TreeStatus::Cut),
+            ],
+        ));
+        let flags = plot_qa(&inv);
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags[0].plot_id, 2);
+        assert_eq!(flags[0].reason, PlotQaReason::SingleLiveTree);
+        assert_eq!(flags[0].value, 1.0);
+    }
+
+    #[test]
+    fn test_plot_qa_clean_inventory() {
+        let mut inv = ForestInventory::new("QA");
+        for id in 1..=5 {
+            inv.plots.push(typical_plot(id));
+        }
+        assert!(plot_qa(&inv).is_empty());
+    }
+
+    #[test]
+    fn test_plot_qa_flags_high_missing_fraction() {
+        let mut inv = ForestInventory::new("QA");
+        inv.plots.push(typical_plot(1));
+        // 2 of 4 trees could not be relocated
+        inv.plots.push(make_plot(
+            2,
+            vec![
+                make_tree(2, 1, 12.0, TreeStatus::Live),
+                make_tree(2, 2, 14.0, TreeStatus::Live),
+                make_tree(2, 3, 16.0, TreeStatus::Missing),
+                make_tree(2, 4, 10.0, TreeStatus::Missing),
+            ],
+        ));
+        // 1 of 5 is within tolerance
+        let mut plot3 = typical_plot(3);
+        plot3.trees.extend([
+            make_tree(3, 3, 12.0, TreeStatus::Live),
+            make_tree(3, 4, 12.0, TreeStatus::Live),
+            make_tree(3, 5, 12.0, TreeStatus::Missing),
+        ]);
+        inv.plots.push(plot3);
+
+        let flags = plot_qa(&inv);
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags[0].plot_id, 2);
+        assert_eq!(flags[0].reason, PlotQaReason::HighMissingFraction);
+        assert!((flags[0].value - 50.0).abs() < 1e-9);
+
+        let report = crate::analysis::missing_tree_report(&inv);
+        assert_eq!(report.total_missing, 3);
+        assert_eq!(report.by_plot.len(), 2);
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ForestError;
+
+/// Seed for the random number generator of a stochastic analysis.
+///
+/// Every stochastic API takes an `Option<RngSeed>`. `None` seeds from OS
+/// entropy, so repeated runs differ; a fixed seed yields identical output
+/// across runs and platforms for a given release of this crate. (The
+/// generator is `rand`'s `StdRng`, whose algorithm may change when that
+/// dependency is upgraded; such a change is noted in the changelog.)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RngSeed(pub u64);
+
+impl From<u64> for RngSeed {
+    fn from(seed: u64) -> Self {
+        Self(seed)
+    }
+}
+
+impl fmt::Display for RngSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for RngSeed {
+    type Err = ForestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse().map(RngSeed).map_err(|_| {
+            ForestError::ParseError(format!("Invalid seed: '{s}'. Use a non-negative integer"))
+        })
+    }
+}
+
+/// The generator for one stochastic analysis: seeded from `seed` if given,
+/// otherwise from OS entropy. All randomness in the crate goes through here.
+pub(crate) fn rng_from_seed(seed: Option<RngSeed>) -> StdRng {
+    match seed {
+        Some(RngSeed(seed)) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draws(seed: Option<RngSeed>) -> Vec<u64> {
+        let mut rng = rng_from_seed(seed);
+        (0..8).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_fixed_seed_repeats() {
+        assert_eq!(draws(Some(RngSeed(7))), draws(Some(RngSeed(7))));
+        assert_ne!(draws(Some(RngSeed(7))), draws(Some(RngSeed(8))));
+    }
+
+    #[test]
+    fn test_entropy_seed_differs() {
+        assert_ne!(draws(None), draws(None));
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!("42".parse::<RngSeed>().unwrap(), RngSeed(42));
+        assert_eq!(RngSeed::from(42).to_string(), "42");
+        assert!("-1".parse::<RngSeed>().is_err());
+        assert!("abc".parse::<RngSeed>().is_err());
+    }
+}
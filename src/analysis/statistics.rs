@@ -1,8 +1,24 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use statrs::distribution::{ContinuousCDF, StudentsT};
 
+use super::rng::{rng_from_seed, RngSeed};
 use crate::error::ForestError;
-use crate::models::ForestInventory;
+use crate::models::{ExpansionKind, ForestInventory, Plot, Tree};
+
+/// Default sampling error percent above which an estimate is reported as
+/// unreliable; see [`ConfidenceInterval::is_reliable`].
+pub const DEFAULT_MAX_SAMPLING_ERROR: f64 = 30.0;
+
+/// Number of resamples for [`SamplingStatistics::compute_bootstrap`] unless
+/// the caller needs more precise bounds.
+pub const DEFAULT_BOOTSTRAP_ITERATIONS: usize = 2000;
+
+/// Stratum that [`SamplingStatistics::compute_stratified`] puts plots
+/// without a `stratum` in.
+pub const UNSTRATIFIED: &str = "unstratified";
 
 /// Confidence interval for a metric.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +30,22 @@ pub struct ConfidenceInterval {
     pub confidence_level: f64,
     pub sample_size: usize,
     pub sampling_error_percent: f64,
+    /// Coefficient of variation of the per-plot values (std dev / mean × 100),
+    /// the input to sample-size planning; unlike `sampling_error_percent` it
+    /// does not depend on the confidence level or sample size. Zero when the
+    /// mean is zero.
+    #[serde(default)]
+    pub cv_percent: f64,
+}
+
+impl ConfidenceInterval {
+    /// Whether the sampling error is within `max_error_percent`.
+    ///
+    /// A few highly variable plots can give a sampling error well over 100%,
+    /// in which case the mean says little about the stand.
+    pub fn is_reliable(&self, max_error_percent: f64) -> bool {
+        self.sampling_error_percent.abs() <= max_error_percent
+    }
 }
 
 /// Complete sampling statistics for the inventory.
@@ -27,7 +59,13 @@ pub struct SamplingStatistics {
 
 impl SamplingStatistics {
     /// Compute sampling statistics from an inventory at a given confidence level (e.g. 0.95).
+    ///
+    /// Returns `ForestError::AnalysisError` if `confidence` is not strictly
+    /// between 0 and 1, since the t-distribution quantile would be NaN.
     pub fn compute(inventory: &ForestInventory, confidence: f64) -> Result<Self, ForestError> {
+        check_confidence(confidence)?;
+
+        let inventory = &*inventory.to_per_acre();
         let n = inventory.num_plots();
         if n < 2 {
             return Err(ForestError::InsufficientData(
@@ -35,40 +73,586 @@ impl SamplingStatistics {
             ));
         }
 
-        let tpa_values: Vec<f64> = inventory.plots.iter().map(|p| p.trees_per_acre()).collect();
-        let ba_values: Vec<f64> = inventory
-            .plots
-            .iter()
-            .map(|p| p.basal_area_per_acre())
-            .collect();
-        let vol_cuft_values: Vec<f64> = inventory
-            .plots
-            .iter()
-            .map(|p| p.volume_cuft_per_acre())
-            .collect();
-        let vol_bdft_values: Vec<f64> = inventory
-            .plots
+        let [tpa_values, ba_values, vol_cuft_values, vol_bdft_values] = plot_values(inventory);
+
+        // All four metrics share df = n - 1, so the t-value is computed once
+        let t_value = t_critical((n - 1) as f64, confidence)?;
+
+        Ok(SamplingStatistics {
+            tpa: ci_from_t(&tpa_values, confidence, t_value)?,
+            basal_area: ci_from_t(&ba_values, confidence, t_value)?,
+            volume_cuft: ci_from_t(&vol_cuft_values, confidence, t_value)?,
+            volume_bdft: ci_from_t(&vol_bdft_values, confidence, t_value)?,
+        })
+    }
+
+    /// Sampling statistics with percentile bootstrap intervals instead of
+    /// t-intervals, for skewed per-plot values or few plots where the
+    /// normal approximation is poor.
+    ///
+    /// Each of `iterations` resamples draws the plots with replacement and
+    /// takes the per-acre means of all four metrics from the same draw. The
+    /// bounds are the empirical percentiles of the resampled means and
+    /// `std_error` is their standard deviation; see [`bootstrap_mean_ci`].
+    /// [`DEFAULT_BOOTSTRAP_ITERATIONS`] is a reasonable count. The same
+    /// `seed` always gives the same intervals; see [`RngSeed`].
+    ///
+    /// Fails like [`SamplingStatistics::compute`], and with
+    /// `ForestError::AnalysisError` for zero iterations.
+    pub fn compute_bootstrap(
+        inventory: &ForestInventory,
+        confidence: f64,
+        iterations: usize,
+        seed: Option<RngSeed>,
+    ) -> Result<Self, ForestError> {
+        check_confidence(confidence)?;
+        check_iterations(iterations)?;
+
+        let inventory = &*inventory.to_per_acre();
+        let n = inventory.num_plots();
+        if n < 2 {
+            return Err(ForestError::InsufficientData(
+                "Need at least 2 plots for statistical analysis".to_string(),
+            ));
+        }
+
+        let values = plot_values(inventory);
+        let mut rng = rng_from_seed(seed);
+        let mut means: [Vec<f64>; 4] = Default::default();
+        for _ in 0..iterations {
+            let mut sums = [0.0; 4];
+            for _ in 0..n {
+                let plot = rng.gen_range(0..n);
+                for (sum, metric) in sums.iter_mut().zip(&values) {
+                    *sum += metric[plot];
+                }
+            }
+            for (metric_means, sum) in means.iter_mut().zip(sums) {
+                metric_means.push(sum / n as f64);
+            }
+        }
+
+        let [tpa, ba, vol_cuft, vol_bdft] = values;
+        let [tpa_means, ba_means, vol_cuft_means, vol_bdft_means] = means;
+        Ok(SamplingStatistics {
+            tpa: bootstrap_ci(&tpa, tpa_means, confidence),
+            basal_area: bootstrap_ci(&ba, ba_means, confidence),
+            volume_cuft: bootstrap_ci(&vol_cuft, vol_cuft_means, confidence),
+            volume_bdft: bootstrap_ci(&vol_bdft, vol_bdft_means, confidence),
+        })
+    }
+
+    /// Sampling statistics after dropping the most extreme plots, so that a
+    /// single mis-measured plot cannot dominate the estimate.
+    ///
+    /// For each metric separately, the plots are sorted by that metric and
+    /// `floor(n × trim_fraction)` are dropped from each end before the
+    /// interval is computed from the rest, so a plot trimmed for TPA may
+    /// still count toward volume. The interval is the ordinary t-interval on
+    /// the remaining plots, which is narrower than a formal trimmed-mean
+    /// interval when the trimmed plots were genuine.
+    ///
+    /// Returns `ForestError::AnalysisError` unless `trim_fraction` is in
+    /// `[0, 0.5)`, and `ForestError::InsufficientData` if fewer than 2 plots
+    /// remain after trimming.
+    pub fn compute_trimmed(
+        inventory: &ForestInventory,
+        confidence: f64,
+        trim_fraction: f64,
+    ) -> Result<TrimmedStatistics, ForestError> {
+        check_confidence(confidence)?;
+        if !(0.0..0.5).contains(&trim_fraction) {
+            return Err(ForestError::AnalysisError(format!(
+                "trim fraction must be at least 0 and below 0.5, got {trim_fraction}"
+            )));
+        }
+
+        let inventory = &*inventory.to_per_acre();
+        let n = inventory.num_plots();
+        let trim_each_end = (n as f64 * trim_fraction).floor() as usize;
+        let kept = n.saturating_sub(2 * trim_each_end);
+        if kept < 2 {
+            return Err(ForestError::InsufficientData(format!(
+                "Trimming {trim_each_end} plots from each end of {n} leaves {kept}; \
+                 need at least 2 for statistical analysis"
+            )));
+        }
+
+        let t_value = t_critical((kept - 1) as f64, confidence)?;
+        let trimmed_ci = |mut values: Vec<f64>| {
+            values.sort_by(f64::total_cmp);
+            let middle = &values[trim_each_end..n - trim_each_end];
+            ci_from_t(middle, confidence, t_value)
+        };
+        let [tpa_values, ba_values, vol_cuft_values, vol_bdft_values] = plot_values(inventory);
+
+        Ok(TrimmedStatistics {
+            statistics: SamplingStatistics {
+                tpa: trimmed_ci(tpa_values)?,
+                basal_area: trimmed_ci(ba_values)?,
+                volume_cuft: trimmed_ci(vol_cuft_values)?,
+                volume_bdft: trimmed_ci(vol_bdft_values)?,
+            },
+            trim_fraction,
+            plots_trimmed: 2 * trim_each_end,
+        })
+    }
+
+    /// Post-stratified sampling statistics, grouping plots by their
+    /// `stratum` and those without one into [`UNSTRATIFIED`].
+    ///
+    /// Each stratum gets its own t-intervals. The combined estimate weights
+    /// each stratum by its share of the plots, `W_h = n_h / n`, giving the
+    /// mean `Σ W_h·ȳ_h` with variance `Σ W_h²·s_h² / n_h`, and its interval
+    /// uses `n − L` degrees of freedom for `L` strata. When the strata
+    /// differ, removing the between-stratum variation makes this standard
+    /// error smaller than [`SamplingStatistics::compute`]'s. The combined
+    /// `cv_percent` is the coefficient of variation a simple random sample
+    /// of the same size would need for the same precision.
+    ///
+    /// Fails like [`SamplingStatistics::compute`], and with
+    /// `ForestError::InsufficientData` if any stratum has fewer than 2 plots.
+    pub fn compute_stratified(
+        inventory: &ForestInventory,
+        confidence: f64,
+    ) -> Result<StratifiedStatistics, ForestError> {
+        check_confidence(confidence)?;
+
+        let inventory = &*inventory.to_per_acre();
+        let n = inventory.num_plots();
+        if n < 2 {
+            return Err(ForestError::InsufficientData(
+                "Need at least 2 plots for statistical analysis".to_string(),
+            ));
+        }
+
+        let mut groups: BTreeMap<String, ForestInventory> = BTreeMap::new();
+        for plot in &inventory.plots {
+            let name = plot.stratum.as_deref().unwrap_or(UNSTRATIFIED);
+            groups
+                .entry(name.to_string())
+                .or_insert_with(|| ForestInventory::new(name))
+                .plots
+                .push(plot.clone());
+        }
+        if let Some(name) = groups
             .iter()
-            .map(|p| p.volume_bdft_per_acre())
-            .collect();
+            .find(|(_, g)| g.num_plots() < 2)
+            .map(|(n, _)| n)
+        {
+            return Err(ForestError::InsufficientData(format!(
+                "Stratum '{name}' has only 1 plot; need at least 2 in every stratum"
+            )));
+        }
+
+        let mut strata = BTreeMap::new();
+        // Per metric, the weighted mean and variance of the weighted mean
+        let mut combined = [(0.0, 0.0); 4];
+        for (name, group) in &groups {
+            let n_h = group.num_plots();
+            let weight = n_h as f64 / n as f64;
+            for ((mean, variance), values) in combined.iter_mut().zip(plot_values(group)) {
+                let y_bar = values.iter().sum::<f64>() / n_h as f64;
+                let s2 = values.iter().map(|y| (y - y_bar).powi(2)).sum::<f64>() / (n_h - 1) as f64;
+                *mean += weight * y_bar;
+                *variance += weight.powi(2) * s2 / n_h as f64;
+            }
+            let statistics = Self::compute(group, confidence)?;
+            strata.insert(
+                name.clone(),
+                StratumStatistics {
+                    num_plots: n_h,
+                    weight,
+                    statistics,
+                },
+            );
+        }
+
+        let t_value = t_critical((n - groups.len()) as f64, confidence)?;
+        // ci_from_moments divides the variance by n for the standard error
+        let [tpa, ba, vol_cuft, vol_bdft] = combined.map(|(mean, variance)| {
+            ci_from_moments(n, mean, variance * n as f64, confidence, t_value)
+        });
+        Ok(StratifiedStatistics {
+            strata,
+            combined: SamplingStatistics {
+                tpa,
+                basal_area: ba,
+                volume_cuft: vol_cuft,
+                volume_bdft: vol_bdft,
+            },
+        })
+    }
+
+    /// Sampling statistics for a single species, e.g. the sampling error on
+    /// Douglas-fir basal area specifically rather than the whole stand.
+    ///
+    /// Each plot's per-acre values are computed from that species' trees
+    /// alone, with zero on plots where it is absent, so the interval reflects
+    /// how unevenly the species is spread across the stand. Returns
+    /// `ForestError::NotFound` if no tree has `species_code`, and
+    /// `ForestError::InsufficientData` with fewer than 2 plots.
+    pub fn compute_for_species(
+        inventory: &ForestInventory,
+        species_code: &str,
+        confidence: f64,
+    ) -> Result<Self, ForestError> {
+        let is_species = |t: &Tree| t.species.code == species_code;
+        let mut trees = inventory.plots.iter().flat_map(|p| &p.trees);
+        if !trees.any(is_species) {
+            return Err(ForestError::NotFound(format!(
+                "Species '{species_code}' not found in inventory"
+            )));
+        }
+        let mut species_only = inventory.clone();
+        for plot in &mut species_only.plots {
+            plot.trees.retain(is_species);
+        }
+        Self::compute(&species_only, confidence)
+    }
+
+    /// Confidence interval on one species' basal area per acre; see
+    /// [`SamplingStatistics::compute_for_species`].
+    pub fn species_basal_area_ci(
+        inventory: &ForestInventory,
+        species_code: &str,
+        confidence: f64,
+    ) -> Result<ConfidenceInterval, ForestError> {
+        Ok(Self::compute_for_species(inventory, species_code, confidence)?.basal_area)
+    }
+
+    /// Metrics whose sampling error exceeds `max_error_percent`, keyed as in
+    /// [`SamplingStatistics::to_flat_map`] (e.g. `basal_area`). Empty when
+    /// every estimate is reliable.
+    pub fn unreliable_metrics(
+        &self,
+        max_error_percent: f64,
+    ) -> Vec<(&'static str, &ConfidenceInterval)> {
+        self.named()
+            .into_iter()
+            .filter(|(_, ci)| !ci.is_reliable(max_error_percent))
+            .collect()
+    }
 
+    /// Flatten the statistics into a map of dotted keys to values, for templating.
+    ///
+    /// Keys are `<metric>.<field>`, e.g. `basal_area.mean` or
+    /// `volume_cuft.sampling_error_percent`.
+    pub fn to_flat_map(&self) -> BTreeMap<String, f64> {
+        let mut map = BTreeMap::new();
+        for (name, ci) in self.named() {
+            map.insert(format!("{name}.mean"), ci.mean);
+            map.insert(format!("{name}.std_error"), ci.std_error);
+            map.insert(format!("{name}.lower"), ci.lower);
+            map.insert(format!("{name}.upper"), ci.upper);
+            map.insert(format!("{name}.confidence_level"), ci.confidence_level);
+            map.insert(format!("{name}.sample_size"), ci.sample_size as f64);
+            map.insert(
+                format!("{name}.sampling_error_percent"),
+                ci.sampling_error_percent,
+            );
+            map.insert(format!("{name}.cv_percent"), ci.cv_percent);
+        }
+        map
+    }
+
+    pub(crate) fn named(&self) -> [(&'static str, &ConfidenceInterval); 4] {
+        [
+            ("tpa", &self.tpa),
+            ("basal_area", &self.basal_area),
+            ("volume_cuft", &self.volume_cuft),
+            ("volume_bdft", &self.volume_bdft),
+        ]
+    }
+}
+
+/// Sampling statistics with extreme plots trimmed; see
+/// [`SamplingStatistics::compute_trimmed`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrimmedStatistics {
+    /// Intervals over the plots that remain after trimming
+    pub statistics: SamplingStatistics,
+    /// Fraction of plots requested to be trimmed from each end
+    pub trim_fraction: f64,
+    /// Plots dropped from each metric, half from each end
+    pub plots_trimmed: usize,
+}
+
+/// Post-stratified sampling statistics; see
+/// [`SamplingStatistics::compute_stratified`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StratifiedStatistics {
+    /// Statistics within each stratum, by stratum name
+    pub strata: BTreeMap<String, StratumStatistics>,
+    /// Stratum-weighted estimate for the whole inventory
+    pub combined: SamplingStatistics,
+}
+
+/// One stratum of [`StratifiedStatistics`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StratumStatistics {
+    pub num_plots: usize,
+    /// Share of all plots in this stratum, the stratum's weight in the
+    /// combined estimate
+    pub weight: f64,
+    /// Intervals from this stratum's plots alone
+    pub statistics: SamplingStatistics,
+}
+
+/// Per-plot TPA, basal area, cubic and board foot volume per acre, in plot order.
+fn plot_values(inventory: &ForestInventory) -> [Vec<f64>; 4] {
+    let values = |per_acre: fn(&Plot) -> f64| inventory.plots.iter().map(per_acre).collect();
+    [
+        values(Plot::trees_per_acre),
+        values(Plot::basal_area_per_acre),
+        values(Plot::volume_cuft_per_acre),
+        values(Plot::volume_bdft_per_acre),
+    ]
+}
+
+/// Running accumulator for [`SamplingStatistics`], fed one plot at a time.
+///
+/// Keeps only a count, mean and sum of squared deviations per metric
+/// (Welford's method, which avoids the cancellation of a naive sum of
+/// squares), so statistics over a file too large to hold in memory can be
+/// computed in a single pass. [`IncrementalStats::finalize`] matches
+/// [`SamplingStatistics::compute`] on the same plots to within rounding.
+///
+/// # Examples
+///
+/// ```
+/// use forest_inventory_analyzer::analysis::IncrementalStats;
+/// use forest_inventory_analyzer::Plot;
+///
+/// let mut stats = IncrementalStats::new();
+/// for plot_id in 1..=3 {
+///     stats.push_plot(&Plot {
+///         plot_id, plot_size_acres: 0.2,
+///         slope_percent: None, aspect_degrees: None, elevation_ft: None,
+///         trees: vec![], stand_id: None, source: None, measurement_date: None,
+///         latitude: None, longitude: None, stratum: None,
+///     });
+/// }
+/// assert_eq!(stats.num_plots(), 3);
+/// assert!(stats.finalize(0.95).is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalStats {
+    expansion_kind: ExpansionKind,
+    tpa: RunningMoments,
+    basal_area: RunningMoments,
+    volume_cuft: RunningMoments,
+    volume_bdft: RunningMoments,
+}
+
+impl IncrementalStats {
+    /// Create an empty accumulator for plots whose expansion factors are
+    /// trees per acre.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty accumulator for plots whose expansion factors have
+    /// the given meaning, e.g. an inventory's `expansion_kind`.
+    pub fn with_expansion_kind(expansion_kind: ExpansionKind) -> Self {
+        Self {
+            expansion_kind,
+            ..Self::default()
+        }
+    }
+
+    /// Add one plot's per-acre values.
+    ///
+    /// Expansion factors are converted to trees per acre with
+    /// [`Plot::per_acre_factor`] for the accumulator's expansion kind.
+    pub fn push_plot(&mut self, plot: &Plot) {
+        let per_acre = plot.per_acre_factor(self.expansion_kind);
+        self.tpa.push(plot.trees_per_acre() * per_acre);
+        self.basal_area.push(plot.basal_area_per_acre() * per_acre);
+        self.volume_cuft
+            .push(plot.volume_cuft_per_acre() * per_acre);
+        self.volume_bdft
+            .push(plot.volume_bdft_per_acre() * per_acre);
+    }
+
+    /// Number of plots pushed so far.
+    pub fn num_plots(&self) -> usize {
+        self.tpa.n
+    }
+
+    /// Sampling statistics for the plots pushed so far.
+    ///
+    /// Fails like [`SamplingStatistics::compute`]: `AnalysisError` for a
+    /// confidence outside (0, 1) and `InsufficientData` for fewer than 2 plots.
+    /// The accumulator is left unchanged, so more plots can still be pushed.
+    pub fn finalize(&self, confidence: f64) -> Result<SamplingStatistics, ForestError> {
+        check_confidence(confidence)?;
+
+        let n = self.num_plots();
+        if n < 2 {
+            return Err(ForestError::InsufficientData(
+                "Need at least 2 plots for statistical analysis".to_string(),
+            ));
+        }
+
+        let t_value = t_critical((n - 1) as f64, confidence)?;
+        let ci = |m: &RunningMoments| ci_from_moments(n, m.mean, m.variance(), confidence, t_value);
         Ok(SamplingStatistics {
-            tpa: compute_ci(&tpa_values, confidence)?,
-            basal_area: compute_ci(&ba_values, confidence)?,
-            volume_cuft: compute_ci(&vol_cuft_values, confidence)?,
-            volume_bdft: compute_ci(&vol_bdft_values, confidence)?,
+            tpa: ci(&self.tpa),
+            basal_area: ci(&self.basal_area),
+            volume_cuft: ci(&self.volume_cuft),
+            volume_bdft: ci(&self.volume_bdft),
         })
     }
 }
 
-/// Compute a confidence interval from a set of values.
-fn compute_ci(values: &[f64], confidence: f64) -> Result<ConfidenceInterval, ForestError> {
+/// Count, mean and sum of squared deviations from the mean of a stream of values.
+#[derive(Debug, Clone, Default)]
+struct RunningMoments {
+    n: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningMoments {
+    fn push(&mut self, x: f64) {
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Sample variance (n - 1 denominator); callers ensure n >= 2.
+    fn variance(&self) -> f64 {
+        self.m2 / (self.n - 1) as f64
+    }
+}
+
+/// Two-sided critical value of Student's t-distribution.
+///
+/// Returns the t such that a `confidence` fraction of the distribution with
+/// `df` degrees of freedom lies within ±t.
+///
+/// # Examples
+///
+/// ```
+/// use forest_inventory_analyzer::analysis::t_critical;
+///
+/// let t = t_critical(4.0, 0.95).unwrap();
+/// assert!((t - 2.776).abs() < 0.001);
+/// ```
+pub fn t_critical(df: f64, confidence: f64) -> Result<f64, ForestError> {
+    check_confidence(confidence)?;
+    let t_dist =
+        StudentsT::new(0.0, 1.0, df).map_err(|e| ForestError::AnalysisError(e.to_string()))?;
+    let alpha = 1.0 - confidence;
+    Ok(t_dist.inverse_cdf(1.0 - alpha / 2.0))
+}
+
+/// Percentile bootstrap confidence interval for the mean of `values`.
+///
+/// Resamples `values` with replacement `iterations` times and takes the
+/// empirical `(1 - confidence) / 2` and `(1 + confidence) / 2` percentiles of
+/// the resampled means as the bounds, so the interval need not be symmetric.
+/// `std_error` is the standard deviation of the resampled means. The same
+/// `seed` always gives the same interval; see [`RngSeed`].
+///
+/// Fails with `AnalysisError` for a confidence outside (0, 1) or zero
+/// iterations, and `InsufficientData` for fewer than 2 values.
+pub fn bootstrap_mean_ci(
+    values: &[f64],
+    confidence: f64,
+    iterations: usize,
+    seed: Option<RngSeed>,
+) -> Result<ConfidenceInterval, ForestError> {
+    check_confidence(confidence)?;
+    check_iterations(iterations)?;
+    let n = values.len();
+    if n < 2 {
+        return Err(ForestError::InsufficientData(
+            "Need at least 2 observations".to_string(),
+        ));
+    }
+
+    let mut rng = rng_from_seed(seed);
+    let means: Vec<f64> = (0..iterations)
+        .map(|_| (0..n).map(|_| values[rng.gen_range(0..n)]).sum::<f64>() / n as f64)
+        .collect();
+    Ok(bootstrap_ci(values, means, confidence))
+}
+
+/// Percentile confidence interval for the mean of `values` from the means of
+/// its bootstrap resamples; see [`bootstrap_mean_ci`]. `values` needs at
+/// least 2 entries and `means` at least 1.
+fn bootstrap_ci(values: &[f64], mut means: Vec<f64>, confidence: f64) -> ConfidenceInterval {
+    means.sort_by(f64::total_cmp);
+    let n = values.len();
+    let iterations = means.len();
+
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let boot_mean = means.iter().sum::<f64>() / iterations as f64;
+    let boot_var = if iterations > 1 {
+        means.iter().map(|m| (m - boot_mean).powi(2)).sum::<f64>() / (iterations - 1) as f64
+    } else {
+        0.0
+    };
+    let std_dev = (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt();
+
+    let alpha = 1.0 - confidence;
+    let lower = percentile(&means, alpha / 2.0);
+    let upper = percentile(&means, 1.0 - alpha / 2.0);
+    let (sampling_error_percent, cv_percent) = if mean.abs() > f64::EPSILON {
+        ((upper - lower) / 2.0 / mean * 100.0, std_dev / mean * 100.0)
+    } else {
+        (0.0, 0.0)
+    };
+
+    ConfidenceInterval {
+        mean,
+        std_error: boot_var.sqrt(),
+        lower,
+        upper,
+        confidence_level: confidence,
+        sample_size: n,
+        sampling_error_percent,
+        cv_percent,
+    }
+}
+
+/// Linearly interpolated `q` quantile of non-empty, ascending `sorted`.
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// Reject a bootstrap with no resamples.
+fn check_iterations(iterations: usize) -> Result<(), ForestError> {
+    if iterations == 0 {
+        return Err(ForestError::AnalysisError(
+            "bootstrap needs at least 1 iteration".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject confidence levels outside the open interval (0, 1), including NaN.
+fn check_confidence(confidence: f64) -> Result<(), ForestError> {
     if !(0.0 < confidence && confidence < 1.0) {
-        return Err(ForestError::ValidationError(format!(
-            "confidence must be in (0.0, 1.0), got {confidence}"
+        return Err(ForestError::AnalysisError(format!(
+            "confidence level must be strictly between 0 and 1 (e.g. 0.95), got {confidence}"
         )));
     }
+    Ok(())
+}
 
+/// Compute a confidence interval from a set of values using a precomputed
+/// critical t-value (see [`t_critical`]).
+pub(crate) fn ci_from_t(
+    values: &[f64],
+    confidence: f64,
+    t_value: f64,
+) -> Result<ConfidenceInterval, ForestError> {
     let n = values.len();
     if n < 2 {
         return Err(ForestError::InsufficientData(
@@ -78,23 +662,28 @@ fn compute_ci(values: &[f64], confidence: f64) -> Result<ConfidenceInterval, For
 
     let mean = values.iter().sum::<f64>() / n as f64;
     let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+    Ok(ci_from_moments(n, mean, variance, confidence, t_value))
+}
+
+/// Build a confidence interval from a sample's size, mean and variance.
+fn ci_from_moments(
+    n: usize,
+    mean: f64,
+    variance: f64,
+    confidence: f64,
+    t_value: f64,
+) -> ConfidenceInterval {
     let std_dev = variance.sqrt();
     let std_error = std_dev / (n as f64).sqrt();
 
-    let df = (n - 1) as f64;
-    let alpha = 1.0 - confidence;
-    let t_dist =
-        StudentsT::new(0.0, 1.0, df).map_err(|e| ForestError::AnalysisError(e.to_string()))?;
-    let t_value = t_dist.inverse_cdf(1.0 - alpha / 2.0);
-
     let margin = t_value * std_error;
-    let sampling_error_percent = if mean.abs() > f64::EPSILON {
-        (margin / mean) * 100.0
+    let (sampling_error_percent, cv_percent) = if mean.abs() > f64::EPSILON {
+        ((margin / mean) * 100.0, (std_dev / mean) * 100.0)
     } else {
-        0.0
+        (0.0, 0.0)
     };
 
-    Ok(ConfidenceInterval {
+    ConfidenceInterval {
         mean,
         std_error,
         lower: mean - margin,
@@ -102,13 +691,14 @@ fn compute_ci(values: &[f64], confidence: f64) -> Result<ConfidenceInterval, For
         confidence_level: confidence,
         sample_size: n,
         sampling_error_percent,
-    })
+        cv_percent,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Plot, Species, Tree, TreeStatus};
+    use crate::models::{Species, TreeStatus};
 
     fn make_plot(plot_id: u32, trees: Vec<Tree>) -> Plot {
         Plot {
@@ -119,6 +709,11 @@ mod tests {
             elevation_ft: None,
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
@@ -137,6 +732,7 @@ mod tests {
             expansion_factor: ef,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -158,10 +754,92 @@ mod tests {
 
     // --- compute_ci tests ---
 
+    #[test]
+    fn test_to_flat_map_keys() {
+        let stats = SamplingStatistics::compute(&sample_inventory(5), 0.95).unwrap();
+        let map = stats.to_flat_map();
+        assert_eq!(map.len(), 32);
+        assert_eq!(map["basal_area.mean"], stats.basal_area.mean);
+        assert_eq!(map["tpa.sample_size"], 5.0);
+        assert_eq!(map["volume_cuft.confidence_level"], 0.95);
+    }
+
+    #[test]
+    fn test_bootstrap_same_seed_same_ci() {
+        let values = [10.0, 12.0, 11.0, 13.0, 9.0, 25.0];
+        let seed = Some(RngSeed(2024));
+        let a = bootstrap_mean_ci(&values, 0.95, 500, seed).unwrap();
+        let b = bootstrap_mean_ci(&values, 0.95, 500, seed).unwrap();
+        assert_eq!(
+            (a.lower, a.upper, a.std_error),
+            (b.lower, b.upper, b.std_error)
+        );
+
+        let other = bootstrap_mean_ci(&values, 0.95, 500, Some(RngSeed(2025))).unwrap();
+        assert_ne!((a.lower, a.upper), (other.lower, other.upper));
+    }
+
+    #[test]
+    fn test_bootstrap_ci_brackets_mean() {
+        let values = [10.0, 12.0, 11.0, 13.0, 9.0, 25.0];
+        let ci = bootstrap_mean_ci(&values, 0.90, 1000, Some(RngSeed(1))).unwrap();
+        assert!((ci.mean - 80.0 / 6.0).abs() < 1e-9);
+        assert!(ci.lower < ci.mean && ci.mean < ci.upper);
+        assert!(ci.lower >= 9.0 && ci.upper <= 25.0);
+        assert_eq!(ci.sample_size, 6);
+        assert!(ci.std_error > 0.0);
+    }
+
+    #[test]
+    fn test_compute_bootstrap_matches_analytic_mean() {
+        let inv = sample_inventory(8);
+        let seed = Some(RngSeed(7));
+        let analytic = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        let boot =
+            SamplingStatistics::compute_bootstrap(&inv, 0.95, DEFAULT_BOOTSTRAP_ITERATIONS, seed)
+                .unwrap();
+        for ((name, t), (_, b)) in analytic.named().iter().zip(boot.named()) {
+            assert!((t.mean - b.mean).abs() < 1e-9, "{name}");
+            assert_eq!(b.sample_size, 8);
+            assert!(b.lower <= b.mean && b.mean <= b.upper, "{name}");
+            // The percentile interval is centered on the mean up to resampling noise
+            let midpoint = (b.lower + b.upper) / 2.0;
+            assert!((midpoint - t.mean).abs() < t.std_error, "{name}");
+            // The bootstrap SE omits the n/(n-1) correction, so it runs a
+            // little below the analytic one; allow for resampling noise
+            let se_diff = (b.std_error - t.std_error).abs();
+            assert!(se_diff <= 0.2 * t.std_error, "{name}");
+        }
+
+        let again =
+            SamplingStatistics::compute_bootstrap(&inv, 0.95, DEFAULT_BOOTSTRAP_ITERATIONS, seed)
+                .unwrap();
+        assert_eq!(
+            (again.tpa.lower, again.tpa.upper),
+            (boot.tpa.lower, boot.tpa.upper)
+        );
+
+        assert!(SamplingStatistics::compute_bootstrap(&inv, 0.95, 0, seed).is_err());
+        assert!(matches!(
+            SamplingStatistics::compute_bootstrap(&sample_inventory(1), 0.95, 100, seed),
+            Err(ForestError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_bootstrap_rejects_bad_input() {
+        assert!(bootstrap_mean_ci(&[1.0, 2.0], 1.5, 100, None).is_err());
+        assert!(bootstrap_mean_ci(&[1.0, 2.0], 0.95, 0, None).is_err());
+        assert!(matches!(
+            bootstrap_mean_ci(&[1.0], 0.95, 100, None),
+            Err(ForestError::InsufficientData(_))
+        ));
+    }
+
     #[test]
     fn test_compute_ci_basic() {
         let values = vec![10.0, 12.0, 11.0, 13.0, 9.0];
-        let ci = compute_ci(&values, 0.95).unwrap();
+        let ci = ci_from_t(&values, 0.95, t_critical(4.0, 0.95).unwrap()).unwrap();
         assert!((ci.mean - 11.0).abs() < 0.001);
         assert!(ci.lower < ci.mean);
         assert!(ci.upper > ci.mean);
@@ -172,7 +850,7 @@ mod tests {
     #[test]
     fn test_compute_ci_symmetric() {
         let values = vec![10.0, 12.0, 11.0, 13.0, 9.0];
-        let ci = compute_ci(&values, 0.95).unwrap();
+        let ci = ci_from_t(&values, 0.95, t_critical(4.0, 0.95).unwrap()).unwrap();
         let lower_margin = ci.mean - ci.lower;
         let upper_margin = ci.upper - ci.mean;
         assert!((lower_margin - upper_margin).abs() < 0.0001);
@@ -181,7 +859,7 @@ mod tests {
     #[test]
     fn test_compute_ci_two_observations() {
         let values = vec![10.0, 20.0];
-        let ci = compute_ci(&values, 0.95).unwrap();
+        let ci = ci_from_t(&values, 0.95, t_critical(1.0, 0.95).unwrap()).unwrap();
         assert!((ci.mean - 15.0).abs() < 0.001);
         assert_eq!(ci.sample_size, 2);
         // Wide CI with only 2 obs
@@ -191,19 +869,21 @@ mod tests {
     #[test]
     fn test_compute_ci_insufficient_data() {
         let values = vec![10.0];
-        assert!(compute_ci(&values, 0.95).is_err());
+        assert!(t_critical(0.0, 0.95)
+            .and_then(|t| ci_from_t(&values, 0.95, t))
+            .is_err());
     }
 
     #[test]
     fn test_compute_ci_empty() {
         let values: Vec<f64> = vec![];
-        assert!(compute_ci(&values, 0.95).is_err());
+        assert!(ci_from_t(&values, 0.95, t_critical(1.0, 0.95).unwrap()).is_err());
     }
 
     #[test]
     fn test_compute_ci_identical_values() {
         let values = vec![10.0, 10.0, 10.0, 10.0];
-        let ci = compute_ci(&values, 0.95).unwrap();
+        let ci = ci_from_t(&values, 0.95, t_critical(3.0, 0.95).unwrap()).unwrap();
         assert!((ci.mean - 10.0).abs() < 0.001);
         assert!((ci.std_error).abs() < 0.001);
         assert!((ci.lower - 10.0).abs() < 0.001);
@@ -213,9 +893,9 @@ mod tests {
     #[test]
     fn test_compute_ci_higher_confidence_wider() {
         let values = vec![10.0, 12.0, 11.0, 13.0, 9.0];
-        let ci_90 = compute_ci(&values, 0.90).unwrap();
-        let ci_95 = compute_ci(&values, 0.95).unwrap();
-        let ci_99 = compute_ci(&values, 0.99).unwrap();
+        let ci_90 = ci_from_t(&values, 0.90, t_critical(4.0, 0.90).unwrap()).unwrap();
+        let ci_95 = ci_from_t(&values, 0.95, t_critical(4.0, 0.95).unwrap()).unwrap();
+        let ci_99 = ci_from_t(&values, 0.99, t_critical(4.0, 0.99).unwrap()).unwrap();
         let width_90 = ci_90.upper - ci_90.lower;
         let width_95 = ci_95.upper - ci_95.lower;
         let width_99 = ci_99.upper - ci_99.lower;
@@ -227,8 +907,8 @@ mod tests {
     fn test_compute_ci_more_data_narrower() {
         let small = vec![10.0, 12.0, 11.0];
         let large = vec![10.0, 12.0, 11.0, 10.5, 11.5, 10.8, 11.2, 11.0, 10.3, 11.7];
-        let ci_small = compute_ci(&small, 0.95).unwrap();
-        let ci_large = compute_ci(&large, 0.95).unwrap();
+        let ci_small = ci_from_t(&small, 0.95, t_critical(2.0, 0.95).unwrap()).unwrap();
+        let ci_large = ci_from_t(&large, 0.95, t_critical(9.0, 0.95).unwrap()).unwrap();
         let width_small = ci_small.upper - ci_small.lower;
         let width_large = ci_large.upper - ci_large.lower;
         assert!(width_large < width_small);
@@ -237,7 +917,7 @@ mod tests {
     #[test]
     fn test_sampling_error_percent() {
         let values = vec![10.0, 12.0, 11.0, 13.0, 9.0];
-        let ci = compute_ci(&values, 0.95).unwrap();
+        let ci = ci_from_t(&values, 0.95, t_critical(4.0, 0.95).unwrap()).unwrap();
         // Sampling error % = (margin / mean) * 100
         let margin = ci.upper - ci.mean;
         let expected_pct = (margin / ci.mean) * 100.0;
@@ -247,8 +927,27 @@ mod tests {
     #[test]
     fn test_sampling_error_percent_zero_mean() {
         let values = vec![-5.0, 5.0, -5.0, 5.0];
-        let ci = compute_ci(&values, 0.95).unwrap();
+        let ci = ci_from_t(&values, 0.95, t_critical(3.0, 0.95).unwrap()).unwrap();
         assert_eq!(ci.sampling_error_percent, 0.0);
+        assert_eq!(ci.cv_percent, 0.0);
+    }
+
+    #[test]
+    fn test_cv_percent_hand_computed() {
+        // mean 11, sum of squares 10, variance 2.5, sd 1.5811
+        let values = vec![10.0, 12.0, 11.0, 13.0, 9.0];
+        let ci = ci_from_t(&values, 0.95, t_critical(4.0, 0.95).unwrap()).unwrap();
+        assert!((ci.cv_percent - 2.5_f64.sqrt() / 11.0 * 100.0).abs() < 1e-9);
+        assert!((ci.cv_percent - 14.374).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_cv_percent_independent_of_confidence() {
+        let values = vec![10.0, 12.0, 11.0, 13.0, 9.0];
+        let ci_90 = ci_from_t(&values, 0.90, t_critical(4.0, 0.90).unwrap()).unwrap();
+        let ci_99 = ci_from_t(&values, 0.99, t_critical(4.0, 0.99).unwrap()).unwrap();
+        assert_eq!(ci_90.cv_percent, ci_99.cv_percent);
+        assert!(ci_99.sampling_error_percent > ci_90.sampling_error_percent);
     }
 
     // --- SamplingStatistics tests ---
@@ -264,6 +963,185 @@ mod tests {
         assert_eq!(stats.tpa.sample_size, 5);
     }
 
+    #[test]
+    fn test_species_ci_on_two_species_stand() {
+        let mut inv = sample_inventory(5);
+        // Western hemlock on three of the five plots, absent elsewhere
+        for (i, plot) in inv.plots.iter_mut().enumerate().take(3) {
+            let mut wh = make_tree_with_ef(plot.plot_id, 10.0 + 4.0 * i as f64, 5.0);
+            wh.species = Species {
+                common_name: "Western Hemlock".to_string(),
+                code: "WH".to_string(),
+            };
+            plot.trees.push(wh);
+        }
+        let stand = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        let df = SamplingStatistics::species_basal_area_ci(&inv, "DF", 0.95).unwrap();
+        let wh = SamplingStatistics::species_basal_area_ci(&inv, "WH", 0.95).unwrap();
+
+        assert_eq!(wh.sample_size, 5);
+        assert!((df.mean + wh.mean - stand.basal_area.mean).abs() < 1e-9);
+        assert!(df.mean < stand.basal_area.mean && wh.mean < stand.basal_area.mean);
+        assert!((df.upper - df.lower - (wh.upper - wh.lower)).abs() > 1e-6);
+        // Hemlock's patchy distribution gives it the larger relative error
+        assert!(wh.sampling_error_percent > df.sampling_error_percent);
+
+        let wh_all = SamplingStatistics::compute_for_species(&inv, "WH", 0.95).unwrap();
+        assert!((wh_all.basal_area.mean - wh.mean).abs() < 1e-12);
+        assert!(wh_all.volume_cuft.mean < stand.volume_cuft.mean);
+    }
+
+    #[test]
+    fn test_species_ci_errors() {
+        let inv = sample_inventory(5);
+        assert!(matches!(
+            SamplingStatistics::species_basal_area_ci(&inv, "RA", 0.95),
+            Err(ForestError::NotFound(_))
+        ));
+        assert!(matches!(
+            SamplingStatistics::species_basal_area_ci(&sample_inventory(1), "DF", 0.95),
+            Err(ForestError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_reliability_flags_few_variable_plots() {
+        let mut sparse = ForestInventory::new("Sparse");
+        sparse.plots = vec![
+            make_plot(1, vec![make_tree_with_ef(1, 8.0, 5.0)]),
+            make_plot(2, vec![make_tree_with_ef(2, 20.0, 40.0)]),
+        ];
+        let stats = SamplingStatistics::compute(&sparse, 0.95).unwrap();
+        assert!(stats.tpa.sampling_error_percent > 100.0);
+        assert!(!stats.basal_area.is_reliable(DEFAULT_MAX_SAMPLING_ERROR));
+        let names: Vec<&str> = stats
+            .unreliable_metrics(DEFAULT_MAX_SAMPLING_ERROR)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["tpa", "basal_area", "volume_cuft", "volume_bdft"]);
+
+        let mut dense = ForestInventory::new("Dense");
+        for i in 1..=50 {
+            let ef = if i % 2 == 0 { 5.0 } else { 8.0 };
+            dense.plots.push(make_plot(i, vec![make_tree_with_ef(i, 14.0, ef)]));
+        }
+        let stats = SamplingStatistics::compute(&dense, 0.95).unwrap();
+        assert!(stats.basal_area.is_reliable(DEFAULT_MAX_SAMPLING_ERROR));
+        assert!(stats.unreliable_metrics(DEFAULT_MAX_SAMPLING_ERROR).is_empty());
+        // A strict enough threshold flags even the 50-plot stand
+        assert_eq!(stats.unreliable_metrics(1.0).len(), 4);
+    }
+
+    #[test]
+    fn test_trimming_shrinks_extreme_plot_influence() {
+        let mut inv = sample_inventory(10);
+        // One plot recorded with a wildly inflated expansion factor
+        inv.plots[3].trees[0].expansion_factor = 200.0;
+
+        let full = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        let trimmed = SamplingStatistics::compute_trimmed(&inv, 0.95, 0.1).unwrap();
+        assert_eq!(trimmed.plots_trimmed, 2);
+        assert_eq!(trimmed.statistics.tpa.sample_size, 8);
+        let clean = SamplingStatistics::compute(&sample_inventory(10), 0.95).unwrap();
+        let full_shift = (full.tpa.mean - clean.tpa.mean).abs();
+        let trimmed_shift = (trimmed.statistics.tpa.mean - clean.tpa.mean).abs();
+        assert!(trimmed_shift < full_shift / 4.0);
+        let trimmed_error = trimmed.statistics.tpa.sampling_error_percent;
+        assert!(trimmed_error < full.tpa.sampling_error_percent);
+
+        // Trimming nothing matches the ordinary estimate
+        let untrimmed = SamplingStatistics::compute_trimmed(&inv, 0.95, 0.0).unwrap();
+        assert_eq!(untrimmed.plots_trimmed, 0);
+        assert!((untrimmed.statistics.basal_area.mean - full.basal_area.mean).abs() < 1e-12);
+        assert!((untrimmed.statistics.basal_area.upper - full.basal_area.upper).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trimming_rejects_bad_fraction_and_too_few_plots() {
+        let inv = sample_inventory(5);
+        for bad in [-0.1, 0.5, f64::NAN] {
+            assert!(matches!(
+                SamplingStatistics::compute_trimmed(&inv, 0.95, bad),
+                Err(ForestError::AnalysisError(_))
+            ));
+        }
+        // Trimming 2 plots from each end of 5 leaves 1
+        assert!(matches!(
+            SamplingStatistics::compute_trimmed(&inv, 0.95, 0.4),
+            Err(ForestError::InsufficientData(_))
+        ));
+        let trimmed = SamplingStatistics::compute_trimmed(&inv, 0.95, 0.2).unwrap();
+        assert_eq!(trimmed.plots_trimmed, 2);
+        assert_eq!(trimmed.statistics.basal_area.sample_size, 3);
+    }
+
+    /// Three sparse "ridge" plots and three dense "valley" plots.
+    fn two_strata_inventory() -> ForestInventory {
+        let mut inv = ForestInventory::new("Strata");
+        for i in 1..=6 {
+            let (stratum, ef) = if i <= 3 {
+                ("ridge", 4.0 + i as f64)
+            } else {
+                ("valley", 16.0 + i as f64)
+            };
+            let mut plot = make_plot(i, vec![make_tree_with_ef(i, 14.0, ef)]);
+            plot.stratum = Some(stratum.to_string());
+            inv.plots.push(plot);
+        }
+        inv
+    }
+
+    #[test]
+    fn test_stratified_error_below_simple_random_when_strata_differ() {
+        let inv = two_strata_inventory();
+        let simple = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        let stratified = SamplingStatistics::compute_stratified(&inv, 0.95).unwrap();
+
+        assert_eq!(
+            stratified.strata.keys().collect::<Vec<_>>(),
+            ["ridge", "valley"]
+        );
+        let ridge = &stratified.strata["ridge"];
+        assert_eq!(ridge.num_plots, 3);
+        assert!((ridge.weight - 0.5).abs() < 1e-12);
+        assert!((ridge.statistics.tpa.mean - 6.0).abs() < 1e-9);
+        assert!((stratified.strata["valley"].statistics.tpa.mean - 21.0).abs() < 1e-9);
+
+        // Weights proportional to plot counts give the simple mean
+        let combined = &stratified.combined;
+        assert!((combined.tpa.mean - simple.tpa.mean).abs() < 1e-9);
+        assert!((combined.basal_area.mean - simple.basal_area.mean).abs() < 1e-9);
+        assert!(combined.tpa.std_error <= simple.tpa.std_error);
+        assert!(combined.basal_area.std_error <= simple.basal_area.std_error);
+        assert!(combined.volume_cuft.std_error <= simple.volume_cuft.std_error);
+        // Σ W²·s²/n with s² = 1 in each stratum of 3 plots
+        let expected_se = (2.0 * 0.25 * 1.0 / 3.0_f64).sqrt();
+        assert!((combined.tpa.std_error - expected_se).abs() < 1e-9);
+        assert_eq!(combined.tpa.sample_size, 6);
+    }
+
+    #[test]
+    fn test_stratified_groups_plots_without_stratum() {
+        // With every plot unstratified the estimate is the simple one
+        let inv = sample_inventory(4);
+        let simple = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        let stratified = SamplingStatistics::compute_stratified(&inv, 0.95).unwrap();
+        assert_eq!(stratified.strata.keys().collect::<Vec<_>>(), [UNSTRATIFIED]);
+        let (a, b) = (&stratified.combined.basal_area, &simple.basal_area);
+        assert!((a.std_error - b.std_error).abs() < 1e-9);
+        assert!((a.lower - b.lower).abs() < 1e-9);
+        assert!((a.cv_percent - b.cv_percent).abs() < 1e-9);
+
+        // A stratum needs 2 plots for its variance
+        let mut inv = two_strata_inventory();
+        inv.plots[0].stratum = None;
+        let err = SamplingStatistics::compute_stratified(&inv, 0.95).unwrap_err();
+        assert!(matches!(err, ForestError::InsufficientData(_)));
+        assert!(err.to_string().contains(UNSTRATIFIED), "{err}");
+        assert!(SamplingStatistics::compute_stratified(&inv, 1.5).is_err());
+    }
+
     #[test]
     fn test_sampling_statistics_insufficient_plots() {
         let inv = sample_inventory(1);
@@ -308,13 +1186,120 @@ mod tests {
     #[test]
     fn test_compute_ci_confidence_too_high() {
         let values = vec![10.0, 12.0, 11.0];
-        assert!(compute_ci(&values, 1.0).is_err());
+        assert!(t_critical(2.0, 1.0)
+            .and_then(|t| ci_from_t(&values, 1.0, t))
+            .is_err());
     }
 
     #[test]
     fn test_compute_ci_confidence_too_low() {
         let values = vec![10.0, 12.0, 11.0];
-        assert!(compute_ci(&values, 0.0).is_err());
-        assert!(compute_ci(&values, -0.5).is_err());
+        assert!(t_critical(2.0, 0.0)
+            .and_then(|t| ci_from_t(&values, 0.0, t))
+            .is_err());
+        assert!(t_critical(2.0, -0.5)
+            .and_then(|t| ci_from_t(&values, -0.5, t))
+            .is_err());
+    }
+
+    #[test]
+    fn test_t_critical_rejects_nan_confidence() {
+        assert!(t_critical(2.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_ci_from_t_margin_is_t_times_std_error() {
+        let values = vec![10.0, 12.0, 11.0, 13.0, 9.0];
+        let ci = ci_from_t(&values, 0.95, 2.0).unwrap();
+        assert!((ci.std_error - (2.5_f64 / 5.0).sqrt()).abs() < 1e-9);
+        assert!((ci.upper - ci.mean - 2.0 * ci.std_error).abs() < 1e-9);
+        assert!((ci.mean - ci.lower - 2.0 * ci.std_error).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sampling_statistics_rejects_out_of_range_confidence() {
+        let inv = sample_inventory(3);
+        for confidence in [0.0, 1.0, 1.5, f64::NAN] {
+            let err = SamplingStatistics::compute(&inv, confidence).unwrap_err();
+            assert!(
+                matches!(err, ForestError::AnalysisError(_)),
+                "confidence {confidence} should be an AnalysisError, got {err:?}"
+            );
+            assert!(err.to_string().contains("strictly between 0 and 1"));
+        }
+    }
+
+    #[test]
+    fn test_sampling_statistics_partial_confidence_levels() {
+        let inv = sample_inventory(3);
+        for confidence in [0.80, 0.90, 0.95, 0.99] {
+            let stats = SamplingStatistics::compute(&inv, confidence).unwrap();
+            assert!(stats.tpa.lower.is_finite() && stats.tpa.upper.is_finite());
+            assert!((stats.tpa.confidence_level - confidence).abs() < f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_t_critical_matches_table() {
+        assert!((t_critical(4.0, 0.95).unwrap() - 2.776).abs() < 0.001);
+        assert!((t_critical(10.0, 0.90).unwrap() - 1.812).abs() < 0.001);
+        assert!(t_critical(4.0, 1.5).is_err());
+        assert!(t_critical(0.0, 0.95).is_err());
+    }
+
+    // --- IncrementalStats tests ---
+
+    #[test]
+    fn test_incremental_matches_batch() {
+        let inv = sample_inventory(12);
+        let mut incremental = IncrementalStats::new();
+        for plot in &inv.plots {
+            incremental.push_plot(plot);
+        }
+        let streamed = incremental.finalize(0.90).unwrap();
+        let batch = SamplingStatistics::compute(&inv, 0.90).unwrap();
+
+        let streamed = streamed.to_flat_map();
+        let batch = batch.to_flat_map();
+        assert_eq!(streamed.len(), batch.len());
+        for (key, value) in &batch {
+            assert!(
+                (streamed[key] - value).abs() < 1e-9,
+                "{key}: incremental {} vs batch {value}",
+                streamed[key]
+            );
+        }
+    }
+
+    #[test]
+    fn test_incremental_converts_count_based_plots() {
+        let mut inv = sample_inventory(6);
+        inv.expansion_kind = ExpansionKind::CountOnPlot;
+        let mut incremental = IncrementalStats::with_expansion_kind(inv.expansion_kind);
+        for plot in &inv.plots {
+            incremental.push_plot(plot);
+        }
+        let streamed = incremental.finalize(0.95).unwrap();
+        let batch = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        assert!((streamed.tpa.mean - batch.tpa.mean).abs() < 1e-9);
+        assert!((streamed.basal_area.mean - batch.basal_area.mean).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_incremental_needs_two_plots() {
+        let inv = sample_inventory(2);
+        let mut incremental = IncrementalStats::new();
+        assert!(matches!(
+            incremental.finalize(0.95),
+            Err(ForestError::InsufficientData(_))
+        ));
+        incremental.push_plot(&inv.plots[0]);
+        assert!(incremental.finalize(0.95).is_err());
+        incremental.push_plot(&inv.plots[1]);
+        assert_eq!(incremental.finalize(0.95).unwrap().tpa.sample_size, 2);
+        assert!(matches!(
+            incremental.finalize(1.0),
+            Err(ForestError::AnalysisError(_))
+        ));
     }
 }
@@ -0,0 +1,159 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::metrics::{compute_stand_metrics, StandMetrics};
+use crate::models::ForestInventory;
+
+/// QMD (inches) below which a stand is still in stand initiation.
+pub const STAND_INITIATION_MAX_QMD: f64 = 5.0;
+
+/// Live trees per acre at or above which a stand is in stem exclusion.
+pub const STEM_EXCLUSION_MIN_TPA: f64 = 150.0;
+
+/// Share of basal area (0-100) in large trees at or above which a stand is
+/// classed as old growth.
+pub const OLD_GROWTH_MIN_LARGE_BA_PERCENT: f64 = 50.0;
+
+/// Structural development stage of a stand, after Oliver and Larson.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructuralStage {
+    /// Young trees establishing after a disturbance
+    StandInitiation,
+    /// A dense canopy suppressing new regeneration and thinning itself
+    StemExclusion,
+    /// Gaps in a thinned canopy letting an understory establish
+    UnderstoryReinitiation,
+    /// Basal area dominated by large trees
+    OldGrowth,
+}
+
+impl StructuralStage {
+    /// Classify a stand from its metrics, applying the first matching rule:
+    ///
+    /// 1. QMD below [`STAND_INITIATION_MAX_QMD`] (including a stand with no
+    ///    live trees): stand initiation
+    /// 2. At least [`OLD_GROWTH_MIN_LARGE_BA_PERCENT`] of basal area in trees
+    ///    of [`LARGE_TREE_DBH`](super::LARGE_TREE_DBH) or more: old growth
+    /// 3. At least [`STEM_EXCLUSION_MIN_TPA`] trees per acre: stem exclusion
+    /// 4. Otherwise: understory reinitiation
+    ///
+    /// The thresholds are converted to the metrics' [`units`](StandMetrics::units).
+    pub fn classify(metrics: &StandMetrics) -> Self {
+        let units = metrics.units;
+        if metrics.quadratic_mean_diameter < units.diameter(STAND_INITIATION_MAX_QMD) {
+            StructuralStage::StandInitiation
+        } else if metrics.ba_in_large_trees_percent >= OLD_GROWTH_MIN_LARGE_BA_PERCENT {
+            StructuralStage::OldGrowth
+        } else if metrics.total_tpa >= units.per_area(STEM_EXCLUSION_MIN_TPA) {
+            StructuralStage::StemExclusion
+        } else {
+            StructuralStage::UnderstoryReinitiation
+        }
+    }
+}
+
+impl fmt::Display for StructuralStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructuralStage::StandInitiation => write!(f, "Stand initiation"),
+            StructuralStage::StemExclusion => write!(f, "Stem exclusion"),
+            StructuralStage::UnderstoryReinitiation => write!(f, "Understory reinitiation"),
+            StructuralStage::OldGrowth => write!(f, "Old growth"),
+        }
+    }
+}
+
+/// Structural stage of an inventory; see [`StructuralStage::classify`].
+pub fn structural_stage(inventory: &ForestInventory) -> StructuralStage {
+    StructuralStage::classify(&compute_stand_metrics(inventory))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Plot, Species, Tree, TreeStatus};
+
+    fn make_tree(plot_id: u32, dbh: f64, ef: f64) -> Tree {
+        Tree {
+            tree_id: 1,
+            plot_id,
+            species: Species {
+                common_name: "Douglas Fir".to_string(),
+                code: "DF".to_string(),
+            },
+            dbh,
+            height: Some(100.0),
+            crown_ratio: Some(0.5),
+            status: TreeStatus::Live,
+            expansion_factor: ef,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    fn inventory(trees: Vec<Tree>) -> ForestInventory {
+        let mut inv = ForestInventory::new("Structure Test");
+        inv.plots.push(Plot {
+            plot_id: 1,
+            plot_size_acres: 0.2,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees,
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        });
+        inv
+    }
+
+    #[test]
+    fn test_dense_small_trees_are_stem_exclusion() {
+        let inv = inventory(vec![make_tree(1, 7.0, 200.0), make_tree(1, 9.0, 200.0)]);
+        let metrics = compute_stand_metrics(&inv);
+        assert_eq!(metrics.ba_in_large_trees_percent, 0.0);
+        assert_eq!(structural_stage(&inv), StructuralStage::StemExclusion);
+        assert_eq!(
+            StructuralStage::classify(&metrics.to_metric()),
+            StructuralStage::StemExclusion
+        );
+    }
+
+    #[test]
+    fn test_sparse_big_trees_are_old_growth() {
+        let inv = inventory(vec![
+            make_tree(1, 36.0, 10.0),
+            make_tree(1, 28.0, 10.0),
+            make_tree(1, 12.0, 20.0),
+        ]);
+        let metrics = compute_stand_metrics(&inv);
+        assert!(metrics.ba_in_large_trees_percent > 80.0);
+        assert_eq!(structural_stage(&inv), StructuralStage::OldGrowth);
+    }
+
+    #[test]
+    fn test_seedlings_and_open_mid_size_stands() {
+        let seedlings = inventory(vec![make_tree(1, 2.0, 400.0)]);
+        assert_eq!(structural_stage(&seedlings), StructuralStage::StandInitiation);
+        assert_eq!(
+            structural_stage(&ForestInventory::new("Empty")),
+            StructuralStage::StandInitiation
+        );
+
+        let open = inventory(vec![make_tree(1, 14.0, 60.0), make_tree(1, 22.0, 10.0)]);
+        assert_eq!(
+            structural_stage(&open),
+            StructuralStage::UnderstoryReinitiation
+        );
+        assert_eq!(
+            StructuralStage::UnderstoryReinitiation.to_string(),
+            "Understory reinitiation"
+        );
+    }
+}
@@ -3,11 +3,14 @@
 //! [`AppConfig`] groups settings for the web server, statistical analysis, growth modeling,
 //! and database storage. All fields have sensible defaults so the config file is optional.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::analysis::{DiameterClassWidth, DEFAULT_MAX_SDI};
 use crate::error::ForestError;
+use crate::models::validate_hex_color;
 
 /// Application configuration loaded from an optional `config.toml` file.
 ///
@@ -20,6 +23,9 @@ pub struct AppConfig {
     pub analysis: AnalysisConfig,
     pub growth: GrowthConfig,
     pub database: DatabaseConfig,
+    /// Species code to hex color overrides for charts (`[species_colors]` table),
+    /// e.g. `DF = "#1b4332"`
+    pub species_colors: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +37,8 @@ pub struct ServerConfig {
     pub bind_address: String,
     /// Maximum upload size in bytes (default: 50 MB)
     pub max_upload_bytes: usize,
+    /// Longest growth projection the web API accepts, in years (default: 500)
+    pub max_growth_years: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +48,8 @@ pub struct AnalysisConfig {
     pub confidence_level: f64,
     /// Diameter class width in inches (default: 2.0)
     pub diameter_class_width: f64,
+    /// Maximum stand density index for relative density (default: 450.0, Douglas-fir)
+    pub max_sdi: f64,
 }
 
 /// Simple tag enum for selecting a growth model type in configuration.
@@ -82,6 +92,7 @@ impl Default for ServerConfig {
             port: 8080,
             bind_address: "127.0.0.1".to_string(),
             max_upload_bytes: 50 * 1024 * 1024,
+            max_growth_years: 500,
         }
     }
 }
@@ -91,6 +102,7 @@ impl Default for AnalysisConfig {
         Self {
             confidence_level: 0.95,
             diameter_class_width: 2.0,
+            max_sdi: DEFAULT_MAX_SDI,
         }
     }
 }
@@ -138,10 +150,18 @@ impl AppConfig {
             )));
         }
 
-        if self.analysis.diameter_class_width <= 0.0 {
+        let width = self.analysis.diameter_class_width;
+        if DiameterClassWidth::new(width).is_err() {
             return Err(ForestError::ValidationError(format!(
-                "diameter_class_width must be > 0.0, got {}",
-                self.analysis.diameter_class_width
+                "diameter_class_width must be at least {} inches, got {width}",
+                DiameterClassWidth::MIN
+            )));
+        }
+
+        let max_sdi = self.analysis.max_sdi;
+        if !(max_sdi.is_finite() && max_sdi > 0.0) {
+            return Err(ForestError::ValidationError(format!(
+                "max_sdi must be > 0.0, got {max_sdi}"
             )));
         }
 
@@ -165,6 +185,12 @@ impl AppConfig {
             ));
         }
 
+        if self.server.max_growth_years == 0 {
+            return Err(ForestError::ValidationError(
+                "max_growth_years must be > 0".to_string(),
+            ));
+        }
+
         if self.growth.carrying_capacity <= 0.0 {
             return Err(ForestError::ValidationError(format!(
                 "carrying_capacity must be > 0.0, got {}",
@@ -172,6 +198,10 @@ impl AppConfig {
             )));
         }
 
+        for (code, color) in &self.species_colors {
+            validate_hex_color(&format!("species_colors.{code}"), color)?;
+        }
+
         Ok(())
     }
 }
@@ -186,6 +216,7 @@ mod tests {
         assert_eq!(config.server.port, 8080);
         assert_eq!(config.server.bind_address, "127.0.0.1");
         assert_eq!(config.server.max_upload_bytes, 50 * 1024 * 1024);
+        assert_eq!(config.server.max_growth_years, 500);
         assert!((config.analysis.confidence_level - 0.95).abs() < f64::EPSILON);
         assert!((config.analysis.diameter_class_width - 2.0).abs() < f64::EPSILON);
         assert_eq!(config.growth.default_model, GrowthModelType::Logistic);
@@ -195,6 +226,30 @@ mod tests {
         assert_eq!(config.database.path, "forest_analyzer.db");
     }
 
+    #[test]
+    fn test_species_colors_table() {
+        let config: AppConfig = toml::from_str(
+            r##"
+[species_colors]
+DF = "#1b4332"
+"##,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.species_colors["DF"], "#1b4332");
+
+        let bad: AppConfig = toml::from_str("[species_colors]\nDF = \"forest\"\n").unwrap();
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_species_colors_error_names_the_code() {
+        let bad: AppConfig = toml::from_str("[species_colors]\nWH = \"#12345\"\n").unwrap();
+        let err = bad.validate().unwrap_err().to_string();
+        assert!(err.contains("species_colors.WH"), "{err}");
+        assert!(err.contains("'#12345'"), "{err}");
+    }
+
     #[test]
     fn test_load_missing_file_returns_defaults() {
         let config = AppConfig::load(Path::new("nonexistent_config.toml")).unwrap();
@@ -309,6 +364,15 @@ path = "custom.db"
         assert!(err.contains("diameter_class_width"));
     }
 
+    #[test]
+    fn test_validate_max_sdi_zero() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.analysis.max_sdi, 450.0);
+        config.analysis.max_sdi = 0.0;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("max_sdi"));
+    }
+
     #[test]
     fn test_validate_negative_annual_rate() {
         let mut config = AppConfig::default();
@@ -340,6 +404,14 @@ path = "custom.db"
         assert!(err.contains("max_upload_bytes"));
     }
 
+    #[test]
+    fn test_validate_max_growth_years_zero() {
+        let mut config = AppConfig::default();
+        config.server.max_growth_years = 0;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("max_growth_years"));
+    }
+
     #[test]
     fn test_validate_carrying_capacity_zero() {
         let mut config = AppConfig::default();
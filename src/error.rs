@@ -1,7 +1,11 @@
 //! Crate-wide error type for forest inventory operations.
 //!
-//! [`ForestError`] covers I/O, CSV/JSON/Excel parsing, validation, analysis,
+//! [`ForestError`] covers I/O, CSV/JSON/Excel/Parquet parsing, validation, analysis,
 //! and database errors with automatic conversions from underlying error types.
+//! Errors from reading a file carry its path and, where known, the line via
+//! [`ForestError::WithContext`].
+
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
@@ -11,6 +15,7 @@ pub enum ForestError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[cfg(feature = "io")]
     #[error("CSV error: {0}")]
     Csv(#[from] csv::Error),
 
@@ -20,6 +25,10 @@ pub enum ForestError {
     #[error("Excel error: {0}")]
     Excel(String),
 
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(String),
+
     #[error("Parse error: {0}")]
     ParseError(String),
 
@@ -37,20 +46,78 @@ pub enum ForestError {
 
     #[error("Database error: {0}")]
     Database(String),
+
+    /// Another error, located in a file and optionally at a line (or Excel
+    /// sheet row). Displays as `error in data.csv:42: <source>`.
+    #[error("error in {}: {source}", location(path, *line))]
+    WithContext {
+        path: PathBuf,
+        line: Option<usize>,
+        source: Box<ForestError>,
+    },
+}
+
+impl ForestError {
+    /// Attach the file `path` and `line` this error came from.
+    ///
+    /// An error that already has context is returned unchanged, so the
+    /// innermost (most specific) location wins.
+    pub fn with_context(self, path: impl AsRef<Path>, line: Option<usize>) -> Self {
+        match self {
+            ForestError::WithContext { .. } => self,
+            source => ForestError::WithContext {
+                path: path.as_ref().to_path_buf(),
+                line,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// The underlying error with any file context stripped.
+    pub fn without_context(&self) -> &ForestError {
+        match self {
+            ForestError::WithContext { source, .. } => source.without_context(),
+            other => other,
+        }
+    }
+}
+
+/// `path` or `path:line`, as in compiler diagnostics.
+fn location(path: &Path, line: Option<usize>) -> String {
+    match line {
+        Some(line) => format!("{}:{line}", path.display()),
+        None => path.display().to_string(),
+    }
 }
 
+#[cfg(feature = "excel")]
 impl From<calamine::Error> for ForestError {
     fn from(e: calamine::Error) -> Self {
         ForestError::Excel(e.to_string())
     }
 }
 
+#[cfg(feature = "excel")]
 impl From<calamine::XlsxError> for ForestError {
     fn from(e: calamine::XlsxError) -> Self {
         ForestError::Excel(e.to_string())
     }
 }
 
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for ForestError {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        ForestError::Parquet(e.to_string())
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<arrow_schema::ArrowError> for ForestError {
+    fn from(e: arrow_schema::ArrowError) -> Self {
+        ForestError::Parquet(e.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +177,27 @@ mod tests {
         assert!(forest_err.to_string().contains("JSON error"));
     }
 
+    #[test]
+    fn test_with_context_display() {
+        let err = ForestError::ValidationError("DBH must be positive".to_string())
+            .with_context("data.csv", Some(42));
+        assert_eq!(
+            err.to_string(),
+            "error in data.csv:42: Validation error: DBH must be positive"
+        );
+        assert!(matches!(
+            err.without_context(),
+            ForestError::ValidationError(_)
+        ));
+
+        // The first (innermost) location is kept
+        let err = err.with_context("other.csv", None);
+        assert!(err.to_string().starts_with("error in data.csv:42: "));
+
+        let err = ForestError::ParseError("bad".to_string()).with_context("data.json", None);
+        assert_eq!(err.to_string(), "error in data.json: Parse error: bad");
+    }
+
     #[test]
     fn test_error_is_debug() {
         let err = ForestError::ParseError("test".to_string());
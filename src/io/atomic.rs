@@ -0,0 +1,140 @@
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use tempfile::NamedTempFile;
+
+use crate::error::ForestError;
+
+/// Delays between attempts to move the finished temp file into place.
+///
+/// Renames can fail transiently with `PermissionDenied` on Windows while a
+/// virus scanner or indexer briefly holds the target open.
+const PERSIST_BACKOFF: [Duration; 3] = [
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+];
+
+/// Write a file atomically: `write` fills a temp file in the same directory,
+/// which replaces `path` only after it has been completely written and synced.
+///
+/// If `write` (or the flush) fails, the temp file is removed and any existing
+/// file at `path` is left untouched. An existing file's permissions are kept.
+pub fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> Result<(), ForestError>,
+) -> Result<(), ForestError> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(".forest-write-");
+    if let Ok(existing) = std::fs::metadata(path) {
+        builder.permissions(existing.permissions());
+    } else {
+        // Match what `File::create` would produce (subject to umask)
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o666));
+        }
+    }
+    let mut tmp = builder.tempfile_in(dir)?;
+
+    {
+        let mut writer = BufWriter::new(tmp.as_file_mut());
+        write(&mut writer)?;
+        writer.flush()?;
+    }
+    tmp.as_file().sync_all()?;
+
+    persist_with_retry(tmp, path)
+}
+
+fn persist_with_retry(mut tmp: NamedTempFile, path: &Path) -> Result<(), ForestError> {
+    for delay in PERSIST_BACKOFF {
+        match tmp.persist(path) {
+            Ok(_) => return Ok(()),
+            Err(e) if e.error.kind() == std::io::ErrorKind::PermissionDenied => {
+                tmp = e.file;
+                std::thread::sleep(delay);
+            }
+            Err(e) => return Err(e.error.into()),
+        }
+    }
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_entries(dir: &Path) -> Vec<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with(".forest-write-"))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.txt");
+        std::fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, |w| Ok(w.write_all(b"new contents")?)).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new contents");
+        assert!(temp_entries(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_failed_write_leaves_original_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inventory.csv");
+        std::fs::write(&path, "good data").unwrap();
+
+        let result = write_atomic(&path, |w| {
+            w.write_all(b"partial")?;
+            Err(ForestError::Io(std::io::Error::other("disk full")))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "good data");
+        assert!(temp_entries(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_failed_write_creates_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("new.json");
+
+        let result = write_atomic(&path, |_| {
+            Err(ForestError::Io(std::io::Error::other("interrupted")))
+        });
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_existing_permissions_preserved() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shared.csv");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+        write_atomic(&path, |w| Ok(w.write_all(b"new")?)).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+}
@@ -7,6 +7,7 @@ use crate::error::ForestError;
 use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus, ValidationIssue};
 
 use super::csv_io::EditableTreeRow;
+use super::validate::FileIssue;
 
 /// Maximum plausible tree height in feet. Values above this are flagged as data entry errors.
 const MAX_TREE_HEIGHT_FT: f64 = 300.0;
@@ -215,6 +216,11 @@ pub fn read_cruise_excel<RS: std::io::Read + std::io::Seek>(
             elevation_ft: None,
             trees: Vec::new(),
             stand_id: Some(cr.stand_id),
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         });
 
         // Null/zero DBH rows represent empty-plot markers — keep the plot but skip the tree
@@ -262,6 +268,7 @@ pub fn read_cruise_excel<RS: std::io::Read + std::io::Seek>(
             expansion_factor: ef,
             age: None,
             defect,
+            decay_class: None,
         };
 
         if let Some(plot) = plots.get_mut(&key) {
@@ -277,16 +284,18 @@ pub fn read_cruise_excel<RS: std::io::Read + std::io::Seek>(
     Ok(inventory)
 }
 
-/// Parse cruise-format Excel leniently, returning editable rows and validation issues.
+/// Parse cruise-format Excel leniently, returning editable rows, validation
+/// errors and [`Tree::warnings`].
 pub fn parse_cruise_lenient<RS: std::io::Read + std::io::Seek>(
     workbook: &mut Xlsx<RS>,
     name: &str,
-) -> Result<(String, Vec<EditableTreeRow>, Vec<ValidationIssue>), ForestError> {
+) -> Result<(String, Vec<EditableTreeRow>, Vec<FileIssue>), ForestError> {
     let sheet_names: Vec<String> = workbook.sheet_names().to_vec();
     let cruise_rows = parse_cruise_sheets(workbook, &sheet_names)?;
 
     let mut editable_rows = Vec::new();
     let mut issues = Vec::new();
+    let mut warnings = Vec::new();
     let mut tree_counters: HashMap<(u32, u32), u32> = HashMap::new();
     let mut row_index: usize = 0;
 
@@ -370,9 +379,11 @@ pub fn parse_cruise_lenient<RS: std::io::Read + std::io::Seek>(
             expansion_factor: ef,
             age: None,
             defect,
+            decay_class: None,
         };
 
         issues.extend(tree.validate_all(row_index));
+        warnings.extend(tree.warnings(row_index));
 
         editable_rows.push(EditableTreeRow {
             row_index,
@@ -391,11 +402,18 @@ pub fn parse_cruise_lenient<RS: std::io::Read + std::io::Seek>(
             slope_percent: None,
             aspect_degrees: None,
             elevation_ft: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+            decay_class: None,
         });
 
         row_index += 1;
     }
 
+    let issues = FileIssue::tagged(issues, warnings);
     Ok((name.to_string(), editable_rows, issues))
 }
 
@@ -1,9 +1,111 @@
 use std::borrow::Cow;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
+use std::str::FromStr;
 
+use super::atomic::write_atomic;
+use super::validate::FileIssue;
 use crate::error::ForestError;
-use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus, ValidationIssue};
+use crate::models::{
+    ExpansionKind, ForestInventory, Plot, Species, Tree, TreeStatus, ValidationIssue,
+    DEFAULT_PLOT_SIZE_ACRES,
+};
+
+/// UTF-8 byte order mark, commonly written by Excel when saving as CSV.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Key of the `# expansion_kind: count` comment line, above the header,
+/// that records a count-based inventory's [`ExpansionKind`]. Files without
+/// it are read as per-acre.
+const EXPANSION_KIND_KEY: &str = "expansion_kind";
+
+/// Text encoding of CSV input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CsvEncoding {
+    /// Strict UTF-8 (a leading BOM is stripped)
+    #[default]
+    Utf8,
+    /// Windows-1252, a superset of Latin-1 used by many Windows exports
+    Windows1252,
+}
+
+impl FromStr for CsvEncoding {
+    type Err = ForestError;
+
+    /// Parse an encoding name (case-insensitive): `utf-8`/`utf8`, or
+    /// `windows-1252`/`cp1252`/`latin-1`/`latin1`/`iso-8859-1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(CsvEncoding::Utf8),
+            "windows-1252" | "cp1252" | "latin-1" | "latin1" | "iso-8859-1" => {
+                Ok(CsvEncoding::Windows1252)
+            }
+            _ => Err(ForestError::ParseError(format!(
+                "Unknown encoding: '{s}'. Use: utf-8 or windows-1252"
+            ))),
+        }
+    }
+}
+
+/// Decode raw CSV bytes to UTF-8, stripping a leading BOM.
+///
+/// UTF-8 input is validated up front so invalid bytes produce a clear error
+/// pointing at the `encoding` option rather than a field-level CSV error.
+fn decode_csv(data: &[u8], encoding: CsvEncoding) -> Result<Cow<'_, [u8]>, ForestError> {
+    match encoding {
+        CsvEncoding::Utf8 => {
+            let data = data.strip_prefix(UTF8_BOM).unwrap_or(data);
+            std::str::from_utf8(data).map_err(|e| {
+                ForestError::ParseError(format!(
+                    "CSV is not valid UTF-8 ({e}); try the windows-1252 encoding"
+                ))
+            })?;
+            Ok(Cow::Borrowed(data))
+        }
+        CsvEncoding::Windows1252 => {
+            // decode() also sniffs and removes a BOM
+            let (text, _, _) = encoding_rs::WINDOWS_1252.decode(data);
+            Ok(match text {
+                Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+                Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+            })
+        }
+    }
+}
+
+/// The expansion kind declared in the comment lines above the header of
+/// decoded CSV data; per-acre if there is none.
+fn declared_expansion_kind(data: &[u8]) -> Result<ExpansionKind, ForestError> {
+    for line in data.split(|&b| b == b'\n') {
+        let line = String::from_utf8_lossy(line);
+        let Some(comment) = line.trim().strip_prefix('#') else {
+            break;
+        };
+        if let Some((key, value)) = comment.split_once(':') {
+            if key.trim() == EXPANSION_KIND_KEY {
+                return value.trim().parse();
+            }
+        }
+    }
+    Ok(ExpansionKind::PerAcre)
+}
+
+/// The expansion kind declared by raw CSV bytes in the given encoding, for
+/// readers that parse rows on their own.
+pub(crate) fn csv_expansion_kind(
+    data: &[u8],
+    encoding: CsvEncoding,
+) -> Result<ExpansionKind, ForestError> {
+    declared_expansion_kind(&decode_csv(data, encoding)?)
+}
+
+/// Record a count-based inventory's expansion kind ahead of the header.
+fn write_expansion_kind(inventory: &ForestInventory, w: &mut dyn Write) -> Result<(), ForestError> {
+    if inventory.expansion_kind != ExpansionKind::PerAcre {
+        writeln!(w, "# {EXPANSION_KIND_KEY}: {}", inventory.expansion_kind)?;
+    }
+    Ok(())
+}
 
 /// CSV row structure for tree data.
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -23,85 +125,178 @@ struct TreeRow {
     slope_percent: Option<f64>,
     aspect_degrees: Option<f64>,
     elevation_ft: Option<f64>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    measurement_date: Option<String>,
+    #[serde(default)]
+    decay_class: Option<u8>,
+    #[serde(default)]
+    latitude: Option<f64>,
+    #[serde(default)]
+    longitude: Option<f64>,
+    #[serde(default)]
+    stratum: Option<String>,
 }
 
+/// Parse every record into plots. When reading a file, errors in a record
+/// are tagged with `path` and the record's line number.
 fn parse_csv_records<R: Read>(
     rdr: &mut csv::Reader<R>,
+    path: Option<&Path>,
+    mapping: &ColumnMapping,
 ) -> Result<std::collections::HashMap<u32, Plot>, ForestError> {
     let mut plots: std::collections::HashMap<u32, Plot> = std::collections::HashMap::new();
-
-    for result in rdr.deserialize() {
-        let row: TreeRow = result?;
-        let status: TreeStatus = row.status.parse()?;
-
-        let tree = Tree {
-            tree_id: row.tree_id,
-            plot_id: row.plot_id,
-            species: Species {
-                common_name: row.species_name,
-                code: row.species_code,
-            },
-            dbh: row.dbh,
-            height: row.height,
-            crown_ratio: row.crown_ratio,
-            status,
-            expansion_factor: row.expansion_factor,
-            age: row.age,
-            defect: row.defect,
-        };
-
-        tree.validate()?;
-
-        let plot = plots.entry(row.plot_id).or_insert_with(|| Plot {
-            plot_id: row.plot_id,
-            plot_size_acres: row.plot_size_acres.unwrap_or(0.2),
-            slope_percent: row.slope_percent,
-            aspect_degrees: row.aspect_degrees,
-            elevation_ft: row.elevation_ft,
-            trees: Vec::new(),
-            stand_id: None,
-        });
-
-        plot.trees.push(tree);
+    let locate = |e: ForestError, line: Option<u64>| match path {
+        Some(path) => e.with_context(path, line.map(|l| l as usize)),
+        None => e,
+    };
+    let locate_csv = |e: csv::Error| {
+        let line = e.position().map(|p| p.line());
+        locate(e.into(), line)
+    };
+
+    let headers = rdr.headers().map_err(locate_csv)?;
+    let headers = if mapping.is_empty() {
+        headers.clone()
+    } else {
+        mapping.apply(headers).map_err(|e| locate(e, Some(1)))?
+    };
+    let mut record = csv::StringRecord::new();
+    while rdr.read_record(&mut record).map_err(locate_csv)? {
+        let line = record.position().map(|p| p.line());
+        parse_csv_record(&record, &headers, &mut plots).map_err(|e| locate(e, line))?;
     }
 
     Ok(plots)
 }
 
+/// Deserialize, validate and file one record under its plot.
+fn parse_csv_record(
+    record: &csv::StringRecord,
+    headers: &csv::StringRecord,
+    plots: &mut std::collections::HashMap<u32, Plot>,
+) -> Result<(), ForestError> {
+    let row: TreeRow = record.deserialize(Some(headers))?;
+    let status: TreeStatus = row.status.parse()?;
+
+    let tree = Tree {
+        tree_id: row.tree_id,
+        plot_id: row.plot_id,
+        species: Species {
+            common_name: row.species_name,
+            code: row.species_code,
+        },
+        dbh: row.dbh,
+        height: row.height,
+        crown_ratio: row.crown_ratio,
+        status,
+        expansion_factor: row.expansion_factor,
+        age: row.age,
+        defect: row.defect,
+        decay_class: row.decay_class,
+    };
+
+    tree.validate()?;
+
+    let plot = plots.entry(row.plot_id).or_insert_with(|| Plot {
+        plot_id: row.plot_id,
+        plot_size_acres: row.plot_size_acres.unwrap_or(DEFAULT_PLOT_SIZE_ACRES),
+        slope_percent: row.slope_percent,
+        aspect_degrees: row.aspect_degrees,
+        elevation_ft: row.elevation_ft,
+        trees: Vec::new(),
+        stand_id: None,
+        source: row.source,
+        measurement_date: row.measurement_date,
+        latitude: row.latitude,
+        longitude: row.longitude,
+        stratum: row.stratum,
+    });
+
+    plot.trees.push(tree);
+    Ok(())
+}
+
 /// Read forest inventory data from a CSV file.
+///
+/// Lines starting with `#` are treated as comments and skipped, except that
+/// a `# expansion_kind: count` line above the header marks the expansion
+/// factors as tree counts (see [`ExpansionKind`]).
 pub fn read_csv(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError> {
-    let path = path.as_ref();
-    let mut rdr = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .flexible(true)
-        .trim(csv::Trim::All)
-        .from_path(path)?;
+    read_csv_with_encoding(path, CsvEncoding::Utf8)
+}
 
-    let plots = parse_csv_records(&mut rdr)?;
+/// Read forest inventory data from a CSV file in the given text encoding.
+pub fn read_csv_with_encoding(
+    path: impl AsRef<Path>,
+    encoding: CsvEncoding,
+) -> Result<ForestInventory, ForestError> {
+    read_csv_mapped_with_encoding(path, encoding, &ColumnMapping::default())
+}
 
-    let mut inventory = ForestInventory::new(
-        path.file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "Unknown".to_string()),
-    );
-    let mut plot_list: Vec<Plot> = plots.into_values().collect();
-    plot_list.sort_by_key(|p| p.plot_id);
-    inventory.plots = plot_list;
+/// Read forest inventory data from a CSV file whose headers are renamed by
+/// `mapping`, e.g. a crew file with `DBH_in` in place of `dbh`.
+///
+/// Columns the mapping does not mention are read under their usual names.
+/// Returns `ForestError::ParseError` naming the field if a required column
+/// is absent after mapping.
+pub fn read_csv_mapped(
+    path: impl AsRef<Path>,
+    mapping: &ColumnMapping,
+) -> Result<ForestInventory, ForestError> {
+    read_csv_mapped_with_encoding(path, CsvEncoding::Utf8, mapping)
+}
 
-    Ok(inventory)
+/// [`read_csv_mapped`], reading the file in the given text encoding.
+pub fn read_csv_mapped_with_encoding(
+    path: impl AsRef<Path>,
+    encoding: CsvEncoding,
+    mapping: &ColumnMapping,
+) -> Result<ForestInventory, ForestError> {
+    let path = path.as_ref();
+    let data = std::fs::read(path).map_err(|e| ForestError::from(e).with_context(path, None))?;
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    parse_csv(&data, &name, encoding, Some(path), mapping).map_err(|e| e.with_context(path, None))
 }
 
 /// Read forest inventory data from CSV bytes.
 pub fn read_csv_from_bytes(data: &[u8], name: &str) -> Result<ForestInventory, ForestError> {
+    read_csv_from_bytes_with_encoding(data, name, CsvEncoding::Utf8)
+}
+
+/// Read forest inventory data from CSV bytes in the given text encoding.
+pub fn read_csv_from_bytes_with_encoding(
+    data: &[u8],
+    name: &str,
+    encoding: CsvEncoding,
+) -> Result<ForestInventory, ForestError> {
+    parse_csv(data, name, encoding, None, &ColumnMapping::default())
+}
+
+/// Parse CSV bytes; `path` is the file they were read from, if any.
+fn parse_csv(
+    data: &[u8],
+    name: &str,
+    encoding: CsvEncoding,
+    path: Option<&Path>,
+    mapping: &ColumnMapping,
+) -> Result<ForestInventory, ForestError> {
+    let data = decode_csv(data, encoding)?;
     let mut rdr = csv::ReaderBuilder::new()
         .has_headers(true)
         .flexible(true)
         .trim(csv::Trim::All)
-        .from_reader(data);
+        .comment(Some(b'#'))
+        .from_reader(data.as_ref());
 
-    let plots = parse_csv_records(&mut rdr)?;
+    let plots = parse_csv_records(&mut rdr, path, mapping)?;
 
     let mut inventory = ForestInventory::new(name);
+    inventory.expansion_kind = declared_expansion_kind(&data)?;
     let mut plot_list: Vec<Plot> = plots.into_values().collect();
     plot_list.sort_by_key(|p| p.plot_id);
     inventory.plots = plot_list;
@@ -110,8 +305,17 @@ pub fn read_csv_from_bytes(data: &[u8], name: &str) -> Result<ForestInventory, F
 }
 
 /// Write forest inventory summary data to a CSV file.
+///
+/// A count-based inventory gets a `# expansion_kind: count` comment line
+/// above the header, so [`read_csv`] restores its [`ExpansionKind`]. The file
+/// is replaced atomically; on failure any existing file is untouched.
 pub fn write_csv(inventory: &ForestInventory, path: impl AsRef<Path>) -> Result<(), ForestError> {
-    let mut wtr = csv::Writer::from_path(path.as_ref())?;
+    write_atomic(path.as_ref(), |w| write_csv_rows(inventory, w))
+}
+
+fn write_csv_rows(inventory: &ForestInventory, w: &mut dyn Write) -> Result<(), ForestError> {
+    write_expansion_kind(inventory, w)?;
+    let mut wtr = csv::Writer::from_writer(w);
 
     for plot in &inventory.plots {
         for tree in &plot.trees {
@@ -131,6 +335,12 @@ pub fn write_csv(inventory: &ForestInventory, path: impl AsRef<Path>) -> Result<
                 slope_percent: plot.slope_percent,
                 aspect_degrees: plot.aspect_degrees,
                 elevation_ft: plot.elevation_ft,
+                source: plot.source.clone(),
+                measurement_date: plot.measurement_date.clone(),
+                latitude: plot.latitude,
+                longitude: plot.longitude,
+                stratum: plot.stratum.clone(),
+                decay_class: tree.decay_class,
             };
             wtr.serialize(&row)?;
         }
@@ -140,9 +350,287 @@ pub fn write_csv(inventory: &ForestInventory, path: impl AsRef<Path>) -> Result<
     Ok(())
 }
 
+/// A column of the tree CSV layout, for writing a subset with [`write_csv_columns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TreeColumn {
+    PlotId,
+    TreeId,
+    SpeciesCode,
+    SpeciesName,
+    Dbh,
+    Height,
+    CrownRatio,
+    Status,
+    ExpansionFactor,
+    Age,
+    Defect,
+    PlotSizeAcres,
+    SlopePercent,
+    AspectDegrees,
+    ElevationFt,
+    Source,
+    MeasurementDate,
+    DecayClass,
+    Latitude,
+    Longitude,
+    Stratum,
+}
+
+impl TreeColumn {
+    /// Every column, in the order [`write_csv`] writes them.
+    pub const ALL: [TreeColumn; 21] = [
+        TreeColumn::PlotId,
+        TreeColumn::TreeId,
+        TreeColumn::SpeciesCode,
+        TreeColumn::SpeciesName,
+        TreeColumn::Dbh,
+        TreeColumn::Height,
+        TreeColumn::CrownRatio,
+        TreeColumn::Status,
+        TreeColumn::ExpansionFactor,
+        TreeColumn::Age,
+        TreeColumn::Defect,
+        TreeColumn::PlotSizeAcres,
+        TreeColumn::SlopePercent,
+        TreeColumn::AspectDegrees,
+        TreeColumn::ElevationFt,
+        TreeColumn::Source,
+        TreeColumn::MeasurementDate,
+        TreeColumn::DecayClass,
+        TreeColumn::Latitude,
+        TreeColumn::Longitude,
+        TreeColumn::Stratum,
+    ];
+
+    /// Header name, matching the full CSV layout.
+    pub fn name(self) -> &'static str {
+        match self {
+            TreeColumn::PlotId => "plot_id",
+            TreeColumn::TreeId => "tree_id",
+            TreeColumn::SpeciesCode => "species_code",
+            TreeColumn::SpeciesName => "species_name",
+            TreeColumn::Dbh => "dbh",
+            TreeColumn::Height => "height",
+            TreeColumn::CrownRatio => "crown_ratio",
+            TreeColumn::Status => "status",
+            TreeColumn::ExpansionFactor => "expansion_factor",
+            TreeColumn::Age => "age",
+            TreeColumn::Defect => "defect",
+            TreeColumn::PlotSizeAcres => "plot_size_acres",
+            TreeColumn::SlopePercent => "slope_percent",
+            TreeColumn::AspectDegrees => "aspect_degrees",
+            TreeColumn::ElevationFt => "elevation_ft",
+            TreeColumn::Source => "source",
+            TreeColumn::MeasurementDate => "measurement_date",
+            TreeColumn::DecayClass => "decay_class",
+            TreeColumn::Latitude => "latitude",
+            TreeColumn::Longitude => "longitude",
+            TreeColumn::Stratum => "stratum",
+        }
+    }
+
+    /// Whether every row must have this column.
+    pub fn is_required(self) -> bool {
+        matches!(
+            self,
+            TreeColumn::PlotId
+                | TreeColumn::TreeId
+                | TreeColumn::SpeciesCode
+                | TreeColumn::SpeciesName
+                | TreeColumn::Dbh
+                | TreeColumn::Status
+                | TreeColumn::ExpansionFactor
+        )
+    }
+
+    fn value<'a>(self, plot: &'a Plot, tree: &'a Tree) -> CsvCell<'a> {
+        match self {
+            TreeColumn::PlotId => CsvCell::Int(Some(tree.plot_id)),
+            TreeColumn::TreeId => CsvCell::Int(Some(tree.tree_id)),
+            TreeColumn::SpeciesCode => CsvCell::Text(Cow::Borrowed(&tree.species.code)),
+            TreeColumn::SpeciesName => CsvCell::Text(Cow::Borrowed(&tree.species.common_name)),
+            TreeColumn::Dbh => CsvCell::Float(Some(tree.dbh)),
+            TreeColumn::Height => CsvCell::Float(tree.height),
+            TreeColumn::CrownRatio => CsvCell::Float(tree.crown_ratio),
+            TreeColumn::Status => CsvCell::Text(Cow::Owned(tree.status.to_string())),
+            TreeColumn::ExpansionFactor => CsvCell::Float(Some(tree.expansion_factor)),
+            TreeColumn::Age => CsvCell::Int(tree.age),
+            TreeColumn::Defect => CsvCell::Float(tree.defect),
+            TreeColumn::PlotSizeAcres => CsvCell::Float(Some(plot.plot_size_acres)),
+            TreeColumn::SlopePercent => CsvCell::Float(plot.slope_percent),
+            TreeColumn::AspectDegrees => CsvCell::Float(plot.aspect_degrees),
+            TreeColumn::ElevationFt => CsvCell::Float(plot.elevation_ft),
+            TreeColumn::Source => {
+                CsvCell::Text(Cow::Borrowed(plot.source.as_deref().unwrap_or_default()))
+            }
+            TreeColumn::MeasurementDate => CsvCell::Text(Cow::Borrowed(
+                plot.measurement_date.as_deref().unwrap_or_default(),
+            )),
+            TreeColumn::DecayClass => CsvCell::Int(tree.decay_class.map(u32::from)),
+            TreeColumn::Latitude => CsvCell::Float(plot.latitude),
+            TreeColumn::Longitude => CsvCell::Float(plot.longitude),
+            TreeColumn::Stratum => {
+                CsvCell::Text(Cow::Borrowed(plot.stratum.as_deref().unwrap_or_default()))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for TreeColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for TreeColumn {
+    type Err = ForestError;
+
+    /// Parse a column by its header name (case-insensitive), e.g. `species_code`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        TreeColumn::ALL
+            .into_iter()
+            .find(|c| c.name() == lower)
+            .ok_or_else(|| {
+                let names: Vec<&str> = TreeColumn::ALL.iter().map(|c| c.name()).collect();
+                ForestError::ParseError(format!("Unknown column: '{s}'. Use: {}", names.join(", ")))
+            })
+    }
+}
+
+/// Header names to read columns from, for CSV files whose headers differ from
+/// the [`TreeColumn`] names. Used by [`read_csv_mapped`].
+///
+/// Headers are matched case-insensitively. Build one with [`ColumnMapping::with`]
+/// or by collecting `(TreeColumn, header)` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    headers: std::collections::HashMap<TreeColumn, String>,
+}
+
+impl ColumnMapping {
+    /// A mapping that reads every column under its usual name.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `column` from the header `header`.
+    pub fn with(mut self, column: TreeColumn, header: impl Into<String>) -> Self {
+        self.insert(column, header);
+        self
+    }
+
+    /// Read `column` from the header `header`, replacing any earlier mapping
+    /// for the column.
+    pub fn insert(&mut self, column: TreeColumn, header: impl Into<String>) {
+        self.headers.insert(column, header.into());
+    }
+
+    /// The header `column` is read from, if it is mapped.
+    pub fn header(&self, column: TreeColumn) -> Option<&str> {
+        self.headers.get(&column).map(String::as_str)
+    }
+
+    /// Whether no column is mapped.
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    /// Rename `headers` to the canonical column names and check that every
+    /// required column is present.
+    ///
+    /// A header that already has a mapped column's canonical name is
+    /// dropped, so the mapped header is the one read.
+    fn apply(&self, headers: &csv::StringRecord) -> Result<csv::StringRecord, ForestError> {
+        let renamed: csv::StringRecord = headers
+            .iter()
+            .map(|header| {
+                let mapped = self
+                    .headers
+                    .iter()
+                    .find(|(_, from)| from.trim().eq_ignore_ascii_case(header));
+                match mapped {
+                    Some((column, _)) => column.name(),
+                    None if self.headers.keys().any(|c| c.name() == header) => "",
+                    None => header,
+                }
+            })
+            .collect();
+
+        for column in TreeColumn::ALL.into_iter().filter(|c| c.is_required()) {
+            if renamed.iter().any(|h| h == column.name()) {
+                continue;
+            }
+            return Err(ForestError::ParseError(match self.header(column) {
+                Some(from) => {
+                    format!("Missing required column '{column}': no header named '{from}'")
+                }
+                None => format!("Missing required column '{column}' in CSV header"),
+            }));
+        }
+        Ok(renamed)
+    }
+}
+
+impl FromIterator<(TreeColumn, String)> for ColumnMapping {
+    fn from_iter<I: IntoIterator<Item = (TreeColumn, String)>>(iter: I) -> Self {
+        Self {
+            headers: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// One field of a [`write_csv_columns`] row, serialized the same way as [`TreeRow`].
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum CsvCell<'a> {
+    Int(Option<u32>),
+    Float(Option<f64>),
+    Text(Cow<'a, str>),
+}
+
+/// Write only `columns` of the tree data, in the given order, to a CSV file.
+///
+/// Values and the expansion kind line are written exactly as in
+/// [`write_csv`]. Returns `ForestError::ValidationError` if `columns` is
+/// empty or names a column twice. The file is replaced atomically.
+pub fn write_csv_columns(
+    inventory: &ForestInventory,
+    path: impl AsRef<Path>,
+    columns: &[TreeColumn],
+) -> Result<(), ForestError> {
+    if columns.is_empty() {
+        return Err(ForestError::ValidationError(
+            "At least one column is required".to_string(),
+        ));
+    }
+    for (i, column) in columns.iter().enumerate() {
+        if columns[..i].contains(column) {
+            return Err(ForestError::ValidationError(format!(
+                "Column '{column}' is requested more than once"
+            )));
+        }
+    }
+
+    write_atomic(path.as_ref(), |w| {
+        write_expansion_kind(inventory, w)?;
+        let mut wtr = csv::Writer::from_writer(w);
+        wtr.write_record(columns.iter().map(|c| c.name()))?;
+        for plot in &inventory.plots {
+            for tree in &plot.trees {
+                let row: Vec<CsvCell> = columns.iter().map(|c| c.value(plot, tree)).collect();
+                wtr.serialize(&row)?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    })
+}
+
 /// Flat, editable representation of a tree row for the web editor.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct EditableTreeRow {
+    #[serde(default)]
     pub row_index: usize,
     pub plot_id: u32,
     pub tree_id: u32,
@@ -159,6 +647,18 @@ pub struct EditableTreeRow {
     pub slope_percent: Option<f64>,
     pub aspect_degrees: Option<f64>,
     pub elevation_ft: Option<f64>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub measurement_date: Option<String>,
+    #[serde(default)]
+    pub decay_class: Option<u8>,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    #[serde(default)]
+    pub stratum: Option<String>,
 }
 
 /// Convert flat editable rows into a `ForestInventory`.
@@ -195,6 +695,7 @@ pub(crate) fn rows_to_inventory(name: &str, rows: &[EditableTreeRow]) -> ForestI
             expansion_factor: row.expansion_factor,
             age: row.age,
             defect: row.defect,
+            decay_class: row.decay_class,
         };
 
         // Log validation issues (non-fatal — include the tree regardless)
@@ -207,12 +708,17 @@ pub(crate) fn rows_to_inventory(name: &str, rows: &[EditableTreeRow]) -> ForestI
 
         let plot = plots.entry(row.plot_id).or_insert_with(|| Plot {
             plot_id: row.plot_id,
-            plot_size_acres: row.plot_size_acres.unwrap_or(0.2),
+            plot_size_acres: row.plot_size_acres.unwrap_or(DEFAULT_PLOT_SIZE_ACRES),
             slope_percent: row.slope_percent,
             aspect_degrees: row.aspect_degrees,
             elevation_ft: row.elevation_ft,
             trees: Vec::new(),
             stand_id: None,
+            source: row.source.clone(),
+            measurement_date: row.measurement_date.clone(),
+            latitude: row.latitude,
+            longitude: row.longitude,
+            stratum: row.stratum.clone(),
         });
 
         // Warn on conflicting plot metadata
@@ -242,19 +748,33 @@ pub(crate) fn rows_to_inventory(name: &str, rows: &[EditableTreeRow]) -> ForestI
 /// Parse CSV leniently: collect all validation issues instead of failing on the first.
 ///
 /// CSV **format** errors (missing columns, type mismatches) are still fatal.
-/// Returns all rows (including invalid ones) + all validation issues.
+/// Returns all rows (including invalid ones) + all validation errors and
+/// [`Tree::warnings`].
+#[cfg(any(feature = "web", feature = "wasm"))]
 pub(crate) fn parse_csv_lenient(
     data: &[u8],
     name: &str,
-) -> Result<(String, Vec<EditableTreeRow>, Vec<ValidationIssue>), ForestError> {
+) -> Result<(String, Vec<EditableTreeRow>, Vec<FileIssue>), ForestError> {
+    parse_csv_lenient_with_encoding(data, name, CsvEncoding::Utf8)
+}
+
+/// Parse CSV leniently from bytes in the given text encoding.
+pub(crate) fn parse_csv_lenient_with_encoding(
+    data: &[u8],
+    name: &str,
+    encoding: CsvEncoding,
+) -> Result<(String, Vec<EditableTreeRow>, Vec<FileIssue>), ForestError> {
+    let data = decode_csv(data, encoding)?;
     let mut rdr = csv::ReaderBuilder::new()
         .has_headers(true)
         .flexible(true)
         .trim(csv::Trim::All)
-        .from_reader(data);
+        .comment(Some(b'#'))
+        .from_reader(data.as_ref());
 
     let mut rows = Vec::new();
     let mut issues = Vec::new();
+    let mut warnings = Vec::new();
 
     for (row_index, result) in rdr.deserialize().enumerate() {
         let csv_row: TreeRow = result?;
@@ -289,10 +809,12 @@ pub(crate) fn parse_csv_lenient(
             expansion_factor: csv_row.expansion_factor,
             age: csv_row.age,
             defect: csv_row.defect,
+            decay_class: csv_row.decay_class,
         };
 
         // Validate leniently
         issues.extend(tree.validate_all(row_index));
+        warnings.extend(tree.warnings(row_index));
 
         rows.push(EditableTreeRow {
             row_index,
@@ -311,8 +833,14 @@ pub(crate) fn parse_csv_lenient(
             slope_percent: csv_row.slope_percent,
             aspect_degrees: csv_row.aspect_degrees,
             elevation_ft: csv_row.elevation_ft,
+            source: csv_row.source.clone(),
+            measurement_date: csv_row.measurement_date.clone(),
+            latitude: csv_row.latitude,
+            longitude: csv_row.longitude,
+            stratum: csv_row.stratum.clone(),
+            decay_class: csv_row.decay_class,
         });
     }
 
-    Ok((name.to_string(), rows, issues))
+    Ok((name.to_string(), rows, FileIssue::tagged(issues, warnings)))
 }
@@ -0,0 +1,274 @@
+use std::path::Path;
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, Worksheet, XlsxError};
+
+use super::atomic::write_atomic;
+use crate::error::ForestError;
+use crate::models::ForestInventory;
+use crate::report::{AnalyzeParams, AnalyzeReport};
+
+const METRICS_SHEET: &str = "Metrics";
+const STATISTICS_SHEET: &str = "Statistics";
+const DISTRIBUTION_SHEET: &str = "Distribution";
+
+fn excel(e: XlsxError) -> ForestError {
+    ForestError::Excel(e.to_string())
+}
+
+/// Write the analysis of `inventory` to an Excel workbook for clients.
+///
+/// Unlike [`write_excel`](super::write_excel), which writes the tree data,
+/// the workbook holds the results of an [`AnalyzeReport`]: a "Metrics" sheet
+/// with stand totals and species composition, a "Statistics" sheet with the
+/// sampling confidence intervals, and a "Distribution" sheet with the
+/// diameter classes and a native column chart of TPA per class bound to its
+/// cells. The file is replaced atomically.
+pub fn write_excel_analysis(
+    inventory: &ForestInventory,
+    path: impl AsRef<Path>,
+    params: AnalyzeParams,
+) -> Result<(), ForestError> {
+    let report = AnalyzeReport::from_inventory(inventory, params)?;
+    let mut workbook = Workbook::new();
+
+    let sheet = workbook
+        .add_worksheet()
+        .set_name(METRICS_SHEET)
+        .map_err(excel)?;
+    write_metrics_sheet(sheet, &report)?;
+
+    let sheet = workbook
+        .add_worksheet()
+        .set_name(STATISTICS_SHEET)
+        .map_err(excel)?;
+    write_statistics_sheet(sheet, &report)?;
+
+    let sheet = workbook
+        .add_worksheet()
+        .set_name(DISTRIBUTION_SHEET)
+        .map_err(excel)?;
+    write_distribution_sheet(sheet, &report)?;
+
+    let buffer = workbook.save_to_buffer().map_err(excel)?;
+    write_atomic(path.as_ref(), |w| Ok(w.write_all(&buffer)?))
+}
+
+fn write_header(sheet: &mut Worksheet, row: u32, headers: &[&str]) -> Result<(), ForestError> {
+    for (col, header) in headers.iter().enumerate() {
+        sheet
+            .write_string(row, col as u16, *header)
+            .map_err(excel)?;
+    }
+    Ok(())
+}
+
+fn write_metrics_sheet(sheet: &mut Worksheet, report: &AnalyzeReport) -> Result<(), ForestError> {
+    let m = &report.metrics;
+    write_header(sheet, 0, &["metric", "value"])?;
+    sheet.write_string(1, 0, "name").map_err(excel)?;
+    sheet.write_string(1, 1, &report.name).map_err(excel)?;
+    let mut values = vec![
+        ("plots", report.num_plots as f64),
+        ("trees", report.num_trees as f64),
+        ("tpa", m.total_tpa),
+        ("basal_area_sqft_per_acre", m.total_basal_area),
+        ("volume_cuft_per_acre", m.total_volume_cuft),
+        ("volume_bdft_per_acre", m.total_volume_bdft),
+        ("quadratic_mean_diameter", m.quadratic_mean_diameter),
+    ];
+    if let Some(height) = m.mean_height {
+        values.push(("mean_height", height));
+    }
+    for (row, (name, value)) in values.iter().enumerate() {
+        let row = row as u32 + 2;
+        sheet.write_string(row, 0, *name).map_err(excel)?;
+        sheet.write_number(row, 1, *value).map_err(excel)?;
+    }
+
+    // Species composition below the totals, after a blank row
+    let top = values.len() as u32 + 3;
+    write_header(
+        sheet,
+        top,
+        &[
+            "species_code",
+            "species_name",
+            "tpa",
+            "basal_area",
+            "percent_tpa",
+            "percent_basal_area",
+            "mean_dbh",
+        ],
+    )?;
+    for (row, sc) in m.species_composition.iter().enumerate() {
+        let row = top + 1 + row as u32;
+        sheet
+            .write_string(row, 0, &sc.species.code)
+            .map_err(excel)?;
+        sheet
+            .write_string(row, 1, &sc.species.common_name)
+            .map_err(excel)?;
+        let numbers = [
+            sc.tpa,
+            sc.basal_area,
+            sc.percent_tpa,
+            sc.percent_basal_area,
+            sc.mean_dbh,
+        ];
+        for (col, value) in numbers.iter().enumerate() {
+            sheet
+                .write_number(row, col as u16 + 2, *value)
+                .map_err(excel)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_statistics_sheet(
+    sheet: &mut Worksheet,
+    report: &AnalyzeReport,
+) -> Result<(), ForestError> {
+    let Some(stats) = &report.statistics else {
+        sheet
+            .write_string(0, 0, "Sampling statistics need at least 2 plots")
+            .map_err(excel)?;
+        return Ok(());
+    };
+    write_header(
+        sheet,
+        0,
+        &[
+            "metric",
+            "mean",
+            "std_error",
+            "lower",
+            "upper",
+            "sampling_error_percent",
+            "cv_percent",
+            "sample_size",
+            "confidence_level",
+        ],
+    )?;
+    for (row, (name, ci)) in stats.named().iter().enumerate() {
+        let row = row as u32 + 1;
+        sheet.write_string(row, 0, *name).map_err(excel)?;
+        let numbers = [
+            ci.mean,
+            ci.std_error,
+            ci.lower,
+            ci.upper,
+            ci.sampling_error_percent,
+            ci.cv_percent,
+            ci.sample_size as f64,
+            ci.confidence_level,
+        ];
+        for (col, value) in numbers.iter().enumerate() {
+            sheet
+                .write_number(row, col as u16 + 1, *value)
+                .map_err(excel)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_distribution_sheet(
+    sheet: &mut Worksheet,
+    report: &AnalyzeReport,
+) -> Result<(), ForestError> {
+    let classes = &report.distribution.classes;
+    write_header(
+        sheet,
+        0,
+        &["class", "lower", "upper", "tpa", "basal_area", "trees"],
+    )?;
+    for (row, class) in classes.iter().enumerate() {
+        let row = row as u32 + 1;
+        sheet
+            .write_string(row, 0, format!("{}-{}", class.lower, class.upper))
+            .map_err(excel)?;
+        let numbers = [
+            class.lower,
+            class.upper,
+            class.tpa,
+            class.basal_area,
+            class.tree_count as f64,
+        ];
+        for (col, value) in numbers.iter().enumerate() {
+            sheet
+                .write_number(row, col as u16 + 1, *value)
+                .map_err(excel)?;
+        }
+    }
+    if classes.is_empty() {
+        return Ok(());
+    }
+
+    // The chart reads the class labels and TPA cells, so edits to the sheet
+    // redraw it in Excel
+    let last = classes.len() as u32;
+    let mut chart = Chart::new(ChartType::Column);
+    chart
+        .add_series()
+        .set_name("TPA")
+        .set_categories((DISTRIBUTION_SHEET, 1, 0, last, 0))
+        .set_values((DISTRIBUTION_SHEET, 1, 3, last, 3));
+    chart.title().set_name("Diameter Distribution");
+    chart.x_axis().set_name("DBH class (in)");
+    chart.y_axis().set_name("Trees per acre");
+    chart.legend().set_hidden();
+    sheet.insert_chart(1, 7, &chart).map_err(excel)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::SAMPLE_CSV;
+    use calamine::{open_workbook, Data, DataType, Reader, Xlsx};
+
+    #[test]
+    fn test_analysis_workbook_has_sheets_and_chart() {
+        let inventory = crate::io::read_csv_from_bytes(SAMPLE_CSV.as_bytes(), "stand").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("analysis.xlsx");
+        write_excel_analysis(&inventory, &path, AnalyzeParams::default()).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        assert_eq!(
+            workbook.sheet_names(),
+            [METRICS_SHEET, STATISTICS_SHEET, DISTRIBUTION_SHEET]
+        );
+
+        let stats = workbook.worksheet_range(STATISTICS_SHEET).unwrap();
+        assert_eq!(stats.get_value((1, 0)), Some(&Data::String("tpa".into())));
+        assert_eq!(stats.get_value((1, 7)), Some(&Data::Float(3.0)));
+
+        let dist = workbook.worksheet_range(DISTRIBUTION_SHEET).unwrap();
+        let class_tpa: f64 = dist
+            .rows()
+            .skip(1)
+            .map(|row| row[3].as_f64().unwrap())
+            .sum();
+        assert!((class_tpa - 20.0 / 3.0).abs() < 1e-9);
+
+        // Zip entry names are stored uncompressed, so the chart part shows
+        // up in the raw bytes
+        let bytes = std::fs::read(&path).unwrap();
+        let chart_part = b"xl/charts/chart1.xml";
+        assert!(bytes.windows(chart_part.len()).any(|w| w == chart_part));
+    }
+
+    #[test]
+    fn test_single_plot_has_no_statistics_table() {
+        let plot_1: Vec<&str> = SAMPLE_CSV.lines().take(2).collect();
+        let inventory =
+            crate::io::read_csv_from_bytes(plot_1.join("\n").as_bytes(), "one").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("one.xlsx");
+        write_excel_analysis(&inventory, &path, AnalyzeParams::default()).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let stats = workbook.worksheet_range(STATISTICS_SHEET).unwrap();
+        assert_eq!(stats.height(), 1);
+    }
+}
@@ -1,28 +1,146 @@
 use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::path::Path;
 
-use calamine::{open_workbook, DataType, Reader, Xlsx};
+use calamine::{open_workbook, Data, Reader, Xlsx};
 use rust_xlsxwriter::Workbook;
 
 use crate::error::ForestError;
-use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus, ValidationIssue};
+use crate::models::{
+    ExpansionKind, ForestInventory, Plot, Species, Tree, TreeStatus, ValidationIssue,
+    DEFAULT_PLOT_SIZE_ACRES,
+};
 
+use super::atomic::write_atomic;
 use super::csv_io::EditableTreeRow;
+use super::metadata::ExportMetadata;
+use super::validate::FileIssue;
+
+/// Columns that must be present in a standard-layout worksheet header.
+///
+/// Mirrors the non-optional fields of the CSV reader.
+const REQUIRED_COLUMNS: &[&str] = &[
+    "plot_id",
+    "tree_id",
+    "species_code",
+    "species_name",
+    "dbh",
+    "status",
+    "expansion_factor",
+];
+
+/// Column positions resolved from a worksheet's header row.
+///
+/// Header names are matched case-insensitively after trimming, so columns may
+/// appear in any order. When a name appears more than once, the first wins.
+struct ColumnMap {
+    index: HashMap<String, usize>,
+}
+
+impl ColumnMap {
+    /// Build the map from a header row, failing if any required column is absent.
+    fn from_header(header: &[Data]) -> Result<Self, ForestError> {
+        let mut index = HashMap::new();
+        for (i, cell) in header.iter().enumerate() {
+            let name = cell.to_string().trim().to_lowercase();
+            if !name.is_empty() {
+                index.entry(name).or_insert(i);
+            }
+        }
+        for &col in REQUIRED_COLUMNS {
+            if !index.contains_key(col) {
+                return Err(ForestError::ParseError(format!(
+                    "Missing required column '{col}' in Excel header"
+                )));
+            }
+        }
+        Ok(Self { index })
+    }
+
+    /// Look up the cell for a named column in a data row.
+    fn cell<'a>(&self, row: &'a [Data], name: &str) -> Option<&'a Data> {
+        self.index.get(name).and_then(|&i| row.get(i))
+    }
+
+    /// Date of a named column as `YYYY-MM-DD`; `None` when the cell is
+    /// missing or blank.
+    ///
+    /// Cells formatted as dates hold a serial day number, so they are
+    /// converted rather than printed; any time of day is dropped. Text cells
+    /// are taken as written.
+    fn date(&self, row: &[Data], name: &str) -> Option<String> {
+        let text = match self.cell(row, name)? {
+            Data::DateTime(dt) => dt.as_datetime()?.date().to_string(),
+            Data::DateTimeIso(iso) => iso.split('T').next().unwrap_or_default().to_string(),
+            cell => cell.to_string(),
+        };
+        Some(text).filter(|s| !s.is_empty())
+    }
+
+    /// Numeric value of a named column; `Ok(None)` when the cell is missing
+    /// or blank.
+    ///
+    /// Numbers stored as text, common in sheets exported from other tools,
+    /// are parsed and a warning is logged naming `sheet_row` (1-based, as
+    /// Excel shows it). Text that is not a number is an `Err` message.
+    fn number(&self, row: &[Data], name: &str, sheet_row: usize) -> Result<Option<f64>, String> {
+        let Some((value, coerced)) = self.number_cell(row, name)? else {
+            return Ok(None);
+        };
+        if let Some(note) = coerced {
+            tracing::warn!("Row {sheet_row}, column '{name}': {note}");
+        }
+        Ok(Some(value))
+    }
+
+    /// Like [`number`](Self::number), but returning a note describing the
+    /// conversion, instead of logging it, when the number was stored as text.
+    fn number_cell(
+        &self,
+        row: &[Data],
+        name: &str,
+    ) -> Result<Option<(f64, Option<String>)>, String> {
+        match self.cell(row, name) {
+            Some(Data::Float(v)) => Ok(Some((*v, None))),
+            Some(Data::Int(v)) => Ok(Some((*v as f64, None))),
+            Some(Data::String(text)) if !text.trim().is_empty() => {
+                let value: f64 = text
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("'{text}' is not a number"))?;
+                let note = format!("number stored as text '{text}', read as {value}");
+                Ok(Some((value, Some(note))))
+            }
+            _ => Ok(None),
+        }
+    }
+}
 
 /// Read forest inventory data from an Excel (.xlsx) file.
 ///
 /// Auto-detects cruise format (Plot_form sheets) vs standard column layout.
+/// Errors carry the path and, for a bad data row, its sheet row number.
 pub fn read_excel(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError> {
     let path = path.as_ref();
-    let mut workbook: Xlsx<_> = open_workbook(path)?;
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    read_workbook(path, &name, Some(path)).map_err(|e| e.with_context(path, None))
+}
+
+/// Read the workbook at `file`; `context` is the path to name in row errors.
+fn read_workbook(
+    file: &Path,
+    name: &str,
+    context: Option<&Path>,
+) -> Result<ForestInventory, ForestError> {
+    let mut workbook: Xlsx<_> = open_workbook(file)?;
 
     // Auto-detect cruise format
     if super::cruise_import::is_cruise_format(&workbook.sheet_names().to_vec()) {
-        let name = path
-            .file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "Unknown".to_string());
-        return super::cruise_import::read_cruise_excel(&mut workbook, &name);
+        return super::cruise_import::read_cruise_excel(&mut workbook, name);
     }
 
     let sheet_name = workbook
@@ -35,66 +153,24 @@ pub fn read_excel(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError
         .worksheet_range(&sheet_name)
         .map_err(|e| ForestError::Excel(e.to_string()))?;
 
-    let mut plots: std::collections::HashMap<u32, Plot> = std::collections::HashMap::new();
+    let mut plots: HashMap<u32, Plot> = HashMap::new();
     let mut rows = range.rows();
 
-    // Skip header row
-    rows.next();
-
-    for row in rows {
-        if row.len() < 9 {
-            continue;
-        }
-
-        let get_f64 =
-            |idx: usize| -> f64 { row.get(idx).and_then(|c| c.get_float()).unwrap_or(0.0) };
-
-        let get_opt_f64 = |idx: usize| -> Option<f64> { row.get(idx).and_then(|c| c.get_float()) };
-
-        let get_string =
-            |idx: usize| -> String { row.get(idx).map(|c| c.to_string()).unwrap_or_default() };
-
-        let plot_id = get_f64(0) as u32;
-        let tree_id = get_f64(1) as u32;
-        let status_str = get_string(7);
-        let status: TreeStatus = status_str.parse()?;
-
-        let tree = Tree {
-            tree_id,
-            plot_id,
-            species: Species {
-                code: get_string(2),
-                common_name: get_string(3),
-            },
-            dbh: get_f64(4),
-            height: get_opt_f64(5),
-            crown_ratio: get_opt_f64(6),
-            status,
-            expansion_factor: get_f64(8),
-            age: get_opt_f64(9).map(|v| v as u32),
-            defect: get_opt_f64(10),
-        };
-
-        tree.validate()?;
-
-        let plot = plots.entry(plot_id).or_insert_with(|| Plot {
-            plot_id,
-            plot_size_acres: get_opt_f64(11).unwrap_or(0.2),
-            slope_percent: get_opt_f64(12),
-            aspect_degrees: get_opt_f64(13),
-            elevation_ft: get_opt_f64(14),
-            trees: Vec::new(),
-            stand_id: None,
-        });
-
-        plot.trees.push(tree);
+    let header = rows
+        .next()
+        .ok_or_else(|| ForestError::Excel(format!("Sheet '{sheet_name}' is empty")))?;
+    let cols = ColumnMap::from_header(header)?;
+
+    // Data rows start on the sheet's second row
+    for (sheet_row, row) in (2..).zip(rows) {
+        read_row(&cols, row, sheet_row, &mut plots).map_err(|e| match context {
+            Some(path) => e.with_context(path, Some(sheet_row)),
+            None => e,
+        })?;
     }
 
-    let mut inventory = ForestInventory::new(
-        path.file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "Unknown".to_string()),
-    );
+    let mut inventory = ForestInventory::new(name);
+    inventory.expansion_kind = declared_expansion_kind(&mut workbook)?;
     let mut plot_list: Vec<Plot> = plots.into_values().collect();
     plot_list.sort_by_key(|p| p.plot_id);
     inventory.plots = plot_list;
@@ -102,6 +178,79 @@ pub fn read_excel(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError
     Ok(inventory)
 }
 
+/// Validate one standard-layout data row and file its tree under its plot.
+fn read_row(
+    cols: &ColumnMap,
+    row: &[Data],
+    sheet_row: usize,
+    plots: &mut HashMap<u32, Plot>,
+) -> Result<(), ForestError> {
+    let get_opt_f64 = |name: &str| -> Result<Option<f64>, ForestError> {
+        cols.number(row, name, sheet_row)
+            .map_err(|e| ForestError::Excel(format!("Row {sheet_row}, column '{name}': {e}")))
+    };
+
+    let get_f64 =
+        |name: &str| -> Result<f64, ForestError> { Ok(get_opt_f64(name)?.unwrap_or(0.0)) };
+
+    let get_string = |name: &str| -> String {
+        cols.cell(row, name)
+            .map(|c| c.to_string())
+            .unwrap_or_default()
+    };
+
+    let get_opt_string = |name: &str| -> Option<String> {
+        cols.cell(row, name)
+            .map(|c| c.to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    let plot_id = get_f64("plot_id")? as u32;
+    let tree_id = get_f64("tree_id")? as u32;
+    let status_str = get_string("status");
+    let status: TreeStatus = status_str.parse()?;
+
+    let tree = Tree {
+        tree_id,
+        plot_id,
+        species: Species {
+            code: get_string("species_code"),
+            common_name: get_string("species_name"),
+        },
+        dbh: get_f64("dbh")?,
+        height: get_opt_f64("height")?,
+        crown_ratio: get_opt_f64("crown_ratio")?,
+        status,
+        expansion_factor: get_f64("expansion_factor")?,
+        age: get_opt_f64("age")?.map(|v| v as u32),
+        defect: get_opt_f64("defect")?,
+        decay_class: get_opt_f64("decay_class")?.map(|v| v as u8),
+    };
+
+    tree.validate()?;
+
+    let plot = match plots.entry(plot_id) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(Plot {
+            plot_id,
+            plot_size_acres: get_opt_f64("plot_size_acres")?.unwrap_or(DEFAULT_PLOT_SIZE_ACRES),
+            slope_percent: get_opt_f64("slope_percent")?,
+            aspect_degrees: get_opt_f64("aspect_degrees")?,
+            elevation_ft: get_opt_f64("elevation_ft")?,
+            trees: Vec::new(),
+            stand_id: None,
+            source: get_opt_string("source"),
+            measurement_date: cols.date(row, "measurement_date"),
+            latitude: get_opt_f64("latitude")?,
+            longitude: get_opt_f64("longitude")?,
+            stratum: get_opt_string("stratum"),
+        }),
+    };
+
+    plot.trees.push(tree);
+    Ok(())
+}
+
 /// Read forest inventory data from Excel bytes.
 ///
 /// Auto-detects cruise format (Plot_form sheets) vs standard column layout.
@@ -111,19 +260,89 @@ pub fn read_excel_from_bytes(data: &[u8], name: &str) -> Result<ForestInventory,
     tmp.write_all(data)?;
     tmp.flush()?;
 
-    let mut workbook: Xlsx<_> = open_workbook(tmp.path())?;
-    if super::cruise_import::is_cruise_format(&workbook.sheet_names().to_vec()) {
-        return super::cruise_import::read_cruise_excel(&mut workbook, name);
+    // The temporary path means nothing to the caller, so errors are not tagged with it
+    read_workbook(tmp.path(), name, None)
+}
+
+/// Name of the sheet holding export provenance metadata and, for count-based
+/// inventories, the [`ExpansionKind`].
+const METADATA_SHEET: &str = "Metadata";
+
+/// Key/value rows of the metadata sheet, or `None` if the workbook has none.
+fn metadata_fields<R: std::io::Read + std::io::Seek>(
+    workbook: &mut Xlsx<R>,
+) -> Result<Option<HashMap<String, String>>, ForestError> {
+    if !workbook.sheet_names().iter().any(|n| n == METADATA_SHEET) {
+        return Ok(None);
     }
-    drop(workbook);
+    let range = workbook.worksheet_range(METADATA_SHEET)?;
+    Ok(Some(
+        range
+            .rows()
+            .filter_map(|row| Some((row.first()?.to_string(), row.get(1)?.to_string())))
+            .collect(),
+    ))
+}
 
-    let mut inventory = read_excel(tmp.path())?;
-    inventory.name = name.to_string();
-    Ok(inventory)
+/// The expansion kind recorded on the metadata sheet; per-acre if there is
+/// none.
+fn declared_expansion_kind<R: std::io::Read + std::io::Seek>(
+    workbook: &mut Xlsx<R>,
+) -> Result<ExpansionKind, ForestError> {
+    match metadata_fields(workbook)?.and_then(|fields| fields.get("expansion_kind").cloned()) {
+        Some(kind) => kind.parse(),
+        None => Ok(ExpansionKind::PerAcre),
+    }
+}
+
+/// The expansion kind recorded in an Excel file's bytes, for readers that
+/// parse rows on their own.
+pub(crate) fn excel_expansion_kind(data: &[u8]) -> Result<ExpansionKind, ForestError> {
+    let mut workbook = Xlsx::new(std::io::Cursor::new(data))?;
+    declared_expansion_kind(&mut workbook)
 }
 
 /// Write forest inventory data to an Excel (.xlsx) file.
+///
+/// A count-based inventory's [`ExpansionKind`] is recorded on a "Metadata"
+/// sheet, so [`read_excel`] restores it.
 pub fn write_excel(inventory: &ForestInventory, path: impl AsRef<Path>) -> Result<(), ForestError> {
+    write_excel_impl(inventory, path.as_ref(), None)
+}
+
+/// Write forest inventory data to an Excel file with a "Metadata" sheet
+/// recording provenance (source file, timestamp, generator, checksum).
+pub fn write_excel_with_metadata(
+    inventory: &ForestInventory,
+    path: impl AsRef<Path>,
+    metadata: &ExportMetadata,
+) -> Result<(), ForestError> {
+    write_excel_impl(inventory, path.as_ref(), Some(metadata))
+}
+
+/// Read the provenance metadata sheet from an Excel file, if present.
+pub fn read_excel_metadata(path: impl AsRef<Path>) -> Result<Option<ExportMetadata>, ForestError> {
+    let mut workbook: Xlsx<_> = open_workbook(path.as_ref())?;
+    // A sheet holding only the expansion kind carries no provenance
+    let Some(fields) = metadata_fields(&mut workbook)?.filter(|f| f.contains_key("content_hash"))
+    else {
+        return Ok(None);
+    };
+    let get = |key: &str| fields.get(key).cloned().unwrap_or_default();
+
+    Ok(Some(ExportMetadata {
+        source_file: fields.get("source_file").filter(|v| !v.is_empty()).cloned(),
+        exported_at: get("exported_at"),
+        generator: get("generator"),
+        content_hash: get("content_hash"),
+    }))
+}
+
+fn write_excel_impl(
+    inventory: &ForestInventory,
+    path: &Path,
+    metadata: Option<&ExportMetadata>,
+) -> Result<(), ForestError> {
     let mut workbook = Workbook::new();
     let worksheet = workbook.add_worksheet();
 
@@ -144,6 +363,12 @@ pub fn write_excel(inventory: &ForestInventory, path: impl AsRef<Path>) -> Resul
         "slope_percent",
         "aspect_degrees",
         "elevation_ft",
+        "source",
+        "measurement_date",
+        "decay_class",
+        "latitude",
+        "longitude",
+        "stratum",
     ];
 
     for (col, header) in headers.iter().enumerate() {
@@ -214,26 +439,82 @@ pub fn write_excel(inventory: &ForestInventory, path: impl AsRef<Path>) -> Resul
                     .write_number(row_idx, 14, elev)
                     .map_err(|e| ForestError::Excel(e.to_string()))?;
             }
+            if let Some(source) = &plot.source {
+                worksheet
+                    .write_string(row_idx, 15, source)
+                    .map_err(|e| ForestError::Excel(e.to_string()))?;
+            }
+            if let Some(date) = &plot.measurement_date {
+                worksheet
+                    .write_string(row_idx, 16, date)
+                    .map_err(|e| ForestError::Excel(e.to_string()))?;
+            }
+            if let Some(class) = tree.decay_class {
+                worksheet
+                    .write_number(row_idx, 17, f64::from(class))
+                    .map_err(|e| ForestError::Excel(e.to_string()))?;
+            }
+            if let Some(lat) = plot.latitude {
+                worksheet
+                    .write_number(row_idx, 18, lat)
+                    .map_err(|e| ForestError::Excel(e.to_string()))?;
+            }
+            if let Some(lon) = plot.longitude {
+                worksheet
+                    .write_number(row_idx, 19, lon)
+                    .map_err(|e| ForestError::Excel(e.to_string()))?;
+            }
+            if let Some(stratum) = &plot.stratum {
+                worksheet
+                    .write_string(row_idx, 20, stratum)
+                    .map_err(|e| ForestError::Excel(e.to_string()))?;
+            }
 
             row_idx += 1;
         }
     }
 
-    workbook
-        .save(path.as_ref())
-        .map_err(|e| ForestError::Excel(e.to_string()))?;
+    let mut entries = Vec::new();
+    if let Some(meta) = metadata {
+        entries.extend([
+            ("source_file", meta.source_file.clone().unwrap_or_default()),
+            ("exported_at", meta.exported_at.clone()),
+            ("generator", meta.generator.clone()),
+            ("content_hash", meta.content_hash.clone()),
+        ]);
+    }
+    if inventory.expansion_kind != ExpansionKind::PerAcre {
+        entries.push(("expansion_kind", inventory.expansion_kind.to_string()));
+    }
+    if !entries.is_empty() {
+        let sheet = workbook
+            .add_worksheet()
+            .set_name(METADATA_SHEET)
+            .map_err(|e| ForestError::Excel(e.to_string()))?;
+        for (row, (key, value)) in entries.iter().enumerate() {
+            sheet
+                .write_string(row as u32, 0, *key)
+                .map_err(|e| ForestError::Excel(e.to_string()))?;
+            sheet
+                .write_string(row as u32, 1, value.as_str())
+                .map_err(|e| ForestError::Excel(e.to_string()))?;
+        }
+    }
 
-    Ok(())
+    let buffer = workbook
+        .save_to_buffer()
+        .map_err(|e| ForestError::Excel(e.to_string()))?;
+    write_atomic(path, |w| Ok(w.write_all(&buffer)?))
 }
 
 /// Parse Excel leniently: write bytes to temp file, read with calamine,
-/// build editable rows, validate all, collect issues.
+/// build editable rows, validate all, collect errors and [`Tree::warnings`].
 ///
 /// Auto-detects cruise format (Plot_form sheets) vs standard column layout.
 pub(crate) fn parse_excel_lenient(
     data: &[u8],
     name: &str,
-) -> Result<(String, Vec<EditableTreeRow>, Vec<ValidationIssue>), ForestError> {
+) -> Result<(String, Vec<EditableTreeRow>, Vec<FileIssue>), ForestError> {
     use std::io::Write;
     let mut tmp = tempfile::NamedTempFile::new()?;
     tmp.write_all(data)?;
@@ -258,39 +539,52 @@ pub(crate) fn parse_excel_lenient(
 
     let mut rows_out = Vec::new();
     let mut issues = Vec::new();
+    let mut warnings = Vec::new();
     let mut excel_rows = range.rows();
 
-    // Skip header row
-    excel_rows.next();
-
-    let mut row_index: usize = 0;
-    for row in excel_rows {
-        if row.len() < 9 {
-            issues.push(ValidationIssue {
-                plot_id: 0,
-                tree_id: 0,
-                row_index,
-                field: Cow::Borrowed("row"),
-                message: Cow::Owned(format!(
-                    "Row has only {} columns (minimum 9 required), skipped",
-                    row.len()
-                )),
-            });
-            row_index += 1;
-            continue;
-        }
+    let header = excel_rows
+        .next()
+        .ok_or_else(|| ForestError::Excel(format!("Sheet '{sheet_name}' is empty")))?;
+    let cols = ColumnMap::from_header(header)?;
+
+    for (row_index, row) in excel_rows.enumerate() {
+        // Text that is not a number reads as blank and is reported once the
+        // row's plot and tree ids are known, as are numbers stored as text
+        let bad_numbers = std::cell::RefCell::new(Vec::new());
+        let text_numbers = std::cell::RefCell::new(Vec::new());
+        let get_opt_f64 = |name: &'static str| -> Option<f64> {
+            match cols.number_cell(row, name) {
+                Ok(Some((value, coerced))) => {
+                    if let Some(note) = coerced {
+                        text_numbers.borrow_mut().push((name, note));
+                    }
+                    Some(value)
+                }
+                Ok(None) => None,
+                Err(e) => {
+                    bad_numbers.borrow_mut().push((name, e));
+                    None
+                }
+            }
+        };
 
-        let get_f64 =
-            |idx: usize| -> f64 { row.get(idx).and_then(|c| c.get_float()).unwrap_or(0.0) };
+        let get_f64 = |name: &'static str| -> f64 { get_opt_f64(name).unwrap_or(0.0) };
 
-        let get_opt_f64 = |idx: usize| -> Option<f64> { row.get(idx).and_then(|c| c.get_float()) };
+        let get_string = |name: &str| -> String {
+            cols.cell(row, name)
+                .map(|c| c.to_string())
+                .unwrap_or_default()
+        };
 
-        let get_string =
-            |idx: usize| -> String { row.get(idx).map(|c| c.to_string()).unwrap_or_default() };
+        let get_opt_string = |name: &str| -> Option<String> {
+            cols.cell(row, name)
+                .map(|c| c.to_string())
+                .filter(|s| !s.is_empty())
+        };
 
-        let plot_id = get_f64(0) as u32;
-        let tree_id = get_f64(1) as u32;
-        let status_str = get_string(7);
+        let plot_id = get_f64("plot_id") as u32;
+        let tree_id = get_f64("tree_id") as u32;
+        let status_str = get_string("status");
         let status: TreeStatus = match status_str.parse() {
             Ok(s) => s,
             Err(_) => {
@@ -309,41 +603,75 @@ pub(crate) fn parse_excel_lenient(
             tree_id,
             plot_id,
             species: Species {
-                code: get_string(2),
-                common_name: get_string(3),
+                code: get_string("species_code"),
+                common_name: get_string("species_name"),
             },
-            dbh: get_f64(4),
-            height: get_opt_f64(5),
-            crown_ratio: get_opt_f64(6),
+            dbh: get_f64("dbh"),
+            height: get_opt_f64("height"),
+            crown_ratio: get_opt_f64("crown_ratio"),
             status: status.clone(),
-            expansion_factor: get_f64(8),
-            age: get_opt_f64(9).map(|v| v as u32),
-            defect: get_opt_f64(10),
+            expansion_factor: get_f64("expansion_factor"),
+            age: get_opt_f64("age").map(|v| v as u32),
+            defect: get_opt_f64("defect"),
+            decay_class: get_opt_f64("decay_class").map(|v| v as u8),
         };
 
         issues.extend(tree.validate_all(row_index));
+        warnings.extend(tree.warnings(row_index));
 
         rows_out.push(EditableTreeRow {
             row_index,
             plot_id,
             tree_id,
-            species_code: get_string(2),
-            species_name: get_string(3),
-            dbh: get_f64(4),
-            height: get_opt_f64(5),
-            crown_ratio: get_opt_f64(6),
+            species_code: get_string("species_code"),
+            species_name: get_string("species_name"),
+            dbh: get_f64("dbh"),
+            height: get_opt_f64("height"),
+            crown_ratio: get_opt_f64("crown_ratio"),
             status: status.to_string(),
-            expansion_factor: get_f64(8),
-            age: get_opt_f64(9).map(|v| v as u32),
-            defect: get_opt_f64(10),
-            plot_size_acres: get_opt_f64(11),
-            slope_percent: get_opt_f64(12),
-            aspect_degrees: get_opt_f64(13),
-            elevation_ft: get_opt_f64(14),
+            expansion_factor: get_f64("expansion_factor"),
+            age: get_opt_f64("age").map(|v| v as u32),
+            defect: get_opt_f64("defect"),
+            plot_size_acres: get_opt_f64("plot_size_acres"),
+            slope_percent: get_opt_f64("slope_percent"),
+            aspect_degrees: get_opt_f64("aspect_degrees"),
+            elevation_ft: get_opt_f64("elevation_ft"),
+            source: get_opt_string("source"),
+            measurement_date: cols.date(row, "measurement_date"),
+            latitude: get_opt_f64("latitude"),
+            longitude: get_opt_f64("longitude"),
+            stratum: get_opt_string("stratum"),
+            decay_class: get_opt_f64("decay_class").map(|v| v as u8),
         });
 
-        row_index += 1;
+        let mut reported = Vec::new();
+        for (field, message) in bad_numbers.into_inner() {
+            if !reported.contains(&field) {
+                reported.push(field);
+                issues.push(ValidationIssue {
+                    plot_id,
+                    tree_id,
+                    row_index,
+                    field: Cow::Borrowed(field),
+                    message: Cow::Owned(message),
+                });
+            }
+        }
+        let mut noted = Vec::new();
+        for (field, message) in text_numbers.into_inner() {
+            if !noted.contains(&field) {
+                noted.push(field);
+                warnings.push(ValidationIssue {
+                    plot_id,
+                    tree_id,
+                    row_index,
+                    field: Cow::Borrowed(field),
+                    message: Cow::Owned(message),
+                });
+            }
+        }
     }
 
+    let issues = FileIssue::tagged(issues, warnings);
     Ok((name.to_string(), rows_out, issues))
 }
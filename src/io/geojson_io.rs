@@ -5,15 +5,43 @@ use serde_json::{json, Value};
 use crate::error::ForestError;
 use crate::models::ForestInventory;
 
+use super::atomic::write_atomic;
+
 /// Build a GeoJSON FeatureCollection value from a forest inventory.
 ///
-/// Each plot becomes a Feature with a null geometry (no coordinates available)
-/// and properties containing plot-level summary metrics plus tree details.
-pub fn build_geojson_value(inventory: &ForestInventory) -> Value {
-    let features: Vec<Value> = inventory
+/// Each plot becomes a Feature with properties containing plot-level summary
+/// metrics plus tree details. The per-acre metrics follow the inventory's
+/// expansion kind. Plots with a latitude and longitude get a `Point`
+/// geometry; plots missing either coordinate get a null geometry.
+///
+/// Returns a validation error if a latitude is outside [-90, 90] or a
+/// longitude outside [-180, 180].
+pub fn build_geojson_value(inventory: &ForestInventory) -> Result<Value, ForestError> {
+    let features = inventory
         .plots
         .iter()
         .map(|plot| {
+            let geometry = match (plot.latitude, plot.longitude) {
+                (Some(lat), Some(lon)) => {
+                    if !(-90.0..=90.0).contains(&lat) {
+                        return Err(ForestError::ValidationError(format!(
+                            "Plot {}: latitude {lat} is outside [-90, 90]",
+                            plot.plot_id
+                        )));
+                    }
+                    if !(-180.0..=180.0).contains(&lon) {
+                        return Err(ForestError::ValidationError(format!(
+                            "Plot {}: longitude {lon} is outside [-180, 180]",
+                            plot.plot_id
+                        )));
+                    }
+                    json!({
+                        "type": "Point",
+                        "coordinates": [lon, lat],
+                    })
+                }
+                _ => Value::Null,
+            };
             let trees: Vec<Value> = plot
                 .trees
                 .iter()
@@ -29,40 +57,42 @@ pub fn build_geojson_value(inventory: &ForestInventory) -> Value {
                         "expansion_factor": t.expansion_factor,
                         "age": t.age,
                         "defect": t.defect,
+                        "decay_class": t.decay_class,
                     })
                 })
                 .collect();
 
-            json!({
+            let per_acre = plot.per_acre_factor(inventory.expansion_kind);
+            Ok(json!({
                 "type": "Feature",
-                "geometry": Value::Null,
+                "geometry": geometry,
                 "properties": {
                     "plot_id": plot.plot_id,
                     "plot_size_acres": plot.plot_size_acres,
                     "slope_percent": plot.slope_percent,
                     "aspect_degrees": plot.aspect_degrees,
                     "elevation_ft": plot.elevation_ft,
-                    "trees_per_acre": plot.trees_per_acre(),
-                    "basal_area_per_acre": plot.basal_area_per_acre(),
-                    "volume_cuft_per_acre": plot.volume_cuft_per_acre(),
-                    "volume_bdft_per_acre": plot.volume_bdft_per_acre(),
+                    "trees_per_acre": plot.trees_per_acre() * per_acre,
+                    "basal_area_per_acre": plot.basal_area_per_acre() * per_acre,
+                    "volume_cuft_per_acre": plot.volume_cuft_per_acre() * per_acre,
+                    "volume_bdft_per_acre": plot.volume_bdft_per_acre() * per_acre,
                     "quadratic_mean_diameter": plot.quadratic_mean_diameter(),
                     "num_trees": plot.trees.len(),
                     "trees": trees,
                 }
-            })
+            }))
         })
-        .collect();
+        .collect::<Result<Vec<Value>, ForestError>>()?;
 
-    json!({
+    Ok(json!({
         "type": "FeatureCollection",
         "features": features,
-    })
+    }))
 }
 
 /// Write a forest inventory as a GeoJSON FeatureCollection file.
 pub fn write_geojson(inventory: &ForestInventory, path: &Path, pretty: bool) -> Result<(), ForestError> {
-    let collection = build_geojson_value(inventory);
+    let collection = build_geojson_value(inventory)?;
 
     let content = if pretty {
         serde_json::to_string_pretty(&collection)?
@@ -70,8 +100,7 @@ pub fn write_geojson(inventory: &ForestInventory, path: &Path, pretty: bool) ->
         serde_json::to_string(&collection)?
     };
 
-    std::fs::write(path, content)?;
-    Ok(())
+    write_atomic(path, |w| Ok(w.write_all(content.as_bytes())?))
 }
 
 #[cfg(test)]
@@ -101,8 +130,14 @@ mod tests {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             }],
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: Some(45.52),
+            longitude: Some(-122.68),
+            stratum: None,
         });
         inv
     }
@@ -123,7 +158,9 @@ mod tests {
 
         let feature = &parsed["features"][0];
         assert_eq!(feature["type"], "Feature");
-        assert!(feature["geometry"].is_null());
+        assert_eq!(feature["geometry"]["type"], "Point");
+        assert_eq!(feature["geometry"]["coordinates"][0], -122.68);
+        assert_eq!(feature["geometry"]["coordinates"][1], 45.52);
         assert_eq!(feature["properties"]["plot_id"], 1);
         assert_eq!(feature["properties"]["num_trees"], 1);
     }
@@ -175,6 +212,74 @@ mod tests {
         assert!(props["quadratic_mean_diameter"].as_f64().unwrap() > 0.0);
     }
 
+    #[test]
+    fn test_count_based_plot_metrics_are_per_acre() {
+        let mut inv = sample_inventory();
+        let per_acre = build_geojson_value(&inv).unwrap();
+        inv.expansion_kind = crate::models::ExpansionKind::CountOnPlot;
+        let counted = build_geojson_value(&inv).unwrap();
+
+        let factor = 1.0 / inv.plots[0].plot_size_acres;
+        for key in ["trees_per_acre", "basal_area_per_acre"] {
+            let expected = per_acre["features"][0]["properties"][key].as_f64().unwrap() * factor;
+            let actual = counted["features"][0]["properties"][key].as_f64().unwrap();
+            assert!(
+                (actual - expected).abs() < 1e-9,
+                "{key}: {actual} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_plots_without_coordinates_have_null_geometry() {
+        let mut inv = sample_inventory();
+        for (plot_id, latitude, longitude) in [
+            (2, Some(45.53), Some(-122.67)),
+            (3, None, Some(-122.66)),
+            (4, None, None),
+        ] {
+            let mut plot = inv.plots[0].clone();
+            plot.plot_id = plot_id;
+            plot.latitude = latitude;
+            plot.longitude = longitude;
+            inv.plots.push(plot);
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.geojson");
+
+        write_geojson(&inv, &path, false).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let parsed: Value = serde_json::from_str(&content).unwrap();
+        let features = parsed["features"].as_array().unwrap();
+        assert_eq!(features.len(), inv.plots.len());
+        assert_eq!(features[1]["geometry"]["type"], "Point");
+        assert!(features[2]["geometry"].is_null());
+        assert!(features[3]["geometry"].is_null());
+        assert_eq!(features[3]["properties"]["plot_id"], 4);
+        assert_eq!(features[3]["properties"]["num_trees"], 1);
+    }
+
+    #[test]
+    fn test_out_of_range_coordinates_are_rejected() {
+        let out_of_range = [(90.5, -122.68), (-91.0, 0.0), (45.52, 180.5), (0.0, -181.0)];
+        for (latitude, longitude) in out_of_range {
+            let mut inv = sample_inventory();
+            inv.plots[0].latitude = Some(latitude);
+            inv.plots[0].longitude = Some(longitude);
+            let err = build_geojson_value(&inv).unwrap_err();
+            assert!(
+                matches!(err, ForestError::ValidationError(_)),
+                "({latitude}, {longitude}): {err}"
+            );
+        }
+
+        let mut inv = sample_inventory();
+        inv.plots[0].latitude = Some(-90.0);
+        inv.plots[0].longitude = Some(180.0);
+        assert!(build_geojson_value(&inv).is_ok());
+    }
+
     #[test]
     fn test_write_geojson_empty_inventory() {
         let inv = ForestInventory::new("Empty");
@@ -1,17 +1,39 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+
+use std::borrow::Cow;
+
 use crate::error::ForestError;
-use crate::models::{ForestInventory, ValidationIssue};
+use crate::models::{
+    ExpansionKind, ForestInventory, Plot, Species, Tree, TreeStatus, ValidationIssue,
+    DEFAULT_PLOT_SIZE_ACRES,
+};
 
+use super::atomic::write_atomic;
 use super::csv_io::EditableTreeRow;
+use super::metadata::ExportMetadata;
+use super::validate::FileIssue;
+
+/// Top-level key holding export provenance metadata in JSON files.
+const METADATA_KEY: &str = "_meta";
 
 /// Read forest inventory data from a JSON file.
+///
+/// Errors carry the path and, for malformed JSON, the line.
 pub fn read_json(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError> {
-    let content = std::fs::read_to_string(path.as_ref())?;
-    let inventory: ForestInventory = serde_json::from_str(&content)?;
+    let path = path.as_ref();
+    let in_file = |e: ForestError| e.with_context(path, None);
+    let content = std::fs::read_to_string(path).map_err(|e| in_file(e.into()))?;
+    let inventory: ForestInventory = serde_json::from_str(&content).map_err(|e| {
+        let line = e.line();
+        ForestError::from(e).with_context(path, Some(line))
+    })?;
     for plot in &inventory.plots {
         for tree in &plot.trees {
-            tree.validate()?;
+            tree.validate().map_err(in_file)?;
         }
     }
     Ok(inventory)
@@ -32,37 +54,373 @@ pub fn read_json_from_bytes(data: &[u8], name: &str) -> Result<ForestInventory,
 }
 
 /// Write forest inventory data to a JSON file.
+///
+/// The JSON is streamed to the file rather than built in memory first. The
+/// file is replaced atomically; on failure any existing file is untouched.
 pub fn write_json(
     inventory: &ForestInventory,
     path: impl AsRef<Path>,
     pretty: bool,
 ) -> Result<(), ForestError> {
-    let content = if pretty {
-        serde_json::to_string_pretty(inventory)?
+    write_atomic(path.as_ref(), |w| write_json_to(inventory, w, pretty))
+}
+
+/// Stream forest inventory data as JSON to any writer, e.g. a response body
+/// or a compressing encoder. Output is identical to [`write_json`].
+///
+/// Wrap unbuffered sinks such as a bare `File` in a `BufWriter`.
+pub fn write_json_to(
+    inventory: &ForestInventory,
+    writer: impl Write,
+    pretty: bool,
+) -> Result<(), ForestError> {
+    if pretty {
+        serde_json::to_writer_pretty(writer, inventory)?;
     } else {
-        serde_json::to_string(inventory)?
-    };
-    std::fs::write(path.as_ref(), content)?;
+        serde_json::to_writer(writer, inventory)?;
+    }
     Ok(())
 }
 
+/// Write forest inventory data to a JSON file with provenance metadata
+/// under a top-level `"_meta"` key. Readers ignore the key.
+pub fn write_json_with_metadata(
+    inventory: &ForestInventory,
+    path: impl AsRef<Path>,
+    pretty: bool,
+    metadata: &ExportMetadata,
+) -> Result<(), ForestError> {
+    let mut value = serde_json::to_value(inventory)?;
+    if let serde_json::Value::Object(map) = &mut value {
+        map.insert(METADATA_KEY.to_string(), serde_json::to_value(metadata)?);
+    }
+    write_atomic(path.as_ref(), |w| {
+        if pretty {
+            serde_json::to_writer_pretty(w, &value)?;
+        } else {
+            serde_json::to_writer(w, &value)?;
+        }
+        Ok(())
+    })
+}
+
+/// Read the `"_meta"` provenance block from a JSON file, if present.
+pub fn read_json_metadata(path: impl AsRef<Path>) -> Result<Option<ExportMetadata>, ForestError> {
+    let content = std::fs::read_to_string(path.as_ref())?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)?;
+    match value.get_mut(METADATA_KEY).map(serde_json::Value::take) {
+        Some(meta) => Ok(Some(serde_json::from_value(meta)?)),
+        None => Ok(None),
+    }
+}
+
+/// One tree in the long JSON format, carrying its plot's attributes.
+#[derive(Debug, Serialize, Deserialize)]
+struct LongTreeRow {
+    plot_id: u32,
+    tree_id: u32,
+    species_code: String,
+    species_name: String,
+    dbh: f64,
+    height: Option<f64>,
+    crown_ratio: Option<f64>,
+    status: TreeStatus,
+    expansion_factor: f64,
+    age: Option<u32>,
+    defect: Option<f64>,
+    plot_size_acres: f64,
+    slope_percent: Option<f64>,
+    aspect_degrees: Option<f64>,
+    elevation_ft: Option<f64>,
+    stand_id: Option<u32>,
+    source: Option<String>,
+    measurement_date: Option<String>,
+    #[serde(default)]
+    decay_class: Option<u8>,
+    #[serde(default)]
+    latitude: Option<f64>,
+    #[serde(default)]
+    longitude: Option<f64>,
+    #[serde(default)]
+    stratum: Option<String>,
+    /// Only written for count-based inventories
+    #[serde(default, skip_serializing_if = "is_per_acre")]
+    expansion_kind: ExpansionKind,
+}
+
+fn is_per_acre(kind: &ExpansionKind) -> bool {
+    *kind == ExpansionKind::PerAcre
+}
+
+/// The expansion kind shared by every row, or a `ParseError` naming the
+/// first row that disagrees. An empty file is per-acre.
+fn rows_expansion_kind(
+    kinds: impl IntoIterator<Item = ExpansionKind>,
+    path: &Path,
+) -> Result<ExpansionKind, ForestError> {
+    let mut kinds = kinds.into_iter().enumerate();
+    let Some((_, first)) = kinds.next() else {
+        return Ok(ExpansionKind::default());
+    };
+    match kinds.find(|(_, kind)| *kind != first) {
+        Some((index, kind)) => Err(ForestError::ParseError(format!(
+            "{}: row {} has expansion_kind {kind}, but earlier rows have {first}",
+            path.display(),
+            index + 1
+        ))),
+        None => Ok(first),
+    }
+}
+
+/// Write the inventory as a flat JSON array with one object per tree.
+///
+/// Each object holds the tree's fields plus its plot's attributes, the same
+/// columns as the CSV format with `stand_id` added, so the file loads directly
+/// into a data frame (e.g. `pandas.read_json`). A count-based inventory adds
+/// `"expansion_kind": "count_on_plot"` to every row. The inventory name and
+/// total acreage are not stored. The file is replaced atomically.
+pub fn write_json_long(
+    inventory: &ForestInventory,
+    path: impl AsRef<Path>,
+) -> Result<(), ForestError> {
+    let rows: Vec<LongTreeRow> = inventory
+        .plots
+        .iter()
+        .flat_map(|plot| {
+            plot.trees.iter().map(move |tree| LongTreeRow {
+                plot_id: tree.plot_id,
+                tree_id: tree.tree_id,
+                species_code: tree.species.code.clone(),
+                species_name: tree.species.common_name.clone(),
+                dbh: tree.dbh,
+                height: tree.height,
+                crown_ratio: tree.crown_ratio,
+                status: tree.status.clone(),
+                expansion_factor: tree.expansion_factor,
+                age: tree.age,
+                defect: tree.defect,
+                plot_size_acres: plot.plot_size_acres,
+                slope_percent: plot.slope_percent,
+                aspect_degrees: plot.aspect_degrees,
+                elevation_ft: plot.elevation_ft,
+                stand_id: plot.stand_id,
+                source: plot.source.clone(),
+                measurement_date: plot.measurement_date.clone(),
+                latitude: plot.latitude,
+                longitude: plot.longitude,
+                stratum: plot.stratum.clone(),
+                decay_class: tree.decay_class,
+                expansion_kind: inventory.expansion_kind,
+            })
+        })
+        .collect();
+    write_atomic(path.as_ref(), |w| Ok(serde_json::to_writer(w, &rows)?))
+}
+
+/// Read a flat JSON array written by [`write_json_long`], regrouping trees into
+/// plots by `plot_id`.
+///
+/// Plots are ordered by `plot_id` and take their attributes from their first
+/// tree. Every row must have the same `expansion_kind` (per-acre if left
+/// out). The inventory is named after the file stem.
+pub fn read_json_long(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)?;
+    let rows: Vec<LongTreeRow> = serde_json::from_str(&content)?;
+    let expansion_kind = rows_expansion_kind(rows.iter().map(|r| r.expansion_kind), path)?;
+
+    let mut plots: BTreeMap<u32, Plot> = BTreeMap::new();
+    for row in rows {
+        let tree = Tree {
+            tree_id: row.tree_id,
+            plot_id: row.plot_id,
+            species: Species {
+                common_name: row.species_name,
+                code: row.species_code,
+            },
+            dbh: row.dbh,
+            height: row.height,
+            crown_ratio: row.crown_ratio,
+            status: row.status,
+            expansion_factor: row.expansion_factor,
+            age: row.age,
+            defect: row.defect,
+            decay_class: row.decay_class,
+        };
+        tree.validate()?;
+
+        plots
+            .entry(row.plot_id)
+            .or_insert_with(|| Plot {
+                plot_id: row.plot_id,
+                plot_size_acres: row.plot_size_acres,
+                slope_percent: row.slope_percent,
+                aspect_degrees: row.aspect_degrees,
+                elevation_ft: row.elevation_ft,
+                trees: Vec::new(),
+                stand_id: row.stand_id,
+                source: row.source,
+                measurement_date: row.measurement_date,
+                latitude: row.latitude,
+                longitude: row.longitude,
+                stratum: row.stratum,
+            })
+            .trees
+            .push(tree);
+    }
+
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let mut inventory = ForestInventory::new(name);
+    inventory.plots = plots.into_values().collect();
+    inventory.expansion_kind = expansion_kind;
+    Ok(inventory)
+}
+
+/// One JSON Lines record: an editable row plus an optional expansion kind.
+#[derive(Deserialize)]
+struct JsonlRow {
+    #[serde(flatten)]
+    row: EditableTreeRow,
+    #[serde(default)]
+    expansion_kind: ExpansionKind,
+}
+
+/// Read JSON Lines: one tree per line, as an object with the fields of
+/// [`EditableTreeRow`] (`row_index` may be left out) and an optional
+/// `expansion_kind`, which must be the same on every line.
+///
+/// The file is read line by line and trees are grouped into plots by
+/// `plot_id` as they arrive, so only the inventory itself is held in memory.
+/// Plots are ordered by `plot_id` and take their attributes from their first
+/// tree. Blank lines are skipped. Malformed lines and unknown statuses are
+/// collected and reported together in one [`ForestError::ParseError`] naming
+/// each line; a tree that fails validation is an error at its line. The
+/// inventory is named after the file stem.
+pub fn read_jsonl(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError> {
+    let path = path.as_ref();
+    let file =
+        std::fs::File::open(path).map_err(|e| ForestError::from(e).with_context(path, None))?;
+
+    let mut plots: BTreeMap<u32, Plot> = BTreeMap::new();
+    let mut kinds = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.map_err(|e| ForestError::from(e).with_context(path, Some(line_number)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = match serde_json::from_str::<JsonlRow>(&line) {
+            Ok(JsonlRow {
+                row,
+                expansion_kind,
+            }) => {
+                kinds.push(expansion_kind);
+                row
+            }
+            Err(e) => {
+                errors.push(format!("line {line_number}: {e}"));
+                continue;
+            }
+        };
+        let status: TreeStatus = match row.status.parse() {
+            Ok(status) => status,
+            Err(e) => {
+                errors.push(format!("line {line_number}: {e}"));
+                continue;
+            }
+        };
+        let tree = Tree {
+            tree_id: row.tree_id,
+            plot_id: row.plot_id,
+            species: Species {
+                common_name: row.species_name,
+                code: row.species_code,
+            },
+            dbh: row.dbh,
+            height: row.height,
+            crown_ratio: row.crown_ratio,
+            status,
+            expansion_factor: row.expansion_factor,
+            age: row.age,
+            defect: row.defect,
+            decay_class: row.decay_class,
+        };
+        tree.validate()
+            .map_err(|e| e.with_context(path, Some(line_number)))?;
+
+        plots
+            .entry(row.plot_id)
+            .or_insert_with(|| Plot {
+                plot_id: row.plot_id,
+                plot_size_acres: row.plot_size_acres.unwrap_or(DEFAULT_PLOT_SIZE_ACRES),
+                slope_percent: row.slope_percent,
+                aspect_degrees: row.aspect_degrees,
+                elevation_ft: row.elevation_ft,
+                trees: Vec::new(),
+                stand_id: None,
+                source: row.source,
+                measurement_date: row.measurement_date,
+                latitude: row.latitude,
+                longitude: row.longitude,
+                stratum: row.stratum,
+            })
+            .trees
+            .push(tree);
+    }
+    if !errors.is_empty() {
+        return Err(ForestError::ParseError(format!(
+            "{} malformed line(s) in {}: {}",
+            errors.len(),
+            path.display(),
+            errors.join("; ")
+        )));
+    }
+    let expansion_kind = rows_expansion_kind(kinds, path)?;
+
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let mut inventory = ForestInventory::new(name);
+    inventory.plots = plots.into_values().collect();
+    inventory.expansion_kind = expansion_kind;
+    Ok(inventory)
+}
+
+/// The expansion kind of a JSON inventory's bytes, for readers that parse
+/// rows on their own.
+pub(crate) fn json_expansion_kind(data: &[u8]) -> Result<ExpansionKind, ForestError> {
+    #[derive(Deserialize)]
+    struct Declared {
+        #[serde(default)]
+        expansion_kind: ExpansionKind,
+    }
+    Ok(serde_json::from_slice::<Declared>(data)?.expansion_kind)
+}
+
 /// Parse JSON leniently: deserialize the inventory, flatten to editable rows,
-/// validate all trees, and collect issues.
+/// validate all trees, and collect errors and [`Tree::warnings`].
 pub(crate) fn parse_json_lenient(
     data: &[u8],
     name: &str,
-) -> Result<(String, Vec<EditableTreeRow>, Vec<ValidationIssue>), ForestError> {
+) -> Result<(String, Vec<EditableTreeRow>, Vec<FileIssue>), ForestError> {
     let content = std::str::from_utf8(data)
         .map_err(|e| ForestError::ParseError(format!("Invalid UTF-8: {e}")))?;
     let inventory: ForestInventory = serde_json::from_str(content)?;
 
     let mut rows = Vec::new();
     let mut issues = Vec::new();
+    let mut warnings = Vec::new();
     let mut row_index: usize = 0;
 
     for plot in &inventory.plots {
         for tree in &plot.trees {
             issues.extend(tree.validate_all(row_index));
+            warnings.extend(tree.warnings(row_index));
 
             rows.push(EditableTreeRow {
                 row_index,
@@ -81,11 +439,114 @@ pub(crate) fn parse_json_lenient(
                 slope_percent: plot.slope_percent,
                 aspect_degrees: plot.aspect_degrees,
                 elevation_ft: plot.elevation_ft,
+                source: plot.source.clone(),
+                measurement_date: plot.measurement_date.clone(),
+                latitude: plot.latitude,
+                longitude: plot.longitude,
+                stratum: plot.stratum.clone(),
+                decay_class: tree.decay_class,
             });
 
             row_index += 1;
         }
     }
 
-    Ok((name.to_string(), rows, issues))
+    Ok((name.to_string(), rows, FileIssue::tagged(issues, warnings)))
+}
+
+/// The expansion kind shared by every line of JSON Lines bytes, for readers
+/// that parse rows on their own. Malformed lines are skipped here and left
+/// for the row parser to report.
+pub(crate) fn jsonl_expansion_kind(data: &[u8], path: &Path) -> Result<ExpansionKind, ForestError> {
+    #[derive(Deserialize)]
+    struct Declared {
+        #[serde(default)]
+        expansion_kind: ExpansionKind,
+    }
+    let content = String::from_utf8_lossy(data);
+    let kinds = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<Declared>(line).ok())
+        .map(|d| d.expansion_kind);
+    rows_expansion_kind(kinds, path)
+}
+
+/// Parse JSON Lines leniently: one editable row per non-blank line, with row
+/// validation errors and warnings collected instead of failing. An unknown
+/// status defaults to Live with an issue, as in CSV. Malformed lines are a
+/// format error naming each line.
+pub(crate) fn parse_jsonl_lenient(
+    data: &[u8],
+    name: &str,
+) -> Result<(String, Vec<EditableTreeRow>, Vec<FileIssue>), ForestError> {
+    let content = std::str::from_utf8(data)
+        .map_err(|e| ForestError::ParseError(format!("Invalid UTF-8: {e}")))?;
+
+    let mut rows = Vec::new();
+    let mut issues = Vec::new();
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut row = match serde_json::from_str::<JsonlRow>(line) {
+            Ok(JsonlRow { row, .. }) => row,
+            Err(e) => {
+                errors.push(format!("line {}: {e}", index + 1));
+                continue;
+            }
+        };
+        let row_index = rows.len();
+        row.row_index = row_index;
+
+        let status: TreeStatus = match row.status.parse() {
+            Ok(s) => s,
+            Err(_) => {
+                issues.push(ValidationIssue {
+                    plot_id: row.plot_id,
+                    tree_id: row.tree_id,
+                    row_index,
+                    field: Cow::Borrowed("status"),
+                    message: Cow::Owned(format!(
+                        "Unknown tree status '{}', defaulting to Live",
+                        row.status
+                    )),
+                });
+                TreeStatus::Live
+            }
+        };
+        row.status = status.to_string();
+
+        let tree = Tree {
+            tree_id: row.tree_id,
+            plot_id: row.plot_id,
+            species: Species {
+                common_name: row.species_name.clone(),
+                code: row.species_code.clone(),
+            },
+            dbh: row.dbh,
+            height: row.height,
+            crown_ratio: row.crown_ratio,
+            status,
+            expansion_factor: row.expansion_factor,
+            age: row.age,
+            defect: row.defect,
+            decay_class: row.decay_class,
+        };
+        issues.extend(tree.validate_all(row_index));
+        warnings.extend(tree.warnings(row_index));
+        rows.push(row);
+    }
+    if !errors.is_empty() {
+        return Err(ForestError::ParseError(format!(
+            "{} malformed line(s): {}",
+            errors.len(),
+            errors.join("; ")
+        )));
+    }
+
+    Ok((name.to_string(), rows, FileIssue::tagged(issues, warnings)))
 }
@@ -0,0 +1,88 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ForestError;
+use crate::models::ForestInventory;
+
+/// Provenance metadata written alongside exported inventory data.
+///
+/// Stored under a `"_meta"` key in JSON and on a "Metadata" sheet in Excel.
+/// Readers ignore it when loading inventory data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportMetadata {
+    /// File the inventory was originally loaded from, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    /// Export time as an RFC 3339 UTC timestamp
+    pub exported_at: String,
+    /// Name and version of the crate that wrote the file
+    pub generator: String,
+    /// SHA-256 of the tree rows (see [`ForestInventory::content_hash`])
+    pub content_hash: String,
+}
+
+impl ExportMetadata {
+    /// Build metadata for an inventory being exported now.
+    pub fn new(
+        inventory: &ForestInventory,
+        source_file: Option<&str>,
+    ) -> Result<Self, ForestError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Ok(Self {
+            source_file: source_file.map(str::to_string),
+            exported_at: format_utc_timestamp(now),
+            generator: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            content_hash: inventory.content_hash()?,
+        })
+    }
+
+    /// Check whether `inventory` still matches the recorded checksum.
+    pub fn matches(&self, inventory: &ForestInventory) -> Result<bool, ForestError> {
+        Ok(self.content_hash == inventory.content_hash()?)
+    }
+}
+
+/// Format seconds since the Unix epoch as `YYYY-MM-DDTHH:MM:SSZ`.
+fn format_utc_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (hour, minute, second) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+
+    // Civil-from-days (Howard Hinnant's algorithm), proleptic Gregorian calendar
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_utc_timestamp() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc_timestamp(1_773_446_399), "2026-03-13T23:59:59Z");
+    }
+
+    #[test]
+    fn test_metadata_matches_inventory() {
+        let inv = ForestInventory::new("Meta");
+        let meta = ExportMetadata::new(&inv, Some("cruise.csv")).unwrap();
+        assert!(meta.matches(&inv).unwrap());
+        assert_eq!(meta.source_file.as_deref(), Some("cruise.csv"));
+        assert!(meta.generator.contains(env!("CARGO_PKG_VERSION")));
+    }
+}
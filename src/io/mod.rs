@@ -1,28 +1,69 @@
 //! Reading and writing forest inventory data in multiple formats.
 //!
-//! Supports CSV, JSON, Excel (.xlsx), and GeoJSON export. Use the [`InventoryReader`] and
+//! Supports CSV, JSON, Excel (.xlsx, behind the `excel` feature), Parquet (behind the
+//! `parquet` feature), and GeoJSON export. Use the [`InventoryReader`] and
 //! [`InventoryWriter`] traits for format-agnostic I/O, or call format-specific functions
 //! directly (e.g., [`read_csv`], [`write_json`]). Includes cruise-format auto-detection.
+//!
+//! All writers go through a temp file in the target directory that is renamed
+//! into place on success, so a failed write never leaves a truncated file.
 
+mod atomic;
+#[cfg(feature = "excel")]
 mod cruise_import;
 mod csv_io;
+#[cfg(all(feature = "excel", feature = "analysis"))]
+mod excel_analysis;
+#[cfg(feature = "excel")]
 mod excel_io;
 mod geojson_io;
 mod json_io;
+mod metadata;
+#[cfg(feature = "parquet")]
+mod parquet_io;
+mod template;
+mod validate;
 
 use std::path::Path;
 
 use crate::error::ForestError;
 use crate::models::ForestInventory;
 
-pub use csv_io::{read_csv, read_csv_from_bytes, write_csv};
-pub use excel_io::{read_excel, read_excel_from_bytes, write_excel};
+pub use atomic::write_atomic;
+pub use csv_io::{
+    read_csv, read_csv_from_bytes, read_csv_from_bytes_with_encoding, read_csv_mapped,
+    read_csv_mapped_with_encoding, read_csv_with_encoding, write_csv, write_csv_columns,
+    ColumnMapping, CsvEncoding, TreeColumn,
+};
+#[cfg(all(feature = "excel", feature = "analysis"))]
+pub use excel_analysis::write_excel_analysis;
+#[cfg(feature = "excel")]
+pub use excel_io::{
+    read_excel, read_excel_from_bytes, read_excel_metadata, write_excel, write_excel_with_metadata,
+};
 pub use geojson_io::{build_geojson_value, write_geojson};
-pub use json_io::{read_json, read_json_from_bytes, write_json};
+pub use json_io::{
+    read_json, read_json_from_bytes, read_json_long, read_json_metadata, read_jsonl, write_json,
+    write_json_long, write_json_to, write_json_with_metadata,
+};
+pub use metadata::ExportMetadata;
+#[cfg(feature = "parquet")]
+pub use parquet_io::{read_parquet, write_parquet};
+#[cfg(feature = "excel")]
+pub use template::write_template_excel;
+pub use template::write_template_csv;
+pub use validate::{read_file_lenient, validate_file, FileIssue, Severity, ValidationReport};
 
-pub(crate) use csv_io::{parse_csv_lenient, rows_to_inventory, EditableTreeRow};
-pub(crate) use excel_io::parse_excel_lenient;
-pub(crate) use json_io::parse_json_lenient;
+#[cfg(any(feature = "web", feature = "wasm"))]
+pub(crate) use csv_io::parse_csv_lenient;
+pub(crate) use csv_io::{
+    csv_expansion_kind, parse_csv_lenient_with_encoding, rows_to_inventory, EditableTreeRow,
+};
+#[cfg(feature = "excel")]
+pub(crate) use excel_io::{excel_expansion_kind, parse_excel_lenient};
+pub(crate) use json_io::{
+    json_expansion_kind, jsonl_expansion_kind, parse_json_lenient, parse_jsonl_lenient,
+};
 
 /// Trait for reading forest inventory data from a file.
 pub trait InventoryReader {
@@ -80,20 +121,74 @@ impl InventoryWriter for GeoJsonFormat {
 }
 
 /// Excel (.xlsx) format reader/writer.
+#[cfg(feature = "excel")]
 pub struct ExcelFormat;
 
+#[cfg(feature = "excel")]
 impl InventoryReader for ExcelFormat {
     fn read(&self, path: &Path) -> Result<ForestInventory, ForestError> {
         read_excel(path)
     }
 }
 
+#[cfg(feature = "excel")]
 impl InventoryWriter for ExcelFormat {
     fn write(&self, inventory: &ForestInventory, path: &Path) -> Result<(), ForestError> {
         write_excel(inventory, path)
     }
 }
 
+/// Parquet format reader/writer, using the flat one-row-per-tree layout.
+#[cfg(feature = "parquet")]
+pub struct ParquetFormat;
+
+#[cfg(feature = "parquet")]
+impl InventoryReader for ParquetFormat {
+    fn read(&self, path: &Path) -> Result<ForestInventory, ForestError> {
+        read_parquet(path)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl InventoryWriter for ParquetFormat {
+    fn write(&self, inventory: &ForestInventory, path: &Path) -> Result<(), ForestError> {
+        write_parquet(inventory, path)
+    }
+}
+
+/// Read an inventory from any supported file, choosing the reader by
+/// extension: `.csv`, `.json`, `.jsonl` (JSON Lines, see [`read_jsonl`]),
+/// `.xlsx`/`.xls` (with the `excel` feature) or
+/// `.parquet` (with the `parquet` feature).
+pub fn read_file(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError> {
+    read_file_with_encoding(path, CsvEncoding::Utf8)
+}
+
+/// [`read_file`], reading CSV input in the given text encoding.
+pub fn read_file_with_encoding(
+    path: impl AsRef<Path>,
+    encoding: CsvEncoding,
+) -> Result<ForestInventory, ForestError> {
+    let path = path.as_ref();
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match ext.as_str() {
+        "csv" => read_csv_with_encoding(path, encoding),
+        "json" => read_json(path),
+        "jsonl" => read_jsonl(path),
+        #[cfg(feature = "excel")]
+        "xlsx" | "xls" => read_excel(path),
+        #[cfg(feature = "parquet")]
+        "parquet" => read_parquet(path),
+        _ => Err(ForestError::ParseError(format!(
+            "Unsupported file format: .{ext}. Use .csv, .json, .jsonl, or .xlsx"
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +217,7 @@ mod tests {
                     expansion_factor: 5.0,
                     age: None,
                     defect: None,
+                    decay_class: None,
                 },
                 Tree {
                     tree_id: 2,
@@ -137,9 +233,15 @@ mod tests {
                     expansion_factor: 5.0,
                     age: None,
                     defect: None,
+                    decay_class: None,
                 },
             ],
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         });
         inv
     }
@@ -177,6 +279,30 @@ mod tests {
         assert_eq!(loaded.plots[0].trees[0].dbh, 14.0);
     }
 
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_trait_roundtrip() {
+        let mut inv = sample_inventory();
+        inv.plots[0].measurement_date = Some("2024-06-12".to_string());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.parquet");
+
+        let writer: &dyn InventoryWriter = &ParquetFormat;
+        writer.write(&inv, &path).unwrap();
+
+        let reader: &dyn InventoryReader = &ParquetFormat;
+        let loaded = reader.read(&path).unwrap();
+
+        assert_eq!(loaded.name, "test");
+        assert_eq!(loaded.num_plots(), inv.num_plots());
+        assert_eq!(loaded.num_trees(), inv.num_trees());
+        let (back, orig) = (&loaded.plots[0], &inv.plots[0]);
+        assert_eq!(back.measurement_date, orig.measurement_date);
+        assert_eq!(back.trees[1].species.code, "WRC");
+        assert_eq!(back.trees[1].crown_ratio, Some(0.6));
+        assert_eq!(back.trees[0].age, None);
+    }
+
     #[test]
     fn test_json_format_default() {
         let fmt = JsonFormat::default();
@@ -0,0 +1,265 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, UInt32Type};
+use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch, StringArray, UInt32Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+
+use crate::error::ForestError;
+use crate::models::{ExpansionKind, ForestInventory, Plot, Species, Tree, DEFAULT_PLOT_SIZE_ACRES};
+
+use super::atomic::write_atomic;
+
+/// Columns of the flat tree-row layout: name, type, and whether the column
+/// may be absent or hold nulls.
+///
+/// The same columns as the long JSON format, one row per tree.
+const COLUMNS: [(&str, DataType, bool); 22] = [
+    ("plot_id", DataType::UInt32, false),
+    ("tree_id", DataType::UInt32, false),
+    ("species_code", DataType::Utf8, false),
+    ("species_name", DataType::Utf8, false),
+    ("dbh", DataType::Float64, false),
+    ("height", DataType::Float64, true),
+    ("crown_ratio", DataType::Float64, true),
+    ("status", DataType::Utf8, false),
+    ("expansion_factor", DataType::Float64, false),
+    ("age", DataType::UInt32, true),
+    ("defect", DataType::Float64, true),
+    ("plot_size_acres", DataType::Float64, true),
+    ("slope_percent", DataType::Float64, true),
+    ("aspect_degrees", DataType::Float64, true),
+    ("elevation_ft", DataType::Float64, true),
+    ("stand_id", DataType::UInt32, true),
+    ("source", DataType::Utf8, true),
+    ("measurement_date", DataType::Utf8, true),
+    ("decay_class", DataType::UInt32, true),
+    ("latitude", DataType::Float64, true),
+    ("longitude", DataType::Float64, true),
+    ("stratum", DataType::Utf8, true),
+];
+
+/// Key of the file metadata entry holding the inventory's [`ExpansionKind`].
+const EXPANSION_KIND_KEY: &str = "expansion_kind";
+
+fn schema() -> Schema {
+    Schema::new(
+        COLUMNS
+            .iter()
+            .map(|(name, data_type, nullable)| Field::new(*name, data_type.clone(), *nullable))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// One record batch's columns, cast to the types in [`COLUMNS`].
+struct BatchColumns {
+    columns: HashMap<&'static str, ArrayRef>,
+}
+
+impl BatchColumns {
+    /// Look up and cast every known column, failing if a required one is
+    /// absent. Integer and float widths are converted, so files written by
+    /// other tools (e.g. with `Int64` ids) load as well.
+    fn new(batch: &RecordBatch) -> Result<Self, ForestError> {
+        let mut columns = HashMap::new();
+        for (name, data_type, nullable) in &COLUMNS {
+            match batch.column_by_name(name) {
+                Some(column) => {
+                    columns.insert(*name, arrow_cast::cast(column, data_type)?);
+                }
+                None if *nullable => {}
+                None => {
+                    return Err(ForestError::ParseError(format!(
+                        "Missing required column '{name}' in Parquet file"
+                    )))
+                }
+            }
+        }
+        Ok(Self { columns })
+    }
+
+    fn valid(&self, name: &str, row: usize) -> Option<&ArrayRef> {
+        self.columns.get(name).filter(|c| c.is_valid(row))
+    }
+
+    fn f64(&self, name: &str, row: usize) -> Option<f64> {
+        self.valid(name, row)
+            .map(|c| c.as_primitive::<Float64Type>().value(row))
+    }
+
+    fn u32(&self, name: &str, row: usize) -> Option<u32> {
+        self.valid(name, row)
+            .map(|c| c.as_primitive::<UInt32Type>().value(row))
+    }
+
+    fn string(&self, name: &str, row: usize) -> Option<String> {
+        self.valid(name, row)
+            .map(|c| c.as_string::<i32>().value(row).to_string())
+    }
+}
+
+/// A required value, or a `ParseError` naming the column if it is null.
+fn required<T>(value: Option<T>, name: &str, row: usize) -> Result<T, ForestError> {
+    value.ok_or_else(|| ForestError::ParseError(format!("Row {}: {name} is null", row + 1)))
+}
+
+/// Read forest inventory data from a Parquet file in the flat tree-row layout
+/// written by [`write_parquet`].
+///
+/// Plots are ordered by `plot_id` and take their attributes from their first
+/// tree. The inventory is named after the file stem; files without an
+/// `expansion_kind` metadata entry are read as per-acre.
+pub fn read_parquet(path: impl AsRef<Path>) -> Result<ForestInventory, ForestError> {
+    let path = path.as_ref();
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+    let expansion_kind = match builder.schema().metadata().get(EXPANSION_KIND_KEY) {
+        Some(kind) => kind.parse()?,
+        None => ExpansionKind::PerAcre,
+    };
+    let reader = builder.build()?;
+
+    let mut plots: BTreeMap<u32, Plot> = BTreeMap::new();
+    let mut row_offset = 0;
+    for batch in reader {
+        let batch = batch?;
+        let cols = BatchColumns::new(&batch)?;
+        for i in 0..batch.num_rows() {
+            let row = row_offset + i;
+            let plot_id = required(cols.u32("plot_id", i), "plot_id", row)?;
+            let tree = Tree {
+                tree_id: required(cols.u32("tree_id", i), "tree_id", row)?,
+                plot_id,
+                species: Species {
+                    common_name: required(cols.string("species_name", i), "species_name", row)?,
+                    code: required(cols.string("species_code", i), "species_code", row)?,
+                },
+                dbh: required(cols.f64("dbh", i), "dbh", row)?,
+                height: cols.f64("height", i),
+                crown_ratio: cols.f64("crown_ratio", i),
+                status: required(cols.string("status", i), "status", row)?.parse()?,
+                expansion_factor: required(
+                    cols.f64("expansion_factor", i),
+                    "expansion_factor",
+                    row,
+                )?,
+                age: cols.u32("age", i),
+                defect: cols.f64("defect", i),
+                decay_class: cols.u32("decay_class", i).map(|v| v.min(u8::MAX.into()) as u8),
+            };
+            tree.validate()?;
+
+            plots
+                .entry(plot_id)
+                .or_insert_with(|| Plot {
+                    plot_id,
+                    plot_size_acres: cols
+                        .f64("plot_size_acres", i)
+                        .unwrap_or(DEFAULT_PLOT_SIZE_ACRES),
+                    slope_percent: cols.f64("slope_percent", i),
+                    aspect_degrees: cols.f64("aspect_degrees", i),
+                    elevation_ft: cols.f64("elevation_ft", i),
+                    trees: Vec::new(),
+                    stand_id: cols.u32("stand_id", i),
+                    source: cols.string("source", i),
+                    measurement_date: cols.string("measurement_date", i),
+                    latitude: cols.f64("latitude", i),
+                    longitude: cols.f64("longitude", i),
+                    stratum: cols.string("stratum", i),
+                })
+                .trees
+                .push(tree);
+        }
+        row_offset += batch.num_rows();
+    }
+
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    let mut inventory = ForestInventory::new(name);
+    inventory.expansion_kind = expansion_kind;
+    inventory.plots = plots.into_values().collect();
+    Ok(inventory)
+}
+
+/// Write the inventory as a Parquet file with one row per tree.
+///
+/// Each row holds the tree's fields plus its plot's attributes, the same
+/// columns as [`write_json_long`](super::write_json_long), so the file loads
+/// directly into columnar tools (e.g. `pandas.read_parquet`). The expansion
+/// kind is kept in the file metadata; the inventory name and total acreage
+/// are not stored. The file is replaced atomically.
+pub fn write_parquet(
+    inventory: &ForestInventory,
+    path: impl AsRef<Path>,
+) -> Result<(), ForestError> {
+    let rows: Vec<(&Plot, &Tree)> = inventory
+        .plots
+        .iter()
+        .flat_map(|plot| plot.trees.iter().map(move |tree| (plot, tree)))
+        .collect();
+    let u32s = |value: fn(&Plot, &Tree) -> Option<u32>| -> ArrayRef {
+        Arc::new(
+            rows.iter()
+                .map(|(p, t)| value(p, t))
+                .collect::<UInt32Array>(),
+        )
+    };
+    let f64s = |value: fn(&Plot, &Tree) -> Option<f64>| -> ArrayRef {
+        Arc::new(
+            rows.iter()
+                .map(|(p, t)| value(p, t))
+                .collect::<Float64Array>(),
+        )
+    };
+    let strings = |value: fn(&Plot, &Tree) -> Option<String>| -> ArrayRef {
+        Arc::new(
+            rows.iter()
+                .map(|(p, t)| value(p, t))
+                .collect::<StringArray>(),
+        )
+    };
+
+    // In COLUMNS order
+    let columns = vec![
+        u32s(|_, t| Some(t.plot_id)),
+        u32s(|_, t| Some(t.tree_id)),
+        strings(|_, t| Some(t.species.code.clone())),
+        strings(|_, t| Some(t.species.common_name.clone())),
+        f64s(|_, t| Some(t.dbh)),
+        f64s(|_, t| t.height),
+        f64s(|_, t| t.crown_ratio),
+        strings(|_, t| Some(t.status.to_string())),
+        f64s(|_, t| Some(t.expansion_factor)),
+        u32s(|_, t| t.age),
+        f64s(|_, t| t.defect),
+        f64s(|p, _| Some(p.plot_size_acres)),
+        f64s(|p, _| p.slope_percent),
+        f64s(|p, _| p.aspect_degrees),
+        f64s(|p, _| p.elevation_ft),
+        u32s(|p, _| p.stand_id),
+        strings(|p, _| p.source.clone()),
+        strings(|p, _| p.measurement_date.clone()),
+        u32s(|_, t| t.decay_class.map(u32::from)),
+        f64s(|p, _| p.latitude),
+        f64s(|p, _| p.longitude),
+        strings(|p, _| p.stratum.clone()),
+    ];
+    let schema = Arc::new(schema().with_metadata(HashMap::from([(
+        EXPANSION_KIND_KEY.to_string(),
+        inventory.expansion_kind.to_string(),
+    )])));
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+
+    // ArrowWriter needs a `Send` sink, so encode in memory first
+    let mut buf = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buf, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    write_atomic(path.as_ref(), |w| Ok(w.write_all(&buf)?))
+}
@@ -0,0 +1,236 @@
+use std::io::Write;
+use std::path::Path;
+
+use super::atomic::write_atomic;
+use super::csv_io::TreeColumn;
+use crate::error::ForestError;
+
+/// Every column of the tree layout with an example value and a note on its
+/// units, in the order [`write_csv`](super::write_csv) writes them.
+const TEMPLATE_COLUMNS: [(TreeColumn, &str, &str); 21] = [
+    (TreeColumn::PlotId, "1", "plot number (required)"),
+    (
+        TreeColumn::TreeId,
+        "1",
+        "tree number within the plot (required)",
+    ),
+    (
+        TreeColumn::SpeciesCode,
+        "DF",
+        "short species code (required)",
+    ),
+    (
+        TreeColumn::SpeciesName,
+        "Douglas Fir",
+        "common name (required)",
+    ),
+    (
+        TreeColumn::Dbh,
+        "14.2",
+        "diameter at breast height, inches (required)",
+    ),
+    (TreeColumn::Height, "92", "total height, feet"),
+    (TreeColumn::CrownRatio, "0.45", "live crown ratio, 0-1"),
+    (
+        TreeColumn::Status,
+        "Live",
+        "Live, Dead, Cut or Missing (required)",
+    ),
+    (
+        TreeColumn::ExpansionFactor,
+        "5",
+        "trees per acre this tree represents (required)",
+    ),
+    (TreeColumn::Age, "65", "age, years"),
+    (TreeColumn::Defect, "0.05", "volume defect, 0-1"),
+    (
+        TreeColumn::PlotSizeAcres,
+        "0.2",
+        "plot size, acres (default 0.2)",
+    ),
+    (TreeColumn::SlopePercent, "15", "slope, percent"),
+    (
+        TreeColumn::AspectDegrees,
+        "180",
+        "aspect, degrees from north",
+    ),
+    (TreeColumn::ElevationFt, "1200", "elevation, feet"),
+    (
+        TreeColumn::Source,
+        "Crew A",
+        "crew, device or file the plot came from",
+    ),
+    (
+        TreeColumn::MeasurementDate,
+        "2024-06-12",
+        "date measured, YYYY-MM-DD",
+    ),
+    (
+        TreeColumn::DecayClass,
+        "",
+        "dead trees only: decay class, 1 (sound) to 5 (soft)",
+    ),
+    (
+        TreeColumn::Latitude,
+        "45.52",
+        "plot center latitude, decimal degrees",
+    ),
+    (
+        TreeColumn::Longitude,
+        "-122.68",
+        "plot center longitude, decimal degrees",
+    ),
+    (
+        TreeColumn::Stratum,
+        "north",
+        "stratum for stratified statistics, e.g. aspect class or stand type",
+    ),
+];
+
+/// Write an empty CSV inventory for users to fill in.
+///
+/// The file holds the header row followed by `#` comment lines: a
+/// commented-out example row and a note on each column's units. Comment
+/// lines are skipped by [`read_csv`](super::read_csv), so the template reads
+/// as an empty inventory. The file is replaced atomically.
+pub fn write_template_csv(path: impl AsRef<Path>) -> Result<(), ForestError> {
+    write_atomic(path.as_ref(), |w| write_template_csv_rows(w))
+}
+
+fn write_template_csv_rows(w: &mut dyn Write) -> Result<(), ForestError> {
+    let names: Vec<&str> = TEMPLATE_COLUMNS.iter().map(|(c, _, _)| c.name()).collect();
+    let example: Vec<&str> = TEMPLATE_COLUMNS.iter().map(|(_, e, _)| *e).collect();
+    writeln!(w, "{}", names.join(","))?;
+    writeln!(w, "# Example row; lines starting with # are ignored:")?;
+    writeln!(w, "# {}", example.join(","))?;
+    writeln!(w, "# Columns:")?;
+    for (column, _, note) in &TEMPLATE_COLUMNS {
+        writeln!(w, "#   {column}: {note}")?;
+    }
+    Ok(())
+}
+
+/// Write an empty Excel inventory for users to fill in.
+///
+/// The first sheet holds only the header row, so the template reads as an
+/// empty inventory. A second "Notes" sheet lists each column with an example
+/// value and its units. The file is replaced atomically.
+#[cfg(feature = "excel")]
+pub fn write_template_excel(path: impl AsRef<Path>) -> Result<(), ForestError> {
+    use rust_xlsxwriter::Workbook;
+
+    let excel = |e: rust_xlsxwriter::XlsxError| ForestError::Excel(e.to_string());
+    let mut workbook = Workbook::new();
+
+    let data = workbook.add_worksheet();
+    for (col, (column, _, _)) in TEMPLATE_COLUMNS.iter().enumerate() {
+        data.write_string(0, col as u16, column.name())
+            .map_err(excel)?;
+    }
+
+    let notes = workbook.add_worksheet().set_name("Notes").map_err(excel)?;
+    for (col, header) in ["column", "example", "notes"].iter().enumerate() {
+        notes.write_string(0, col as u16, *header).map_err(excel)?;
+    }
+    for (row, (column, example, note)) in TEMPLATE_COLUMNS.iter().enumerate() {
+        let row = row as u32 + 1;
+        notes.write_string(row, 0, column.name()).map_err(excel)?;
+        notes.write_string(row, 1, *example).map_err(excel)?;
+        notes.write_string(row, 2, *note).map_err(excel)?;
+    }
+
+    let buffer = workbook.save_to_buffer().map_err(excel)?;
+    write_atomic(path.as_ref(), |w| Ok(w.write_all(&buffer)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{read_csv, read_csv_from_bytes, write_csv};
+    use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus};
+
+    fn one_tree_inventory() -> ForestInventory {
+        let mut inv = ForestInventory::new("Header");
+        inv.plots.push(Plot {
+            plot_id: 1,
+            plot_size_acres: 0.2,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees: vec![Tree {
+                tree_id: 1,
+                plot_id: 1,
+                species: Species {
+                    common_name: "Douglas Fir".to_string(),
+                    code: "DF".to_string(),
+                },
+                dbh: 14.0,
+                height: None,
+                crown_ratio: None,
+                status: TreeStatus::Live,
+                expansion_factor: 5.0,
+                age: None,
+                defect: None,
+                decay_class: None,
+            }],
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        });
+        inv
+    }
+
+    #[test]
+    fn test_template_columns_cover_every_column() {
+        let columns: Vec<TreeColumn> = TEMPLATE_COLUMNS.iter().map(|(c, _, _)| *c).collect();
+        assert_eq!(columns, TreeColumn::ALL);
+    }
+
+    #[test]
+    fn test_csv_template_reads_as_empty_inventory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("template.csv");
+        write_template_csv(&path).unwrap();
+
+        let inv = read_csv(&path).unwrap();
+        assert_eq!(inv.num_plots(), 0);
+        assert_eq!(inv.num_trees(), 0);
+
+        // The header matches what write_csv emits for TreeRow
+        let written = dir.path().join("written.csv");
+        write_csv(&one_tree_inventory(), &written).unwrap();
+        let template = std::fs::read_to_string(&path).unwrap();
+        let full = std::fs::read_to_string(&written).unwrap();
+        assert_eq!(template.lines().next(), full.lines().next());
+    }
+
+    #[test]
+    fn test_uncommented_example_row_is_valid() {
+        let mut buf = Vec::new();
+        write_template_csv_rows(&mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let mut lines = text.lines();
+        let header = lines.next().unwrap();
+        let example = lines.find_map(|l| l.strip_prefix("# 1,")).unwrap();
+
+        let inv = read_csv_from_bytes(format!("{header}\n1,{example}\n").as_bytes(), "t").unwrap();
+        assert_eq!(inv.num_trees(), 1);
+        let plot = &inv.plots[0];
+        assert_eq!(plot.trees[0].species.code, "DF");
+        assert_eq!(plot.measurement_date.as_deref(), Some("2024-06-12"));
+    }
+
+    #[cfg(feature = "excel")]
+    #[test]
+    fn test_excel_template_reads_as_empty_inventory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("template.xlsx");
+        write_template_excel(&path).unwrap();
+
+        let inv = crate::io::read_excel(&path).unwrap();
+        assert_eq!(inv.num_trees(), 0);
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    csv_expansion_kind, json_expansion_kind, jsonl_expansion_kind, parse_csv_lenient_with_encoding,
+    parse_json_lenient, parse_jsonl_lenient, rows_to_inventory, CsvEncoding, EditableTreeRow,
+};
+#[cfg(feature = "excel")]
+use super::{excel_expansion_kind, parse_excel_lenient};
+use crate::error::ForestError;
+use crate::models::{
+    canonical_species_names, measurement_date_issue, species_name_issue, ExpansionFlag,
+    ExpansionKind, ForestInventory, Species, ValidationIssue, DEFAULT_PLOT_SIZE_ACRES,
+};
+#[cfg(doc)]
+use crate::models::{Plot, Tree};
+
+/// How serious a [`FileIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The value is invalid; strict readers reject the file
+    Error,
+    /// The value is plausible but worth a second look
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A validation issue found in an input file, with its severity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileIssue {
+    #[serde(flatten)]
+    pub issue: ValidationIssue,
+    pub severity: Severity,
+}
+
+impl FileIssue {
+    /// Tag the errors and warnings found by a lenient parser with their
+    /// severity, errors first.
+    pub(crate) fn tagged(
+        errors: Vec<ValidationIssue>,
+        warnings: Vec<ValidationIssue>,
+    ) -> Vec<FileIssue> {
+        let tag = |severity| move |issue| FileIssue { issue, severity };
+        errors
+            .into_iter()
+            .map(tag(Severity::Error))
+            .chain(warnings.into_iter().map(tag(Severity::Warning)))
+            .collect()
+    }
+}
+
+/// Every issue found by [`validate_file`], ordered by row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Inventory name derived from the file
+    pub name: String,
+    /// Number of tree rows read
+    pub num_rows: usize,
+    pub issues: Vec<FileIssue>,
+}
+
+impl ValidationReport {
+    /// Number of issues with [`Severity::Error`].
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    /// Number of issues with [`Severity::Warning`].
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    /// Whether any issue would make a strict reader reject the file.
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.issues.iter().filter(|i| i.severity == severity).count()
+    }
+}
+
+/// Check an input file without loading it for analysis.
+///
+/// Runs the same lenient parser as the web upload, so every invalid value is
+/// reported instead of just the first, and adds non-fatal measurement warnings
+/// (see [`Tree::warnings`] and [`Plot::warnings`]) and species codes recorded
+/// under conflicting names (see [`ForestInventory::species_name_issues`]).
+/// Format-level problems such as a missing column or an unsupported extension
+/// are still returned as an `Err`. `encoding` applies to CSV input only.
+pub fn validate_file(
+    path: impl AsRef<Path>,
+    encoding: CsvEncoding,
+) -> Result<ValidationReport, ForestError> {
+    Ok(check_file(path.as_ref(), encoding)?.0)
+}
+
+/// Read an input file, skipping rows with invalid values instead of
+/// rejecting the file.
+///
+/// Returns the inventory built from the rows without errors, with the
+/// file's [`ExpansionKind`], along with the [`validate_file`] report listing
+/// the skipped rows' errors and any warnings. Format-level problems are still
+/// returned as an `Err`.
+pub fn read_file_lenient(
+    path: impl AsRef<Path>,
+    encoding: CsvEncoding,
+) -> Result<(ForestInventory, ValidationReport), ForestError> {
+    let (report, rows, expansion_kind) = check_file(path.as_ref(), encoding)?;
+    let skipped: HashSet<usize> = report
+        .issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .map(|i| i.issue.row_index)
+        .collect();
+    let valid: Vec<EditableTreeRow> = rows
+        .into_iter()
+        .filter(|r| !skipped.contains(&r.row_index))
+        .collect();
+    let mut inventory = rows_to_inventory(&report.name, &valid);
+    inventory.expansion_kind = expansion_kind;
+    Ok((inventory, report))
+}
+
+fn check_file(
+    path: &Path,
+    encoding: CsvEncoding,
+) -> Result<(ValidationReport, Vec<EditableTreeRow>, ExpansionKind), ForestError> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("inventory")
+        .to_string();
+
+    let data = std::fs::read(path)?;
+    let ((name, rows, parsed), expansion_kind) = match ext.as_str() {
+        "csv" => (
+            parse_csv_lenient_with_encoding(&data, &name, encoding)?,
+            csv_expansion_kind(&data, encoding)?,
+        ),
+        "json" => (
+            parse_json_lenient(&data, &name)?,
+            json_expansion_kind(&data)?,
+        ),
+        "jsonl" => (
+            parse_jsonl_lenient(&data, &name)?,
+            jsonl_expansion_kind(&data, path)?,
+        ),
+        #[cfg(feature = "excel")]
+        "xlsx" | "xls" => (
+            parse_excel_lenient(&data, &name)?,
+            excel_expansion_kind(&data)?,
+        ),
+        _ => {
+            return Err(ForestError::ParseError(format!(
+                "Unsupported file format: .{ext}. Use .csv, .json, .jsonl, or .xlsx"
+            )))
+        }
+    };
+
+    let mut issues: Vec<FileIssue> = parsed
+        .into_iter()
+        .chain(
+            rows.iter()
+                .flat_map(measurement_date_warning)
+                .chain(species_name_warnings(&rows))
+                .chain(expansion_warnings(&rows))
+                .map(|issue| FileIssue {
+                    issue,
+                    severity: Severity::Warning,
+                }),
+        )
+        .collect();
+    // Stable, so issues within a row keep the parser's field order
+    issues.sort_by_key(|i| (i.issue.row_index, i.severity));
+
+    let report = ValidationReport {
+        name,
+        num_rows: rows.len(),
+        issues,
+    };
+    Ok((report, rows, expansion_kind))
+}
+
+fn species_name_warnings(rows: &[EditableTreeRow]) -> Vec<ValidationIssue> {
+    let species: Vec<Species> = rows
+        .iter()
+        .map(|row| Species {
+            common_name: row.species_name.clone(),
+            code: row.species_code.clone(),
+        })
+        .collect();
+    let canonical = canonical_species_names(&species);
+    rows.iter()
+        .zip(&species)
+        .filter_map(|(row, sp)| {
+            species_name_issue(&canonical, sp, row.plot_id, row.tree_id, row.row_index)
+        })
+        .collect()
+}
+
+/// [`Plot::expansion_consistency`] for each plot, reported on its first row.
+fn expansion_warnings(rows: &[EditableTreeRow]) -> Vec<ValidationIssue> {
+    let mut plots: BTreeMap<u32, Vec<&EditableTreeRow>> = BTreeMap::new();
+    for row in rows {
+        plots.entry(row.plot_id).or_default().push(row);
+    }
+    plots
+        .into_iter()
+        .filter_map(|(plot_id, rows)| {
+            let first = rows[0];
+            let plot_size = first.plot_size_acres.unwrap_or(DEFAULT_PLOT_SIZE_ACRES);
+            ExpansionFlag::check(plot_id, plot_size, rows.iter().map(|r| r.expansion_factor))
+                .map(|flag| flag.to_issue(first.row_index))
+        })
+        .collect()
+}
+
+fn measurement_date_warning(row: &EditableTreeRow) -> Option<ValidationIssue> {
+    row.measurement_date
+        .as_deref()
+        .and_then(|date| measurement_date_issue(date, row.plot_id, row.tree_id, row.row_index))
+}
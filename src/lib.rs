@@ -2,26 +2,52 @@
 //!
 //! Provides I/O in multiple formats (CSV, JSON, Excel, GeoJSON), statistical analysis,
 //! diameter distributions, growth projections, text-based visualization, and an optional
-//! web server (behind the `web` feature). Key entry points: [`Analyzer`], [`ForestInventory`],
-//! [`InventoryReader`], and [`InventoryWriter`].
+//! web server (behind the `web` feature). Key entry points: [`analyze_file`], [`Analyzer`],
+//! [`ForestInventory`], [`InventoryReader`], and [`InventoryWriter`].
+//!
+//! Only [`models`] and [`error`] are always built. Everything else sits behind
+//! a cargo feature so library users can skip heavy dependencies: `analysis`,
+//! `io` (CSV/JSON/GeoJSON), `excel`, `visualization`, `config`, `cli`, `wasm`,
+//! `watch` and `web`. The default features enable all but `wasm` and `watch`;
+//! for just the data model and metrics, use
+//! `default-features = false, features = ["analysis"]`.
 
+#[cfg(feature = "analysis")]
 pub mod analysis;
+#[cfg(feature = "config")]
 pub mod config;
 pub mod error;
+#[cfg(feature = "io")]
 pub mod io;
 pub mod models;
+#[cfg(all(feature = "analysis", feature = "io"))]
+pub mod report;
+#[cfg(all(feature = "analysis", any(test, feature = "testing")))]
+pub mod testing;
+#[cfg(feature = "visualization")]
 pub mod visualization;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "watch")]
+pub mod watch;
 #[cfg(feature = "web")]
 pub mod web;
 
+#[cfg(feature = "analysis")]
 pub use analysis::{
     Analyzer, ConfidenceInterval, DiameterClass, DiameterDistribution, GrowthModel,
-    GrowthProjection, SamplingStatistics, SpeciesComposition, StandMetrics,
+    GrowthProjection, SamplingStatistics, SpeciesComposition, SpeciesGrowthProjection,
+    StandMetrics,
 };
+#[cfg(feature = "config")]
 pub use config::AppConfig;
 pub use error::ForestError;
+#[cfg(feature = "io")]
 pub use io::{GeoJsonFormat, InventoryReader, InventoryWriter};
 pub use models::{
-    ForestInventory, Plot, Species, Tree, TreeStatus, ValidationIssue, VolumeEquation,
+    AdjustSpec, ExpansionKind, ForestInventory, Plot, RenumberPolicy, Species, SpeciesVolumeTable,
+    Tree, TreeStatus, UnitSystem, ValidationIssue, VolumeEquation, VolumeEquationSet, VolumeModel,
 };
+#[cfg(all(feature = "analysis", feature = "io"))]
+pub use report::{analyze_file, AnalyzeParams, AnalyzeReport};
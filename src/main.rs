@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -6,26 +7,38 @@ use colored::Colorize;
 
 use forest_inventory_analyzer::{
     analysis::{
-        compute_stand_metrics, project_growth, DiameterDistribution, GrowthModel,
-        SamplingStatistics,
+        compute_stand_metrics, compute_stand_metrics_grouped, harvest_summary, missing_tree_report,
+        model_fit, plot_qa_with, project_growth_with_thinning, DiameterClassWidth,
+        DiameterDistribution, DistributionOptions, GrowthModel, RecruitmentModel,
+        SamplingStatistics, SpeciesGroups, StandMetrics, Thinning, DEFAULT_MAX_SAMPLING_ERROR,
+        DEFAULT_OUTLIER_SD,
     },
     config::AppConfig,
     io,
+    models::{ExpansionKind, ForestInventory, TreeStatus, UnitSystem},
     visualization::{
-        print_diameter_histogram, print_growth_table, print_species_table, print_stand_summary,
-        print_statistics_table,
+        format_diameter_histogram_svg, format_species_composition_svg, format_statistics_table_in,
+        print_compact_summary, print_cumulative_distribution_in, print_diameter_histogram_in,
+        print_group_table, print_growth_table_with, print_harvest_table, print_plot_qa_table,
+        print_species_table_top_with, print_species_table_with, print_stand_summary_with,
+        print_validation_table, BoardFootUnit, ClassOrder, HistogramMetric, HistogramOptions,
+        SpeciesOrder, SpeciesPalette,
     },
 };
 
 /// Supported input file extensions for inventory data.
-const SUPPORTED_INPUT_EXTS: &[&str] = &["csv", "json", "xlsx", "xls"];
+#[cfg(not(feature = "parquet"))]
+const SUPPORTED_INPUT_EXTS: &[&str] = &["csv", "json", "jsonl", "xlsx", "xls"];
+#[cfg(feature = "parquet")]
+const SUPPORTED_INPUT_EXTS: &[&str] = &["csv", "json", "jsonl", "xlsx", "xls", "parquet"];
 
 /// Parse and validate a confidence level in (0.0, 1.0) exclusive.
 fn parse_confidence(s: &str) -> Result<f64, String> {
     let val: f64 = s
         .parse()
         .map_err(|_| format!("'{s}' is not a valid number"))?;
-    if val <= 0.0 || val >= 1.0 {
+    // Written so NaN, which fails every comparison, is rejected too
+    if !(0.0 < val && val < 1.0) {
         return Err(format!(
             "confidence must be between 0.0 and 1.0 exclusive, got {val}"
         ));
@@ -33,6 +46,72 @@ fn parse_confidence(s: &str) -> Result<f64, String> {
     Ok(val)
 }
 
+/// Parse and validate a sampling error percentage (> 0).
+fn parse_error_percent(s: &str) -> Result<f64, String> {
+    let val: f64 = s
+        .parse()
+        .map_err(|_| format!("'{s}' is not a valid number"))?;
+    if !(val > 0.0 && val.is_finite()) {
+        return Err(format!("sampling error must be a positive percentage, got {val}"));
+    }
+    Ok(val)
+}
+
+/// Parse and validate a tract area in acres (> 0).
+fn parse_acres(s: &str) -> Result<f64, String> {
+    let val: f64 = s
+        .parse()
+        .map_err(|_| format!("'{s}' is not a valid number"))?;
+    if !(val > 0.0 && val.is_finite()) {
+        return Err(format!("acres must be positive, got {val}"));
+    }
+    Ok(val)
+}
+
+/// Parse a histogram metric name (tpa or ba).
+fn parse_histogram_metric(s: &str) -> Result<HistogramMetric, String> {
+    s.parse().map_err(|e: forest_inventory_analyzer::ForestError| e.to_string())
+}
+
+/// Parse a thinning given as YEAR:FRACTION.
+fn parse_thinning(s: &str) -> Result<Thinning, String> {
+    s.parse().map_err(|e: forest_inventory_analyzer::ForestError| e.to_string())
+}
+
+/// Parse a species table order (ba, tpa, name or importance).
+fn parse_species_order(s: &str) -> Result<SpeciesOrder, String> {
+    s.parse().map_err(|e: forest_inventory_analyzer::ForestError| e.to_string())
+}
+
+/// Parse a diameter class order (asc or desc).
+fn parse_class_order(s: &str) -> Result<ClassOrder, String> {
+    s.parse().map_err(|e: forest_inventory_analyzer::ForestError| e.to_string())
+}
+
+/// Parse a CSV text encoding name (utf-8 or windows-1252).
+fn parse_encoding(s: &str) -> Result<io::CsvEncoding, String> {
+    s.parse().map_err(|e: forest_inventory_analyzer::ForestError| e.to_string())
+}
+
+/// Parse an expansion factor kind (per-acre or count).
+fn parse_expansion_kind(s: &str) -> Result<ExpansionKind, String> {
+    s.parse().map_err(|e: forest_inventory_analyzer::ForestError| e.to_string())
+}
+
+/// Parse a `FIELD=HEADER` column mapping, e.g. `dbh=DBH_in`.
+fn parse_column_map(s: &str) -> Result<(io::TreeColumn, String), String> {
+    let (field, header) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected FIELD=HEADER, got '{s}'"))?;
+    let column = field
+        .parse()
+        .map_err(|e: forest_inventory_analyzer::ForestError| e.to_string())?;
+    if header.trim().is_empty() {
+        return Err(format!("no header given for '{field}'"));
+    }
+    Ok((column, header.trim().to_string()))
+}
+
 /// Extract the lowercased file extension from a path, or empty string if none.
 fn file_extension(path: &Path) -> String {
     path.extension()
@@ -47,18 +126,66 @@ fn is_supported_inventory_file(path: &Path) -> bool {
     SUPPORTED_INPUT_EXTS.contains(&ext.as_str())
 }
 
-/// Load a forest inventory from a supported file format (CSV, JSON, Excel).
-fn load_inventory(path: &Path) -> Result<forest_inventory_analyzer::models::ForestInventory> {
-    let ext = file_extension(path);
-    match ext.as_str() {
-        "csv" => Ok(io::read_csv(path)?),
-        "json" => Ok(io::read_json(path)?),
-        "xlsx" | "xls" => Ok(io::read_excel(path)?),
-        _ => anyhow::bail!("Unsupported file format: .{ext}. Use .csv, .json, or .xlsx"),
+/// Convert stand metrics for display in `units`.
+fn in_units(metrics: StandMetrics, units: UnitSystem) -> StandMetrics {
+    match units {
+        UnitSystem::Imperial => metrics,
+        UnitSystem::Metric => metrics.to_metric(),
+    }
+}
+
+/// Load a forest inventory from a supported file format (CSV, JSON, JSON Lines,
+/// Excel, and Parquet with the `parquet` feature). A non-empty `mapping` renames CSV
+/// headers and is rejected for other formats. `expansion_kind`, if given,
+/// overrides the kind recorded in the file.
+fn load_inventory(
+    path: &Path,
+    encoding: io::CsvEncoding,
+    mapping: &io::ColumnMapping,
+    expansion_kind: Option<ExpansionKind>,
+) -> Result<forest_inventory_analyzer::models::ForestInventory> {
+    let mut inventory = if mapping.is_empty() {
+        io::read_file_with_encoding(path, encoding)?
+    } else if file_extension(path) != "csv" {
+        anyhow::bail!("--map only applies to CSV input, not {}", path.display());
+    } else {
+        io::read_csv_mapped_with_encoding(path, encoding, mapping)?
+    };
+    if let Some(kind) = expansion_kind {
+        inventory.expansion_kind = kind;
     }
+    Ok(inventory)
 }
 
-/// Save a forest inventory to a supported output format (CSV, JSON, Excel, GeoJSON).
+/// Load an inventory, skipping rows with invalid values. Returns the
+/// inventory and the validation report of skipped rows and warnings.
+fn load_inventory_lenient(
+    path: &Path,
+    encoding: io::CsvEncoding,
+    mapping: &io::ColumnMapping,
+    expansion_kind: Option<ExpansionKind>,
+) -> Result<(ForestInventory, io::ValidationReport)> {
+    if !mapping.is_empty() {
+        anyhow::bail!("--map cannot be combined with --lenient");
+    }
+    let (mut inventory, report) = io::read_file_lenient(path, encoding)?;
+    if let Some(kind) = expansion_kind {
+        inventory.expansion_kind = kind;
+    }
+    if !report.issues.is_empty() {
+        eprintln!(
+            "{}: skipped rows with {} errors; {} warnings in {}",
+            "Warning".yellow(),
+            report.error_count(),
+            report.warning_count(),
+            path.display()
+        );
+    }
+    Ok((inventory, report))
+}
+
+/// Save a forest inventory to a supported output format (CSV, JSON, Excel, GeoJSON,
+/// and Parquet with the `parquet` feature).
 fn save_inventory(
     inventory: &forest_inventory_analyzer::models::ForestInventory,
     path: &Path,
@@ -70,6 +197,8 @@ fn save_inventory(
         "json" => io::write_json(inventory, path, pretty)?,
         "xlsx" => io::write_excel(inventory, path)?,
         "geojson" => io::write_geojson(inventory, path, pretty)?,
+        #[cfg(feature = "parquet")]
+        "parquet" => io::write_parquet(inventory, path)?,
         _ => anyhow::bail!(
             "Unsupported output format: .{ext}. Use .csv, .json, .xlsx, or .geojson"
         ),
@@ -77,10 +206,100 @@ fn save_inventory(
     Ok(())
 }
 
-#[derive(Parser)]
+/// Parse a growth model name, then override its defaults with the
+/// rate/capacity/shape/mortality arguments that were given.
+fn build_growth_model(
+    model: &str,
+    rate: Option<f64>,
+    capacity: Option<f64>,
+    shape: Option<f64>,
+    mortality: Option<f64>,
+) -> Result<GrowthModel> {
+    let mut growth_model: GrowthModel = model.parse().map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    match &mut growth_model {
+        GrowthModel::Exponential {
+            annual_rate,
+            mortality_rate,
+        } => {
+            if let Some(r) = rate { *annual_rate = r; }
+            if let Some(m) = mortality { *mortality_rate = m; }
+        }
+        GrowthModel::Logistic {
+            annual_rate,
+            carrying_capacity,
+            mortality_rate,
+        } => {
+            if let Some(r) = rate { *annual_rate = r; }
+            if let Some(c) = capacity { *carrying_capacity = c; }
+            if let Some(m) = mortality { *mortality_rate = m; }
+        }
+        GrowthModel::Linear {
+            annual_increment,
+            mortality_rate,
+        } => {
+            if let Some(r) = rate { *annual_increment = r; }
+            if let Some(m) = mortality { *mortality_rate = m; }
+        }
+        GrowthModel::ChapmanRichards {
+            asymptote,
+            rate: k,
+            shape: p,
+            mortality_rate,
+        } => {
+            if let Some(r) = rate { *k = r; }
+            if let Some(c) = capacity { *asymptote = c; }
+            if let Some(s) = shape { *p = s; }
+            if let Some(m) = mortality { *mortality_rate = m; }
+        }
+    }
+    Ok(growth_model)
+}
+
+/// Convert a single inventory file, optionally embedding provenance metadata.
+fn convert_file(
+    input: &Path,
+    output: &Path,
+    pretty: bool,
+    metadata: bool,
+    encoding: io::CsvEncoding,
+    mapping: &io::ColumnMapping,
+    expansion_kind: Option<ExpansionKind>,
+) -> Result<()> {
+    let inventory = load_inventory(input, encoding, mapping, expansion_kind)?;
+    if metadata {
+        let source = input.file_name().map(|n| n.to_string_lossy().to_string());
+        let meta = io::ExportMetadata::new(&inventory, source.as_deref())?;
+        match file_extension(output).as_str() {
+            "json" => io::write_json_with_metadata(&inventory, output, pretty, &meta)?,
+            "xlsx" => io::write_excel_with_metadata(&inventory, output, &meta)?,
+            ext => anyhow::bail!(
+                "--metadata is only supported for .json and .xlsx output, got .{ext}"
+            ),
+        }
+    } else {
+        save_inventory(&inventory, output, pretty)?;
+    }
+    Ok(())
+}
+
+/// Exit status of a run that completed. Hard failures exit with 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Outcome {
+    /// Success with clean data
+    Clean = 0,
+    /// Success, but the input has validation warnings or skipped rows
+    Warnings = 2,
+    /// The analysis ran, but some sampling errors exceed `--max-sampling-error`
+    Unreliable = 3,
+}
+
+#[derive(Parser, Clone)]
 #[command(
     name = "forest-analyzer",
     about = "Forest Inventory Analyzer - Comprehensive stand analysis tool",
+    after_help = "Exit status: 0 success, 1 failure or invalid arguments, 2 success \
+                  with validation warnings, 3 success with unreliable sampling statistics",
     version,
     author
 )]
@@ -89,11 +308,33 @@ struct Cli {
     #[arg(long, global = true, default_value = "config.toml")]
     config: PathBuf,
 
+    /// Text encoding of CSV input files: utf-8 (default) or windows-1252
+    #[arg(long, global = true, default_value = "utf-8", value_parser = parse_encoding)]
+    encoding: io::CsvEncoding,
+
+    /// Read a CSV column from a differently named header, e.g. --map dbh=DBH_in
+    /// (repeatable)
+    #[arg(long, global = true, value_name = "FIELD=HEADER", value_parser = parse_column_map)]
+    map: Vec<(io::TreeColumn, String)>,
+
+    /// Read tree expansion factors as per-acre or count (trees on the plot),
+    /// overriding the kind recorded in the input file
+    #[arg(long, global = true, value_name = "KIND", value_parser = parse_expansion_kind)]
+    expansion_kind: Option<ExpansionKind>,
+
+    /// Show board-foot volumes in thousand board feet (MBF) instead of board feet
+    #[arg(long, global = true)]
+    mbf: bool,
+
+    /// Unit label used with --mbf (default: MBF)
+    #[arg(long, global = true, default_value = "MBF", requires = "mbf")]
+    mbf_label: String,
+
     #[command(subcommand)]
     command: Commands,
 }
 
-#[derive(Subcommand)]
+#[derive(Subcommand, Clone)]
 enum Commands {
     /// Analyze forest inventory data and display stand metrics
     Analyze {
@@ -108,16 +349,97 @@ enum Commands {
 
         /// Diameter class width in inches for distribution.
         /// Falls back to config.toml analysis.diameter_class_width if not specified.
-        #[arg(short, long)]
-        diameter_class_width: Option<f64>,
+        #[arg(short, long, allow_hyphen_values = true)]
+        diameter_class_width: Option<DiameterClassWidth>,
 
         /// Show detailed species composition
         #[arg(long, default_value = "true")]
         species: bool,
 
+        /// Also show composition rolled up into species groups, read from a
+        /// TOML (or .json) file mapping species codes to group names, e.g.
+        /// DF = "Conifer". Unlisted species are grouped as "Other"
+        #[arg(long, value_name = "FILE")]
+        group_by: Option<PathBuf>,
+
         /// Show diameter distribution histogram
         #[arg(long, default_value = "true")]
         distribution: bool,
+
+        /// Also show the cumulative (ogive) diameter distribution
+        #[arg(long)]
+        cumulative: bool,
+
+        /// Tree status the diameter distribution covers: live, dead, cut or missing
+        #[arg(long, default_value = "live")]
+        distribution_status: TreeStatus,
+
+        /// Smallest DBH in inches to include in the diameter distribution;
+        /// the first class starts here
+        #[arg(long)]
+        distribution_min_dbh: Option<f64>,
+
+        /// Add a leading class from 0 to --distribution-min-dbh holding the
+        /// trees below the threshold
+        #[arg(long, requires = "distribution_min_dbh")]
+        ingrowth_class: bool,
+
+        /// Length in characters of the longest histogram bar
+        #[arg(long, default_value_t = 40)]
+        histogram_width: usize,
+
+        /// Value histogram bars are scaled by: tpa or ba
+        #[arg(long, default_value = "tpa", value_parser = parse_histogram_metric)]
+        histogram_metric: HistogramMetric,
+
+        /// Species table row order: ba, tpa, name or importance (% TPA + % BA);
+        /// numeric orders list the largest first
+        #[arg(long, default_value = "ba", value_parser = parse_species_order)]
+        sort_species: SpeciesOrder,
+
+        /// Show only the N species with the most basal area, rolling the rest
+        /// into one "Other" row (default: all species)
+        #[arg(long, value_name = "N")]
+        top_species: Option<usize>,
+
+        /// Diameter class order in the histogram: asc or desc
+        #[arg(long, default_value = "asc", value_parser = parse_class_order)]
+        sort_classes: ClassOrder,
+
+        /// Target sampling error in percent (e.g. 10); adds a pass/fail status column
+        #[arg(long, value_parser = parse_error_percent)]
+        target_error: Option<f64>,
+
+        /// Sampling error in percent above which an estimate is warned about as unreliable
+        #[arg(long, default_value_t = DEFAULT_MAX_SAMPLING_ERROR, value_parser = parse_error_percent)]
+        max_sampling_error: f64,
+
+        /// Write species composition and diameter distribution SVG charts to this directory.
+        /// Species colors come from config.toml [species_colors]
+        #[arg(long)]
+        svg_dir: Option<PathBuf>,
+
+        /// Print one plain line per metric, e.g. "TPA: 123.4 ± 8.2 (±6.7% at 95%)",
+        /// instead of the tables
+        #[arg(long, visible_alias = "summary-only")]
+        compact: bool,
+
+        /// Skip rows with invalid values instead of rejecting the file. Skipped
+        /// rows and warnings are reported and the exit status is 2
+        #[arg(long)]
+        lenient: bool,
+
+        /// Units for the stand summary, species, diameter distribution and
+        /// sampling statistics tables: imperial or metric (cm, m, trees/ha,
+        /// m²/ha, m³/ha)
+        #[arg(long, default_value = "imperial")]
+        units: UnitSystem,
+
+        /// Re-run whenever the input file changes, redrawing the output.
+        /// Read errors are shown and the next change is awaited
+        #[cfg(feature = "watch")]
+        #[arg(long)]
+        watch: bool,
     },
 
     /// Project stand growth over time
@@ -130,19 +452,77 @@ enum Commands {
         #[arg(short, long, default_value = "20")]
         years: u32,
 
-        /// Growth model: exponential, logistic, or linear
+        /// Growth model: exponential, logistic, linear, or chapman (Chapman-Richards)
         #[arg(short, long, default_value = "logistic")]
         model: String,
 
-        /// Annual growth rate (for exponential/logistic models)
+        /// Annual growth rate (for exponential/logistic models, or k for chapman).
+        /// Negative values model decline for the exponential and linear models
+        #[arg(short, long, allow_hyphen_values = true)]
+        rate: Option<f64>,
+
+        /// Carrying capacity for basal area (logistic model, or the chapman
+        /// asymptote, sq ft/acre)
+        #[arg(short, long)]
+        capacity: Option<f64>,
+
+        /// Shape parameter p of the chapman model; above 1 gives an inflection
+        #[arg(long)]
+        shape: Option<f64>,
+
+        /// Annual mortality rate (proportion for exponential/logistic/chapman,
+        /// TPA/year for linear)
+        #[arg(long)]
+        mortality: Option<f64>,
+
+        /// Ingrowth: new trees per acre recruited each year
+        #[arg(long)]
+        recruitment: Option<f64>,
+
+        /// DBH of recruited trees in inches
+        #[arg(long, default_value = "1.0", requires = "recruitment")]
+        recruitment_dbh: f64,
+
+        /// Thin the stand to FRACTION of its TPA, basal area and volume at
+        /// YEAR, e.g. 15:0.6; repeat for several thinnings
+        #[arg(long, value_name = "YEAR:FRACTION", value_parser = parse_thinning)]
+        thin: Vec<Thinning>,
+    },
+
+    /// Measure how well a growth model reproduces an observed re-measurement
+    Fit {
+        /// Earlier measurement (CSV, JSON, or Excel)
+        #[arg(long)]
+        before: PathBuf,
+
+        /// Later measurement of the same plots
+        #[arg(long)]
+        after: PathBuf,
+
+        /// Years between the two measurements (may be fractional)
         #[arg(short, long)]
+        years: f64,
+
+        /// Growth model: exponential, logistic, linear, or chapman (Chapman-Richards)
+        #[arg(short, long, default_value = "logistic")]
+        model: String,
+
+        /// Annual growth rate (for exponential/logistic models, or k for
+        /// chapman), or annual basal area increment for the linear model
+        #[arg(short, long, allow_hyphen_values = true)]
         rate: Option<f64>,
 
-        /// Carrying capacity for basal area (logistic model, sq ft/acre)
+        /// Carrying capacity for basal area (logistic model, or the chapman
+        /// asymptote, sq ft/acre)
         #[arg(short, long)]
         capacity: Option<f64>,
 
-        /// Annual mortality rate (proportion for exponential/logistic, TPA/year for linear)
+        /// Shape parameter p of the chapman model; above 1 gives an inflection
+        #[arg(long)]
+        shape: Option<f64>,
+
+        /// Annual mortality rate (proportion for exponential/logistic/chapman,
+        /// TPA/year for linear)
         #[arg(long)]
         mortality: Option<f64>,
     },
@@ -160,6 +540,14 @@ enum Commands {
         /// Pretty-print JSON output
         #[arg(long)]
         pretty: bool,
+
+        /// Embed provenance metadata (source file, timestamp, checksum) in JSON or Excel output
+        #[arg(long)]
+        metadata: bool,
+
+        /// Output extension when converting a directory (e.g. json, csv, xlsx; default: json)
+        #[arg(long)]
+        format: Option<String>,
     },
 
     /// Analyze multiple inventory files in a directory
@@ -183,6 +571,54 @@ enum Commands {
         /// Path to input file
         #[arg(short, long)]
         input: PathBuf,
+
+        /// Total tract area in acres, for whole-tract totals (overrides the file's total_acres)
+        #[arg(long, value_parser = parse_acres)]
+        acres: Option<f64>,
+
+        /// Units for the summary: imperial or metric (cm, trees/ha, m²/ha, m³/ha, ha)
+        #[arg(long, default_value = "imperial")]
+        units: UnitSystem,
+
+        /// Re-run whenever the input file changes, redrawing the output.
+        /// Read errors are shown and the next change is awaited
+        #[cfg(feature = "watch")]
+        #[arg(long)]
+        watch: bool,
+    },
+
+    /// Summarize removals of trees tagged as cut (status "Cut"), by species
+    Harvest {
+        /// Path to input file
+        #[arg(short, long)]
+        input: PathBuf,
+    },
+
+    /// Flag problem plots (no live trees, a single tree, or basal area outliers)
+    QaPlots {
+        /// Path to input file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Standard deviations from the mean basal area that mark an outlier
+        #[arg(short, long, default_value_t = DEFAULT_OUTLIER_SD)]
+        k: f64,
+    },
+
+    /// Check an input file for invalid values without analyzing it.
+    /// Exits with 1 if any errors are found, or 2 if there are only warnings
+    Validate {
+        /// Path to input file (CSV, JSON, or Excel)
+        #[arg(short, long)]
+        input: PathBuf,
+    },
+
+    /// Write an empty CSV or Excel file with every supported column, an
+    /// example row and notes on units, ready to fill in
+    Template {
+        /// Output file path (.csv or .xlsx)
+        #[arg(short, long)]
+        output: PathBuf,
     },
 
     /// Start the web UI server
@@ -195,15 +631,100 @@ enum Commands {
         /// Address to bind the server to
         #[arg(short, long, default_value = "127.0.0.1")]
         bind: String,
+
+        /// SQLite database path (overrides config); use ":memory:" for an ephemeral store
+        #[arg(long)]
+        db: Option<String>,
     },
 }
 
-fn main() -> Result<()> {
+#[cfg(feature = "watch")]
+impl Commands {
+    /// The input file to watch, when `--watch` was given.
+    fn watched_input(&self) -> Option<PathBuf> {
+        match self {
+            Commands::Analyze {
+                input, watch: true, ..
+            }
+            | Commands::Summary {
+                input, watch: true, ..
+            } => Some(input.clone()),
+            _ => None,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    // clap exits with 2 on usage errors, which would read as Outcome::Warnings
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            return if e.use_stderr() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
+    match run(cli) {
+        Ok(outcome) => ExitCode::from(outcome as u8),
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<Outcome> {
     tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .init();
-    let cli = Cli::parse();
     let config = AppConfig::load(&cli.config)?;
+    #[cfg(feature = "watch")]
+    if let Some(input) = cli.command.watched_input() {
+        return watch_and_rerun(cli, config, input);
+    }
+    execute(cli, config)
+}
+
+/// Re-run `cli` on every change to `input` until interrupted. Errors are
+/// printed rather than returned so a half-saved file does not end the watch.
+#[cfg(feature = "watch")]
+fn watch_and_rerun(cli: Cli, config: AppConfig, input: PathBuf) -> Result<Outcome> {
+    use std::io::Write;
+    use std::ops::ControlFlow;
+
+    use forest_inventory_analyzer::watch::{watch_file, DEFAULT_DEBOUNCE};
+
+    let rerun = || {
+        // Clear the screen and move the cursor home before redrawing
+        print!("\x1b[2J\x1b[H");
+        if let Err(e) = execute(cli.clone(), config.clone()) {
+            eprintln!("Error: {e:?}");
+        }
+        println!(
+            "\n{}",
+            format!("Watching {} for changes (Ctrl-C to stop)", input.display()).dimmed()
+        );
+        let _ = std::io::stdout().flush();
+    };
+    rerun();
+    watch_file(&input, DEFAULT_DEBOUNCE, || {
+        rerun();
+        ControlFlow::Continue(())
+    })?;
+    Ok(Outcome::Clean)
+}
+
+fn execute(cli: Cli, config: AppConfig) -> Result<Outcome> {
+    let bdft_unit = if cli.mbf {
+        BoardFootUnit::Mbf(cli.mbf_label.clone())
+    } else {
+        BoardFootUnit::BoardFeet
+    };
+    let mapping: io::ColumnMapping = cli.map.iter().cloned().collect();
+    let mut outcome = Outcome::Clean;
 
     match cli.command {
         Commands::Analyze {
@@ -211,11 +732,66 @@ fn main() -> Result<()> {
             confidence,
             diameter_class_width,
             species,
+            group_by,
             distribution,
+            cumulative,
+            distribution_status,
+            distribution_min_dbh,
+            ingrowth_class,
+            histogram_width,
+            histogram_metric,
+            sort_species,
+            top_species,
+            sort_classes,
+            target_error,
+            max_sampling_error,
+            svg_dir,
+            compact,
+            lenient,
+            units,
+            ..
         } => {
             let confidence = confidence.unwrap_or(config.analysis.confidence_level);
-            let diameter_class_width =
-                diameter_class_width.unwrap_or(config.analysis.diameter_class_width);
+            let diameter_class_width = match diameter_class_width {
+                Some(width) => width,
+                None => DiameterClassWidth::new(config.analysis.diameter_class_width)?,
+            };
+            if top_species == Some(0) {
+                anyhow::bail!("--top-species must be positive");
+            }
+
+            let (inventory, report) = if lenient {
+                let (inventory, report) =
+                    load_inventory_lenient(&input, cli.encoding, &mapping, cli.expansion_kind)?;
+                (inventory, Some(report))
+            } else {
+                (
+                    load_inventory(&input, cli.encoding, &mapping, cli.expansion_kind)?,
+                    None,
+                )
+            };
+            if report.as_ref().is_some_and(|r| !r.issues.is_empty()) {
+                outcome = Outcome::Warnings;
+            }
+
+            if compact {
+                let metrics = in_units(
+                    compute_stand_metrics(&inventory).with_max_sdi(config.analysis.max_sdi),
+                    units,
+                );
+                let stats = match SamplingStatistics::compute(&inventory, confidence) {
+                    Ok(stats) => Some(stats),
+                    Err(e) => {
+                        eprintln!("{}: {e}", "Warning".yellow());
+                        None
+                    }
+                };
+                print_compact_summary(&metrics, stats.as_ref());
+                if stats.is_some_and(|s| !s.unreliable_metrics(max_sampling_error).is_empty()) {
+                    outcome = Outcome::Unreliable;
+                }
+                return Ok(outcome);
+            }
 
             println!(
                 "\n{}",
@@ -223,28 +799,93 @@ fn main() -> Result<()> {
                     .bold()
                     .cyan()
             );
-
-            let inventory = load_inventory(&input)?;
+            if let Some(report) = report.filter(|r| !r.issues.is_empty()) {
+                print_validation_table(&report);
+            }
             println!(
                 "  Loaded {} plots with {} trees",
                 inventory.num_plots(),
                 inventory.num_trees()
             );
 
-            let metrics = compute_stand_metrics(&inventory);
-            print_stand_summary(&metrics);
+            let metrics = in_units(
+                compute_stand_metrics(&inventory).with_max_sdi(config.analysis.max_sdi),
+                units,
+            );
+            print_stand_summary_with(&metrics, &bdft_unit);
 
             if species {
-                print_species_table(&metrics);
+                match top_species {
+                    Some(n) => print_species_table_top_with(&metrics, sort_species, n),
+                    None => print_species_table_with(&metrics, sort_species),
+                }
+            }
+
+            if let Some(path) = &group_by {
+                let groups = SpeciesGroups::load(path)?;
+                print_group_table(&compute_stand_metrics_grouped(&inventory, &groups));
             }
 
             if distribution {
-                let dist = DiameterDistribution::from_inventory(&inventory, diameter_class_width);
-                print_diameter_histogram(&dist);
+                let dist_options = DistributionOptions {
+                    class_width: diameter_class_width,
+                    min_dbh: distribution_min_dbh,
+                    status_filter: distribution_status,
+                    include_ingrowth_class: ingrowth_class,
+                };
+                let dist = DiameterDistribution::with_options(&inventory, &dist_options)?;
+                let options = HistogramOptions {
+                    bar_width: histogram_width,
+                    metric: histogram_metric,
+                    class_order: sort_classes,
+                };
+                print_diameter_histogram_in(&dist, &options, units);
+                if cumulative {
+                    print_cumulative_distribution_in(&dist, units);
+                }
+            }
+
+            if let Some(dir) = &svg_dir {
+                std::fs::create_dir_all(dir)?;
+                let palette = &SpeciesPalette::from_colors(config.species_colors.clone())?;
+                let dist = DiameterDistribution::with_width(&inventory, diameter_class_width);
+                let composition = dir.join("species_composition.svg");
+                let histogram = dir.join("diameter_distribution.svg");
+                let composition_svg = format_species_composition_svg(&metrics, palette);
+                let histogram_svg = format_diameter_histogram_svg(&inventory, &dist, palette);
+                io::write_atomic(&composition, |w| {
+                    Ok(w.write_all(composition_svg.as_bytes())?)
+                })?;
+                io::write_atomic(&histogram, |w| Ok(w.write_all(histogram_svg.as_bytes())?))?;
+                println!(
+                    "  {} Wrote {} and {}",
+                    "Charts:".green().bold(),
+                    composition.display(),
+                    histogram.display()
+                );
             }
 
             match SamplingStatistics::compute(&inventory, confidence) {
-                Ok(stats) => print_statistics_table(&stats),
+                Ok(stats) => {
+                    // println! ends the table's last line, so it reaches the
+                    // terminal before any warning on stderr
+                    println!(
+                        "{}",
+                        format_statistics_table_in(&stats, target_error, units)
+                    );
+                    let unreliable = stats.unreliable_metrics(max_sampling_error);
+                    if !unreliable.is_empty() {
+                        outcome = Outcome::Unreliable;
+                        eprintln!(
+                            "\n{}: sampling error exceeds {max_sampling_error:.1}%; \
+                             these estimates are unreliable, consider more plots:",
+                            "Warning".yellow().bold()
+                        );
+                        for (name, ci) in unreliable {
+                            eprintln!("  {name}: ±{:.1}%", ci.sampling_error_percent);
+                        }
+                    }
+                }
                 Err(e) => {
                     eprintln!("{}: {e}", "Warning".yellow());
                 }
@@ -261,7 +902,7 @@ fn main() -> Result<()> {
                 );
                 println!("{}", "=".repeat(72));
                 for (stand_id, sub_inv) in &stands {
-                    let sm = compute_stand_metrics(sub_inv);
+                    let sm = compute_stand_metrics(sub_inv).with_max_sdi(config.analysis.max_sdi);
                     println!(
                         "\n  {} ({} plots, {} trees)",
                         format!("Stand {stand_id}").bold(),
@@ -269,11 +910,11 @@ fn main() -> Result<()> {
                         sub_inv.num_trees()
                     );
                     println!(
-                        "    TPA: {:.1}  |  BA: {:.1} ft\u{00B2}/ac  |  QMD: {:.1}\"  |  Vol: {:.0} bd ft/ac",
+                        "    TPA: {:.1}  |  BA: {:.1} ft\u{00B2}/ac  |  QMD: {:.1}\"  |  Vol: {} {bdft_unit}/ac",
                         sm.total_tpa,
                         sm.total_basal_area,
                         sm.quadratic_mean_diameter,
-                        sm.total_volume_bdft
+                        bdft_unit.format(sm.total_volume_bdft)
                     );
                 }
             }
@@ -285,42 +926,14 @@ fn main() -> Result<()> {
             model,
             rate,
             capacity,
+            shape,
             mortality,
+            recruitment,
+            recruitment_dbh,
+            thin,
         } => {
-            let inventory = load_inventory(&input)?;
-
-            // Parse the model name into a GrowthModel with defaults, then
-            // override individual fields with explicit CLI arguments.
-            let mut growth_model: GrowthModel = model.parse().map_err(|e| {
-                anyhow::anyhow!("{e}")
-            })?;
-
-            // Apply CLI overrides for rate/capacity/mortality (only when explicitly provided)
-            match &mut growth_model {
-                GrowthModel::Exponential {
-                    annual_rate,
-                    mortality_rate,
-                } => {
-                    if let Some(r) = rate { *annual_rate = r; }
-                    if let Some(m) = mortality { *mortality_rate = m; }
-                }
-                GrowthModel::Logistic {
-                    annual_rate,
-                    carrying_capacity,
-                    mortality_rate,
-                } => {
-                    if let Some(r) = rate { *annual_rate = r; }
-                    if let Some(c) = capacity { *carrying_capacity = c; }
-                    if let Some(m) = mortality { *mortality_rate = m; }
-                }
-                GrowthModel::Linear {
-                    annual_increment,
-                    mortality_rate,
-                } => {
-                    if let Some(r) = rate { *annual_increment = r; }
-                    if let Some(m) = mortality { *mortality_rate = m; }
-                }
-            }
+            let inventory = load_inventory(&input, cli.encoding, &mapping, cli.expansion_kind)?;
+            let growth_model = build_growth_model(&model, rate, capacity, shape, mortality)?;
 
             println!(
                 "\n{}",
@@ -329,24 +942,142 @@ fn main() -> Result<()> {
                     .cyan()
             );
 
-            let projections = project_growth(&inventory, &growth_model, years)?;
-            print_growth_table(&projections);
+            let recruitment = recruitment.map(|annual_tpa| RecruitmentModel::Constant {
+                annual_tpa,
+                dbh: recruitment_dbh,
+            });
+            let projections = project_growth_with_thinning(
+                &inventory,
+                &growth_model,
+                &thin,
+                recruitment.as_ref(),
+                years,
+            )?;
+            print_growth_table_with(&projections, &bdft_unit);
+        }
+
+        Commands::Fit {
+            before,
+            after,
+            years,
+            model,
+            rate,
+            capacity,
+            shape,
+            mortality,
+        } => {
+            let growth_model = build_growth_model(&model, rate, capacity, shape, mortality)?;
+            let before = load_inventory(&before, cli.encoding, &mapping, cli.expansion_kind)?;
+            let after = load_inventory(&after, cli.encoding, &mapping, cli.expansion_kind)?;
+            let fit = model_fit(&growth_model, &before, &after, years)?;
+
+            println!(
+                "\n{}",
+                format!("Growth Model Fit: {model} over {years} years")
+                    .bold()
+                    .cyan()
+            );
+            println!("{}", "=".repeat(40));
+            println!("  Plots compared: {}", fit.num_plots);
+            println!("  RMSE BA/ac:     {:.2} sq ft", fit.rmse_ba);
+            println!("  RMSE Vol/ac:    {:.1} cu ft", fit.rmse_volume);
+            println!("  Bias BA/ac:     {:+.2} sq ft", fit.bias);
         }
 
         Commands::Convert {
             input,
             output,
             pretty,
+            metadata,
+            format,
         } => {
-            let inventory = load_inventory(&input)?;
-            save_inventory(&inventory, &output, pretty)?;
+            if input.is_dir() {
+                let ext = format.unwrap_or_else(|| "json".to_string()).to_lowercase();
+                let ext = ext.trim_start_matches('.');
+                std::fs::create_dir_all(&output)?;
+
+                let mut files: Vec<PathBuf> = std::fs::read_dir(&input)?
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|p| is_supported_inventory_file(p))
+                    .collect();
+                files.sort();
+
+                if files.is_empty() {
+                    anyhow::bail!(
+                        "No inventory files (.csv, .json, .xlsx) found in {}",
+                        input.display()
+                    );
+                }
 
-            println!(
-                "{} Converted {} -> {}",
-                "Success:".green().bold(),
-                input.display(),
-                output.display()
-            );
+                println!(
+                    "\n{}",
+                    format!("Batch Convert: {} files -> .{ext}", files.len())
+                        .bold()
+                        .cyan()
+                );
+
+                let mut converted = 0;
+                let mut failed = 0;
+
+                for file in &files {
+                    let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown");
+                    let out_path = output.join(format!("{stem}.{ext}"));
+                    match convert_file(
+                        file,
+                        &out_path,
+                        pretty,
+                        metadata,
+                        cli.encoding,
+                        &mapping,
+                        cli.expansion_kind,
+                    ) {
+                        Ok(()) => {
+                            println!(
+                                "  {} {} -> {}",
+                                "OK".green(),
+                                file.display(),
+                                out_path.display()
+                            );
+                            converted += 1;
+                        }
+                        Err(e) => {
+                            eprintln!("  {} {} — {e}", "FAIL".red(), file.display());
+                            failed += 1;
+                        }
+                    }
+                }
+
+                println!(
+                    "\n{} Converted {converted} files, {failed} failed. Output in {}",
+                    "Done.".green().bold(),
+                    output.display()
+                );
+
+                if failed > 0 {
+                    anyhow::bail!("{failed} file(s) failed during batch conversion");
+                }
+            } else {
+                if format.is_some() {
+                    anyhow::bail!("--format only applies when --input is a directory");
+                }
+                convert_file(
+                    &input,
+                    &output,
+                    pretty,
+                    metadata,
+                    cli.encoding,
+                    &mapping,
+                    cli.expansion_kind,
+                )?;
+
+                println!(
+                    "{} Converted {} -> {}",
+                    "Success:".green().bold(),
+                    input.display(),
+                    output.display()
+                );
+            }
         }
 
         Commands::AnalyzeBatch {
@@ -387,9 +1118,10 @@ fn main() -> Result<()> {
 
             for file in &files {
                 let name = file.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown");
-                match load_inventory(file) {
+                match load_inventory(file, cli.encoding, &mapping, cli.expansion_kind) {
                     Ok(inventory) => {
-                        let metrics = compute_stand_metrics(&inventory);
+                        let metrics = compute_stand_metrics(&inventory)
+                            .with_max_sdi(config.analysis.max_sdi);
                         let stats = SamplingStatistics::compute(&inventory, confidence).ok();
 
                         let report = serde_json::json!({
@@ -420,8 +1152,9 @@ fn main() -> Result<()> {
                         });
 
                         let out_path = output_dir.join(format!("{name}.json"));
-                        let content = serde_json::to_string_pretty(&report)?;
-                        std::fs::write(&out_path, content)?;
+                        io::write_atomic(&out_path, |w| {
+                            Ok(serde_json::to_writer_pretty(w, &report)?)
+                        })?;
 
                         println!("  {} {}", "OK".green(), file.display());
                         processed += 1;
@@ -444,35 +1177,170 @@ fn main() -> Result<()> {
             }
         }
 
-        Commands::Summary { input } => {
-            let inventory = load_inventory(&input)?;
+        Commands::Summary {
+            input,
+            acres,
+            units,
+            ..
+        } => {
+            let mut inventory = load_inventory(&input, cli.encoding, &mapping, cli.expansion_kind)?;
+            if acres.is_some() {
+                inventory.total_acres = acres;
+            }
+            let ba_unit = units.basal_area_unit();
+            let vol_unit = units.volume_unit();
+            let area_unit = units.area_unit();
+            let (density, per_area) = match units {
+                UnitSystem::Imperial => ("TPA", "ac"),
+                UnitSystem::Metric => ("Trees/ha", "ha"),
+            };
+            let imperial = units == UnitSystem::Imperial;
 
             println!("\n{}", "Quick Summary".bold().cyan());
             println!("{}", "=".repeat(40));
             println!("  Name:           {}", inventory.name);
             println!("  Plots:          {}", inventory.num_plots());
+            match inventory.measurement_date_range() {
+                Some((first, last)) if first == last => println!("  Measured:       {first}"),
+                Some((first, last)) => println!("  Measured:       {first} to {last}"),
+                None => {}
+            }
             println!("  Total Trees:    {}", inventory.num_trees());
             println!("  Species:        {}", inventory.species_list().len());
-            println!("  Mean TPA:       {:.1}", inventory.mean_tpa());
-            println!("  Mean BA/ac:     {:.1} sq ft", inventory.mean_basal_area());
             println!(
-                "  Mean Vol/ac:    {:.1} cu ft",
-                inventory.mean_volume_cuft()
+                "  {:<16}{:.1}",
+                format!("Mean {density}:"),
+                units.per_area(inventory.mean_tpa())
+            );
+            println!(
+                "  {:<16}{:.1} {ba_unit}",
+                format!("Mean BA/{per_area}:"),
+                units.basal_area_per_area(inventory.mean_basal_area())
             );
             println!(
-                "  Mean Vol/ac:    {:.0} bd ft",
-                inventory.mean_volume_bdft()
+                "  {:<16}{:.1} {vol_unit}",
+                format!("Mean Vol/{per_area}:"),
+                units.volume_per_area(inventory.mean_volume_cuft())
             );
+            // Board feet are a North American measure; metric output skips them
+            if imperial {
+                println!(
+                    "  Mean Vol/ac:    {} {bdft_unit}",
+                    bdft_unit.format(inventory.mean_volume_bdft())
+                );
+            }
+
+            let sampling = inventory.sampling_summary();
+            println!("\n  {}", "Sampling Intensity".bold());
+            println!(
+                "  Plot Area:      {:.2} {area_unit} on {} plots",
+                units.area(sampling.total_plot_acres),
+                sampling.num_plots
+            );
+            println!("  Trees Measured: {}", sampling.trees_measured);
+            let measured_ba = units.basal_area(sampling.measured_ba_sqft);
+            match sampling.measured_ba_per_plot_acre() {
+                Some(per_acre) => println!(
+                    "  Measured BA:    {measured_ba:.1} {ba_unit} ({:.1} {ba_unit}/{per_area} of plot area, {:.1} expanded)",
+                    units.basal_area_per_area(per_acre),
+                    units.basal_area_per_area(sampling.expanded_ba_per_acre)
+                ),
+                None => println!("  Measured BA:    {measured_ba:.1} {ba_unit}"),
+            }
+
+            if let Some(total_acres) = inventory.total_acres {
+                println!(
+                    "\n  {}",
+                    format!("Tract Totals ({:.1} {area_unit})", units.area(total_acres)).bold()
+                );
+                if let Some(stems) = inventory.total_stems() {
+                    println!("  Total Stems:    {stems:.0}");
+                }
+                if let Some(ba) = inventory.total_basal_area() {
+                    println!("  Total BA:       {:.0} {ba_unit}", units.basal_area(ba));
+                }
+                if let Some(vol) = inventory.total_standing_volume_cuft() {
+                    println!("  Total Volume:   {:.0} {vol_unit}", units.volume(vol));
+                }
+                if let Some(vol) = inventory.total_standing_volume_bdft().filter(|_| imperial) {
+                    println!("  Total Volume:   {} {bdft_unit}", bdft_unit.format(vol));
+                }
+            }
+        }
+
+        Commands::Harvest { input } => {
+            let inventory = load_inventory(&input, cli.encoding, &mapping, cli.expansion_kind)?;
+            print_harvest_table(&harvest_summary(&inventory));
+        }
+
+        Commands::QaPlots { input, k } => {
+            if k.is_nan() || k <= 0.0 {
+                anyhow::bail!("--k must be positive, got {k}");
+            }
+            let inventory = load_inventory(&input, cli.encoding, &mapping, cli.expansion_kind)?;
+            let flags = plot_qa_with(&inventory, k);
+            print_plot_qa_table(&flags);
+            println!(
+                "  {} of {} plots flagged",
+                flags
+                    .iter()
+                    .map(|f| f.plot_id)
+                    .collect::<std::collections::HashSet<_>>()
+                    .len(),
+                inventory.num_plots()
+            );
+            let missing = missing_tree_report(&inventory);
+            if missing.total_missing > 0 {
+                println!(
+                    "  {} of {} trees Missing ({:.1}%)",
+                    missing.total_missing,
+                    missing.total_trees,
+                    missing.missing_fraction * 100.0
+                );
+            }
+        }
+
+        Commands::Validate { input } => {
+            if !mapping.is_empty() {
+                anyhow::bail!("--map is not supported by validate");
+            }
+            let report = io::validate_file(&input, cli.encoding)?;
+            print_validation_table(&report);
+            if report.has_errors() {
+                anyhow::bail!(
+                    "{} has {} validation errors",
+                    input.display(),
+                    report.error_count()
+                );
+            }
+            if report.warning_count() > 0 {
+                outcome = Outcome::Warnings;
+            }
+        }
+
+        Commands::Template { output } => {
+            let ext = file_extension(&output);
+            match ext.as_str() {
+                "csv" => io::write_template_csv(&output)?,
+                "xlsx" => io::write_template_excel(&output)?,
+                _ => anyhow::bail!("Unsupported template format: .{ext}. Use .csv or .xlsx"),
+            }
+            println!("Template written to {}", output.display());
         }
 
         #[cfg(feature = "web")]
-        Commands::Serve { port, bind } => {
+        Commands::Serve { port, bind, db } => {
             let mut server_config = config;
             server_config.server.port = port;
             server_config.server.bind_address = bind;
+            if let Some(db) = db {
+                server_config.database.path = db;
+            }
 
             // Resolve relative database path relative to the executable's directory
-            if !std::path::Path::new(&server_config.database.path).is_absolute() {
+            let in_memory =
+                server_config.database.path == forest_inventory_analyzer::web::IN_MEMORY_DB_PATH;
+            if !in_memory && !std::path::Path::new(&server_config.database.path).is_absolute() {
                 if let Some(exe_dir) = std::env::current_exe()
                     .ok()
                     .and_then(|p| p.parent().map(|d| d.to_path_buf()))
@@ -489,5 +1357,5 @@ fn main() -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(outcome)
 }
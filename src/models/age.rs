@@ -0,0 +1,104 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Largest age range, as a fraction of mean age, for a stand to be classed
+/// as even-aged. Follows the common rule that an even-aged stand's ages fall
+/// within about 20% of its rotation age, using mean age as the rotation.
+pub const EVEN_AGED_MAX_RELATIVE_RANGE: f64 = 0.2;
+
+/// Heuristic age structure of a stand, from the spread of cored tree ages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgeStructure {
+    /// Ages span at most [`EVEN_AGED_MAX_RELATIVE_RANGE`] of the mean age
+    EvenAged,
+    /// Ages span more than [`EVEN_AGED_MAX_RELATIVE_RANGE`] of the mean age
+    UnevenAged,
+}
+
+impl fmt::Display for AgeStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgeStructure::EvenAged => write!(f, "Even-aged"),
+            AgeStructure::UnevenAged => write!(f, "Uneven-aged"),
+        }
+    }
+}
+
+/// Breast-height age statistics of cored live trees.
+///
+/// Ages are summarized per tree, without expansion factors: cored trees are
+/// a subsample, so weighting them by the stems they represent would let a
+/// few small trees dominate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgeSummary {
+    /// Number of trees with an age
+    pub count: usize,
+    pub mean: f64,
+    pub min: u32,
+    pub max: u32,
+    /// Sample standard deviation (n − 1); zero for a single tree
+    pub std_dev: f64,
+    pub structure: AgeStructure,
+}
+
+impl AgeSummary {
+    /// Summarize a set of ages, or `None` if there are none.
+    pub fn from_ages(ages: impl IntoIterator<Item = u32>) -> Option<Self> {
+        let ages: Vec<f64> = ages.into_iter().map(f64::from).collect();
+        if ages.is_empty() {
+            return None;
+        }
+        let n = ages.len() as f64;
+        let mean = ages.iter().sum::<f64>() / n;
+        let std_dev = if ages.len() > 1 {
+            (ages.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let min = ages.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = ages.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let structure = if mean > 0.0 && (max - min) / mean > EVEN_AGED_MAX_RELATIVE_RANGE {
+            AgeStructure::UnevenAged
+        } else {
+            AgeStructure::EvenAged
+        };
+        Some(Self {
+            count: ages.len(),
+            mean,
+            min: min as u32,
+            max: max as u32,
+            std_dev,
+            structure,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ages_statistics() {
+        let summary = AgeSummary::from_ages([40, 50, 60]).unwrap();
+        assert_eq!(summary.count, 3);
+        assert!((summary.mean - 50.0).abs() < 1e-9);
+        assert_eq!((summary.min, summary.max), (40, 60));
+        assert!((summary.std_dev - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_from_ages_empty_and_single() {
+        assert!(AgeSummary::from_ages([]).is_none());
+        let single = AgeSummary::from_ages([35]).unwrap();
+        assert_eq!(single.std_dev, 0.0);
+        assert_eq!(single.structure, AgeStructure::EvenAged);
+    }
+
+    #[test]
+    fn test_structure_display() {
+        assert_eq!(AgeStructure::EvenAged.to_string(), "Even-aged");
+        assert_eq!(AgeStructure::UnevenAged.to_string(), "Uneven-aged");
+    }
+}
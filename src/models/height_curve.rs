@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Breast height in feet, the height of a tree with zero DBH on a
+/// [`HeightCurve`].
+pub const BREAST_HEIGHT_FT: f64 = 4.5;
+
+/// Fewest measured heights a species needs before
+/// [`ForestInventory::impute_heights`](super::ForestInventory::impute_heights)
+/// fits a curve for it.
+pub const MIN_HEIGHTS_TO_IMPUTE: usize = 3;
+
+/// Range of the rate `b2` searched when fitting, per inch of DBH.
+const MIN_RATE: f64 = 1e-4;
+const MAX_RATE: f64 = 1.0;
+
+/// Grid points over the rate range before refining around the best one.
+const RATE_GRID: usize = 200;
+
+/// Height-diameter curve `H = 4.5 + b1·(1 − e^(−b2·DBH))`, with height in
+/// feet and DBH in inches.
+///
+/// With positive coefficients, which [`HeightCurve::fit`] guarantees,
+/// height rises with DBH towards an asymptote of `4.5 + b1`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeightCurve {
+    /// Height above breast height that large trees approach, in feet
+    pub b1: f64,
+    /// Rate of approach to the asymptote, per inch of DBH
+    pub b2: f64,
+}
+
+impl HeightCurve {
+    /// Predicted total height in feet of a tree of `dbh` inches.
+    pub fn predict(&self, dbh: f64) -> f64 {
+        BREAST_HEIGHT_FT + self.b1 * (1.0 - (-self.b2 * dbh).exp())
+    }
+
+    /// Least-squares fit to `(dbh, height)` pairs.
+    ///
+    /// For a given `b2` the best `b1` has a closed form, so the fit is a
+    /// one-dimensional search over `b2`: a log-spaced grid, then a
+    /// golden-section refinement around the best grid point. Returns `None`
+    /// for fewer than [`MIN_HEIGHTS_TO_IMPUTE`] pairs with positive values,
+    /// or if no curve with positive coefficients fits, e.g. when every
+    /// height is at or below breast height.
+    pub fn fit(points: &[(f64, f64)]) -> Option<Self> {
+        let points: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|&(d, h)| d > 0.0 && h > 0.0 && d.is_finite() && h.is_finite())
+            .collect();
+        if points.len() < MIN_HEIGHTS_TO_IMPUTE {
+            return None;
+        }
+
+        let step = (MAX_RATE / MIN_RATE).ln() / (RATE_GRID - 1) as f64;
+        let rate = |i: usize| MIN_RATE * (step * i as f64).exp();
+        let best = (0..RATE_GRID)
+            .min_by(|&a, &b| sse(&points, rate(a)).total_cmp(&sse(&points, rate(b))))?;
+
+        // Refine between the grid neighbours of the best point
+        let (mut lo, mut hi) = (
+            rate(best.saturating_sub(1)),
+            rate((best + 1).min(RATE_GRID - 1)),
+        );
+        let inv_phi = (5.0_f64.sqrt() - 1.0) / 2.0;
+        for _ in 0..60 {
+            let a = hi - inv_phi * (hi - lo);
+            let b = lo + inv_phi * (hi - lo);
+            if sse(&points, a) < sse(&points, b) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+        let b2 = (lo + hi) / 2.0;
+        let b1 = asymptote(&points, b2)?;
+        (b1 > 0.0 && b1.is_finite()).then_some(Self { b1, b2 })
+    }
+}
+
+/// The least-squares `b1` for rate `b2`, or `None` if it is undefined.
+fn asymptote(points: &[(f64, f64)], b2: f64) -> Option<f64> {
+    let (sxy, sxx) = points.iter().fold((0.0, 0.0), |(sxy, sxx), &(d, h)| {
+        let x = 1.0 - (-b2 * d).exp();
+        (sxy + x * (h - BREAST_HEIGHT_FT), sxx + x * x)
+    });
+    (sxx > 0.0).then(|| sxy / sxx)
+}
+
+/// Sum of squared residuals with rate `b2` and its least-squares `b1`.
+fn sse(points: &[(f64, f64)], b2: f64) -> f64 {
+    let Some(b1) = asymptote(points, b2) else {
+        return f64::INFINITY;
+    };
+    let curve = HeightCurve { b1, b2 };
+    points
+        .iter()
+        .map(|&(d, h)| (h - curve.predict(d)).powi(2))
+        .sum()
+}
+
+/// What [`ForestInventory::impute_heights`](super::ForestInventory::impute_heights)
+/// filled in, by species code.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HeightImputation {
+    /// Number of heights imputed for each species
+    pub imputed: BTreeMap<String, usize>,
+    /// Number of trees left without a height for each species that had too
+    /// few measured heights, or no usable curve
+    pub skipped: BTreeMap<String, usize>,
+    /// The curve fitted for each species that had heights imputed
+    pub curves: BTreeMap<String, HeightCurve>,
+}
+
+impl HeightImputation {
+    /// Total number of heights imputed across all species.
+    pub fn total_imputed(&self) -> usize {
+        self.imputed.values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_recovers_known_curve() {
+        let truth = HeightCurve {
+            b1: 150.0,
+            b2: 0.05,
+        };
+        let points: Vec<(f64, f64)> = (1..=12)
+            .map(|i| {
+                let dbh = f64::from(i) * 3.0;
+                (dbh, truth.predict(dbh))
+            })
+            .collect();
+        let fit = HeightCurve::fit(&points).unwrap();
+        assert!((fit.b1 - truth.b1).abs() < 0.5, "{fit:?}");
+        assert!((fit.b2 - truth.b2).abs() < 1e-3, "{fit:?}");
+    }
+
+    #[test]
+    fn test_fit_needs_three_usable_points() {
+        assert!(HeightCurve::fit(&[(10.0, 60.0), (20.0, 90.0)]).is_none());
+        assert!(HeightCurve::fit(&[(10.0, 60.0), (20.0, 90.0), (0.0, 30.0)]).is_none());
+        // Trees no taller than breast height give no positive asymptote
+        assert!(HeightCurve::fit(&[(1.0, 4.0), (2.0, 4.5), (3.0, 3.0)]).is_none());
+    }
+}
@@ -1,8 +1,130 @@
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use super::{Plot, Species};
+use super::plot::is_iso_date;
+use super::{
+    king_site_index, AgeSummary, HeightCurve, HeightImputation, Plot, Species, Tree,
+    ValidationIssue, VolumeModel, DEFAULT_PLOT_SIZE_ACRES, DOMINANT_TPA, SITE_INDEX_SPECIES_CODE,
+};
+use crate::error::ForestError;
+
+/// What a tree's `expansion_factor` means.
+///
+/// Getting this wrong scales every per-acre metric by the inverse plot size,
+/// e.g. 5x for 1/5-acre plots, so count-based data must be marked as such.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpansionKind {
+    /// Trees per acre represented by the tree (the default, and what all
+    /// readers assume)
+    #[default]
+    PerAcre,
+    /// Number of trees the record stands for on the plot; divided by the
+    /// plot's `plot_size_acres` to get trees per acre
+    CountOnPlot,
+}
+
+impl fmt::Display for ExpansionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpansionKind::PerAcre => write!(f, "per-acre"),
+            ExpansionKind::CountOnPlot => write!(f, "count"),
+        }
+    }
+}
+
+impl FromStr for ExpansionKind {
+    type Err = ForestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "per-acre" | "per_acre" | "peracre" => Ok(ExpansionKind::PerAcre),
+            "count" | "count-on-plot" | "count_on_plot" | "countonplot" => {
+                Ok(ExpansionKind::CountOnPlot)
+            }
+            _ => Err(ForestError::ParseError(format!(
+                "Unknown expansion kind: '{s}'. Use: per-acre or count"
+            ))),
+        }
+    }
+}
+
+/// A systematic change to every tree's measurements, for what-if analysis
+/// with [`ForestInventory::adjusted`], e.g. testing the effect of a DBH tape
+/// that reads 0.3 inch high.
+///
+/// The default changes nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AdjustSpec {
+    /// Inches added to every DBH (negative to subtract)
+    pub dbh_delta: f64,
+    /// Factor every measured height is multiplied by
+    pub height_scale: f64,
+    /// Factor every expansion factor is multiplied by
+    pub ef_scale: f64,
+}
+
+impl Default for AdjustSpec {
+    fn default() -> Self {
+        Self {
+            dbh_delta: 0.0,
+            height_scale: 1.0,
+            ef_scale: 1.0,
+        }
+    }
+}
+
+/// How [`ForestInventory::renumber`] orders plots and trees before numbering
+/// them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenumberPolicy {
+    /// Number plots and trees in their current order
+    #[default]
+    KeepOrder,
+    /// Sort plots by their old id, and trees within each plot by their old
+    /// id, before numbering
+    SortById,
+}
+
+/// How intensively an inventory was sampled, for reviewing cruise design;
+/// see [`ForestInventory::sampling_summary`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SamplingSummary {
+    pub num_plots: usize,
+    /// Sum of every plot's `plot_size_acres`
+    pub total_plot_acres: f64,
+    /// Tree records of any status
+    pub trees_measured: usize,
+    /// Basal area of the live trees actually measured, before expansion (sq ft)
+    pub measured_ba_sqft: f64,
+    /// Mean basal area per acre after expansion, as in
+    /// [`ForestInventory::mean_basal_area`]
+    pub expanded_ba_per_acre: f64,
+}
+
+impl SamplingSummary {
+    /// Measured basal area spread over the sampled plot area (sq ft/acre),
+    /// or `None` when no plot area was recorded.
+    ///
+    /// On fixed-area plots whose expansion factors match their sizes this
+    /// equals `expanded_ba_per_acre`; a large gap means the expansion factors
+    /// imply more (or less) area than was sampled.
+    pub fn measured_ba_per_plot_acre(&self) -> Option<f64> {
+        (self.total_plot_acres > 0.0).then(|| self.measured_ba_sqft / self.total_plot_acres)
+    }
+}
+
+/// Version of the tree-row fields hashed by [`ForestInventory::content_hash`].
+///
+/// It is hashed first, so bumping it when the field list changes makes older
+/// checksums stop matching instead of silently meaning something else.
+pub const CONTENT_HASH_VERSION: u32 = 2;
 
 /// A complete forest inventory dataset.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,20 +133,183 @@ pub struct ForestInventory {
     pub name: String,
     /// Total area in acres
     pub total_acres: Option<f64>,
+    /// How tree expansion factors are interpreted; defaults to per-acre
+    #[serde(default)]
+    pub expansion_kind: ExpansionKind,
     /// All plots in the inventory
     pub plots: Vec<Plot>,
 }
 
 impl ForestInventory {
-    /// Create a new empty inventory.
+    /// Create a new empty inventory with per-acre expansion factors.
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
             total_acres: None,
+            expansion_kind: ExpansionKind::PerAcre,
             plots: Vec::new(),
         }
     }
 
+    /// This inventory with expansion factors in trees per acre.
+    ///
+    /// Borrows `self` when [`ExpansionKind::PerAcre`]; otherwise returns a copy
+    /// with each tree's expansion factor divided by its plot size. Analysis
+    /// functions call this first, so they are correct for either kind.
+    pub fn to_per_acre(&self) -> Cow<'_, ForestInventory> {
+        match self.expansion_kind {
+            ExpansionKind::PerAcre => Cow::Borrowed(self),
+            ExpansionKind::CountOnPlot => {
+                let mut inventory = self.clone();
+                for plot in &mut inventory.plots {
+                    let factor = plot.per_acre_factor(ExpansionKind::CountOnPlot);
+                    for tree in &mut plot.trees {
+                        tree.expansion_factor *= factor;
+                    }
+                }
+                inventory.expansion_kind = ExpansionKind::PerAcre;
+                Cow::Owned(inventory)
+            }
+        }
+    }
+
+    /// Build an inventory from a flat list of trees, grouped into plots by `plot_id`.
+    ///
+    /// Plots are ordered by `plot_id` and keep their trees in input order. No
+    /// plot attributes are known, so every plot is [`DEFAULT_PLOT_SIZE_ACRES`]
+    /// in size with no slope, aspect, elevation or stand. Per-acre metrics
+    /// depend only on expansion factors, so they are unaffected by this
+    /// assumption.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use forest_inventory_analyzer::{ForestInventory, Species, Tree, TreeStatus};
+    ///
+    /// let tree = |plot_id| Tree {
+    ///     tree_id: 1, plot_id,
+    ///     species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
+    ///     dbh: 14.0, height: Some(90.0), crown_ratio: None,
+    ///     status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///     decay_class: None,
+    /// };
+    /// let inv = ForestInventory::from_trees("Simulated", vec![tree(2), tree(1), tree(2)]);
+    /// assert_eq!(inv.num_plots(), 2);
+    /// assert_eq!(inv.plots[0].plot_id, 1);
+    /// ```
+    pub fn from_trees(name: impl Into<String>, trees: impl IntoIterator<Item = Tree>) -> Self {
+        let mut plots: BTreeMap<u32, Vec<Tree>> = BTreeMap::new();
+        for tree in trees {
+            plots.entry(tree.plot_id).or_default().push(tree);
+        }
+
+        let mut inventory = Self::new(name);
+        inventory.plots = plots
+            .into_iter()
+            .map(|(plot_id, trees)| Plot {
+                plot_id,
+                plot_size_acres: DEFAULT_PLOT_SIZE_ACRES,
+                slope_percent: None,
+                aspect_degrees: None,
+                elevation_ft: None,
+                trees,
+                stand_id: None,
+                source: None,
+                measurement_date: None,
+                latitude: None,
+                longitude: None,
+                stratum: None,
+            })
+            .collect();
+        inventory
+    }
+
+    /// Validate the whole inventory. Returns the first `ForestError::ValidationError` found.
+    ///
+    /// Delegates to `validate_all()` so both paths share the same checks.
+    pub fn validate(&self) -> Result<(), ForestError> {
+        if let Some(issue) = self.validate_all().into_iter().next() {
+            return Err(ForestError::ValidationError(format!(
+                "Plot {}, Tree {}: {}",
+                issue.plot_id, issue.tree_id, issue.message
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate every plot and tree, collecting all issues.
+    ///
+    /// Runs [`Plot::validate_all`] on each plot, then checks that span
+    /// records: plot IDs that appear more than once (noting when the copies
+    /// disagree on plot size), tree IDs repeated within a plot, and trees
+    /// whose `plot_id` differs from the plot holding them. `row_index` counts
+    /// trees across the whole inventory in plot order, as they would appear in
+    /// a flat export; plot-level issues use the row of the plot's first tree.
+    pub fn validate_all(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut plot_sizes: HashMap<u32, f64> = HashMap::new();
+        let mut row_offset = 0;
+
+        for plot in &self.plots {
+            issues.extend(plot.validate_all().into_iter().map(|mut issue| {
+                issue.row_index += row_offset;
+                issue
+            }));
+
+            if let Some(&first_size) = plot_sizes.get(&plot.plot_id) {
+                let message = if first_size == plot.plot_size_acres {
+                    format!("plot {} appears more than once", plot.plot_id)
+                } else {
+                    format!(
+                        "plot {} appears more than once with different plot sizes ({} vs {} acres)",
+                        plot.plot_id, first_size, plot.plot_size_acres
+                    )
+                };
+                issues.push(ValidationIssue {
+                    plot_id: plot.plot_id,
+                    tree_id: 0,
+                    row_index: row_offset,
+                    field: Cow::Borrowed("plot_id"),
+                    message: Cow::Owned(message),
+                });
+            } else {
+                plot_sizes.insert(plot.plot_id, plot.plot_size_acres);
+            }
+
+            let mut tree_ids = HashSet::new();
+            for (i, tree) in plot.trees.iter().enumerate() {
+                if tree.plot_id != plot.plot_id {
+                    issues.push(ValidationIssue {
+                        plot_id: plot.plot_id,
+                        tree_id: tree.tree_id,
+                        row_index: row_offset + i,
+                        field: Cow::Borrowed("plot_id"),
+                        message: Cow::Owned(format!(
+                            "tree records plot {} but is stored in plot {}",
+                            tree.plot_id, plot.plot_id
+                        )),
+                    });
+                }
+                if !tree_ids.insert(tree.tree_id) {
+                    issues.push(ValidationIssue {
+                        plot_id: plot.plot_id,
+                        tree_id: tree.tree_id,
+                        row_index: row_offset + i,
+                        field: Cow::Borrowed("tree_id"),
+                        message: Cow::Owned(format!(
+                            "tree {} appears more than once in plot {}",
+                            tree.tree_id, plot.plot_id
+                        )),
+                    });
+                }
+            }
+
+            row_offset += plot.trees.len();
+        }
+
+        issues
+    }
+
     /// Get all unique species across the inventory.
     ///
     /// Uses `HashSet` for O(n) deduplication instead of O(n log n) sort+dedup,
@@ -42,6 +327,152 @@ impl ForestInventory {
         species
     }
 
+    /// Earliest and latest plot measurement dates, or `None` if no plot has
+    /// a valid `measurement_date`. Malformed dates are ignored.
+    pub fn measurement_date_range(&self) -> Option<(&str, &str)> {
+        let mut dates = self
+            .plots
+            .iter()
+            .filter_map(|p| p.measurement_date.as_deref())
+            .filter(|date| is_iso_date(date));
+        let first = dates.next()?;
+        // YYYY-MM-DD strings sort chronologically
+        Some(dates.fold((first, first), |(min, max), date| {
+            (min.min(date), max.max(date))
+        }))
+    }
+
+    /// Trees whose species name disagrees with the most common name for
+    /// their species code, e.g. "Douglas Fir" among mostly "Douglas-fir".
+    ///
+    /// [`species_list`](Self::species_list) deduplicates by code and shows
+    /// the first name it meets, so such spellings go unnoticed in reports.
+    /// One `species_name` issue is returned per disagreeing tree, with
+    /// `row_index` counted as in [`validate_all`](Self::validate_all). These
+    /// are warnings; see
+    /// [`canonicalize_species_names`](Self::canonicalize_species_names).
+    pub fn species_name_issues(&self) -> Vec<ValidationIssue> {
+        let canonical = canonical_species_names(self.trees().map(|t| &t.species));
+        let mut issues = Vec::new();
+        let mut row_index = 0;
+        for plot in &self.plots {
+            for tree in &plot.trees {
+                if let Some(issue) = species_name_issue(
+                    &canonical,
+                    &tree.species,
+                    plot.plot_id,
+                    tree.tree_id,
+                    row_index,
+                ) {
+                    issues.push(issue);
+                }
+                row_index += 1;
+            }
+        }
+        issues
+    }
+
+    /// Rename every species to the most frequent name recorded for its code
+    /// (ties go to the name seen first), so each code has a single name.
+    pub fn canonicalize_species_names(&mut self) {
+        let canonical: HashMap<String, String> =
+            canonical_species_names(self.trees().map(|t| &t.species))
+                .into_iter()
+                .map(|(code, name)| (code.to_string(), name.to_string()))
+                .collect();
+        for tree in self.plots.iter_mut().flat_map(|p| p.trees.iter_mut()) {
+            if let Some(name) = canonical.get(&tree.species.code) {
+                if tree.species.common_name != *name {
+                    tree.species.common_name = name.clone();
+                }
+            }
+        }
+    }
+
+    /// Fill in missing heights of live trees from a [`HeightCurve`] fitted
+    /// per species to the live trees that have heights, so their volume is
+    /// counted.
+    ///
+    /// Species with fewer than
+    /// [`MIN_HEIGHTS_TO_IMPUTE`](super::MIN_HEIGHTS_TO_IMPUTE) measured
+    /// heights, or whose heights no curve fits, are left untouched and
+    /// reported in [`HeightImputation::skipped`]. Measured heights are never
+    /// changed. Dead trees are left alone, as snags often have broken tops.
+    pub fn impute_heights(&mut self) -> HeightImputation {
+        let mut measured: HashMap<String, Vec<(f64, f64)>> = HashMap::new();
+        for tree in self.trees().filter(|t| t.is_live()) {
+            if let Some(height) = tree.height {
+                measured
+                    .entry(tree.species.code.clone())
+                    .or_default()
+                    .push((tree.dbh, height));
+            }
+        }
+
+        let mut curves: HashMap<String, Option<HeightCurve>> = HashMap::new();
+        let mut summary = HeightImputation::default();
+        for tree in self.plots.iter_mut().flat_map(|p| p.trees.iter_mut()) {
+            if !tree.is_live() || tree.height.is_some() {
+                continue;
+            }
+            let code = &tree.species.code;
+            let curve = *curves
+                .entry(code.clone())
+                .or_insert_with(|| measured.get(code).and_then(|m| HeightCurve::fit(m)));
+            match curve {
+                Some(curve) => {
+                    tree.height = Some(curve.predict(tree.dbh));
+                    *summary.imputed.entry(code.clone()).or_default() += 1;
+                }
+                None => *summary.skipped.entry(code.clone()).or_default() += 1,
+            }
+        }
+        summary.curves = curves
+            .into_iter()
+            .filter_map(|(code, curve)| Some((code, curve?)))
+            .collect();
+        summary
+    }
+
+    fn trees(&self) -> impl Iterator<Item = &Tree> {
+        self.plots.iter().flat_map(|p| p.trees.iter())
+    }
+
+    /// Breast-height age statistics and age structure of cored live trees,
+    /// or `None` if no live tree has an age.
+    pub fn age_summary(&self) -> Option<AgeSummary> {
+        AgeSummary::from_ages(
+            self.plots
+                .iter()
+                .flat_map(|p| p.live_trees())
+                .filter_map(|t| t.age),
+        )
+    }
+
+    /// Douglas-fir site index in feet at breast-height `base_age` (typically
+    /// [`DEFAULT_SITE_INDEX_BASE_AGE`](super::DEFAULT_SITE_INDEX_BASE_AGE)),
+    /// averaged over plots, or `None` if no live Douglas-fir has both an age
+    /// and a height.
+    ///
+    /// Only trees coded [`SITE_INDEX_SPECIES_CODE`] are site trees. On each
+    /// plot, the dominant height and age are the expansion-weighted means
+    /// over the largest-DBH [`DOMINANT_TPA`] of them per acre that have both,
+    /// and are projected to `base_age` along King's Douglas-fir curve;
+    /// see [`king_site_index`](super::king_site_index). A stand already at
+    /// `base_age` gets its dominant height.
+    pub fn site_index(&self, base_age: u32) -> Option<f64> {
+        let inventory = self.to_per_acre();
+        let indices: Vec<f64> = inventory
+            .plots
+            .iter()
+            .filter_map(|plot| {
+                let (height, age) = dominant_height_and_age(plot)?;
+                king_site_index(age, height, base_age)
+            })
+            .collect();
+        (!indices.is_empty()).then(|| indices.iter().sum::<f64>() / indices.len() as f64)
+    }
+
     /// Total number of plots.
     pub fn num_plots(&self) -> usize {
         self.plots.len()
@@ -52,6 +483,23 @@ impl ForestInventory {
         self.plots.iter().map(|p| p.trees.len()).sum()
     }
 
+    /// Plot area sampled, trees measured and measured versus expanded basal
+    /// area, aggregated over every plot.
+    pub fn sampling_summary(&self) -> SamplingSummary {
+        SamplingSummary {
+            num_plots: self.num_plots(),
+            total_plot_acres: self.plots.iter().map(|p| p.plot_size_acres).sum(),
+            trees_measured: self.num_trees(),
+            measured_ba_sqft: self
+                .plots
+                .iter()
+                .flat_map(|p| p.live_trees())
+                .map(|t| t.basal_area_sqft())
+                .sum(),
+            expanded_ba_per_acre: self.mean_basal_area(),
+        }
+    }
+
     /// Mean trees per acre across all plots.
     ///
     /// # Examples
@@ -68,8 +516,14 @@ impl ForestInventory {
     ///         species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
     ///         dbh: 14.0, height: Some(90.0), crown_ratio: None,
     ///         status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///         decay_class: None,
     ///     }],
     ///     stand_id: None,
+    ///     source: None,
+    ///     measurement_date: None,
+    ///     latitude: None,
+    ///     longitude: None,
+    ///     stratum: None,
     /// });
     /// assert!((inv.mean_tpa() - 5.0).abs() < 0.001);
     /// ```
@@ -93,8 +547,14 @@ impl ForestInventory {
     ///         species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
     ///         dbh: 14.0, height: Some(90.0), crown_ratio: None,
     ///         status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///         decay_class: None,
     ///     }],
     ///     stand_id: None,
+    ///     source: None,
+    ///     measurement_date: None,
+    ///     latitude: None,
+    ///     longitude: None,
+    ///     stratum: None,
     /// });
     /// assert!(inv.mean_basal_area() > 0.0);
     /// ```
@@ -107,12 +567,66 @@ impl ForestInventory {
         self.mean_of(Plot::volume_cuft_per_acre)
     }
 
+    /// Mean cubic foot volume per acre using any [`VolumeModel`].
+    pub fn mean_volume_cuft_with(&self, model: &dyn VolumeModel) -> f64 {
+        self.mean_of(|p| p.volume_cuft_per_acre_with(model))
+    }
+
     /// Mean board foot volume per acre across all plots.
     pub fn mean_volume_bdft(&self) -> f64 {
         self.mean_of(Plot::volume_bdft_per_acre)
     }
 
-    /// Compute the mean of a per-plot metric across all plots.
+    /// Mean board foot volume per acre using any [`VolumeModel`].
+    pub fn mean_volume_bdft_with(&self, model: &dyn VolumeModel) -> f64 {
+        self.mean_of(|p| p.volume_bdft_per_acre_with(model))
+    }
+
+    /// Total live stems on the tract: mean TPA × `total_acres`.
+    ///
+    /// Returns `None` when `total_acres` is not set.
+    pub fn total_stems(&self) -> Option<f64> {
+        self.expand_to_tract(self.mean_tpa())
+    }
+
+    /// Total basal area on the tract (sq ft): mean BA/acre × `total_acres`.
+    ///
+    /// Returns `None` when `total_acres` is not set.
+    pub fn total_basal_area(&self) -> Option<f64> {
+        self.expand_to_tract(self.mean_basal_area())
+    }
+
+    /// Total standing cubic foot volume: mean cu ft/acre × `total_acres`.
+    ///
+    /// Returns `None` when `total_acres` is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use forest_inventory_analyzer::ForestInventory;
+    ///
+    /// let mut inv = ForestInventory::new("Example");
+    /// assert!(inv.total_standing_volume_cuft().is_none());
+    /// inv.total_acres = Some(40.0);
+    /// assert_eq!(inv.total_standing_volume_cuft(), Some(0.0));
+    /// ```
+    pub fn total_standing_volume_cuft(&self) -> Option<f64> {
+        self.expand_to_tract(self.mean_volume_cuft())
+    }
+
+    /// Total standing board foot volume: mean bd ft/acre × `total_acres`.
+    ///
+    /// Returns `None` when `total_acres` is not set.
+    pub fn total_standing_volume_bdft(&self) -> Option<f64> {
+        self.expand_to_tract(self.mean_volume_bdft())
+    }
+
+    fn expand_to_tract(&self, per_acre: f64) -> Option<f64> {
+        self.total_acres.map(|acres| per_acre * acres)
+    }
+
+    /// Compute the mean of a per-plot metric across all plots, converted to
+    /// per-acre according to `expansion_kind`.
     ///
     /// Returns `0.0` for an empty inventory. All plots are equally weighted
     /// (not weighted by `plot_size_acres`).
@@ -120,7 +634,12 @@ impl ForestInventory {
         if self.plots.is_empty() {
             return 0.0;
         }
-        let sum: f64 = self.plots.iter().map(f).sum();
+        // Every metric averaged here is linear in the expansion factors
+        let sum: f64 = self
+            .plots
+            .iter()
+            .map(|p| f(p) * p.per_acre_factor(self.expansion_kind))
+            .sum();
         sum / self.plots.len() as f64
     }
 
@@ -153,6 +672,7 @@ impl ForestInventory {
             .into_iter()
             .map(|(sid, plots)| {
                 let mut inv = ForestInventory::new(format!("{} - Stand {}", self.name, sid));
+                inv.expansion_kind = self.expansion_kind;
                 inv.plots = plots;
                 (sid, inv)
             })
@@ -160,12 +680,295 @@ impl ForestInventory {
         result.sort_by_key(|(sid, _)| *sid);
         result
     }
+
+    /// A copy of the inventory holding only the plots for which `keep`
+    /// returns true, for analyzing part of a cruise.
+    ///
+    /// The name and expansion kind are kept. The total acreage is dropped,
+    /// since the area the remaining plots represent is not known.
+    pub fn filter_plots(&self, mut keep: impl FnMut(&Plot) -> bool) -> ForestInventory {
+        let mut inv = ForestInventory::new(self.name.clone());
+        inv.expansion_kind = self.expansion_kind;
+        inv.plots = self.plots.iter().filter(|p| keep(p)).cloned().collect();
+        inv
+    }
+
+    /// A copy of the inventory holding only the plots whose ids are in
+    /// `ids`, in their original order; see [`ForestInventory::filter_plots`].
+    /// Ids with no matching plot are ignored.
+    pub fn select_plots(&self, ids: &[u32]) -> ForestInventory {
+        self.filter_plots(|p| ids.contains(&p.plot_id))
+    }
+
+    /// A copy of the inventory holding only the plots located inside the
+    /// box from (`min_lat`, `min_lon`) to (`max_lat`, `max_lon`), edges
+    /// included; see [`ForestInventory::filter_plots`]. Plots without both
+    /// coordinates are excluded.
+    pub fn within_bbox(
+        &self,
+        min_lat: f64,
+        min_lon: f64,
+        max_lat: f64,
+        max_lon: f64,
+    ) -> ForestInventory {
+        self.filter_plots(|p| match (p.latitude, p.longitude) {
+            (Some(lat), Some(lon)) => {
+                (min_lat..=max_lat).contains(&lat) && (min_lon..=max_lon).contains(&lon)
+            }
+            _ => false,
+        })
+    }
+
+    /// Combine several inventories, e.g. stands cruised separately, into one.
+    ///
+    /// Every plot keeps its trees and is labelled with the name of the
+    /// inventory it came from in [`Plot::source`] (unless it already has a
+    /// source from an earlier merge), so merged results can still be split by
+    /// origin. Plot ids are kept where they are unique; a plot whose id was
+    /// already taken by an earlier inventory gets the next id above every id
+    /// in the inputs, and its trees' `plot_id` is updated to match.
+    ///
+    /// The total acreage is the sum of the inputs', or `None` if any is
+    /// unknown. Inputs with different [`ExpansionKind`]s are converted to
+    /// per-acre expansion factors first.
+    pub fn merge<'a>(
+        name: impl Into<String>,
+        inventories: impl IntoIterator<Item = &'a ForestInventory>,
+    ) -> ForestInventory {
+        let inventories: Vec<&ForestInventory> = inventories.into_iter().collect();
+        let mut merged = ForestInventory::new(name);
+        let Some(first) = inventories.first() else {
+            return merged;
+        };
+        let same_kind = inventories
+            .iter()
+            .all(|inv| inv.expansion_kind == first.expansion_kind);
+        if same_kind {
+            merged.expansion_kind = first.expansion_kind;
+        }
+        merged.total_acres = inventories.iter().map(|inv| inv.total_acres).sum();
+
+        let mut next_id = inventories
+            .iter()
+            .flat_map(|inv| inv.plots.iter().map(|p| p.plot_id))
+            .max()
+            .map_or(1, |max| max + 1);
+        let mut used_ids = HashSet::new();
+        for inventory in inventories {
+            let inventory = if same_kind {
+                Cow::Borrowed(inventory)
+            } else {
+                inventory.to_per_acre()
+            };
+            for plot in &inventory.plots {
+                let mut plot = plot.clone();
+                if !used_ids.insert(plot.plot_id) {
+                    plot.plot_id = next_id;
+                    used_ids.insert(next_id);
+                    next_id += 1;
+                    for tree in &mut plot.trees {
+                        tree.plot_id = plot.plot_id;
+                    }
+                }
+                if plot.source.is_none() {
+                    plot.source = Some(inventory.name.clone());
+                }
+                merged.plots.push(plot);
+            }
+        }
+        merged
+    }
+
+    /// A copy of the inventory with `spec` applied to every tree, dead or
+    /// alive, so metrics can be recomputed for the adjusted stand.
+    ///
+    /// DBH is floored at zero so a negative delta cannot produce negative
+    /// diameters; missing heights stay missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use forest_inventory_analyzer::{AdjustSpec, ForestInventory, Species, Tree, TreeStatus};
+    ///
+    /// let tree = Tree {
+    ///     tree_id: 1, plot_id: 1,
+    ///     species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
+    ///     dbh: 14.0, height: Some(90.0), crown_ratio: None,
+    ///     status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///     decay_class: None,
+    /// };
+    /// let inv = ForestInventory::from_trees("Cruise", vec![tree]);
+    /// let spec = AdjustSpec { dbh_delta: -0.3, ..AdjustSpec::default() };
+    /// assert_eq!(inv.adjusted(spec).plots[0].trees[0].dbh, 13.7);
+    /// ```
+    pub fn adjusted(&self, spec: AdjustSpec) -> ForestInventory {
+        let mut inventory = self.clone();
+        for tree in inventory.plots.iter_mut().flat_map(|p| p.trees.iter_mut()) {
+            tree.dbh = (tree.dbh + spec.dbh_delta).max(0.0);
+            tree.height = tree.height.map(|h| h * spec.height_scale);
+            tree.expansion_factor *= spec.ef_scale;
+        }
+        inventory
+    }
+
+    /// Reassign plot ids 1, 2, ... and tree ids 1, 2, ... within each plot,
+    /// closing gaps and collisions left by merging or editing.
+    ///
+    /// Each tree's `plot_id` is updated to its plot's new id. Only the order
+    /// chosen by `policy` and the ids change; no measurement is touched.
+    pub fn renumber(&mut self, policy: RenumberPolicy) {
+        if policy == RenumberPolicy::SortById {
+            self.plots.sort_by_key(|p| p.plot_id);
+        }
+        for (plot, plot_id) in self.plots.iter_mut().zip(1..) {
+            plot.plot_id = plot_id;
+            if policy == RenumberPolicy::SortById {
+                plot.trees.sort_by_key(|t| t.tree_id);
+            }
+            for (tree, tree_id) in plot.trees.iter_mut().zip(1..) {
+                tree.tree_id = tree_id;
+                tree.plot_id = plot_id;
+            }
+        }
+    }
+
+    /// SHA-256 checksum of the tree rows, as a lowercase hex string.
+    ///
+    /// Each tree contributes a fixed list of fields, versioned by
+    /// [`CONTENT_HASH_VERSION`]: its plot's ID, size, slope, aspect,
+    /// elevation, stand ID, source, measurement date, latitude, longitude and
+    /// stratum, then its own ID, species code and name, DBH, height, crown
+    /// ratio, status, expansion factor, age, defect and decay class. The
+    /// expansion kind is hashed once up front. Editing any of them changes
+    /// the hash; the inventory name does not.
+    pub fn content_hash(&self) -> Result<String, ForestError> {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("content-hash-v{CONTENT_HASH_VERSION}\n"));
+        hasher.update(format!("expansion_kind: {}\n", self.expansion_kind));
+        for plot in &self.plots {
+            let plot_fields = (
+                plot.plot_id,
+                plot.plot_size_acres,
+                plot.slope_percent,
+                plot.aspect_degrees,
+                plot.elevation_ft,
+                plot.stand_id,
+                &plot.source,
+                &plot.measurement_date,
+                plot.latitude,
+                plot.longitude,
+                &plot.stratum,
+            );
+            for tree in &plot.trees {
+                let tree_fields = (
+                    tree.tree_id,
+                    &tree.species.code,
+                    &tree.species.common_name,
+                    tree.dbh,
+                    tree.height,
+                    tree.crown_ratio,
+                    &tree.status,
+                    tree.expansion_factor,
+                    tree.age,
+                    tree.defect,
+                    tree.decay_class,
+                );
+                serde_json::to_writer(&mut hasher, &(&plot_fields, tree_fields))?;
+                hasher.update(b"\n");
+            }
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+}
+
+/// Expansion-weighted mean height and age of the largest-DBH
+/// [`DOMINANT_TPA`] live Douglas-fir per acre on `plot` with both, counting
+/// only part of the tree that crosses the limit. `None` if no tree has both.
+fn dominant_height_and_age(plot: &Plot) -> Option<(f64, f64)> {
+    let mut site_trees: Vec<(&Tree, f64, u32)> = plot
+        .live_trees()
+        .into_iter()
+        .filter(|t| t.species.code == SITE_INDEX_SPECIES_CODE)
+        .filter_map(|t| Some((t, t.height?, t.age?)))
+        .filter(|(t, _, age)| t.expansion_factor > 0.0 && *age > 0)
+        .collect();
+    site_trees.sort_by(|a, b| b.0.dbh.total_cmp(&a.0.dbh));
+
+    let (mut tpa, mut height_sum, mut age_sum) = (0.0, 0.0, 0.0);
+    for (tree, height, age) in site_trees {
+        let weight = tree.expansion_factor.min(DOMINANT_TPA - tpa);
+        tpa += weight;
+        height_sum += height * weight;
+        age_sum += f64::from(age) * weight;
+        if tpa >= DOMINANT_TPA {
+            break;
+        }
+    }
+    (tpa > 0.0).then(|| (height_sum / tpa, age_sum / tpa))
+}
+
+/// The most frequent name for each species code recorded under more than
+/// one name; ties go to the name seen first. Codes with a single name are
+/// omitted.
+pub(crate) fn canonical_species_names<'a>(
+    species: impl IntoIterator<Item = &'a Species>,
+) -> HashMap<&'a str, &'a str> {
+    // Per code, each name with its count, in order of first appearance
+    let mut names: HashMap<&str, Vec<(&str, usize)>> = HashMap::new();
+    for sp in species {
+        let counts = names.entry(sp.code.as_str()).or_default();
+        match counts.iter_mut().find(|(name, _)| *name == sp.common_name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((sp.common_name.as_str(), 1)),
+        }
+    }
+    names
+        .into_iter()
+        .filter(|(_, counts)| counts.len() > 1)
+        .map(|(code, counts)| {
+            // max_by_key keeps the last maximum, so scan in reverse
+            let (name, _) = counts
+                .iter()
+                .rev()
+                .max_by_key(|(_, count)| *count)
+                .expect("at least two names");
+            (code, *name)
+        })
+        .collect()
+}
+
+/// A `species_name` warning if `species` is not named as in `canonical`.
+pub(crate) fn species_name_issue(
+    canonical: &HashMap<&str, &str>,
+    species: &Species,
+    plot_id: u32,
+    tree_id: u32,
+    row_index: usize,
+) -> Option<ValidationIssue> {
+    let name = canonical.get(species.code.as_str())?;
+    (species.common_name != *name).then(|| ValidationIssue {
+        plot_id,
+        tree_id,
+        row_index,
+        field: Cow::Borrowed("species_name"),
+        message: Cow::Owned(format!(
+            "species {} is named '{}' here but '{}' on most trees",
+            species.code, species.common_name, name
+        )),
+    })
+}
+
+/// Collects trees into an unnamed inventory; see [`ForestInventory::from_trees`].
+impl FromIterator<Tree> for ForestInventory {
+    fn from_iter<I: IntoIterator<Item = Tree>>(iter: I) -> Self {
+        Self::from_trees(String::new(), iter)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Tree, TreeStatus};
+    use crate::models::{AgeStructure, TreeStatus, DEFAULT_SITE_INDEX_BASE_AGE};
 
     fn make_species(code: &str, name: &str) -> Species {
         Species {
@@ -186,6 +989,7 @@ mod tests {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -198,6 +1002,11 @@ mod tests {
             elevation_ft: None,
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
@@ -223,6 +1032,45 @@ mod tests {
         inv
     }
 
+    /// `sample_inventory` with the given ages assigned to its trees in order.
+    fn aged_inventory(ages: &[Option<u32>]) -> ForestInventory {
+        let mut inv = sample_inventory();
+        let trees = inv.plots.iter_mut().flat_map(|p| p.trees.iter_mut());
+        for (tree, age) in trees.zip(ages) {
+            tree.age = *age;
+        }
+        inv
+    }
+
+    #[test]
+    fn test_age_summary_narrow_range_is_even_aged() {
+        // The dead tree's age is ignored
+        let inv = aged_inventory(&[Some(58), Some(62), Some(60), Some(150)]);
+        let summary = inv.age_summary().unwrap();
+        assert_eq!(summary.count, 3);
+        assert!((summary.mean - 60.0).abs() < 1e-9);
+        assert_eq!((summary.min, summary.max), (58, 62));
+        assert_eq!(summary.structure, AgeStructure::EvenAged);
+    }
+
+    #[test]
+    fn test_age_summary_wide_range_is_uneven_aged() {
+        let inv = aged_inventory(&[Some(25), Some(140), Some(80), None]);
+        let summary = inv.age_summary().unwrap();
+        assert_eq!((summary.min, summary.max), (25, 140));
+        assert!(summary.std_dev > 50.0);
+        assert_eq!(summary.structure, AgeStructure::UnevenAged);
+    }
+
+    #[test]
+    fn test_age_summary_without_ages() {
+        assert!(sample_inventory().age_summary().is_none());
+        // Only the dead tree is cored
+        assert!(aged_inventory(&[None, None, None, Some(70)])
+            .age_summary()
+            .is_none());
+    }
+
     #[test]
     fn test_new_inventory() {
         let inv = ForestInventory::new("My Inventory");
@@ -231,6 +1079,42 @@ mod tests {
         assert!(inv.plots.is_empty());
     }
 
+    #[test]
+    fn test_from_trees_groups_by_plot() {
+        let expected = sample_inventory();
+        // Interleave plots so grouping, not input order, determines the layout
+        let trees = vec![
+            expected.plots[1].trees[0].clone(),
+            expected.plots[0].trees[0].clone(),
+            expected.plots[1].trees[1].clone(),
+            expected.plots[0].trees[1].clone(),
+        ];
+        let inv = ForestInventory::from_trees("Test", trees);
+        assert_eq!(inv.num_plots(), 2);
+        assert_eq!(inv.num_trees(), 4);
+        assert_eq!(inv.plots[0].plot_id, 1);
+        assert!(inv
+            .plots
+            .iter()
+            .all(|p| p.plot_size_acres == DEFAULT_PLOT_SIZE_ACRES));
+        assert!((inv.mean_tpa() - 7.5).abs() < 0.001);
+        assert_eq!(
+            inv.content_hash().unwrap(),
+            expected.content_hash().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_collect_trees_into_inventory() {
+        let df = make_species("DF", "Douglas Fir");
+        let inv: ForestInventory = (1..=3)
+            .map(|plot_id| make_tree(plot_id, df.clone(), 12.0, TreeStatus::Live))
+            .collect();
+        assert!(inv.name.is_empty());
+        assert_eq!(inv.num_plots(), 3);
+        assert!((inv.mean_tpa() - 5.0).abs() < 0.001);
+    }
+
     #[test]
     fn test_new_inventory_string_conversion() {
         let inv = ForestInventory::new(String::from("Owned String"));
@@ -288,6 +1172,200 @@ mod tests {
         assert!(species.iter().any(|s| s.code == "DF"));
     }
 
+    #[test]
+    fn test_species_name_issues_and_canonicalize() {
+        let df = |name: &str| make_species("DF", name);
+        let mut inv = ForestInventory::new("Spellings");
+        inv.plots.push(make_plot_with_trees(
+            1,
+            vec![
+                make_tree(1, df("Douglas Fir"), 12.0, TreeStatus::Live),
+                make_tree(1, df("Douglas-fir"), 14.0, TreeStatus::Live),
+                make_tree(
+                    1,
+                    make_species("WH", "Western Hemlock"),
+                    10.0,
+                    TreeStatus::Live,
+                ),
+            ],
+        ));
+        inv.plots.push(make_plot_with_trees(
+            2,
+            vec![make_tree(2, df("Douglas-fir"), 16.0, TreeStatus::Dead)],
+        ));
+
+        let issues = inv.species_name_issues();
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].field, "species_name");
+        assert_eq!((issues[0].plot_id, issues[0].row_index), (1, 0));
+        assert!(issues[0].message.contains("'Douglas-fir'"));
+        assert!(inv
+            .validate_all()
+            .iter()
+            .all(|issue| issue.field != "species_name"));
+
+        inv.canonicalize_species_names();
+        assert!(inv.species_name_issues().is_empty());
+        assert!(inv
+            .plots
+            .iter()
+            .flat_map(|p| &p.trees)
+            .filter(|t| t.species.code == "DF")
+            .all(|t| t.species.common_name == "Douglas-fir"));
+        assert_eq!(inv.species_list()[0].common_name, "Douglas-fir");
+        assert_eq!(inv.species_list()[1].common_name, "Western Hemlock");
+    }
+
+    /// Douglas-fir on one plot with heights on every other tree, and two
+    /// cedars without heights.
+    fn partly_measured_inventory() -> ForestInventory {
+        let df = make_species("DF", "Douglas Fir");
+        let wrc = make_species("WRC", "Western Red Cedar");
+        let mut trees: Vec<Tree> = (1..=10)
+            .map(|i| {
+                let dbh = f64::from(i) * 3.0;
+                let mut tree = make_tree(1, df.clone(), dbh, TreeStatus::Live);
+                tree.tree_id = i;
+                tree.height = (i % 2 == 1).then(|| 4.5 + 140.0 * (1.0 - (-0.04 * dbh).exp()));
+                tree
+            })
+            .collect();
+        let mut cedar = make_tree(1, wrc, 14.0, TreeStatus::Live);
+        cedar.height = None;
+        trees.push(Tree {
+            tree_id: 11,
+            ..cedar.clone()
+        });
+        cedar.height = Some(70.0);
+        trees.push(Tree {
+            tree_id: 12,
+            ..cedar
+        });
+        let mut snag = make_tree(1, df, 20.0, TreeStatus::Dead);
+        snag.height = None;
+        trees.push(Tree {
+            tree_id: 13,
+            ..snag
+        });
+
+        let mut inv = ForestInventory::new("Heights");
+        inv.plots.push(make_plot_with_trees(1, trees));
+        inv
+    }
+
+    #[test]
+    fn test_impute_heights_fills_missing_live_heights() {
+        let mut inv = partly_measured_inventory();
+        let before = inv.clone();
+        let summary = inv.impute_heights();
+
+        assert_eq!(summary.imputed.get("DF"), Some(&5));
+        assert_eq!(summary.total_imputed(), 5);
+        assert!(summary.curves.contains_key("DF"));
+        // One cedar height is too few to fit a curve
+        assert_eq!(summary.skipped.get("WRC"), Some(&1));
+        assert!(!summary.curves.contains_key("WRC"));
+
+        for (tree, orig) in inv.plots[0].trees.iter().zip(&before.plots[0].trees) {
+            match (orig.height, orig.species.code.as_str(), orig.is_live()) {
+                // Measured heights are kept as they were
+                (Some(h), _, _) => assert_eq!(tree.height, Some(h)),
+                (None, "DF", true) => assert!(tree.height.unwrap() > 0.0),
+                (None, _, _) => assert_eq!(tree.height, None),
+            }
+        }
+
+        // Imputed heights rise with DBH
+        let imputed: Vec<f64> = inv.plots[0]
+            .trees
+            .iter()
+            .zip(&before.plots[0].trees)
+            .filter(|(_, orig)| orig.height.is_none() && orig.species.code == "DF")
+            .filter_map(|(tree, _)| tree.height)
+            .collect();
+        assert!(imputed.windows(2).all(|w| w[1] > w[0]), "{imputed:?}");
+    }
+
+    #[test]
+    fn test_impute_heights_leaves_measured_inventory_alone() {
+        let mut inv = sample_inventory();
+        let summary = inv.impute_heights();
+        assert_eq!(summary, HeightImputation::default());
+        assert!(inv.trees().all(|t| t.height == Some(80.0)));
+    }
+
+    #[test]
+    fn test_site_index_at_base_age_is_dominant_height() {
+        let df = make_species("DF", "Douglas Fir");
+        let trees = [(20.0, 120.0), (18.0, 110.0), (10.0, 70.0)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (dbh, height))| {
+                let mut tree = make_tree(1, df.clone(), dbh, TreeStatus::Live);
+                tree.tree_id = i as u32 + 1;
+                tree.height = Some(height);
+                tree.age = Some(50);
+                tree.expansion_factor = 20.0;
+                tree
+            })
+            .collect();
+        let mut inv = ForestInventory::new("Site");
+        inv.plots.push(make_plot_with_trees(1, trees));
+
+        // The two largest trees make up the dominant 40 TPA
+        let si = inv.site_index(DEFAULT_SITE_INDEX_BASE_AGE).unwrap();
+        assert!((si - 115.0).abs() < 1e-9, "{si}");
+
+        // At an older base age the same stand is taller
+        assert!(inv.site_index(100).unwrap() > si);
+    }
+
+    #[test]
+    fn test_site_index_needs_age_and_height() {
+        let mut inv = sample_inventory();
+        assert_eq!(inv.site_index(DEFAULT_SITE_INDEX_BASE_AGE), None);
+
+        // A younger stand of the same height is on a better site
+        for tree in inv.plots.iter_mut().flat_map(|p| p.trees.iter_mut()) {
+            tree.age = Some(30);
+        }
+        let si = inv.site_index(DEFAULT_SITE_INDEX_BASE_AGE).unwrap();
+        assert!(si > 80.0, "{si}");
+    }
+
+    #[test]
+    fn test_site_index_uses_douglas_fir_only() {
+        let mut inv = sample_inventory();
+        for tree in inv.plots.iter_mut().flat_map(|p| p.trees.iter_mut()) {
+            tree.age = Some(30);
+        }
+        let si = inv.site_index(DEFAULT_SITE_INDEX_BASE_AGE).unwrap();
+
+        // Making the cedars taller does not change a Douglas-fir site index
+        for tree in inv.plots.iter_mut().flat_map(|p| p.trees.iter_mut()) {
+            if tree.species.code == "WRC" {
+                tree.height = Some(150.0);
+            }
+        }
+        assert_eq!(inv.site_index(DEFAULT_SITE_INDEX_BASE_AGE), Some(si));
+
+        inv.plots
+            .iter_mut()
+            .flat_map(|p| p.trees.iter_mut())
+            .for_each(|t| t.species = make_species("WRC", "Western Red Cedar"));
+        assert_eq!(inv.site_index(DEFAULT_SITE_INDEX_BASE_AGE), None);
+    }
+
+    #[test]
+    fn test_canonical_species_name_tie_goes_to_first() {
+        let species = [
+            make_species("DF", "Douglas Fir"),
+            make_species("DF", "Douglas-fir"),
+        ];
+        let canonical = canonical_species_names(&species);
+        assert_eq!(canonical.get("DF"), Some(&"Douglas Fir"));
+    }
+
     #[test]
     fn test_species_list_empty() {
         let inv = ForestInventory::new("Empty");
@@ -318,6 +1396,34 @@ mod tests {
         assert!(ba > 0.0);
     }
 
+    #[test]
+    fn test_sampling_summary() {
+        let inv = sample_inventory();
+        let summary = inv.sampling_summary();
+        assert_eq!(summary.num_plots, 2);
+        assert_eq!(summary.trees_measured, 4);
+        assert!((summary.total_plot_acres - 0.4).abs() < 1e-12);
+
+        // Unexpanded sum over the live trees; the dead 8" tree is left out
+        let ba = |dbh: f64| std::f64::consts::PI * (dbh / 2.0).powi(2) / 144.0;
+        let measured = ba(16.0) + ba(12.0) + ba(18.0);
+        assert!((summary.measured_ba_sqft - measured).abs() < 1e-12);
+        assert!((summary.expanded_ba_per_acre - inv.mean_basal_area()).abs() < 1e-12);
+        // Expansion factors of 5 match the 1/5-acre plots
+        let per_plot_acre = summary.measured_ba_per_plot_acre().unwrap();
+        assert!((per_plot_acre - summary.expanded_ba_per_acre).abs() < 1e-9);
+
+        let mut mixed = sample_inventory();
+        mixed.plots[1].plot_size_acres = 0.1;
+        let summary = mixed.sampling_summary();
+        let plot_acres: f64 = mixed.plots.iter().map(|p| p.plot_size_acres).sum();
+        assert_eq!(summary.total_plot_acres, plot_acres);
+
+        let empty = ForestInventory::new("Empty").sampling_summary();
+        assert_eq!(empty.trees_measured, 0);
+        assert!(empty.measured_ba_per_plot_acre().is_none());
+    }
+
     #[test]
     fn test_mean_basal_area_empty() {
         let inv = ForestInventory::new("Empty");
@@ -350,6 +1456,29 @@ mod tests {
         assert_eq!(inv.mean_volume_bdft(), 0.0);
     }
 
+    #[test]
+    fn test_tract_totals_multiply_by_acres() {
+        let mut inv = sample_inventory();
+        assert!(inv.total_stems().is_none());
+        assert!(inv.total_basal_area().is_none());
+        assert!(inv.total_standing_volume_cuft().is_none());
+        assert!(inv.total_standing_volume_bdft().is_none());
+
+        inv.total_acres = Some(40.0);
+        let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9;
+        assert!(close(inv.total_stems(), inv.mean_tpa() * 40.0));
+        assert!(close(inv.total_basal_area(), inv.mean_basal_area() * 40.0));
+        assert!(close(
+            inv.total_standing_volume_cuft(),
+            inv.mean_volume_cuft() * 40.0
+        ));
+        assert!(close(
+            inv.total_standing_volume_bdft(),
+            inv.mean_volume_bdft() * 40.0
+        ));
+        assert!(inv.total_standing_volume_cuft().unwrap() > 0.0);
+    }
+
     #[test]
     fn test_inventory_json_roundtrip() {
         let inv = sample_inventory();
@@ -373,6 +1502,49 @@ mod tests {
         assert!((inv.mean_basal_area() - plot_ba).abs() < 0.001);
     }
 
+    #[test]
+    fn test_select_and_filter_plots() {
+        let mut inv = sample_inventory();
+        inv.total_acres = Some(40.0);
+
+        let selected = inv.select_plots(&[2, 7]);
+        assert_eq!(selected.name, "Test");
+        assert_eq!(selected.num_plots(), 1);
+        assert_eq!(selected.plots[0].plot_id, 2);
+        assert_eq!(selected.num_trees(), 2);
+        assert_eq!(selected.total_acres, None);
+        assert_eq!(inv.select_plots(&[]).num_plots(), 0);
+
+        let with_cedar = inv.filter_plots(|p| p.trees.iter().any(|t| t.species.code == "WRC"));
+        assert_eq!(with_cedar.num_plots(), 1);
+        assert_eq!(with_cedar.plots[0].plot_id, 1);
+        assert_eq!(inv.num_plots(), 2);
+    }
+
+    #[test]
+    fn test_within_bbox_keeps_located_plots_inside_the_box() {
+        let mut inv = ForestInventory::new("Located");
+        for (id, coords) in [
+            (1, Some((45.52, -122.68))),
+            (2, Some((46.10, -122.68))),
+            (3, Some((45.40, -121.90))),
+            (4, None),
+            (5, Some((45.00, -123.00))),
+        ] {
+            let mut plot = make_plot_with_trees(id, vec![]);
+            plot.latitude = coords.map(|(lat, _)| lat);
+            plot.longitude = coords.map(|(_, lon)| lon);
+            inv.plots.push(plot);
+        }
+        inv.plots[3].latitude = Some(45.5);
+
+        let inside = inv.within_bbox(45.0, -123.0, 46.0, -122.0);
+        let ids: Vec<u32> = inside.plots.iter().map(|p| p.plot_id).collect();
+        assert_eq!(ids, vec![1, 5]);
+        assert_eq!(inside.name, "Located");
+        assert_eq!(inv.within_bbox(0.0, 0.0, 1.0, 1.0).num_plots(), 0);
+    }
+
     #[test]
     fn test_stands_returns_empty_when_no_stand_ids() {
         let inv = sample_inventory();
@@ -425,4 +1597,314 @@ mod tests {
         assert!(sub_inv.mean_tpa() > 0.0);
         assert!(sub_inv.mean_basal_area() > 0.0);
     }
+
+    #[test]
+    fn test_adjusted_dbh_increases_basal_area_predictably() {
+        let inv = sample_inventory();
+        let spec = AdjustSpec {
+            dbh_delta: 0.3,
+            ..AdjustSpec::default()
+        };
+        let adjusted = inv.adjusted(spec);
+
+        // BA = 0.005454 * DBH² * EF per live tree, averaged over 2 plots
+        let expected: f64 = [16.3_f64, 12.3, 18.3]
+            .iter()
+            .map(|d| 0.005454 * d * d * 5.0)
+            .sum::<f64>()
+            / 2.0;
+        assert!((adjusted.mean_basal_area() - expected).abs() < 0.01);
+        assert!(adjusted.mean_basal_area() > inv.mean_basal_area());
+        assert_eq!(adjusted.mean_tpa(), inv.mean_tpa());
+        // The original is untouched
+        assert_eq!(inv.plots[0].trees[0].dbh, 16.0);
+    }
+
+    #[test]
+    fn test_adjusted_scales_heights_and_expansion() {
+        let inv = sample_inventory();
+        assert_eq!(
+            inv.adjusted(AdjustSpec::default()).content_hash().unwrap(),
+            inv.content_hash().unwrap()
+        );
+
+        let adjusted = inv.adjusted(AdjustSpec {
+            dbh_delta: -20.0,
+            height_scale: 0.5,
+            ef_scale: 2.0,
+        });
+        let tree = &adjusted.plots[0].trees[0];
+        assert_eq!(tree.dbh, 0.0);
+        assert_eq!(tree.height, Some(40.0));
+        assert_eq!(tree.expansion_factor, 10.0);
+        assert_eq!(adjusted.mean_tpa(), 2.0 * inv.mean_tpa());
+    }
+
+    #[test]
+    fn test_renumber_makes_ids_contiguous() {
+        let df = make_species("DF", "Douglas Fir");
+        // DBH records the old tree id, to follow trees through the renumbering
+        let tree = |plot_id, tree_id: u32| Tree {
+            tree_id,
+            ..make_tree(plot_id, df.clone(), 10.0 + f64::from(tree_id), TreeStatus::Live)
+        };
+        let mut inv = ForestInventory::new("Gappy");
+        inv.plots.push(make_plot_with_trees(7, vec![tree(7, 12), tree(7, 3)]));
+        inv.plots.push(make_plot_with_trees(2, vec![tree(2, 5)]));
+        // A stale plot_id on a tree, e.g. from a hand edit
+        inv.plots.push(make_plot_with_trees(2, vec![tree(9, 1), tree(2, 1)]));
+        let original = inv.clone();
+
+        inv.renumber(RenumberPolicy::KeepOrder);
+        let ids: Vec<(u32, Vec<u32>)> = inv
+            .plots
+            .iter()
+            .map(|p| (p.plot_id, p.trees.iter().map(|t| t.tree_id).collect()))
+            .collect();
+        assert_eq!(ids, vec![(1, vec![1, 2]), (2, vec![1]), (3, vec![1, 2])]);
+        for plot in &inv.plots {
+            assert!(plot.trees.iter().all(|t| t.plot_id == plot.plot_id));
+        }
+        assert_eq!(inv.mean_basal_area(), original.mean_basal_area());
+        assert_eq!(inv.plots[0].trees[0].dbh, 22.0);
+
+        let mut sorted = original;
+        sorted.renumber(RenumberPolicy::SortById);
+        // The old plot 7 moves last, with its old tree 3 before old tree 12
+        let dbhs: Vec<Vec<f64>> = sorted
+            .plots
+            .iter()
+            .map(|p| p.trees.iter().map(|t| t.dbh).collect())
+            .collect();
+        assert_eq!(dbhs, vec![vec![15.0], vec![11.0, 11.0], vec![13.0, 22.0]]);
+        assert_eq!(sorted.plots[2].trees[0].tree_id, 1);
+    }
+
+    #[test]
+    fn test_merge_remaps_colliding_plot_ids() {
+        let df = make_species("DF", "Douglas Fir");
+        let mut north = ForestInventory::new("North");
+        north.total_acres = Some(40.0);
+        north.plots.push(make_plot_with_trees(
+            1,
+            vec![make_tree(1, df.clone(), 14.0, TreeStatus::Live)],
+        ));
+        let mut south = ForestInventory::new("South");
+        south.total_acres = Some(60.0);
+        south.plots.push(make_plot_with_trees(
+            1,
+            vec![
+                make_tree(1, df.clone(), 20.0, TreeStatus::Live),
+                make_tree(1, df, 22.0, TreeStatus::Live),
+            ],
+        ));
+
+        let merged = ForestInventory::merge("Both", [&north, &south]);
+        assert_eq!(merged.name, "Both");
+        assert_eq!(merged.total_acres, Some(100.0));
+        assert_eq!(merged.num_plots(), 2);
+        assert_eq!(merged.num_trees(), 3);
+
+        let (first, second) = (&merged.plots[0], &merged.plots[1]);
+        assert_eq!(first.plot_id, 1);
+        assert_eq!(first.source.as_deref(), Some("North"));
+        assert_eq!(first.trees[0].dbh, 14.0);
+        assert_eq!(second.plot_id, 2);
+        assert_eq!(second.source.as_deref(), Some("South"));
+        assert_eq!(second.trees.len(), 2);
+        assert!(second.trees.iter().all(|t| t.plot_id == 2));
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_source_and_converts_mixed_kinds() {
+        let df = make_species("DF", "Douglas Fir");
+        let mut counted = ForestInventory::new("Counted");
+        counted.expansion_kind = ExpansionKind::CountOnPlot;
+        counted.plots.push(make_plot_with_trees(
+            3,
+            vec![make_tree(3, df.clone(), 14.0, TreeStatus::Live)],
+        ));
+        let mut labelled = make_plot_with_trees(5, vec![make_tree(5, df, 14.0, TreeStatus::Live)]);
+        labelled.source = Some("Earlier".to_string());
+        let mut per_acre = ForestInventory::new("PerAcre");
+        per_acre.plots.push(labelled);
+
+        let merged = ForestInventory::merge("Mixed", [&counted, &per_acre]);
+        assert_eq!(merged.expansion_kind, ExpansionKind::PerAcre);
+        assert_eq!(merged.total_acres, None);
+        // A count of 5 on a 0.2-acre plot is 25 trees per acre
+        assert!((merged.plots[0].trees[0].expansion_factor - 25.0).abs() < 1e-9);
+        assert_eq!(merged.plots[1].plot_id, 5);
+        assert_eq!(merged.plots[1].source.as_deref(), Some("Earlier"));
+        assert_eq!(ForestInventory::merge("Empty", []).num_plots(), 0);
+    }
+
+    #[test]
+    fn test_content_hash_identical_inventories_match() {
+        let a = sample_inventory();
+        let mut b = sample_inventory();
+        b.name = "Renamed".to_string();
+        assert_eq!(a.content_hash().unwrap(), b.content_hash().unwrap());
+        assert_eq!(a.content_hash().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_content_hash_is_lowercase_hex() {
+        let hash = sample_inventory().content_hash().unwrap();
+        assert!(hash
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)));
+    }
+
+    #[test]
+    fn test_content_hash_changes_on_dbh_edit() {
+        let a = sample_inventory();
+        let mut b = sample_inventory();
+        b.plots[0].trees[0].dbh += 0.1;
+        assert_ne!(a.content_hash().unwrap(), b.content_hash().unwrap());
+    }
+
+    #[test]
+    fn test_content_hash_changes_on_slope_edit() {
+        let a = sample_inventory();
+        let mut b = sample_inventory();
+        b.plots[0].slope_percent = Some(b.plots[0].slope_percent.unwrap_or(0.0) + 5.0);
+        assert_ne!(a.content_hash().unwrap(), b.content_hash().unwrap());
+    }
+
+    #[test]
+    fn test_content_hash_covers_every_persisted_field() {
+        let a = sample_inventory();
+        let edits: [fn(&mut ForestInventory); 9] = [
+            |inv| inv.expansion_kind = ExpansionKind::CountOnPlot,
+            |inv| inv.plots[0].aspect_degrees = Some(271.0),
+            |inv| inv.plots[0].elevation_ft = Some(1200.0),
+            |inv| inv.plots[0].stand_id = Some(42),
+            |inv| inv.plots[0].source = Some("Re-cruise".to_string()),
+            |inv| inv.plots[0].measurement_date = Some("2024-06-01".to_string()),
+            |inv| inv.plots[0].latitude = Some(44.0),
+            |inv| inv.plots[0].stratum = Some("North".to_string()),
+            |inv| inv.plots[0].trees[0].decay_class = Some(3),
+        ];
+        for (i, edit) in edits.iter().enumerate() {
+            let mut b = sample_inventory();
+            edit(&mut b);
+            assert_ne!(
+                a.content_hash().unwrap(),
+                b.content_hash().unwrap(),
+                "edit {i}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expansion_kind_defaults_to_per_acre() {
+        assert_eq!(ExpansionKind::default(), ExpansionKind::PerAcre);
+        let inv: ForestInventory =
+            serde_json::from_str(r#"{"name":"Old","total_acres":null,"plots":[]}"#).unwrap();
+        assert_eq!(inv.expansion_kind, ExpansionKind::PerAcre);
+    }
+
+    #[test]
+    fn test_expansion_kind_from_str() {
+        assert_eq!(
+            "per-acre".parse::<ExpansionKind>().unwrap(),
+            ExpansionKind::PerAcre
+        );
+        assert_eq!(
+            "Count".parse::<ExpansionKind>().unwrap(),
+            ExpansionKind::CountOnPlot
+        );
+        assert_eq!(
+            "count_on_plot".parse::<ExpansionKind>().unwrap(),
+            ExpansionKind::CountOnPlot
+        );
+        assert!("hectare".parse::<ExpansionKind>().is_err());
+    }
+
+    #[test]
+    fn test_count_on_plot_scales_tpa_by_inverse_plot_size() {
+        let per_acre = sample_inventory();
+        let mut count = sample_inventory();
+        count.expansion_kind = ExpansionKind::CountOnPlot;
+
+        // 0.2-acre plots: each counted tree represents 5 trees per acre
+        assert!((count.mean_tpa() - 5.0 * per_acre.mean_tpa()).abs() < 1e-9);
+        assert!((count.mean_basal_area() - 5.0 * per_acre.mean_basal_area()).abs() < 1e-9);
+
+        let converted = count.to_per_acre();
+        assert_eq!(converted.expansion_kind, ExpansionKind::PerAcre);
+        assert_eq!(converted.plots[0].trees[0].expansion_factor, 25.0);
+        assert!(matches!(per_acre.to_per_acre(), Cow::Borrowed(_)));
+
+        let by_stand = {
+            let mut inv = count.clone();
+            inv.plots[0].stand_id = Some(1);
+            inv.stands()
+        };
+        assert_eq!(by_stand[0].1.expansion_kind, ExpansionKind::CountOnPlot);
+    }
+
+    /// `sample_inventory` with unique tree IDs within each plot.
+    fn numbered_inventory() -> ForestInventory {
+        let mut inv = sample_inventory();
+        for plot in &mut inv.plots {
+            for (i, tree) in plot.trees.iter_mut().enumerate() {
+                tree.tree_id = i as u32 + 1;
+            }
+        }
+        inv
+    }
+
+    #[test]
+    fn test_validate_all_sample_inventory_is_clean() {
+        let inv = numbered_inventory();
+        assert!(inv.validate_all().is_empty());
+        assert!(inv.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_all_reports_every_problem() {
+        let mut inv = numbered_inventory();
+        // A bad DBH on the second plot's first tree (row 2 overall)...
+        inv.plots[1].trees[0].dbh = -1.0;
+        // ...and a third plot reusing plot 1's ID with a different size
+        let df = make_species("DF", "Douglas Fir");
+        let mut copy = make_plot_with_trees(1, vec![make_tree(1, df, 10.0, TreeStatus::Live)]);
+        copy.plot_size_acres = 0.1;
+        inv.plots.push(copy);
+
+        let issues = inv.validate_all();
+        assert_eq!(issues.len(), 2, "{issues:?}");
+
+        assert_eq!(issues[0].field, "dbh");
+        assert_eq!(issues[0].plot_id, 2);
+        assert_eq!(issues[0].row_index, 2);
+
+        assert_eq!(issues[1].field, "plot_id");
+        assert_eq!(issues[1].row_index, 4);
+        let message = &issues[1].message;
+        assert!(
+            message.contains("different plot sizes (0.2 vs 0.1 acres)"),
+            "{message}"
+        );
+
+        let err = inv.validate().unwrap_err().to_string();
+        assert!(err.contains("DBH must be positive"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_all_flags_duplicate_and_misfiled_trees() {
+        let mut inv = numbered_inventory();
+        inv.plots[0].trees[1].tree_id = inv.plots[0].trees[0].tree_id;
+        inv.plots[1].trees[1].plot_id = 7;
+
+        let issues = inv.validate_all();
+        let fields: Vec<(&str, usize)> = issues
+            .iter()
+            .map(|i| (i.field.as_ref(), i.row_index))
+            .collect();
+        assert_eq!(fields, vec![("tree_id", 1), ("plot_id", 3)]);
+        assert!(issues[1].message.contains("records plot 7"));
+    }
 }
@@ -1,14 +1,43 @@
 //! Core domain types for forest inventory data.
 //!
 //! Key types: [`ForestInventory`] (top-level container), [`Plot`], [`Tree`], [`Species`],
-//! [`TreeStatus`], and [`VolumeEquation`].
+//! [`TreeStatus`], and [`VolumeEquation`] / [`VolumeEquationSet`], which implement the
+//! pluggable [`VolumeModel`] trait. [`UnitSystem`] converts results for reporting.
 
+mod age;
+mod height_curve;
 mod inventory;
 mod plot;
+mod site_index;
+mod taper;
 mod tree;
+mod units;
 mod volume;
 
-pub use inventory::ForestInventory;
-pub use plot::Plot;
-pub use tree::{Species, Tree, TreeStatus, ValidationIssue};
-pub use volume::VolumeEquation;
+pub use age::{AgeStructure, AgeSummary, EVEN_AGED_MAX_RELATIVE_RANGE};
+pub use height_curve::{HeightCurve, HeightImputation, BREAST_HEIGHT_FT, MIN_HEIGHTS_TO_IMPUTE};
+pub use inventory::CONTENT_HASH_VERSION;
+#[cfg(feature = "io")]
+pub(crate) use inventory::{canonical_species_names, species_name_issue};
+pub use inventory::{AdjustSpec, ExpansionKind, ForestInventory, RenumberPolicy, SamplingSummary};
+#[cfg(feature = "io")]
+pub(crate) use plot::measurement_date_issue;
+pub use plot::{ExpansionFlag, Plot, DEFAULT_PLOT_SIZE_ACRES, EXPANSION_MISMATCH_RATIO};
+pub use site_index::{
+    king_height, king_site_index, DEFAULT_SITE_INDEX_BASE_AGE, DOMINANT_TPA,
+    SITE_INDEX_SPECIES_CODE,
+};
+pub use taper::{TaperEquation, UtilizationStandard};
+pub use tree::{
+    decay_volume_factor, wood_density, Species, Tree, TreeStatus, ValidationIssue,
+    CARBON_FRACTION, DECAY_VOLUME_FACTORS, DEFAULT_WOOD_DENSITY, SLENDERNESS_WARNING_THRESHOLD,
+};
+#[cfg(any(feature = "config", feature = "visualization"))]
+pub(crate) use tree::validate_hex_color;
+pub use units::{
+    UnitSystem, CM_PER_INCH, CU_M_HA_PER_CU_FT_ACRE, CU_M_PER_CU_FT, HA_PER_ACRE, M_PER_FOOT,
+    PER_HA_PER_ACRE, SQ_M_HA_PER_SQ_FT_ACRE, SQ_M_PER_SQ_FT, TONNES_HA_PER_TONS_ACRE,
+};
+pub use volume::{
+    FormFactorVolume, SpeciesVolumeTable, VolumeEquation, VolumeEquationSet, VolumeModel,
+};
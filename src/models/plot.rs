@@ -1,7 +1,82 @@
 use serde::{Deserialize, Serialize};
 
 use super::tree::ValidationIssue;
-use super::Tree;
+use super::{
+    ExpansionKind, TaperEquation, Tree, TreeStatus, UtilizationStandard, VolumeEquation,
+    VolumeEquationSet, VolumeModel,
+};
+
+/// Plot size assumed when none is recorded: a 1/5-acre fixed-radius plot.
+pub const DEFAULT_PLOT_SIZE_ACRES: f64 = 0.2;
+
+/// How far, as a ratio either way, a plot's implied size may stray from its
+/// stated `plot_size_acres` before [`Plot::expansion_consistency`] flags it.
+pub const EXPANSION_MISMATCH_RATIO: f64 = 2.0;
+
+/// A plot whose expansion factors do not match its stated size.
+///
+/// On a fixed-area plot each tree stands for `1 / plot_size_acres` trees per
+/// acre, so `1 / expansion_factor` should come out close to the plot size.
+/// A large mismatch usually means the expansion factors or the plot size
+/// were keyed for a different plot design.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExpansionFlag {
+    pub plot_id: u32,
+    /// Median expansion factor of the plot's trees
+    pub expansion_factor: f64,
+    /// Plot size in acres implied by that factor, `1 / expansion_factor`
+    pub implied_plot_acres: f64,
+    /// Plot size in acres recorded for the plot
+    pub stated_plot_acres: f64,
+}
+
+impl ExpansionFlag {
+    /// Compare the median of `factors` with `plot_size_acres`, returning a
+    /// flag if they are more than [`EXPANSION_MISMATCH_RATIO`] apart.
+    pub(crate) fn check(
+        plot_id: u32,
+        plot_size_acres: f64,
+        factors: impl IntoIterator<Item = f64>,
+    ) -> Option<Self> {
+        let mut factors: Vec<f64> = factors
+            .into_iter()
+            .filter(|ef| ef.is_finite() && *ef > 0.0)
+            .collect();
+        if factors.is_empty() || !plot_size_acres.is_finite() || plot_size_acres <= 0.0 {
+            return None;
+        }
+        factors.sort_by(f64::total_cmp);
+        let mid = factors.len() / 2;
+        let median = if factors.len() % 2 == 0 {
+            (factors[mid - 1] + factors[mid]) / 2.0
+        } else {
+            factors[mid]
+        };
+
+        let implied = 1.0 / median;
+        let ratio = implied.max(plot_size_acres) / implied.min(plot_size_acres);
+        (ratio > EXPANSION_MISMATCH_RATIO).then_some(Self {
+            plot_id,
+            expansion_factor: median,
+            implied_plot_acres: implied,
+            stated_plot_acres: plot_size_acres,
+        })
+    }
+
+    /// This flag as a warning on the `expansion_factor` field.
+    pub fn to_issue(&self, row_index: usize) -> ValidationIssue {
+        ValidationIssue {
+            plot_id: self.plot_id,
+            tree_id: 0,
+            row_index,
+            field: std::borrow::Cow::Borrowed("expansion_factor"),
+            message: std::borrow::Cow::Owned(format!(
+                "expansion factor {} implies a {:.3}-acre plot but plot_size_acres is {}",
+                self.expansion_factor, self.implied_plot_acres, self.stated_plot_acres
+            )),
+        }
+    }
+}
 
 /// A sample plot in the forest inventory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +96,23 @@ pub struct Plot {
     /// Stand identifier (populated from cruise imports, None for standard imports)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stand_id: Option<u32>,
+    /// Label of the inventory this plot came from, set by
+    /// [`ForestInventory::merge`](super::ForestInventory::merge)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Date the plot was measured, as an ISO 8601 calendar date (`YYYY-MM-DD`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurement_date: Option<String>,
+    /// Plot center latitude in decimal degrees (WGS 84)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    /// Plot center longitude in decimal degrees (WGS 84)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+    /// Stratum for post-stratified sampling statistics, e.g. an aspect class
+    /// or stand type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stratum: Option<String>,
 }
 
 impl Plot {
@@ -29,6 +121,23 @@ impl Plot {
         self.trees.iter().filter(|t| t.is_live()).collect()
     }
 
+    /// Get the trees on this plot with the given status.
+    pub fn trees_with_status(&self, status: &TreeStatus) -> Vec<&Tree> {
+        self.trees.iter().filter(|t| &t.status == status).collect()
+    }
+
+    /// Factor converting this plot's expansion factors to trees per acre.
+    ///
+    /// `1.0` for [`ExpansionKind::PerAcre`]; `1 / plot_size_acres` for
+    /// [`ExpansionKind::CountOnPlot`]. The per-acre roll-ups below sum raw
+    /// expansion factors, so multiply them by this factor for count-based data.
+    pub fn per_acre_factor(&self, kind: ExpansionKind) -> f64 {
+        match kind {
+            ExpansionKind::PerAcre => 1.0,
+            ExpansionKind::CountOnPlot => 1.0 / self.plot_size_acres,
+        }
+    }
+
     /// Calculate trees per acre for this plot.
     ///
     /// Sums the expansion factors of all live trees.
@@ -47,9 +156,15 @@ impl Plot {
     ///             species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
     ///             dbh: 14.0, height: Some(90.0), crown_ratio: None,
     ///             status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///             decay_class: None,
     ///         },
     ///     ],
     ///     stand_id: None,
+    ///     source: None,
+    ///     measurement_date: None,
+    ///     latitude: None,
+    ///     longitude: None,
+    ///     stratum: None,
     /// };
     /// assert!((plot.trees_per_acre() - 5.0).abs() < 0.001);
     /// ```
@@ -73,8 +188,14 @@ impl Plot {
     ///         species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
     ///         dbh: 12.0, height: Some(80.0), crown_ratio: None,
     ///         status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///         decay_class: None,
     ///     }],
     ///     stand_id: None,
+    ///     source: None,
+    ///     measurement_date: None,
+    ///     latitude: None,
+    ///     longitude: None,
+    ///     stratum: None,
     /// };
     /// assert!(plot.basal_area_per_acre() > 0.0);
     /// ```
@@ -87,17 +208,66 @@ impl Plot {
 
     /// Calculate total cubic foot volume per acre for this plot.
     pub fn volume_cuft_per_acre(&self) -> f64 {
+        self.volume_cuft_per_acre_with(&VolumeEquation::default())
+    }
+
+    /// Calculate total cubic foot volume per acre of live trees using any
+    /// [`VolumeModel`], e.g. a [`VolumeEquation`] with custom coefficients.
+    pub fn volume_cuft_per_acre_with(&self, model: &dyn VolumeModel) -> f64 {
         self.live_trees()
             .iter()
-            .filter_map(|t| t.volume_cuft().map(|v| v * t.expansion_factor))
+            .filter_map(|t| model.cuft(t).map(|v| v * t.expansion_factor))
             .sum()
     }
 
     /// Calculate total board foot volume per acre for this plot.
     pub fn volume_bdft_per_acre(&self) -> f64 {
+        self.volume_bdft_per_acre_with(&VolumeEquation::default())
+    }
+
+    /// Calculate total board foot volume per acre of live trees using any
+    /// [`VolumeModel`], e.g. a [`VolumeEquation`] with custom coefficients.
+    pub fn volume_bdft_per_acre_with(&self, model: &dyn VolumeModel) -> f64 {
+        self.live_trees()
+            .iter()
+            .filter_map(|t| model.bdft(t).map(|v| v * t.expansion_factor))
+            .sum()
+    }
+
+    /// Calculate total cubic foot volume per acre using each tree's species equation.
+    pub fn volume_cuft_per_acre_with_set(&self, set: &VolumeEquationSet) -> f64 {
+        self.volume_cuft_per_acre_with(set)
+    }
+
+    /// Calculate total board foot volume per acre using each tree's species
+    /// equation, so each species' merchantable DBH threshold applies.
+    pub fn volume_bdft_per_acre_with_set(&self, set: &VolumeEquationSet) -> f64 {
+        self.volume_bdft_per_acre_with(set)
+    }
+
+    /// Recoverable cubic foot volume per acre of dead trees, reduced for
+    /// decay; see [`Tree::salvage_volume_cuft_with`].
+    pub fn salvage_volume_cuft_per_acre_with(&self, eq: &VolumeEquation) -> f64 {
+        self.trees
+            .iter()
+            .filter_map(|t| t.salvage_volume_cuft_with(eq).map(|v| v * t.expansion_factor))
+            .sum()
+    }
+
+    /// Merchantable cubic foot volume per acre of live trees under a
+    /// utilization standard; see [`Tree::merchantable_volume_cuft`]. Trees
+    /// without a height contribute nothing.
+    pub fn merchantable_volume_cuft_per_acre(
+        &self,
+        taper: &TaperEquation,
+        util: &UtilizationStandard,
+    ) -> f64 {
         self.live_trees()
             .iter()
-            .filter_map(|t| t.volume_bdft().map(|v| v * t.expansion_factor))
+            .filter_map(|t| {
+                t.merchantable_volume_cuft(taper, util)
+                    .map(|v| v * t.expansion_factor)
+            })
             .sum()
     }
 
@@ -182,6 +352,82 @@ impl Plot {
 
         issues
     }
+
+    /// Check that the trees' expansion factors fit the stated plot size.
+    ///
+    /// Returns a flag when the plot size implied by the median expansion
+    /// factor (`1 / EF`) is more than [`EXPANSION_MISMATCH_RATIO`] times
+    /// larger or smaller than `plot_size_acres`. Only meaningful for
+    /// fixed-area plots with per-acre expansion factors; variable-radius
+    /// plots and [`ExpansionKind::CountOnPlot`] data will be flagged.
+    pub fn expansion_consistency(&self) -> Option<ExpansionFlag> {
+        ExpansionFlag::check(
+            self.plot_id,
+            self.plot_size_acres,
+            self.trees.iter().map(|t| t.expansion_factor),
+        )
+    }
+
+    /// Collect non-fatal warnings about plot-level fields.
+    ///
+    /// Like [`Tree::warnings`], these never cause a load to fail. Currently
+    /// flags a `measurement_date` that is not an ISO 8601 calendar date and
+    /// expansion factors inconsistent with the plot size (see
+    /// [`Plot::expansion_consistency`]).
+    pub fn warnings(&self, row_index: usize) -> Vec<ValidationIssue> {
+        self.measurement_date
+            .as_deref()
+            .and_then(|date| measurement_date_issue(date, self.plot_id, 0, row_index))
+            .into_iter()
+            .chain(self.expansion_consistency().map(|f| f.to_issue(row_index)))
+            .collect()
+    }
+}
+
+/// A `measurement_date` warning if `date` is not a valid `YYYY-MM-DD` date.
+pub(crate) fn measurement_date_issue(
+    date: &str,
+    plot_id: u32,
+    tree_id: u32,
+    row_index: usize,
+) -> Option<ValidationIssue> {
+    (!is_iso_date(date)).then(|| ValidationIssue {
+        plot_id,
+        tree_id,
+        row_index,
+        field: std::borrow::Cow::Borrowed("measurement_date"),
+        message: std::borrow::Cow::Owned(format!(
+            "measurement_date '{}' is not an ISO 8601 date (YYYY-MM-DD)",
+            date
+        )),
+    })
+}
+
+pub(super) fn is_iso_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let number = |range: std::ops::Range<usize>| -> Option<u32> {
+        let digits = &date[range];
+        if digits.bytes().all(|b| b.is_ascii_digit()) {
+            digits.parse().ok()
+        } else {
+            None
+        }
+    };
+    let (Some(year), Some(month), Some(day)) = (number(0..4), number(5..7), number(8..10)) else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day)
 }
 
 #[cfg(test)]
@@ -204,6 +450,7 @@ mod tests {
             expansion_factor: ef,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -216,6 +463,11 @@ mod tests {
             elevation_ft: Some(3000.0),
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
@@ -233,6 +485,58 @@ mod tests {
         assert_eq!(live[1].tree_id, 3);
     }
 
+    #[test]
+    fn test_measurement_date_warnings() {
+        let mut plot = make_plot(vec![]);
+        assert!(plot.warnings(0).is_empty());
+
+        for date in ["2024-06-12", "2024-02-29", "2000-02-29"] {
+            plot.measurement_date = Some(date.to_string());
+            assert!(plot.warnings(0).is_empty(), "{date}");
+        }
+        for date in [
+            "2023-02-29",
+            "1900-02-29",
+            "2024-13-01",
+            "2024-6-12",
+            "12/06/2024",
+            "",
+        ] {
+            plot.measurement_date = Some(date.to_string());
+            let warnings = plot.warnings(4);
+            assert_eq!(warnings.len(), 1, "{date}");
+            assert_eq!(warnings[0].field, "measurement_date");
+            assert_eq!(warnings[0].row_index, 4);
+        }
+    }
+
+    #[test]
+    fn test_expansion_consistency() {
+        let mut plot = make_plot(vec![
+            make_tree(1, 12.0, Some(80.0), TreeStatus::Live, 5.0),
+            make_tree(2, 10.0, Some(60.0), TreeStatus::Live, 5.0),
+        ]);
+        plot.plot_size_acres = 0.5;
+        let flag = plot.expansion_consistency().expect("EF 5 on a 0.5-acre plot");
+        assert_eq!(flag.expansion_factor, 5.0);
+        assert!((flag.implied_plot_acres - 0.2).abs() < 1e-12);
+        assert_eq!(flag.stated_plot_acres, 0.5);
+        let warnings = plot.warnings(7);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field, "expansion_factor");
+        assert_eq!(warnings[0].row_index, 7);
+
+        for tree in &mut plot.trees {
+            tree.expansion_factor = 2.0;
+        }
+        assert_eq!(plot.expansion_consistency(), None);
+        assert!(plot.warnings(0).is_empty());
+
+        // A single mis-keyed tree does not move the median
+        plot.trees.push(make_tree(3, 8.0, None, TreeStatus::Live, 20.0));
+        assert_eq!(plot.expansion_consistency(), None);
+    }
+
     #[test]
     fn test_live_trees_empty_plot() {
         let plot = make_plot(vec![]);
@@ -311,6 +615,74 @@ mod tests {
         assert_eq!(plot.volume_bdft_per_acre(), 0.0);
     }
 
+    #[test]
+    fn test_volume_bdft_per_acre_honors_species_min_dbh() {
+        // Two 8" trees: WRC needs 10" for sawtimber, PP only 6"
+        let with_species = |tree_id, code: &str| Tree {
+            species: Species {
+                common_name: code.to_string(),
+                code: code.to_string(),
+            },
+            ..make_tree(tree_id, 8.0, Some(60.0), TreeStatus::Live, 5.0)
+        };
+        let set = VolumeEquationSet::default()
+            .with_species(
+                "WRC",
+                VolumeEquation {
+                    bdft_min_dbh: 10.0,
+                    ..VolumeEquation::default()
+                },
+            )
+            .with_species("PP", VolumeEquation::default());
+
+        let wrc = make_plot(vec![with_species(1, "WRC")]);
+        let pp = make_plot(vec![with_species(1, "PP")]);
+        assert_eq!(wrc.volume_bdft_per_acre_with_set(&set), 0.0);
+        assert!(pp.volume_bdft_per_acre_with_set(&set) > 0.0);
+        assert_eq!(
+            pp.volume_bdft_per_acre_with_set(&set),
+            pp.volume_bdft_per_acre()
+        );
+
+        let mixed = make_plot(vec![with_species(1, "WRC"), with_species(2, "PP")]);
+        assert_eq!(
+            mixed.volume_bdft_per_acre_with_set(&set),
+            pp.volume_bdft_per_acre_with_set(&set)
+        );
+        // Cubic volume has no merchantability threshold
+        assert_eq!(
+            mixed.volume_cuft_per_acre_with_set(&set),
+            mixed.volume_cuft_per_acre()
+        );
+    }
+
+    #[test]
+    fn test_merchantable_volume_depends_on_top_diameter() {
+        let plot = make_plot(vec![
+            make_tree(1, 18.0, Some(110.0), TreeStatus::Live, 5.0),
+            make_tree(2, 7.0, Some(55.0), TreeStatus::Live, 20.0),
+            make_tree(3, 16.0, None, TreeStatus::Live, 5.0),
+            make_tree(4, 20.0, Some(100.0), TreeStatus::Dead, 5.0),
+        ]);
+        let taper = TaperEquation::default();
+        let four_inch = UtilizationStandard::default();
+        let eight_inch = UtilizationStandard {
+            top_dib: 8.0,
+            ..four_inch
+        };
+
+        let pulp = plot.merchantable_volume_cuft_per_acre(&taper, &four_inch);
+        let saw = plot.merchantable_volume_cuft_per_acre(&taper, &eight_inch);
+        assert!(saw > 0.0);
+        assert!(pulp > saw);
+        // Only the 18" tree reaches an 8" top, so the 7" trees add to pulp alone
+        let big = make_plot(vec![make_tree(1, 18.0, Some(110.0), TreeStatus::Live, 5.0)]);
+        assert_eq!(big.merchantable_volume_cuft_per_acre(&taper, &eight_inch), saw);
+        assert!(big.merchantable_volume_cuft_per_acre(&taper, &four_inch) < pulp);
+        // Merchantable volume is part of the whole stem
+        assert!(pulp < plot.volume_cuft_per_acre());
+    }
+
     #[test]
     fn test_quadratic_mean_diameter() {
         // Two trees with same DBH and same EF -> QMD should equal that DBH
@@ -0,0 +1,85 @@
+use super::BREAST_HEIGHT_FT;
+
+/// Trees per acre, taken largest DBH first, whose mean height is the
+/// dominant height used for site index.
+pub const DOMINANT_TPA: f64 = 40.0;
+
+/// Species code of the site trees used for site index, since King's curve is
+/// for Douglas-fir only.
+pub const SITE_INDEX_SPECIES_CODE: &str = "DF";
+
+/// Breast-height age that King's Douglas-fir site index is defined at.
+pub const DEFAULT_SITE_INDEX_BASE_AGE: u32 = 50;
+
+/// Coefficients of King's (1966) Douglas-fir height curve
+/// `H = 4.5 + A² / (c0(A) + c1(A)·Z)` with `Z = 2500 / (SI − 4.5)`, where
+/// `c0(A)` and `c1(A)` are quadratics in breast-height age `A`.
+fn king_coefficients(age: f64) -> (f64, f64) {
+    let c0 = -0.954038 + 0.0558178 * age - 0.000733819 * age * age;
+    let c1 = 0.109757 + 0.00792236 * age + 0.000197693 * age * age;
+    (c0, c1)
+}
+
+/// Height in feet at breast-height `age` on King's Douglas-fir curve through
+/// `height` feet at `base_age`, or `None` outside the curve's range.
+///
+/// With `base_age` 50 this is King's height for site index `height`. A tree
+/// already at `base_age` keeps its height.
+pub fn king_height(height: f64, base_age: u32, age: f64) -> Option<f64> {
+    let z = king_z(f64::from(base_age), height)?;
+    let (c0, c1) = king_coefficients(age);
+    let denominator = c0 + c1 * z;
+    (age > 0.0 && denominator > 0.0).then(|| BREAST_HEIGHT_FT + age * age / denominator)
+}
+
+/// Site index in feet at `base_age` of a stand whose dominant trees are
+/// `height` feet tall at breast-height `age`, by King's (1966) Douglas-fir
+/// curve; see [`king_height`].
+pub fn king_site_index(age: f64, height: f64, base_age: u32) -> Option<f64> {
+    let z = king_z(age, height)?;
+    let base_age = f64::from(base_age);
+    let (c0, c1) = king_coefficients(base_age);
+    let denominator = c0 + c1 * z;
+    (base_age > 0.0 && denominator > 0.0)
+        .then(|| BREAST_HEIGHT_FT + base_age * base_age / denominator)
+}
+
+/// King's `Z` for a tree `height` feet tall at breast-height `age`.
+fn king_z(age: f64, height: f64) -> Option<f64> {
+    if age <= 0.0 || height <= BREAST_HEIGHT_FT || !age.is_finite() || !height.is_finite() {
+        return None;
+    }
+    let (c0, c1) = king_coefficients(age);
+    let z = (age * age / (height - BREAST_HEIGHT_FT) - c0) / c1;
+    (z > 0.0).then_some(z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_king_site_index_at_base_age_is_height() {
+        let si = king_site_index(50.0, 120.0, DEFAULT_SITE_INDEX_BASE_AGE).unwrap();
+        assert!((si - 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_king_curve_rises_with_age() {
+        // A younger stand at the same height is on a better site
+        let young = king_site_index(30.0, 100.0, 50).unwrap();
+        let old = king_site_index(70.0, 100.0, 50).unwrap();
+        assert!(young > 100.0 && old < 100.0, "{young} {old}");
+
+        // Projecting the site index back to age 30 recovers the height
+        let back = king_height(young, 50, 30.0).unwrap();
+        assert!((back - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_king_site_index_rejects_out_of_range_inputs() {
+        assert!(king_site_index(0.0, 100.0, 50).is_none());
+        assert!(king_site_index(40.0, 4.0, 50).is_none());
+        assert!(king_site_index(40.0, 100.0, 0).is_none());
+    }
+}
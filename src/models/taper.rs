@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::ForestError;
+
+/// Square feet of cross-section per square inch of diameter: π / (4 × 144).
+const SQFT_PER_SQIN_DIAMETER: f64 = 0.005454154;
+
+/// Stem taper model giving inside-bark diameter along the bole.
+///
+/// `dib(h) = dib_ratio * DBH * ((H - h) / (H - 4.5))^shape` for a tree of
+/// total height `H` feet, so the stem is `dib_ratio * DBH` across at breast
+/// height and comes to a point at the tip. A `shape` of 0.5 is a paraboloid
+/// and 1.0 a cone; most conifer boles fall in between.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TaperEquation {
+    /// Inside-bark diameter at breast height as a fraction of DBH
+    pub dib_ratio: f64,
+    /// Taper exponent
+    pub shape: f64,
+}
+
+impl Default for TaperEquation {
+    fn default() -> Self {
+        Self {
+            dib_ratio: 0.92,
+            shape: 0.7,
+        }
+    }
+}
+
+impl TaperEquation {
+    /// Inside-bark diameter (inches) at `h` feet above ground on a tree of
+    /// `dbh` inches and `height` feet. Zero at and above the tip.
+    pub fn dib_at(&self, dbh: f64, height: f64, h: f64) -> f64 {
+        if h >= height {
+            return 0.0;
+        }
+        self.dib_ratio * dbh * ((height - h) / (height - 4.5)).powf(self.shape)
+    }
+
+    /// Height (feet) at which the inside-bark diameter narrows to `dib`.
+    fn height_to_dib(&self, dbh: f64, height: f64, dib: f64) -> f64 {
+        if dib <= 0.0 {
+            return height;
+        }
+        height - (height - 4.5) * (dib / (self.dib_ratio * dbh)).powf(1.0 / self.shape)
+    }
+
+    /// Inside-bark cubic feet of the bole between `lower` and `upper` feet
+    /// above ground, integrating the cross-sectional area exactly.
+    fn volume_between(&self, dbh: f64, height: f64, lower: f64, upper: f64) -> f64 {
+        let exponent = 2.0 * self.shape + 1.0;
+        let area_at_bh = SQFT_PER_SQIN_DIAMETER * (self.dib_ratio * dbh).powi(2);
+        let scale = area_at_bh / (height - 4.5).powf(2.0 * self.shape);
+        scale * ((height - lower).powf(exponent) - (height - upper).powf(exponent)) / exponent
+    }
+}
+
+/// Which part of a stem a buyer pays for: the bole from the stump up to a
+/// minimum top diameter, on trees of at least a minimum DBH.
+///
+/// Cruise bids differ mainly on these specs, so the same trees give different
+/// merchantable volumes under, say, a 4-inch pulp top and an 8-inch saw top.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UtilizationStandard {
+    /// Stump height left in the woods, in feet
+    pub stump_height: f64,
+    /// Smallest inside-bark top diameter that is utilized, in inches
+    pub top_dib: f64,
+    /// Smallest DBH that is utilized at all, in inches
+    pub min_dbh: f64,
+}
+
+impl Default for UtilizationStandard {
+    fn default() -> Self {
+        Self {
+            stump_height: 1.0,
+            top_dib: 4.0,
+            min_dbh: 6.0,
+        }
+    }
+}
+
+impl UtilizationStandard {
+    /// Validate that every limit is finite and non-negative.
+    pub fn validate(&self) -> Result<(), ForestError> {
+        for (name, value) in [
+            ("stump_height", self.stump_height),
+            ("top_dib", self.top_dib),
+            ("min_dbh", self.min_dbh),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(ForestError::ValidationError(format!(
+                    "{name} must be a non-negative number, got {value}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Merchantable inside-bark cubic feet of one stem, before defect.
+    ///
+    /// Zero when the tree is below `min_dbh`, no taller than breast height,
+    /// or narrows to `top_dib` at or below the stump.
+    pub fn merchantable_cuft(&self, taper: &TaperEquation, dbh: f64, height: f64) -> f64 {
+        if dbh < self.min_dbh || dbh <= 0.0 || height <= 4.5 {
+            return 0.0;
+        }
+        let top = taper.height_to_dib(dbh, height, self.top_dib);
+        if top <= self.stump_height {
+            return 0.0;
+        }
+        taper.volume_between(dbh, height, self.stump_height, top)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dib_at_breast_height_and_tip() {
+        let taper = TaperEquation::default();
+        assert!((taper.dib_at(20.0, 100.0, 4.5) - 18.4).abs() < 1e-9);
+        assert_eq!(taper.dib_at(20.0, 100.0, 100.0), 0.0);
+        assert!(taper.dib_at(20.0, 100.0, 50.0) < taper.dib_at(20.0, 100.0, 20.0));
+    }
+
+    #[test]
+    fn test_paraboloid_volume_matches_closed_form() {
+        // Paraboloid with no bark: V = A_bh / (H - 4.5) * (H - stump)² / 2
+        let taper = TaperEquation {
+            dib_ratio: 1.0,
+            shape: 0.5,
+        };
+        let util = UtilizationStandard {
+            stump_height: 0.0,
+            top_dib: 0.0,
+            min_dbh: 0.0,
+        };
+        let area = SQFT_PER_SQIN_DIAMETER * 16.0_f64.powi(2);
+        let expected = area / 95.5 * 100.0_f64.powi(2) / 2.0;
+        assert!((util.merchantable_cuft(&taper, 16.0, 100.0) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_higher_top_and_stump_cut_volume() {
+        let taper = TaperEquation::default();
+        let four = UtilizationStandard::default();
+        let eight = UtilizationStandard {
+            top_dib: 8.0,
+            ..four
+        };
+        let pulp = four.merchantable_cuft(&taper, 16.0, 100.0);
+        let saw = eight.merchantable_cuft(&taper, 16.0, 100.0);
+        assert!(pulp > saw && saw > 0.0);
+
+        let high_stump = UtilizationStandard {
+            stump_height: 2.0,
+            ..four
+        };
+        assert!(high_stump.merchantable_cuft(&taper, 16.0, 100.0) < pulp);
+        // Too small for the 8-inch top, and below the minimum DBH
+        assert_eq!(eight.merchantable_cuft(&taper, 8.0, 50.0), 0.0);
+        assert_eq!(four.merchantable_cuft(&taper, 5.0, 40.0), 0.0);
+    }
+
+    #[test]
+    fn test_validate_utilization() {
+        assert!(UtilizationStandard::default().validate().is_ok());
+        let bad = UtilizationStandard {
+            top_dib: -1.0,
+            ..UtilizationStandard::default()
+        };
+        assert!(bad.validate().unwrap_err().to_string().contains("top_dib"));
+    }
+}
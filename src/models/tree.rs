@@ -2,7 +2,8 @@ use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
-use super::volume::VolumeEquation;
+use super::taper::{TaperEquation, UtilizationStandard};
+use super::volume::{VolumeEquation, VolumeEquationSet, VolumeModel};
 
 /// A single validation issue found during lenient validation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +84,55 @@ impl std::fmt::Display for Species {
     }
 }
 
+/// Slenderness (height:DBH ratio) above which a tree is considered
+/// windthrow-prone. Published thresholds fall between roughly 80 and 100;
+/// the upper bound is used so only clearly unstable stems are flagged.
+pub const SLENDERNESS_WARNING_THRESHOLD: f64 = 100.0;
+
+/// Share of its volume a standing dead tree still holds in each decay class,
+/// from class 1 (recently dead, bark and fine twigs intact) to class 5
+/// (soft, broken-topped snag).
+pub const DECAY_VOLUME_FACTORS: [f64; 5] = [1.0, 0.9, 0.75, 0.5, 0.25];
+
+/// Fraction of a snag's volume left at decay `class` (1-5); see
+/// [`DECAY_VOLUME_FACTORS`]. Classes outside 1-5 fail validation and are
+/// given full volume here.
+pub fn decay_volume_factor(class: u8) -> f64 {
+    match class {
+        1..=5 => DECAY_VOLUME_FACTORS[usize::from(class) - 1],
+        _ => 1.0,
+    }
+}
+
+/// Share of dry biomass that is carbon.
+pub const CARBON_FRACTION: f64 = 0.5;
+
+/// Weight of a cubic foot of water in pounds, which a specific gravity scales.
+const WATER_LBS_PER_CUFT: f64 = 62.4;
+
+/// Wood density [`wood_density`] gives species it does not list, in pounds
+/// of oven-dry wood per green cubic foot: a specific gravity of 0.45.
+pub const DEFAULT_WOOD_DENSITY: f64 = 0.45 * WATER_LBS_PER_CUFT;
+
+/// Wood density of a species in pounds of oven-dry wood per green cubic
+/// foot, from the green-volume specific gravities in the USDA Wood Handbook.
+/// Unlisted species codes get [`DEFAULT_WOOD_DENSITY`].
+pub fn wood_density(species_code: &str) -> f64 {
+    let specific_gravity = match species_code.to_uppercase().as_str() {
+        "DF" => 0.45,
+        "WH" => 0.42,
+        "WRC" => 0.31,
+        "PP" => 0.38,
+        "WF" => 0.37,
+        "SP" => 0.34,
+        "IC" => 0.35,
+        "RA" => 0.37,
+        "BM" => 0.44,
+        _ => return DEFAULT_WOOD_DENSITY,
+    };
+    specific_gravity * WATER_LBS_PER_CUFT
+}
+
 /// A single tree measurement record.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tree {
@@ -106,6 +156,10 @@ pub struct Tree {
     pub age: Option<u32>,
     /// Defect percentage (0.0 - 1.0)
     pub defect: Option<f64>,
+    /// Decay class of a standing dead tree, 1 (recently dead) to 5 (soft,
+    /// broken snag); see [`decay_volume_factor`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decay_class: Option<u8>,
 }
 
 impl Tree {
@@ -123,6 +177,7 @@ impl Tree {
     ///     species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
     ///     dbh: 12.0, height: Some(80.0), crown_ratio: Some(0.5),
     ///     status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///     decay_class: None,
     /// };
     /// let ba = tree.basal_area_sqft();
     /// assert!((ba - 0.7854).abs() < 0.001);
@@ -151,6 +206,7 @@ impl Tree {
     ///     species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
     ///     dbh: 16.0, height: Some(100.0), crown_ratio: None,
     ///     status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///     decay_class: None,
     /// };
     /// let vol = tree.volume_cuft().unwrap();
     /// assert!((vol - 62.82).abs() < 0.1);
@@ -192,6 +248,7 @@ impl Tree {
     ///     species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
     ///     dbh: 16.0, height: Some(100.0), crown_ratio: None,
     ///     status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///     decay_class: None,
     /// };
     /// let vol = tree.volume_bdft().unwrap();
     /// assert!(vol > 0.0);
@@ -218,6 +275,92 @@ impl Tree {
         Some(gross_volume * defect_factor)
     }
 
+    /// Estimate cubic foot volume with this tree's species equation from `set`.
+    pub fn volume_cuft_with_set(&self, set: &VolumeEquationSet) -> Option<f64> {
+        self.volume_cuft_with(set.equation_for(&self.species.code))
+    }
+
+    /// Estimate board foot volume with this tree's species equation from `set`,
+    /// including that species' merchantable DBH threshold.
+    pub fn volume_bdft_with_set(&self, set: &VolumeEquationSet) -> Option<f64> {
+        self.volume_bdft_with(set.equation_for(&self.species.code))
+    }
+
+    /// Stem biomass in pounds: cubic foot volume, net of defect, times
+    /// `wood_density` in pounds per cubic foot. With an oven-dry density such
+    /// as [`wood_density`](fn@wood_density) gives, this is dry biomass; with a
+    /// green density, green weight.
+    ///
+    /// Returns `None` if height is not available.
+    pub fn biomass_lbs(&self, wood_density: f64) -> Option<f64> {
+        self.biomass_lbs_with(&VolumeEquation::default(), wood_density)
+    }
+
+    /// [`biomass_lbs`](Self::biomass_lbs) from the cubic foot volume of any
+    /// [`VolumeModel`].
+    pub fn biomass_lbs_with(&self, model: &dyn VolumeModel, wood_density: f64) -> Option<f64> {
+        Some(model.cuft(self)? * wood_density)
+    }
+
+    /// Carbon in pounds: [`CARBON_FRACTION`] of the dry biomass at the
+    /// species' [`wood_density`]. Returns `None` if height is not available.
+    pub fn carbon_lbs(&self) -> Option<f64> {
+        Some(self.biomass_lbs(wood_density(&self.species.code))? * CARBON_FRACTION)
+    }
+
+    /// Recoverable cubic foot volume of a dead tree: its volume from `eq`,
+    /// net of defect, reduced by [`decay_volume_factor`] for its decay
+    /// class. A dead tree with no decay class keeps its full volume.
+    ///
+    /// Returns `None` for trees that are not dead or have no height.
+    pub fn salvage_volume_cuft_with(&self, eq: &VolumeEquation) -> Option<f64> {
+        if self.status != TreeStatus::Dead {
+            return None;
+        }
+        let factor = self.decay_class.map_or(1.0, decay_volume_factor);
+        Some(self.volume_cuft_with(eq)? * factor)
+    }
+
+    /// Merchantable inside-bark cubic foot volume under a utilization
+    /// standard, from the stump to the top diameter given by `taper`, net of
+    /// defect. Returns `None` if height is not available.
+    pub fn merchantable_volume_cuft(
+        &self,
+        taper: &TaperEquation,
+        util: &UtilizationStandard,
+    ) -> Option<f64> {
+        let height = self.height?;
+        let defect_factor = 1.0 - self.defect.unwrap_or(0.0);
+        Some(util.merchantable_cuft(taper, self.dbh, height) * defect_factor)
+    }
+
+    /// Height-to-diameter (slenderness) ratio in consistent units.
+    ///
+    /// Computed as height (ft) × 12 / DBH (in). Returns `None` if height is
+    /// missing or either dimension is non-positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use forest_inventory_analyzer::{Tree, Species, TreeStatus};
+    ///
+    /// let tree = Tree {
+    ///     tree_id: 1, plot_id: 1,
+    ///     species: Species { common_name: "Douglas Fir".into(), code: "DF".into() },
+    ///     dbh: 12.0, height: Some(80.0), crown_ratio: None,
+    ///     status: TreeStatus::Live, expansion_factor: 5.0, age: None, defect: None,
+    ///     decay_class: None,
+    /// };
+    /// assert!((tree.slenderness().unwrap() - 80.0).abs() < 1e-9);
+    /// ```
+    pub fn slenderness(&self) -> Option<f64> {
+        let height = self.height?;
+        if self.dbh <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        Some(height * 12.0 / self.dbh)
+    }
+
     /// Check if the tree is alive.
     pub fn is_live(&self) -> bool {
         self.status == TreeStatus::Live
@@ -295,9 +438,65 @@ impl Tree {
                 });
             }
         }
+        if let Some(class) = self.decay_class {
+            if !(1..=5).contains(&class) {
+                issues.push(ValidationIssue {
+                    plot_id: self.plot_id,
+                    tree_id: self.tree_id,
+                    row_index,
+                    field: Cow::Borrowed("decay_class"),
+                    message: Cow::Owned(format!("decay_class must be in 1..=5, got {}", class)),
+                });
+            }
+        }
 
         issues
     }
+
+    /// Collect non-fatal warnings about plausible but noteworthy measurements.
+    ///
+    /// Unlike `validate_all()`, these never cause a load to fail. Currently
+    /// flags trees whose slenderness exceeds [`SLENDERNESS_WARNING_THRESHOLD`].
+    pub fn warnings(&self, row_index: usize) -> Vec<ValidationIssue> {
+        let mut warnings = Vec::new();
+
+        if let Some(ratio) = self.slenderness() {
+            if ratio > SLENDERNESS_WARNING_THRESHOLD {
+                warnings.push(ValidationIssue {
+                    plot_id: self.plot_id,
+                    tree_id: self.tree_id,
+                    row_index,
+                    field: Cow::Borrowed("height"),
+                    message: Cow::Owned(format!(
+                        "slenderness {:.0} exceeds {:.0}; tree may be windthrow-prone",
+                        ratio, SLENDERNESS_WARNING_THRESHOLD
+                    )),
+                });
+            }
+        }
+
+        warnings
+    }
+}
+
+/// Check that `color` is a 6-digit hex color like `#1b4332`, naming it by
+/// `label` in the error, for species chart colors.
+#[cfg(any(feature = "config", feature = "visualization"))]
+pub(crate) fn validate_hex_color(
+    label: &str,
+    color: &str,
+) -> Result<(), crate::error::ForestError> {
+    // Six digits only, so consumers can append an alpha channel
+    let valid = color
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+    if valid {
+        Ok(())
+    } else {
+        Err(crate::error::ForestError::ValidationError(format!(
+            "{label} must be a 6-digit hex color like '#1b4332', got '{color}'"
+        )))
+    }
 }
 
 #[cfg(test)]
@@ -319,6 +518,7 @@ mod tests {
             expansion_factor: ef,
             age: Some(60),
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -528,6 +728,47 @@ mod tests {
         assert!((vol - expected).abs() < 0.1);
     }
 
+    #[test]
+    fn test_salvage_volume_reduced_by_decay_class() {
+        let eq = VolumeEquation::default();
+        let live = make_tree(16.0, Some(100.0), TreeStatus::Live, 5.0);
+        assert_eq!(live.salvage_volume_cuft_with(&eq), None);
+
+        let mut snag = make_tree(16.0, Some(100.0), TreeStatus::Dead, 5.0);
+        let full = live.volume_cuft().unwrap();
+        assert_eq!(snag.salvage_volume_cuft_with(&eq), Some(full));
+
+        snag.decay_class = Some(4);
+        let salvage = snag.salvage_volume_cuft_with(&eq).unwrap();
+        assert!((salvage - full * decay_volume_factor(4)).abs() < 1e-9);
+        assert!(salvage < full);
+        assert_eq!(decay_volume_factor(1), 1.0);
+        assert_eq!(decay_volume_factor(9), 1.0);
+    }
+
+    #[test]
+    fn test_carbon_is_half_of_biomass() {
+        let tree = make_tree(16.0, Some(100.0), TreeStatus::Live, 5.0);
+        let density = wood_density("DF");
+        assert!((density - 0.45 * 62.4).abs() < 1e-9);
+        let biomass = tree.biomass_lbs(density).unwrap();
+        assert!((biomass - tree.volume_cuft().unwrap() * density).abs() < 1e-9);
+        assert_eq!(tree.carbon_lbs().unwrap(), biomass * 0.5);
+
+        // Lighter cedar wood gives less biomass for the same stem
+        assert!(wood_density("WRC") < density);
+        assert_eq!(wood_density("XYZ"), DEFAULT_WOOD_DENSITY);
+    }
+
+    #[test]
+    fn test_biomass_needs_height() {
+        let tree = make_tree(16.0, None, TreeStatus::Live, 5.0);
+        assert_eq!(tree.biomass_lbs(DEFAULT_WOOD_DENSITY), None);
+        assert_eq!(tree.carbon_lbs(), None);
+        let flat = make_tree(16.0, Some(0.0), TreeStatus::Live, 5.0);
+        assert_eq!(flat.carbon_lbs(), Some(0.0));
+    }
+
     #[test]
     fn test_volume_bdft_normal_tree() {
         let tree = make_tree(16.0, Some(100.0), TreeStatus::Live, 5.0);
@@ -707,6 +948,20 @@ mod tests {
         assert!(err.to_string().contains("defect must be in 0.0..=1.0"));
     }
 
+    #[test]
+    fn test_validate_decay_class_range() {
+        let mut tree = make_tree(12.0, Some(80.0), TreeStatus::Dead, 5.0);
+        for class in 1..=5 {
+            tree.decay_class = Some(class);
+            assert!(tree.validate().is_ok());
+        }
+        for class in [0, 6] {
+            tree.decay_class = Some(class);
+            let err = tree.validate().unwrap_err();
+            assert!(err.to_string().contains("decay_class must be in 1..=5"));
+        }
+    }
+
     #[test]
     fn test_validate_defect_negative() {
         let mut tree = make_tree(12.0, Some(80.0), TreeStatus::Live, 5.0);
@@ -778,4 +1033,34 @@ mod tests {
         };
         assert_eq!(tree.volume_bdft_with(&eq).unwrap(), 0.0);
     }
+
+    // --- Slenderness tests ---
+
+    #[test]
+    fn test_slenderness_tall_skinny_tree_warns() {
+        // 110 ft tall, 10" DBH -> 110 * 12 / 10 = 132
+        let tree = make_tree(10.0, Some(110.0), TreeStatus::Live, 5.0);
+        assert!((tree.slenderness().unwrap() - 132.0).abs() < 1e-9);
+        let warnings = tree.warnings(3);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field, "height");
+        assert_eq!(warnings[0].row_index, 3);
+        // Slenderness is advisory only and must not fail validation
+        assert!(tree.validate().is_ok());
+    }
+
+    #[test]
+    fn test_slenderness_stout_tree_no_warning() {
+        // 80 ft tall, 24" DBH -> 80 * 12 / 24 = 40
+        let tree = make_tree(24.0, Some(80.0), TreeStatus::Live, 5.0);
+        assert!((tree.slenderness().unwrap() - 40.0).abs() < 1e-9);
+        assert!(tree.warnings(0).is_empty());
+    }
+
+    #[test]
+    fn test_slenderness_none_without_height() {
+        let tree = make_tree(12.0, None, TreeStatus::Live, 5.0);
+        assert!(tree.slenderness().is_none());
+        assert!(tree.warnings(0).is_empty());
+    }
 }
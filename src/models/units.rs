@@ -0,0 +1,226 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ForestError;
+
+/// Centimeters per inch.
+pub const CM_PER_INCH: f64 = 2.54;
+
+/// Meters per foot.
+pub const M_PER_FOOT: f64 = 0.3048;
+
+/// Hectares per acre.
+pub const HA_PER_ACRE: f64 = 0.404_686;
+
+/// Square meters per square foot.
+pub const SQ_M_PER_SQ_FT: f64 = 0.092_903;
+
+/// Cubic meters per cubic foot.
+pub const CU_M_PER_CU_FT: f64 = 0.028_317;
+
+/// Per-hectare value of one unit per acre, e.g. trees per hectare per TPA.
+pub const PER_HA_PER_ACRE: f64 = 2.471;
+
+/// Square meters per hectare in one square foot per acre of basal area.
+pub const SQ_M_HA_PER_SQ_FT_ACRE: f64 = 0.2296;
+
+/// Cubic meters per hectare in one cubic foot per acre of volume.
+pub const CU_M_HA_PER_CU_FT_ACRE: f64 = 0.06997;
+
+/// Metric tonnes per hectare in one short ton per acre.
+pub const TONNES_HA_PER_TONS_ACRE: f64 = 2.2417;
+
+/// Units that analysis results are reported in.
+///
+/// All computation is done in imperial units, which is what inventory files
+/// hold; the conversion methods take an imperial value and return it in
+/// `self`'s units, so [`UnitSystem::Imperial`] leaves values unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    /// Inches, feet, acres, square and cubic feet
+    #[default]
+    Imperial,
+    /// Centimeters, meters, hectares, square and cubic meters
+    Metric,
+}
+
+impl UnitSystem {
+    fn factor(self, metric: f64) -> f64 {
+        match self {
+            UnitSystem::Imperial => 1.0,
+            UnitSystem::Metric => metric,
+        }
+    }
+
+    /// A diameter given in inches.
+    pub fn diameter(self, inches: f64) -> f64 {
+        inches * self.factor(CM_PER_INCH)
+    }
+
+    /// A height given in feet.
+    pub fn height(self, feet: f64) -> f64 {
+        feet * self.factor(M_PER_FOOT)
+    }
+
+    /// An area given in acres.
+    pub fn area(self, acres: f64) -> f64 {
+        acres * self.factor(HA_PER_ACRE)
+    }
+
+    /// A count per acre, such as trees per acre.
+    pub fn per_area(self, per_acre: f64) -> f64 {
+        per_acre * self.factor(PER_HA_PER_ACRE)
+    }
+
+    /// A basal area given in square feet.
+    pub fn basal_area(self, sq_ft: f64) -> f64 {
+        sq_ft * self.factor(SQ_M_PER_SQ_FT)
+    }
+
+    /// A basal area given in square feet per acre.
+    pub fn basal_area_per_area(self, sq_ft_per_acre: f64) -> f64 {
+        sq_ft_per_acre * self.factor(SQ_M_HA_PER_SQ_FT_ACRE)
+    }
+
+    /// A volume given in cubic feet.
+    pub fn volume(self, cu_ft: f64) -> f64 {
+        cu_ft * self.factor(CU_M_PER_CU_FT)
+    }
+
+    /// A volume given in cubic feet per acre.
+    pub fn volume_per_area(self, cu_ft_per_acre: f64) -> f64 {
+        cu_ft_per_acre * self.factor(CU_M_HA_PER_CU_FT_ACRE)
+    }
+
+    /// A mass given in short tons per acre.
+    pub fn mass_per_area(self, tons_per_acre: f64) -> f64 {
+        tons_per_acre * self.factor(TONNES_HA_PER_TONS_ACRE)
+    }
+
+    /// Unit label for [`diameter`](Self::diameter) values.
+    pub fn diameter_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "inches",
+            UnitSystem::Metric => "cm",
+        }
+    }
+
+    /// Unit label for [`height`](Self::height) values.
+    pub fn height_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "feet",
+            UnitSystem::Metric => "m",
+        }
+    }
+
+    /// Unit label for [`area`](Self::area) values.
+    pub fn area_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "acres",
+            UnitSystem::Metric => "ha",
+        }
+    }
+
+    /// The area that per-area values are per, e.g. "acre" in "sq ft/acre".
+    pub fn per_area_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "acre",
+            UnitSystem::Metric => "ha",
+        }
+    }
+
+    /// Unit label for [`basal_area`](Self::basal_area) values.
+    pub fn basal_area_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "sq ft",
+            UnitSystem::Metric => "m²",
+        }
+    }
+
+    /// Unit label for [`volume`](Self::volume) values.
+    pub fn volume_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "cu ft",
+            UnitSystem::Metric => "m³",
+        }
+    }
+
+    /// Unit label for the mass in [`mass_per_area`](Self::mass_per_area) values.
+    pub fn mass_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "tons",
+            UnitSystem::Metric => "t",
+        }
+    }
+
+    /// Unit label for stem density, [`per_area`](Self::per_area) of trees.
+    pub fn density_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Imperial => "TPA",
+            UnitSystem::Metric => "trees/ha",
+        }
+    }
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitSystem::Imperial => write!(f, "imperial"),
+            UnitSystem::Metric => write!(f, "metric"),
+        }
+    }
+}
+
+impl std::str::FromStr for UnitSystem {
+    type Err = ForestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "imperial" | "us" => Ok(UnitSystem::Imperial),
+            "metric" | "si" => Ok(UnitSystem::Metric),
+            _ => Err(ForestError::ParseError(format!(
+                "Unknown unit system: '{s}' (expected imperial or metric)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imperial_is_identity() {
+        let units = UnitSystem::Imperial;
+        assert_eq!(units.diameter(12.0), 12.0);
+        assert_eq!(units.per_area(100.0), 100.0);
+        assert_eq!(units.basal_area_per_area(150.0), 150.0);
+        assert_eq!(units.volume_per_area(3000.0), 3000.0);
+    }
+
+    #[test]
+    fn test_metric_factors() {
+        let units = UnitSystem::Metric;
+        assert!((units.diameter(12.0) - 30.48).abs() < 1e-9);
+        assert!((units.height(100.0) - 30.48).abs() < 1e-9);
+        assert!((units.per_area(100.0) - 247.1).abs() < 1e-9);
+        assert!((units.basal_area_per_area(100.0) - 22.96).abs() < 1e-9);
+        assert!((units.volume_per_area(1000.0) - 69.97).abs() < 1e-9);
+        assert!((units.mass_per_area(10.0) - 22.417).abs() < 1e-9);
+        // The per-area factors agree with the separate area and quantity factors
+        let ba = units.basal_area(1.0) / units.area(1.0);
+        assert!((ba - SQ_M_HA_PER_SQ_FT_ACRE).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("Metric".parse::<UnitSystem>().unwrap(), UnitSystem::Metric);
+        assert_eq!(
+            "imperial".parse::<UnitSystem>().unwrap(),
+            UnitSystem::Imperial
+        );
+        assert!("furlongs".parse::<UnitSystem>().is_err());
+    }
+}
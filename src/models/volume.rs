@@ -1,7 +1,30 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
 
+use super::Tree;
 use crate::error::ForestError;
 
+/// A tree volume function.
+///
+/// Implement this to plug any volume model (combined-variable, taper
+/// integrated, form class, NVEL-style lookups) into the plot and stand
+/// roll-ups such as [`Plot::volume_cuft_per_acre_with`](super::Plot::volume_cuft_per_acre_with)
+/// and `compute_stand_metrics_with`. [`VolumeEquation`],
+/// [`VolumeEquationSet`] and [`FormFactorVolume`] are the built-in models.
+///
+/// Both methods return volume for one tree, not per acre; `None` means the
+/// model cannot estimate that tree (e.g. it has no height) and the tree
+/// contributes nothing to the roll-up.
+pub trait VolumeModel {
+    /// Cubic foot volume of `tree`.
+    fn cuft(&self, tree: &Tree) -> Option<f64>;
+
+    /// Board foot volume of `tree`.
+    fn bdft(&self, tree: &Tree) -> Option<f64>;
+}
+
 /// Configurable volume equation coefficients.
 ///
 /// Cubic foot volume: `V = cuft_b1 * DBH^2 * H`
@@ -88,6 +111,165 @@ impl Default for VolumeEquation {
     }
 }
 
+/// Volume equations chosen by species code, with a fallback for all others.
+///
+/// Lets merchantability differ by species: with a 10" `bdft_min_dbh` for one
+/// species and 6" for another, an 8" stem is sawtimber only for the second.
+/// Deserializes from e.g.
+///
+/// ```toml
+/// [default]
+/// cuft_b1 = 0.002454
+/// bdft_b1 = 0.01159
+/// bdft_b2 = 4.0
+/// bdft_min_dbh = 6.0
+///
+/// [species.WRC]
+/// cuft_b1 = 0.002454
+/// bdft_b1 = 0.01159
+/// bdft_b2 = 4.0
+/// bdft_min_dbh = 10.0
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VolumeEquationSet {
+    /// Equation for species without an entry in `species`
+    #[serde(default)]
+    pub default: VolumeEquation,
+    /// Equations keyed by species code
+    #[serde(default)]
+    pub species: BTreeMap<String, VolumeEquation>,
+}
+
+impl VolumeEquationSet {
+    /// Create a set that uses `default` for every species.
+    pub fn new(default: VolumeEquation) -> Self {
+        Self {
+            default,
+            species: BTreeMap::new(),
+        }
+    }
+
+    /// Use `eq` for trees with species code `code`.
+    pub fn with_species(mut self, code: impl Into<String>, eq: VolumeEquation) -> Self {
+        self.species.insert(code.into(), eq);
+        self
+    }
+
+    /// The equation for a species code, falling back to `default`.
+    pub fn equation_for(&self, code: &str) -> &VolumeEquation {
+        self.species.get(code).unwrap_or(&self.default)
+    }
+
+    /// Cubic foot volume of `tree` from its species equation, or from
+    /// `default` if its species code has no entry.
+    pub fn volume_cuft_for(&self, tree: &Tree) -> Option<f64> {
+        tree.volume_cuft_with_set(self)
+    }
+
+    /// Read a set from a JSON file such as
+    /// `{"default": {...}, "species": {"DF": {...}, "WRC": {...}}}`, where
+    /// each equation has the [`VolumeEquation`] fields. Either key may be
+    /// omitted. Every equation is validated; errors carry the path.
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self, ForestError> {
+        let path = path.as_ref();
+        let in_file = |e: ForestError| e.with_context(path, None);
+        let content = std::fs::read_to_string(path).map_err(|e| in_file(e.into()))?;
+        let set: Self = serde_json::from_str(&content).map_err(|e| {
+            let line = e.line();
+            ForestError::from(e).with_context(path, Some(line))
+        })?;
+        set.validate().map_err(in_file)?;
+        Ok(set)
+    }
+
+    /// Validate the default and every species equation.
+    ///
+    /// Errors from a species equation are prefixed with its code.
+    pub fn validate(&self) -> Result<(), ForestError> {
+        self.default.validate()?;
+        for (code, eq) in &self.species {
+            eq.validate().map_err(|e| match e {
+                ForestError::ValidationError(msg) => {
+                    ForestError::ValidationError(format!("species {code}: {msg}"))
+                }
+                other => other,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl VolumeModel for VolumeEquation {
+    fn cuft(&self, tree: &Tree) -> Option<f64> {
+        tree.volume_cuft_with(self)
+    }
+
+    fn bdft(&self, tree: &Tree) -> Option<f64> {
+        tree.volume_bdft_with(self)
+    }
+}
+
+impl VolumeModel for VolumeEquationSet {
+    fn cuft(&self, tree: &Tree) -> Option<f64> {
+        tree.volume_cuft_with_set(self)
+    }
+
+    fn bdft(&self, tree: &Tree) -> Option<f64> {
+        tree.volume_bdft_with_set(self)
+    }
+}
+
+/// Absolute form factor volume: `V = form_factor * BA * H`, where `BA` is the
+/// tree's basal area (sq ft) and `H` its total height (ft).
+///
+/// The form factor is the ratio of stem volume to a cylinder of the same
+/// basal area and height, typically 0.35 to 0.5 for conifers. Defect is
+/// deducted as for [`VolumeEquation`]. This model gives cubic feet only;
+/// [`VolumeModel::bdft`] returns `None`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FormFactorVolume {
+    /// Absolute (breast-height) form factor
+    pub form_factor: f64,
+}
+
+impl FormFactorVolume {
+    /// Validate that the form factor is finite and in (0, 1].
+    pub fn validate(&self) -> Result<(), ForestError> {
+        if !self.form_factor.is_finite() || self.form_factor <= 0.0 || self.form_factor > 1.0 {
+            return Err(ForestError::ValidationError(format!(
+                "form_factor must be in (0, 1], got {}",
+                self.form_factor
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl VolumeModel for FormFactorVolume {
+    fn cuft(&self, tree: &Tree) -> Option<f64> {
+        let height = tree.height?;
+        if tree.dbh <= 0.0 || height <= 0.0 {
+            return Some(0.0);
+        }
+        let gross_volume = self.form_factor * tree.basal_area_sqft() * height;
+        Some(gross_volume * (1.0 - tree.defect.unwrap_or(0.0)))
+    }
+
+    fn bdft(&self, _tree: &Tree) -> Option<f64> {
+        None
+    }
+}
+
+/// Species-specific volume equations as a lookup table; the same type as
+/// [`VolumeEquationSet`].
+pub type SpeciesVolumeTable = VolumeEquationSet;
+
+impl From<VolumeEquation> for VolumeEquationSet {
+    fn from(default: VolumeEquation) -> Self {
+        Self::new(default)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,4 +394,148 @@ mod tests {
         assert!((deserialized.cuft_b1 - 0.003).abs() < 1e-9);
         assert!((deserialized.bdft_b1 - 0.012).abs() < 1e-9);
     }
+
+    // --- VolumeEquationSet tests ---
+
+    #[test]
+    fn test_equation_set_falls_back_to_default() {
+        let wrc = VolumeEquation {
+            bdft_min_dbh: 10.0,
+            ..VolumeEquation::default()
+        };
+        let set = VolumeEquationSet::default().with_species("WRC", wrc);
+        assert_eq!(set.equation_for("WRC").bdft_min_dbh, 10.0);
+        assert_eq!(set.equation_for("DF").bdft_min_dbh, 6.0);
+    }
+
+    #[test]
+    fn test_equation_set_validate_names_species() {
+        let bad = VolumeEquation {
+            bdft_min_dbh: -1.0,
+            ..VolumeEquation::default()
+        };
+        let set = VolumeEquationSet::default().with_species("PP", bad);
+        let err = set.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("species PP: bdft_min_dbh must be positive"),
+            "{err}"
+        );
+        assert!(VolumeEquationSet::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_species_table_json_file_and_fallback() {
+        let wrc = VolumeEquation {
+            cuft_b1: 0.0018,
+            ..VolumeEquation::default()
+        };
+        let table = SpeciesVolumeTable::default().with_species("WRC", wrc);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("volumes.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&table).unwrap()).unwrap();
+
+        let loaded = SpeciesVolumeTable::from_json_file(&path).unwrap();
+        assert_eq!(loaded.species.len(), 1);
+        assert_eq!(loaded.equation_for("WRC").cuft_b1, 0.0018);
+
+        let tree = |code: &str| Tree {
+            tree_id: 1,
+            plot_id: 1,
+            species: super::super::Species {
+                common_name: code.to_string(),
+                code: code.to_string(),
+            },
+            dbh: 16.0,
+            height: Some(100.0),
+            crown_ratio: None,
+            status: super::super::TreeStatus::Live,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        };
+        // 0.0018 * 16^2 * 100 for WRC; codes without an entry use the default
+        let wrc_vol = loaded.volume_cuft_for(&tree("WRC")).unwrap();
+        assert!((wrc_vol - 46.08).abs() < 1e-9);
+        let unknown = loaded.volume_cuft_for(&tree("XYZ")).unwrap();
+        assert!((unknown - 62.8224).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_species_table_json_file_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("volumes.json");
+        std::fs::write(&path, r#"{"species": {"DF": {"cuft_b1": -1.0, "bdft_b1": 0.01, "bdft_b2": 4.0, "bdft_min_dbh": 6.0}}}"#).unwrap();
+        let err = SpeciesVolumeTable::from_json_file(&path).unwrap_err();
+        assert!(err.to_string().contains("species DF: cuft_b1"), "{err}");
+
+        std::fs::write(&path, "{\n  \"default\": 3\n}").unwrap();
+        let err = SpeciesVolumeTable::from_json_file(&path).unwrap_err();
+        assert!(err.to_string().contains("volumes.json:2"), "{err}");
+    }
+
+    // --- VolumeModel tests ---
+
+    fn tree(dbh: f64, height: Option<f64>) -> Tree {
+        Tree {
+            tree_id: 1,
+            plot_id: 1,
+            species: crate::models::Species {
+                common_name: "Douglas Fir".to_string(),
+                code: "DF".to_string(),
+            },
+            dbh,
+            height,
+            crown_ratio: None,
+            status: crate::models::TreeStatus::Live,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    #[test]
+    fn test_volume_equation_model_matches_tree_methods() {
+        let eq = VolumeEquation::default();
+        let t = tree(16.0, Some(100.0));
+        assert_eq!(VolumeModel::cuft(&eq, &t), t.volume_cuft());
+        assert_eq!(VolumeModel::bdft(&eq, &t), t.volume_bdft());
+    }
+
+    #[test]
+    fn test_form_factor_volume() {
+        let model = FormFactorVolume { form_factor: 0.42 };
+        let t = tree(16.0, Some(100.0));
+        // 0.42 * (0.005454 * 256) * 100 = 58.64
+        let vol = model.cuft(&t).unwrap();
+        assert!((vol - 0.42 * t.basal_area_sqft() * 100.0).abs() < 1e-9);
+        assert!((vol - 58.64).abs() < 0.01);
+        assert_eq!(model.bdft(&t), None);
+        assert_eq!(model.cuft(&tree(16.0, None)), None);
+
+        let defective = Tree {
+            defect: Some(0.25),
+            ..t
+        };
+        assert!((model.cuft(&defective).unwrap() - vol * 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_form_factor_validate() {
+        assert!(FormFactorVolume { form_factor: 0.45 }.validate().is_ok());
+        for bad in [0.0, -0.1, 1.5, f64::NAN] {
+            assert!(FormFactorVolume { form_factor: bad }.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_equation_set_deserialize_defaults() {
+        let set: VolumeEquationSet = serde_json::from_str(
+            r#"{"species":{"WRC":{"cuft_b1":0.002454,"bdft_b1":0.01159,"bdft_b2":4.0,"bdft_min_dbh":10.0}}}"#,
+        )
+        .unwrap();
+        assert_eq!(set.default.bdft_min_dbh, 6.0);
+        assert_eq!(set.equation_for("WRC").bdft_min_dbh, 10.0);
+    }
 }
@@ -0,0 +1,150 @@
+//! One-call analysis of an inventory file, for embedding the crate.
+//!
+//! [`analyze_file`] loads any supported file and returns the stand metrics,
+//! sampling statistics and diameter distribution that the CLI `analyze`
+//! command prints, as one serializable [`AnalyzeReport`].
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{
+    Analyzer, DiameterClassWidth, DiameterDistribution, SamplingStatistics, StandMetrics,
+};
+use crate::error::ForestError;
+use crate::io;
+use crate::models::ForestInventory;
+
+/// Settings for [`analyze_file`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnalyzeParams {
+    /// Confidence level for the sampling statistics, strictly between 0 and 1
+    pub confidence: f64,
+    /// Diameter class width in inches
+    pub class_width: f64,
+}
+
+impl Default for AnalyzeParams {
+    /// 95% confidence and 2-inch diameter classes, as the CLI uses.
+    fn default() -> Self {
+        Self {
+            confidence: 0.95,
+            class_width: DiameterClassWidth::default().get(),
+        }
+    }
+}
+
+/// Everything the CLI `analyze` command reports for one inventory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyzeReport {
+    pub name: String,
+    pub num_plots: usize,
+    pub num_trees: usize,
+    pub metrics: StandMetrics,
+    /// `None` when the inventory has fewer than 2 plots
+    pub statistics: Option<SamplingStatistics>,
+    pub distribution: DiameterDistribution,
+}
+
+impl AnalyzeReport {
+    /// Analyze an inventory that is already loaded; see [`analyze_file`].
+    pub fn from_inventory(
+        inventory: &ForestInventory,
+        params: AnalyzeParams,
+    ) -> Result<Self, ForestError> {
+        let analyzer = Analyzer::new(inventory);
+        let width = DiameterClassWidth::new(params.class_width)?;
+        let statistics = match analyzer.sampling_statistics(params.confidence) {
+            Ok(stats) => Some(stats),
+            Err(ForestError::InsufficientData(_)) => None,
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            name: inventory.name.clone(),
+            num_plots: inventory.num_plots(),
+            num_trees: inventory.num_trees(),
+            metrics: analyzer.stand_metrics(),
+            statistics,
+            distribution: analyzer.diameter_distribution(width.get()),
+        })
+    }
+}
+
+/// Load a CSV, JSON, Excel or Parquet file (see [`io::read_file`]) and
+/// compute its stand metrics, sampling statistics and diameter distribution.
+///
+/// Fails if the file cannot be read, the confidence level is not strictly
+/// between 0 and 1, or the class width is not a valid
+/// [`DiameterClassWidth`]. A single-plot inventory still gets a report, with
+/// no statistics.
+///
+/// # Examples
+///
+/// ```
+/// use forest_inventory_analyzer::{analyze_file, AnalyzeParams};
+///
+/// let report = analyze_file("data/samples/sample_inventory.csv", AnalyzeParams::default())?;
+/// assert!(report.metrics.total_basal_area > 0.0);
+/// let ba = report.statistics.expect("the sample has many plots").basal_area;
+/// println!("BA {:.1} ± {:.1}%", ba.mean, ba.sampling_error_percent);
+/// # Ok::<(), forest_inventory_analyzer::ForestError>(())
+/// ```
+pub fn analyze_file(
+    path: impl AsRef<Path>,
+    params: AnalyzeParams,
+) -> Result<AnalyzeReport, ForestError> {
+    let inventory = io::read_file(path)?;
+    AnalyzeReport::from_inventory(&inventory, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::SAMPLE_CSV as CSV;
+
+    #[test]
+    fn test_analyze_csv_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stand.csv");
+        std::fs::write(&path, CSV).unwrap();
+
+        let params = AnalyzeParams {
+            confidence: 0.90,
+            class_width: 4.0,
+        };
+        let report = analyze_file(&path, params).unwrap();
+        assert_eq!(report.name, "stand");
+        assert_eq!(report.num_plots, 3);
+        assert_eq!(report.num_trees, 4);
+        assert!((report.metrics.total_tpa - 20.0 / 3.0).abs() < 1e-9);
+        assert_eq!(report.metrics.species_composition.len(), 2);
+
+        let stats = report.statistics.unwrap();
+        assert_eq!(stats.tpa.sample_size, 3);
+        assert_eq!(stats.basal_area.confidence_level, 0.90);
+
+        assert_eq!(report.distribution.class_width, 4.0);
+        let class_tpa: f64 = report.distribution.classes.iter().map(|c| c.tpa).sum();
+        assert!((class_tpa - report.metrics.total_tpa).abs() < 1e-9);
+
+        let bad_width = AnalyzeParams {
+            class_width: 0.0,
+            ..params
+        };
+        assert!(matches!(
+            analyze_file(&path, bad_width),
+            Err(ForestError::ValidationError(_))
+        ));
+        assert!(analyze_file(dir.path().join("stand.txt"), params).is_err());
+    }
+
+    #[test]
+    fn test_single_plot_has_no_statistics() {
+        let plot_1: Vec<&str> = CSV.lines().take(3).collect();
+        let inventory = io::read_csv_from_bytes(plot_1.join("\n").as_bytes(), "one").unwrap();
+        let report = AnalyzeReport::from_inventory(&inventory, AnalyzeParams::default()).unwrap();
+        assert_eq!(report.num_plots, 1);
+        assert!(report.statistics.is_none());
+        assert!(!report.distribution.classes.is_empty());
+    }
+}
@@ -0,0 +1,254 @@
+//! Synthetic inventories for tests, benchmarks and demos.
+//!
+//! Test-support only: available under `cfg(test)` and the `testing` feature
+//! (which enables `analysis`), and not covered by the crate's stability
+//! guarantees. Generated stands look plausible but are not calibrated to any
+//! real forest type.
+
+use rand::Rng;
+
+use crate::analysis::{rng_from_seed, RngSeed};
+use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus, DEFAULT_PLOT_SIZE_ACRES};
+
+/// Share of generated trees recorded as dead.
+const DEAD_FRACTION: f64 = 0.05;
+
+/// Smallest DBH the generator produces, in inches.
+const MIN_DBH: f64 = 1.0;
+
+/// Small tree-row CSV for tests that read an inventory from text: three
+/// plots and four live trees of two species, each expanding to 5 trees per
+/// acre, so mean TPA is 20/3.
+pub const SAMPLE_CSV: &str = "\
+plot_id,tree_id,species_code,species_name,dbh,height,crown_ratio,status,expansion_factor
+1,1,DF,Douglas Fir,14.0,90.0,0.5,Live,5.0
+1,2,WRC,Western Red Cedar,20.0,110.0,0.6,Live,5.0
+2,1,DF,Douglas Fir,9.0,60.0,0.4,Live,5.0
+3,1,DF,Douglas Fir,17.0,95.0,0.5,Live,5.0
+";
+
+/// Parameters for [`generate_stand`].
+#[derive(Debug, Clone)]
+pub struct GenConfig {
+    /// Number of plots, numbered from 1
+    pub plots: u32,
+    /// Trees recorded on every plot
+    pub trees_per_plot: u32,
+    /// Species to draw from, uniformly; must not be empty
+    pub species: Vec<Species>,
+    /// Mean DBH in inches
+    pub dbh_mean: f64,
+    /// Standard deviation of DBH in inches
+    pub dbh_sd: f64,
+    /// RNG seed; with a seed the same config always produces the same
+    /// inventory, `None` draws a different stand every time
+    pub seed: Option<RngSeed>,
+}
+
+impl Default for GenConfig {
+    fn default() -> Self {
+        let species = [
+            ("DF", "Douglas Fir"),
+            ("WH", "Western Hemlock"),
+            ("WRC", "Western Red Cedar"),
+        ];
+        Self {
+            plots: 10,
+            trees_per_plot: 8,
+            species: species
+                .iter()
+                .map(|(code, name)| Species {
+                    common_name: name.to_string(),
+                    code: code.to_string(),
+                })
+                .collect(),
+            dbh_mean: 14.0,
+            dbh_sd: 5.0,
+            seed: Some(RngSeed(42)),
+        }
+    }
+}
+
+/// Generate a seeded, reproducible inventory of fixed-area plots.
+///
+/// DBH is normally distributed (truncated at 1 inch), heights follow a
+/// saturating height-diameter curve with noise, and about 5% of trees are
+/// dead. Plots are [`DEFAULT_PLOT_SIZE_ACRES`] with matching per-acre
+/// expansion factors, and every tree passes [`Tree::validate`]. Randomness
+/// comes from the crate's shared seeded RNG, so a seed reproduces a stand
+/// for a given release, as described under [`RngSeed`].
+///
+/// # Panics
+///
+/// Panics if `config.species` is empty.
+///
+/// ```
+/// use forest_inventory_analyzer::testing::{generate_stand, GenConfig};
+///
+/// let config = GenConfig {
+///     plots: 4,
+///     seed: Some(7.into()),
+///     ..GenConfig::default()
+/// };
+/// let inv = generate_stand(config.clone());
+/// assert_eq!(inv.num_plots(), 4);
+/// assert_eq!(
+///     inv.content_hash().unwrap(),
+///     generate_stand(config).content_hash().unwrap()
+/// );
+/// ```
+pub fn generate_stand(config: GenConfig) -> ForestInventory {
+    assert!(
+        !config.species.is_empty(),
+        "GenConfig.species must contain at least one species"
+    );
+
+    let mut rng = rng_from_seed(config.seed);
+    let expansion_factor = 1.0 / DEFAULT_PLOT_SIZE_ACRES;
+    let mut inventory = ForestInventory::new(match config.seed {
+        Some(seed) => format!("Synthetic stand (seed {seed})"),
+        None => "Synthetic stand".to_string(),
+    });
+
+    for plot_id in 1..=config.plots {
+        let trees = (1..=config.trees_per_plot)
+            .map(|tree_id| {
+                let species = &config.species[rng.gen_range(0..config.species.len())];
+                let dbh = round_to(
+                    (config.dbh_mean + config.dbh_sd * standard_normal(&mut rng)).max(MIN_DBH),
+                    0.1,
+                );
+                let curve = 4.5 + 140.0 * (1.0 - (-0.045 * dbh).exp()).powf(1.1);
+                let noise = 1.0 + 0.08 * standard_normal(&mut rng);
+                let height = round_to((curve * noise).max(5.0), 1.0);
+                let status = if rng.gen::<f64>() < DEAD_FRACTION {
+                    TreeStatus::Dead
+                } else {
+                    TreeStatus::Live
+                };
+                Tree {
+                    tree_id,
+                    plot_id,
+                    species: species.clone(),
+                    dbh,
+                    height: Some(height),
+                    crown_ratio: Some(round_to(0.2 + 0.6 * rng.gen::<f64>(), 0.01)),
+                    status,
+                    expansion_factor,
+                    age: None,
+                    defect: None,
+                    decay_class: None,
+                }
+            })
+            .collect();
+
+        inventory.plots.push(Plot {
+            plot_id,
+            plot_size_acres: DEFAULT_PLOT_SIZE_ACRES,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees,
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        });
+    }
+
+    inventory
+}
+
+fn round_to(value: f64, step: f64) -> f64 {
+    (value / step).round() * step
+}
+
+/// Standard normal deviate (Box-Muller).
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_stand() {
+        let a = generate_stand(GenConfig::default());
+        let b = generate_stand(GenConfig::default());
+        assert_eq!(a.content_hash().unwrap(), b.content_hash().unwrap());
+
+        let c = generate_stand(GenConfig {
+            seed: Some(RngSeed(43)),
+            ..GenConfig::default()
+        });
+        assert_ne!(a.content_hash().unwrap(), c.content_hash().unwrap());
+    }
+
+    #[test]
+    fn test_unseeded_stands_differ() {
+        let unseeded = || {
+            generate_stand(GenConfig {
+                seed: None,
+                ..GenConfig::default()
+            })
+        };
+        let a = unseeded();
+        assert_eq!(a.name, "Synthetic stand");
+        assert_ne!(
+            a.content_hash().unwrap(),
+            unseeded().content_hash().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generated_stand_shape_and_validity() {
+        let config = GenConfig {
+            plots: 6,
+            trees_per_plot: 12,
+            ..GenConfig::default()
+        };
+        let inv = generate_stand(config);
+        assert_eq!(inv.num_plots(), 6);
+        assert_eq!(inv.num_trees(), 72);
+        for plot in &inv.plots {
+            plot.validate().unwrap();
+            for tree in &plot.trees {
+                tree.validate().unwrap();
+                assert!(tree.dbh >= MIN_DBH);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dbh_mean_roughly_matches_config() {
+        let inv = generate_stand(GenConfig {
+            plots: 50,
+            trees_per_plot: 20,
+            dbh_mean: 18.0,
+            dbh_sd: 4.0,
+            ..GenConfig::default()
+        });
+        let dbhs: Vec<f64> = inv
+            .plots
+            .iter()
+            .flat_map(|p| &p.trees)
+            .map(|t| t.dbh)
+            .collect();
+        let mean = dbhs.iter().sum::<f64>() / dbhs.len() as f64;
+        assert!((mean - 18.0).abs() < 0.5, "mean DBH {mean}");
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one species")]
+    fn test_empty_species_panics() {
+        generate_stand(GenConfig {
+            species: Vec::new(),
+            ..GenConfig::default()
+        });
+    }
+}
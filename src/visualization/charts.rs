@@ -1,9 +1,106 @@
+use std::str::FromStr;
+
 use colored::Colorize;
 
-use crate::analysis::DiameterDistribution;
+use crate::analysis::{DiameterClass, DiameterDistribution};
+use crate::error::ForestError;
+use crate::models::UnitSystem;
+
+/// Which per-class value a histogram bar represents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistogramMetric {
+    /// Trees per acre (stem counts)
+    #[default]
+    Tpa,
+    /// Basal area per acre, which emphasizes the few large trees
+    BasalArea,
+}
+
+impl FromStr for HistogramMetric {
+    type Err = ForestError;
+
+    /// Parse a metric name (case-insensitive): `tpa`, or `ba`/`basal-area`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tpa" => Ok(HistogramMetric::Tpa),
+            "ba" | "basal-area" | "basal_area" => Ok(HistogramMetric::BasalArea),
+            _ => Err(ForestError::ParseError(format!(
+                "Unknown histogram metric: '{s}'. Use: tpa or ba"
+            ))),
+        }
+    }
+}
+
+/// Order in which diameter classes are listed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClassOrder {
+    /// Smallest diameter class first
+    #[default]
+    Ascending,
+    /// Largest diameter class first
+    Descending,
+}
+
+impl FromStr for ClassOrder {
+    type Err = ForestError;
+
+    /// Parse an order name (case-insensitive): `asc` or `desc`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "asc" | "ascending" => Ok(ClassOrder::Ascending),
+            "desc" | "descending" => Ok(ClassOrder::Descending),
+            _ => Err(ForestError::ParseError(format!(
+                "Unknown class order: '{s}'. Use: asc or desc"
+            ))),
+        }
+    }
+}
+
+/// Rendering options for [`format_diameter_histogram_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistogramOptions {
+    /// Length in characters of the longest bar
+    pub bar_width: usize,
+    /// Value the bars are scaled by
+    pub metric: HistogramMetric,
+    /// Order of the class rows
+    pub class_order: ClassOrder,
+}
+
+impl Default for HistogramOptions {
+    fn default() -> Self {
+        Self {
+            bar_width: 40,
+            metric: HistogramMetric::Tpa,
+            class_order: ClassOrder::Ascending,
+        }
+    }
+}
 
 /// Format a text-based histogram of the diameter distribution as a string.
+///
+/// Uses [`HistogramOptions::default`]: 40-character bars scaled by TPA,
+/// smallest class first.
 pub fn format_diameter_histogram(dist: &DiameterDistribution) -> String {
+    format_diameter_histogram_with(dist, &HistogramOptions::default())
+}
+
+/// Format a text-based histogram of the diameter distribution with custom
+/// bar width, metric and class order.
+pub fn format_diameter_histogram_with(
+    dist: &DiameterDistribution,
+    options: &HistogramOptions,
+) -> String {
+    format_diameter_histogram_in(dist, options, UnitSystem::Imperial)
+}
+
+/// Like [`format_diameter_histogram_with`], with class bounds, density and
+/// basal area converted to `units`.
+pub fn format_diameter_histogram_in(
+    dist: &DiameterDistribution,
+    options: &HistogramOptions,
+    units: UnitSystem,
+) -> String {
     let mut output = String::new();
     output.push_str(&format!("\n{}\n", "Diameter Distribution".bold().green()));
     output.push_str(&format!("{}\n", "=".repeat(60)));
@@ -13,19 +110,30 @@ pub fn format_diameter_histogram(dist: &DiameterDistribution) -> String {
         return output;
     }
 
-    let max_tpa = dist.classes.iter().map(|c| c.tpa).fold(0.0f64, f64::max);
-
-    let bar_width = 40;
+    let value = |class: &DiameterClass| match options.metric {
+        HistogramMetric::Tpa => class.tpa,
+        HistogramMetric::BasalArea => class.basal_area,
+    };
+    let max_value = dist.classes.iter().map(value).fold(0.0f64, f64::max);
 
+    let label = match options.metric {
+        HistogramMetric::Tpa => "Distribution",
+        HistogramMetric::BasalArea => "Distribution (BA)",
+    };
+    let (density, basal_area) = column_labels(units);
     output.push_str(&format!(
-        "  {:>10}  {:>8}  {:>8}  Distribution\n",
-        "DBH Class", "TPA", "BA/ac"
+        "  {:>10}  {:>8}  {:>8}  {label}\n",
+        "DBH Class", density, basal_area
     ));
     output.push_str(&format!("  {}\n", "-".repeat(60)));
 
-    for class in &dist.classes {
-        let bar_len = if max_tpa > 0.0 {
-            ((class.tpa / max_tpa) * bar_width as f64).round() as usize
+    let classes: Box<dyn Iterator<Item = &DiameterClass>> = match options.class_order {
+        ClassOrder::Ascending => Box::new(dist.classes.iter()),
+        ClassOrder::Descending => Box::new(dist.classes.iter().rev()),
+    };
+    for class in classes {
+        let bar_len = if max_value > 0.0 {
+            ((value(class) / max_value) * options.bar_width as f64).round() as usize
         } else {
             0
         };
@@ -33,11 +141,12 @@ pub fn format_diameter_histogram(dist: &DiameterDistribution) -> String {
         let bar = "\u{2588}".repeat(bar_len);
 
         output.push_str(&format!(
-            "  {:>4.0}-{:<4.0}\"  {:>8.1}  {:>8.1}  {}\n",
-            class.lower,
-            class.upper,
-            class.tpa,
-            class.basal_area,
+            "  {:>4.0}-{:<4.0}{}  {:>8.1}  {:>8.1}  {}\n",
+            units.diameter(class.lower),
+            units.diameter(class.upper),
+            diameter_mark(units),
+            units.per_area(class.tpa),
+            units.basal_area_per_area(class.basal_area),
             bar.green()
         ));
     }
@@ -46,15 +155,109 @@ pub fn format_diameter_histogram(dist: &DiameterDistribution) -> String {
     output
 }
 
+/// Density and basal-area column headers for `units`.
+fn column_labels(units: UnitSystem) -> (&'static str, &'static str) {
+    match units {
+        UnitSystem::Imperial => ("TPA", "BA/ac"),
+        UnitSystem::Metric => ("Trees/ha", "BA/ha"),
+    }
+}
+
+/// Unit mark after a diameter: an inch mark for imperial, "cm" for metric.
+fn diameter_mark(units: UnitSystem) -> &'static str {
+    match units {
+        UnitSystem::Imperial => "\"",
+        UnitSystem::Metric => "cm",
+    }
+}
+
 /// Print a text-based histogram of the diameter distribution.
 pub fn print_diameter_histogram(dist: &DiameterDistribution) {
     print!("{}", format_diameter_histogram(dist));
 }
 
+/// Print a text-based histogram of the diameter distribution with custom options.
+pub fn print_diameter_histogram_with(dist: &DiameterDistribution, options: &HistogramOptions) {
+    print!("{}", format_diameter_histogram_with(dist, options));
+}
+
+/// Print a text-based histogram of the diameter distribution in `units`.
+pub fn print_diameter_histogram_in(
+    dist: &DiameterDistribution,
+    options: &HistogramOptions,
+    units: UnitSystem,
+) {
+    print!("{}", format_diameter_histogram_in(dist, options, units));
+}
+
+/// Format a text-based ogive (cumulative distribution) of TPA and basal area.
+///
+/// Bars show cumulative TPA as a percentage of the stand total, so percentiles
+/// can be read off directly (e.g. the DBH below which half the trees fall).
+pub fn format_cumulative_distribution(dist: &DiameterDistribution) -> String {
+    format_cumulative_distribution_in(dist, UnitSystem::Imperial)
+}
+
+/// Like [`format_cumulative_distribution`], with class bounds, density and
+/// basal area converted to `units`.
+pub fn format_cumulative_distribution_in(dist: &DiameterDistribution, units: UnitSystem) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\n{}\n",
+        "Cumulative Diameter Distribution".bold().green()
+    ));
+    output.push_str(&format!("{}\n", "=".repeat(60)));
+
+    let ogive = dist.cumulative();
+    let Some(&(_, total_tpa, total_ba)) = ogive.last() else {
+        output.push_str("  No data available.\n");
+        return output;
+    };
+
+    let bar_width = 30;
+
+    let (density, basal_area) = column_labels(units);
+    output.push_str(&format!(
+        "  {:>8}  {:>8}  {:>6}  {:>8}  {:>6}  Cumulative {density}\n",
+        "DBH <", density, "%", basal_area, "%"
+    ));
+    output.push_str(&format!("  {}\n", "-".repeat(60)));
+
+    let mark = diameter_mark(units);
+    for (upper, tpa, ba) in ogive {
+        let tpa_pct = if total_tpa > 0.0 { tpa / total_tpa * 100.0 } else { 0.0 };
+        let ba_pct = if total_ba > 0.0 { ba / total_ba * 100.0 } else { 0.0 };
+        let bar_len = (tpa_pct / 100.0 * bar_width as f64).round() as usize;
+
+        output.push_str(&format!(
+            "  {:>width$.0}{mark}  {:>8.1}  {:>5.1}%  {:>8.1}  {:>5.1}%  {}\n",
+            units.diameter(upper),
+            units.per_area(tpa),
+            tpa_pct,
+            units.basal_area_per_area(ba),
+            ba_pct,
+            "\u{2588}".repeat(bar_len).green(),
+            width = 8 - mark.len(),
+        ));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Print a text-based ogive (cumulative distribution) of TPA and basal area.
+pub fn print_cumulative_distribution(dist: &DiameterDistribution) {
+    print!("{}", format_cumulative_distribution(dist));
+}
+
+/// Print a text-based ogive of TPA and basal area in `units`.
+pub fn print_cumulative_distribution_in(dist: &DiameterDistribution, units: UnitSystem) {
+    print!("{}", format_cumulative_distribution_in(dist, units));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analysis::{DiameterClass, DiameterDistribution};
 
     #[test]
     fn test_format_histogram_empty() {
@@ -79,6 +282,7 @@ mod tests {
                     tpa: 25.0,
                     basal_area: 15.0,
                     tree_count: 5,
+                    tpa_ci: None,
                 },
                 DiameterClass {
                     lower: 12.0,
@@ -87,6 +291,7 @@ mod tests {
                     tpa: 15.0,
                     basal_area: 12.0,
                     tree_count: 3,
+                    tpa_ci: None,
                 },
             ],
         };
@@ -97,6 +302,156 @@ mod tests {
         assert!(output.contains("Distribution"));
     }
 
+    fn two_class_dist() -> DiameterDistribution {
+        // Many small trees vs. few large trees: TPA and BA tell opposite stories
+        DiameterDistribution {
+            class_width: 2.0,
+            classes: vec![
+                DiameterClass {
+                    lower: 4.0,
+                    upper: 6.0,
+                    midpoint: 5.0,
+                    tpa: 100.0,
+                    basal_area: 10.0,
+                    tree_count: 20,
+                    tpa_ci: None,
+                },
+                DiameterClass {
+                    lower: 30.0,
+                    upper: 32.0,
+                    midpoint: 31.0,
+                    tpa: 10.0,
+                    basal_area: 50.0,
+                    tree_count: 2,
+                    tpa_ci: None,
+                },
+            ],
+        }
+    }
+
+    fn bar_lengths(output: &str) -> Vec<usize> {
+        output
+            .lines()
+            .filter(|l| l.contains('"'))
+            .map(|l| l.matches('\u{2588}').count())
+            .collect()
+    }
+
+    #[test]
+    fn test_histogram_basal_area_mode_scales_by_ba() {
+        let options = HistogramOptions {
+            bar_width: 50,
+            metric: HistogramMetric::BasalArea,
+            ..HistogramOptions::default()
+        };
+        let output = format_diameter_histogram_with(&two_class_dist(), &options);
+        assert!(output.contains("Distribution (BA)"));
+        // 10/50 of 50 chars and the full 50 chars
+        assert_eq!(bar_lengths(&output), vec![10, 50]);
+    }
+
+    #[test]
+    fn test_histogram_default_matches_tpa_mode() {
+        let dist = two_class_dist();
+        let output = format_diameter_histogram(&dist);
+        assert_eq!(bar_lengths(&output), vec![40, 4]);
+        assert_eq!(
+            output,
+            format_diameter_histogram_with(&dist, &HistogramOptions::default())
+        );
+
+        let wide = HistogramOptions {
+            bar_width: 80,
+            ..HistogramOptions::default()
+        };
+        assert_eq!(
+            bar_lengths(&format_diameter_histogram_with(&dist, &wide)),
+            vec![80, 8]
+        );
+    }
+
+    #[test]
+    fn test_histogram_descending_class_order() {
+        let options = HistogramOptions {
+            class_order: ClassOrder::Descending,
+            ..HistogramOptions::default()
+        };
+        let output = format_diameter_histogram_with(&two_class_dist(), &options);
+        assert_eq!(bar_lengths(&output), vec![4, 40]);
+
+        assert_eq!(
+            "DESC".parse::<ClassOrder>().unwrap(),
+            ClassOrder::Descending
+        );
+        assert_eq!("asc".parse::<ClassOrder>().unwrap(), ClassOrder::Ascending);
+        assert!("up".parse::<ClassOrder>().is_err());
+    }
+
+    #[test]
+    fn test_histogram_metric_from_str() {
+        assert_eq!("TPA".parse::<HistogramMetric>().unwrap(), HistogramMetric::Tpa);
+        assert_eq!(
+            "basal-area".parse::<HistogramMetric>().unwrap(),
+            HistogramMetric::BasalArea
+        );
+        assert!("volume".parse::<HistogramMetric>().is_err());
+    }
+
+    #[test]
+    fn test_format_cumulative_distribution() {
+        let dist = DiameterDistribution {
+            class_width: 2.0,
+            classes: vec![
+                DiameterClass {
+                    lower: 10.0,
+                    upper: 12.0,
+                    midpoint: 11.0,
+                    tpa: 25.0,
+                    basal_area: 15.0,
+                    tree_count: 5,
+                    tpa_ci: None,
+                },
+                DiameterClass {
+                    lower: 12.0,
+                    upper: 14.0,
+                    midpoint: 13.0,
+                    tpa: 75.0,
+                    basal_area: 45.0,
+                    tree_count: 3,
+                    tpa_ci: None,
+                },
+            ],
+        };
+        let output = format_cumulative_distribution(&dist);
+        assert!(output.contains("Cumulative Diameter Distribution"));
+        assert!(output.contains("25.0%"));
+        assert!(output.contains("100.0%"));
+        assert!(output.contains("100.0"));
+    }
+
+    #[test]
+    fn test_histogram_and_cumulative_in_metric_units() {
+        let dist = two_class_dist();
+        let output =
+            format_diameter_histogram_in(&dist, &HistogramOptions::default(), UnitSystem::Metric);
+        assert!(output.contains("Trees/ha"));
+        assert!(output.contains("BA/ha"));
+        assert!(!output.contains("BA/ac"));
+        // 4-6" is 10-15 cm; 100 TPA is 247.1 trees/ha
+        assert!(output.contains("  10-15  cm"), "{output}");
+        assert!(output.contains("247.1"));
+
+        let cumulative = format_cumulative_distribution_in(&dist, UnitSystem::Metric);
+        assert!(cumulative.contains("Cumulative Trees/ha"));
+        assert!(cumulative.contains("271.8"));
+        assert!(!cumulative.contains('"'));
+
+        assert_eq!(
+            format_diameter_histogram_in(&dist, &HistogramOptions::default(), UnitSystem::Imperial),
+            format_diameter_histogram(&dist)
+        );
+    }
+
     #[test]
     fn test_format_histogram_contains_values() {
         let dist = DiameterDistribution {
@@ -108,6 +463,7 @@ mod tests {
                 tpa: 30.0,
                 basal_area: 20.0,
                 tree_count: 6,
+                tpa_ci: None,
             }],
         };
         let output = format_diameter_histogram(&dist);
@@ -0,0 +1,183 @@
+use crate::analysis::{ConfidenceInterval, SamplingStatistics, StandMetrics};
+use crate::models::UnitSystem;
+
+/// Format a terse, one-line-per-metric stand summary for scripts and quick checks.
+///
+/// With `stats`, the per-acre totals include their confidence interval
+/// half-width and sampling error, e.g. `TPA: 123.4 ± 8.2 (±6.7% at 95%)`.
+/// Output is plain text without colors or box drawing.
+pub fn format_compact_summary(
+    metrics: &StandMetrics,
+    stats: Option<&SamplingStatistics>,
+) -> String {
+    // Sampling statistics are always imperial; their half-widths are
+    // converted to the metrics' units
+    let units = metrics.units;
+    let (per_area, diameter, height) = match units {
+        UnitSystem::Imperial => ("ac", "in", "ft"),
+        UnitSystem::Metric => ("ha", "cm", "m"),
+    };
+    let mut lines = vec![
+        compact_line(
+            units.density_unit(),
+            metrics.total_tpa,
+            1,
+            stats.map(|s| (units.per_area(s.tpa.upper - s.tpa.mean), &s.tpa)),
+        ),
+        compact_line(
+            &format!("BA ({}/{per_area})", units.basal_area_unit()),
+            metrics.total_basal_area,
+            1,
+            stats.map(|s| {
+                let half_width = s.basal_area.upper - s.basal_area.mean;
+                (units.basal_area_per_area(half_width), &s.basal_area)
+            }),
+        ),
+        compact_line(
+            &format!("Volume ({}/{per_area})", units.volume_unit()),
+            metrics.total_volume_cuft,
+            1,
+            stats.map(|s| {
+                let half_width = s.volume_cuft.upper - s.volume_cuft.mean;
+                (units.volume_per_area(half_width), &s.volume_cuft)
+            }),
+        ),
+    ];
+    if units == UnitSystem::Imperial {
+        lines.push(compact_line(
+            "Volume (bd ft/ac)",
+            metrics.total_volume_bdft,
+            0,
+            stats.map(|s| (s.volume_bdft.upper - s.volume_bdft.mean, &s.volume_bdft)),
+        ));
+    }
+    lines.push(format!(
+        "QMD ({diameter}): {:.1}",
+        metrics.quadratic_mean_diameter
+    ));
+    if let Some(h) = metrics.mean_height {
+        lines.push(format!("Mean height ({height}): {h:.1}"));
+    }
+    lines.push(format!("Species: {}", metrics.num_species));
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+/// Print a compact stand summary.
+pub fn print_compact_summary(metrics: &StandMetrics, stats: Option<&SamplingStatistics>) {
+    print!("{}", format_compact_summary(metrics, stats));
+}
+
+/// One metric line; `ci` pairs an interval with its half-width in the
+/// value's units.
+fn compact_line(
+    label: &str,
+    value: f64,
+    precision: usize,
+    ci: Option<(f64, &ConfidenceInterval)>,
+) -> String {
+    match ci {
+        Some((half_width, ci)) => format!(
+            "{label}: {value:.precision$} ± {half_width:.precision$} (±{:.1}% at {:.0}%)",
+            ci.sampling_error_percent,
+            ci.confidence_level * 100.0
+        ),
+        None => format!("{label}: {value:.precision$}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::compute_stand_metrics;
+    use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus};
+
+    fn sample_inventory() -> ForestInventory {
+        let mut inv = ForestInventory::new("Compact");
+        for (plot_id, dbhs) in [(1, [12.0, 16.0]), (2, [10.0, 20.0]), (3, [14.0, 15.0])] {
+            inv.plots.push(Plot {
+                plot_id,
+                plot_size_acres: 0.2,
+                slope_percent: None,
+                aspect_degrees: None,
+                elevation_ft: None,
+                trees: dbhs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &dbh)| Tree {
+                        tree_id: i as u32 + 1,
+                        plot_id,
+                        species: Species {
+                            common_name: "Douglas Fir".to_string(),
+                            code: "DF".to_string(),
+                        },
+                        dbh,
+                        height: Some(90.0),
+                        crown_ratio: Some(0.5),
+                        status: TreeStatus::Live,
+                        expansion_factor: 5.0 * plot_id as f64,
+                        age: None,
+                        defect: None,
+                        decay_class: None,
+                    })
+                    .collect(),
+                stand_id: None,
+                source: None,
+                measurement_date: None,
+                latitude: None,
+                longitude: None,
+                stratum: None,
+            });
+        }
+        inv
+    }
+
+    #[test]
+    fn test_compact_summary_with_statistics() {
+        let inv = sample_inventory();
+        let metrics = compute_stand_metrics(&inv);
+        let stats = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        let output = format_compact_summary(&metrics, Some(&stats));
+
+        let tpa = output.lines().next().unwrap();
+        let expected = format!(
+            "TPA: {:.1} ± {:.1} (±{:.1}% at 95%)",
+            metrics.total_tpa,
+            stats.tpa.upper - stats.tpa.mean,
+            stats.tpa.sampling_error_percent
+        );
+        assert_eq!(tpa, expected);
+        assert!(output.contains("QMD (in): "));
+        assert!(output.ends_with("Species: 1\n"));
+        assert!(!output.contains('│'));
+    }
+
+    #[test]
+    fn test_compact_summary_without_statistics() {
+        let metrics = compute_stand_metrics(&sample_inventory());
+        let output = format_compact_summary(&metrics, None);
+        assert!(output.starts_with(&format!("TPA: {:.1}\n", metrics.total_tpa)));
+        assert!(!output.contains('±'));
+    }
+
+    #[test]
+    fn test_compact_summary_metric() {
+        let inv = sample_inventory();
+        let metrics = compute_stand_metrics(&inv).to_metric();
+        let stats = SamplingStatistics::compute(&inv, 0.95).unwrap();
+        let output = format_compact_summary(&metrics, Some(&stats));
+
+        let expected = format!(
+            "trees/ha: {:.1} ± {:.1} (±{:.1}% at 95%)",
+            metrics.total_tpa,
+            UnitSystem::Metric.per_area(stats.tpa.upper - stats.tpa.mean),
+            stats.tpa.sampling_error_percent
+        );
+        assert_eq!(output.lines().next().unwrap(), expected);
+        assert!(output.contains("BA (m²/ha): "));
+        assert!(output.contains("QMD (cm): "));
+        assert!(!output.contains("bd ft"));
+    }
+}
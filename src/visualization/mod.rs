@@ -2,12 +2,34 @@
 //!
 //! Each output has a `print_*` variant (writes to stdout) and a `format_*` variant
 //! (returns a `String`), making it easy to use in both CLI and programmatic contexts.
+//! SVG charts are available via the `format_*_svg` functions, colored per species
+//! through a [`SpeciesPalette`].
 
 mod charts;
+mod compact;
+mod palette;
+mod svg;
 mod tables;
+mod units;
 
-pub use charts::{format_diameter_histogram, print_diameter_histogram};
+pub use charts::{
+    format_cumulative_distribution, format_cumulative_distribution_in, format_diameter_histogram,
+    format_diameter_histogram_in, format_diameter_histogram_with, print_cumulative_distribution,
+    print_cumulative_distribution_in, print_diameter_histogram, print_diameter_histogram_in,
+    print_diameter_histogram_with, ClassOrder, HistogramMetric, HistogramOptions,
+};
+pub use compact::{format_compact_summary, print_compact_summary};
+pub use palette::{SpeciesPalette, DEFAULT_SPECIES_COLORS};
+pub use svg::{format_diameter_histogram_svg, format_species_composition_svg};
 pub use tables::{
-    format_growth_table, format_species_table, format_stand_summary, format_statistics_table,
-    print_growth_table, print_species_table, print_stand_summary, print_statistics_table,
+    format_group_table, format_growth_table, format_growth_table_with, format_harvest_table,
+    format_plot_qa_table, format_species_table, format_species_table_top,
+    format_species_table_top_with, format_species_table_with, format_stand_summary,
+    format_stand_summary_with, format_statistics_table, format_statistics_table_in,
+    format_statistics_table_with, format_validation_table, print_group_table, print_growth_table,
+    print_growth_table_with, print_harvest_table, print_plot_qa_table, print_species_table,
+    print_species_table_top_with, print_species_table_with, print_stand_summary,
+    print_stand_summary_with, print_statistics_table, print_statistics_table_in,
+    print_statistics_table_with, print_validation_table, SpeciesOrder,
 };
+pub use units::BoardFootUnit;
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ForestError;
+use crate::models::validate_hex_color;
+
+/// Default categorical palette used for species without an explicit color.
+pub const DEFAULT_SPECIES_COLORS: [&str; 10] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+    "#9c755f", "#bab0ac",
+];
+
+/// Mapping from species code to a hex color, shared by every chart output.
+///
+/// Species without an explicit entry are assigned a color from
+/// [`DEFAULT_SPECIES_COLORS`] by hashing the species code, so the same code
+/// always gets the same color regardless of which species are present or
+/// their order. The CLI and web server build it with
+/// [`SpeciesPalette::from_colors`] from the `[species_colors]` table of
+/// `config.toml`:
+///
+/// ```toml
+/// [species_colors]
+/// DF = "#1b4332"
+/// WRC = "#95d5b2"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SpeciesPalette {
+    colors: BTreeMap<String, String>,
+}
+
+impl SpeciesPalette {
+    /// Create an empty palette that uses only automatic colors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a palette from species code to hex color pairs, validating
+    /// every color.
+    pub fn from_colors(colors: BTreeMap<String, String>) -> Result<Self, ForestError> {
+        let palette = Self { colors };
+        palette.validate()?;
+        Ok(palette)
+    }
+
+    /// Assign a color to a species code, validating the hex format.
+    pub fn with_color(mut self, code: &str, color: &str) -> Result<Self, ForestError> {
+        self.set_color(code, color)?;
+        Ok(self)
+    }
+
+    /// Assign a color to a species code, validating the hex format.
+    pub fn set_color(&mut self, code: &str, color: &str) -> Result<(), ForestError> {
+        validate_hex_color("species color", color)?;
+        self.colors.insert(code.to_string(), color.to_string());
+        Ok(())
+    }
+
+    /// Color for a species code: the configured color, or a stable default.
+    pub fn color_for(&self, code: &str) -> &str {
+        match self.colors.get(code) {
+            Some(color) => color,
+            None => {
+                let n = DEFAULT_SPECIES_COLORS.len() as u64;
+                DEFAULT_SPECIES_COLORS[(fnv1a(code.as_bytes()) % n) as usize]
+            }
+        }
+    }
+
+    /// Check that every configured color is a valid hex color.
+    pub fn validate(&self) -> Result<(), ForestError> {
+        self.colors
+            .values()
+            .try_for_each(|c| validate_hex_color("species color", c))
+    }
+}
+
+/// FNV-1a hash; unlike `DefaultHasher` it is stable across releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supplied_color_overrides_default() {
+        let palette = SpeciesPalette::new().with_color("DF", "#123456").unwrap();
+        assert_eq!(palette.color_for("DF"), "#123456");
+        assert_ne!(palette.color_for("DF"), SpeciesPalette::new().color_for("DF"));
+    }
+
+    #[test]
+    fn test_unmapped_species_get_stable_colors() {
+        let palette = SpeciesPalette::new().with_color("DF", "#123456").unwrap();
+        let other = SpeciesPalette::new();
+        for code in ["WRC", "WH", "RA", "PP"] {
+            let color = palette.color_for(code);
+            assert!(DEFAULT_SPECIES_COLORS.contains(&color));
+            assert_eq!(color, palette.color_for(code));
+            assert_eq!(color, other.color_for(code));
+        }
+    }
+
+    #[test]
+    fn test_invalid_color_rejected() {
+        assert!(SpeciesPalette::new().with_color("DF", "green").is_err());
+        assert!(SpeciesPalette::new().with_color("DF", "#12345g").is_err());
+        assert!(SpeciesPalette::new().with_color("DF", "#abc").is_err());
+        assert!(SpeciesPalette::new().with_color("DF", "#AbC123").is_ok());
+    }
+
+    #[test]
+    fn test_from_colors() {
+        let colors = BTreeMap::from([("DF".to_string(), "#1b4332".to_string())]);
+        let palette = SpeciesPalette::from_colors(colors).unwrap();
+        assert_eq!(palette.color_for("DF"), "#1b4332");
+
+        let bad = BTreeMap::from([("DF".to_string(), "forest".to_string())]);
+        assert!(SpeciesPalette::from_colors(bad).is_err());
+    }
+}
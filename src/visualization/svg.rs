@@ -0,0 +1,260 @@
+use std::fmt::Write;
+
+use crate::analysis::{DiameterDistribution, StandMetrics};
+use crate::models::ForestInventory;
+
+use super::palette::SpeciesPalette;
+
+const WIDTH: f64 = 640.0;
+const MARGIN_LEFT: f64 = 150.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 40.0;
+const BAR_HEIGHT: f64 = 22.0;
+const BAR_GAP: f64 = 8.0;
+const LEGEND_ROW: f64 = 18.0;
+
+/// Format species composition (basal area per acre) as a horizontal bar chart SVG.
+///
+/// Bars are colored by species code using `palette`.
+pub fn format_species_composition_svg(
+    metrics: &StandMetrics,
+    palette: &SpeciesPalette,
+) -> String {
+    let rows = metrics.species_composition.len().max(1) as f64;
+    let height = MARGIN_TOP + rows * (BAR_HEIGHT + BAR_GAP) + 20.0;
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let max_ba = metrics
+        .species_composition
+        .iter()
+        .map(|sc| sc.basal_area)
+        .fold(0.0_f64, f64::max);
+
+    let mut svg = svg_open(height, "Species Composition (BA ft\u{00B2}/ac)");
+    for (i, sc) in metrics.species_composition.iter().enumerate() {
+        let y = MARGIN_TOP + i as f64 * (BAR_HEIGHT + BAR_GAP);
+        let w = if max_ba > 0.0 {
+            sc.basal_area / max_ba * (plot_width - 50.0)
+        } else {
+            0.0
+        };
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{:.1}" y="{:.1}" text-anchor="end" font-size="12">{}</text>"#,
+            MARGIN_LEFT - 8.0,
+            y + BAR_HEIGHT * 0.7,
+            escape(&sc.species.common_name)
+        );
+        let _ = writeln!(
+            svg,
+            r#"  <rect x="{MARGIN_LEFT:.1}" y="{y:.1}" width="{w:.1}" height="{BAR_HEIGHT:.1}" fill="{}" data-species="{}"/>"#,
+            palette.color_for(&sc.species.code),
+            escape(&sc.species.code)
+        );
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{:.1}" y="{:.1}" font-size="11">{:.1}</text>"#,
+            MARGIN_LEFT + w + 4.0,
+            y + BAR_HEIGHT * 0.7,
+            sc.basal_area
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Format the diameter distribution as a stacked-bar SVG histogram of TPA.
+///
+/// Each class bar is split by species, colored using `palette`. The per-species
+/// breakdown is computed from `inventory`, which should be the inventory `dist`
+/// was built from.
+pub fn format_diameter_histogram_svg(
+    inventory: &ForestInventory,
+    dist: &DiameterDistribution,
+    palette: &SpeciesPalette,
+) -> String {
+    let inventory = &*inventory.to_per_acre();
+    let chart_height = 240.0;
+    let species = species_order(inventory);
+    let legend_height = species.len() as f64 * LEGEND_ROW;
+    let height = MARGIN_TOP + chart_height + 40.0 + legend_height;
+    let left = 50.0;
+    let plot_width = WIDTH - left - MARGIN_RIGHT;
+
+    let mut svg = svg_open(height, "Diameter Distribution (TPA)");
+    if dist.classes.is_empty() {
+        svg.push_str("</svg>\n");
+        return svg;
+    }
+
+    let max_tpa = dist.classes.iter().map(|c| c.tpa).fold(0.0_f64, f64::max);
+    let num_plots = inventory.num_plots().max(1) as f64;
+    let slot = plot_width / dist.classes.len() as f64;
+    let baseline = MARGIN_TOP + chart_height;
+
+    for (i, class) in dist.classes.iter().enumerate() {
+        let x = left + i as f64 * slot + slot * 0.1;
+        let mut y = baseline;
+        for code in &species {
+            let tpa: f64 = inventory
+                .plots
+                .iter()
+                .flat_map(|p| p.live_trees())
+                .filter(|t| {
+                    &t.species.code == code && t.dbh >= class.lower && t.dbh < class.upper
+                })
+                .map(|t| t.expansion_factor)
+                .sum::<f64>()
+                / num_plots;
+            if tpa <= 0.0 || max_tpa <= 0.0 {
+                continue;
+            }
+            let h = tpa / max_tpa * chart_height;
+            y -= h;
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{x:.1}" y="{y:.1}" width="{:.1}" height="{h:.1}" fill="{}" data-species="{}"/>"#,
+                slot * 0.8,
+                palette.color_for(code),
+                escape(code)
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{:.1}" y="{:.1}" text-anchor="middle" font-size="11">{:.0}</text>"#,
+            x + slot * 0.4,
+            baseline + 14.0,
+            class.midpoint
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r##"  <line x1="{left:.1}" y1="{baseline:.1}" x2="{:.1}" y2="{baseline:.1}" stroke="#333"/>"##,
+        left + plot_width
+    );
+
+    for (i, code) in species.iter().enumerate() {
+        let y = baseline + 30.0 + i as f64 * LEGEND_ROW;
+        let _ = writeln!(
+            svg,
+            r#"  <rect x="{left:.1}" y="{y:.1}" width="12" height="12" fill="{}"/>"#,
+            palette.color_for(code)
+        );
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{:.1}" y="{:.1}" font-size="11">{}</text>"#,
+            left + 18.0,
+            y + 10.0,
+            escape(code)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Live species codes ordered by descending basal area, matching the
+/// species composition table.
+fn species_order(inventory: &ForestInventory) -> Vec<String> {
+    crate::analysis::compute_stand_metrics(inventory)
+        .species_composition
+        .into_iter()
+        .map(|sc| sc.species.code)
+        .collect()
+}
+
+fn svg_open(height: f64, title: &str) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH:.0}" height="{height:.0}" viewBox="0 0 {WIDTH:.0} {height:.0}" font-family="sans-serif">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{:.1}" y="22" text-anchor="middle" font-size="14" font-weight="bold">{}</text>"#,
+        WIDTH / 2.0,
+        escape(title)
+    );
+    svg
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::compute_stand_metrics;
+    use crate::models::{Plot, Species, Tree, TreeStatus};
+
+    fn make_tree(code: &str, name: &str, dbh: f64) -> Tree {
+        Tree {
+            tree_id: 1,
+            plot_id: 1,
+            species: Species {
+                common_name: name.to_string(),
+                code: code.to_string(),
+            },
+            dbh,
+            height: Some(90.0),
+            crown_ratio: Some(0.5),
+            status: TreeStatus::Live,
+            expansion_factor: 5.0,
+            age: None,
+            defect: None,
+            decay_class: None,
+        }
+    }
+
+    fn sample_inventory() -> ForestInventory {
+        let mut inv = ForestInventory::new("SVG Test");
+        inv.plots.push(Plot {
+            plot_id: 1,
+            plot_size_acres: 0.2,
+            slope_percent: None,
+            aspect_degrees: None,
+            elevation_ft: None,
+            trees: vec![
+                make_tree("DF", "Douglas Fir", 16.0),
+                make_tree("DF", "Douglas Fir", 12.0),
+                make_tree("WRC", "Western Red Cedar <old>", 12.5),
+            ],
+            stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+        });
+        inv
+    }
+
+    #[test]
+    fn test_composition_svg_uses_palette() {
+        let inv = sample_inventory();
+        let palette = SpeciesPalette::new().with_color("DF", "#123456").unwrap();
+        let svg = format_species_composition_svg(&compute_stand_metrics(&inv), &palette);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r##"fill="#123456" data-species="DF""##));
+        let wrc = format!(r#"fill="{}" data-species="WRC""#, palette.color_for("WRC"));
+        assert!(svg.contains(&wrc));
+        assert!(svg.contains("Western Red Cedar &lt;old&gt;"));
+    }
+
+    #[test]
+    fn test_histogram_svg_colors_match_composition() {
+        let inv = sample_inventory();
+        let palette = SpeciesPalette::new().with_color("WRC", "#abcdef").unwrap();
+        let dist = DiameterDistribution::from_inventory(&inv, 2.0);
+        let svg = format_diameter_histogram_svg(&inv, &dist, &palette);
+        // The 12-14" class is stacked: one DF and one WRC segment
+        assert!(svg.contains(r##"fill="#abcdef" data-species="WRC""##));
+        let df = format!(r#"fill="{}" data-species="DF""#, palette.color_for("DF"));
+        assert_eq!(svg.matches(&df).count(), 2);
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -1,12 +1,78 @@
+use std::str::FromStr;
+
 use colored::Colorize;
 use comfy_table::{
-    modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Cell, ContentArrangement, Table,
+    modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Cell, Color, ContentArrangement, Table,
+};
+
+use super::BoardFootUnit;
+use crate::analysis::{
+    GroupComposition, GrowthProjection, HarvestSummary, PlotQaFlag, SamplingStatistics,
+    SpeciesComposition, StandMetrics, StructuralStage, LARGE_TREE_DBH, OTHER_GROUP,
 };
+use crate::error::ForestError;
+use crate::io::{Severity, ValidationReport};
+use crate::models::{UnitSystem, DEFAULT_SITE_INDEX_BASE_AGE};
 
-use crate::analysis::{GrowthProjection, SamplingStatistics, StandMetrics};
+/// Row order of the species composition table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpeciesOrder {
+    /// Largest basal area per acre first
+    #[default]
+    BasalArea,
+    /// Largest trees per acre first
+    Tpa,
+    /// Alphabetical by common name
+    Name,
+    /// Largest importance first: % TPA plus % BA (relative density plus
+    /// relative dominance, 0-200)
+    Importance,
+}
+
+impl SpeciesOrder {
+    fn sort(self, rows: &mut [&SpeciesComposition]) {
+        match self {
+            SpeciesOrder::BasalArea => rows.sort_by(|a, b| b.basal_area.total_cmp(&a.basal_area)),
+            SpeciesOrder::Tpa => rows.sort_by(|a, b| b.tpa.total_cmp(&a.tpa)),
+            SpeciesOrder::Name => rows.sort_by(|a, b| {
+                a.species
+                    .common_name
+                    .to_lowercase()
+                    .cmp(&b.species.common_name.to_lowercase())
+                    .then_with(|| a.species.code.cmp(&b.species.code))
+            }),
+            SpeciesOrder::Importance => {
+                let importance = |sp: &SpeciesComposition| sp.percent_tpa + sp.percent_basal_area;
+                rows.sort_by(|a, b| importance(b).total_cmp(&importance(a)))
+            }
+        }
+    }
+}
+
+impl FromStr for SpeciesOrder {
+    type Err = ForestError;
+
+    /// Parse an order name (case-insensitive): `ba`, `tpa`, `name` or `importance`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ba" | "basal-area" | "basal_area" => Ok(SpeciesOrder::BasalArea),
+            "tpa" => Ok(SpeciesOrder::Tpa),
+            "name" => Ok(SpeciesOrder::Name),
+            "importance" => Ok(SpeciesOrder::Importance),
+            _ => Err(ForestError::ParseError(format!(
+                "Unknown species order: '{s}'. Use: ba, tpa, name or importance"
+            ))),
+        }
+    }
+}
 
 /// Format a stand summary table as a string.
 pub fn format_stand_summary(metrics: &StandMetrics) -> String {
+    format_stand_summary_with(metrics, &BoardFootUnit::BoardFeet)
+}
+
+/// Format a stand summary table with board-foot volume shown in `unit`.
+pub fn format_stand_summary_with(metrics: &StandMetrics, unit: &BoardFootUnit) -> String {
     let mut output = String::new();
     output.push_str(&format!("\n{}\n", "Stand Summary".bold().green()));
     output.push_str(&format!("{}\n", "=".repeat(50)));
@@ -18,36 +84,82 @@ pub fn format_stand_summary(metrics: &StandMetrics) -> String {
         .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec!["Metric", "Value", "Unit"]);
 
+    let units = metrics.units;
+    let per_area = units.per_area_unit();
     table.add_row(vec![
-        Cell::new("Trees per Acre"),
+        Cell::new(match units {
+            UnitSystem::Imperial => "Trees per Acre",
+            UnitSystem::Metric => "Trees per Hectare",
+        }),
         Cell::new(format!("{:.1}", metrics.total_tpa)),
-        Cell::new("TPA"),
+        Cell::new(units.density_unit()),
     ]);
     table.add_row(vec![
         Cell::new("Basal Area"),
         Cell::new(format!("{:.1}", metrics.total_basal_area)),
-        Cell::new("sq ft/acre"),
+        Cell::new(format!("{}/{per_area}", units.basal_area_unit())),
     ]);
     table.add_row(vec![
-        Cell::new("Volume (cubic ft)"),
+        Cell::new(match units {
+            UnitSystem::Imperial => "Volume (cubic ft)",
+            UnitSystem::Metric => "Volume",
+        }),
         Cell::new(format!("{:.1}", metrics.total_volume_cuft)),
-        Cell::new("cu ft/acre"),
+        Cell::new(format!("{}/{per_area}", units.volume_unit())),
+    ]);
+    // Board feet are a North American measure; metric reports skip them
+    if units == UnitSystem::Imperial {
+        table.add_row(vec![
+            Cell::new("Volume (board ft)"),
+            Cell::new(unit.format(metrics.total_volume_bdft)),
+            Cell::new(format!("{unit}/acre")),
+        ]);
+    }
+    let mass_per_area = format!("{}/{per_area}", units.mass_unit());
+    table.add_row(vec![
+        Cell::new("Biomass"),
+        Cell::new(format!("{:.1}", metrics.total_biomass_tons_per_acre)),
+        Cell::new(format!("{mass_per_area} dry")),
     ]);
     table.add_row(vec![
-        Cell::new("Volume (board ft)"),
-        Cell::new(format!("{:.0}", metrics.total_volume_bdft)),
-        Cell::new("bd ft/acre"),
+        Cell::new("Carbon"),
+        Cell::new(format!("{:.1}", metrics.total_carbon_tons_per_acre)),
+        Cell::new(mass_per_area),
     ]);
     table.add_row(vec![
         Cell::new("QMD"),
         Cell::new(format!("{:.1}", metrics.quadratic_mean_diameter)),
-        Cell::new("inches"),
+        Cell::new(units.diameter_unit()),
+    ]);
+    table.add_row(vec![
+        Cell::new("Stand Density Index"),
+        Cell::new(format!("{:.0}", metrics.stand_density_index)),
+        Cell::new("Reineke SDI"),
+    ]);
+    table.add_row(vec![
+        Cell::new("Relative Density"),
+        Cell::new(format!("{:.0}", metrics.relative_density)),
+        Cell::new("% of max SDI"),
     ]);
     if let Some(h) = metrics.mean_height {
         table.add_row(vec![
             Cell::new("Mean Height"),
             Cell::new(format!("{:.1}", h)),
-            Cell::new("feet"),
+            Cell::new(units.height_unit()),
+        ]);
+    }
+    if let Some(s) = metrics.mean_slenderness {
+        table.add_row(vec![
+            Cell::new("Mean Slenderness"),
+            Cell::new(format!("{:.0}", s)),
+            Cell::new("H:D ratio"),
+        ]);
+    }
+    if let Some(cr) = metrics.mean_crown_ratio {
+        table.add_row(vec![
+            Cell::new("Mean Crown Ratio"),
+            Cell::new(format!("{:.0}", cr * 100.0)),
+            Cell::new("% of height"),
         ]);
     }
     table.add_row(vec![
@@ -55,8 +167,86 @@ pub fn format_stand_summary(metrics: &StandMetrics) -> String {
         Cell::new(format!("{}", metrics.num_species)),
         Cell::new(""),
     ]);
+    table.add_row(vec![
+        Cell::new("Shannon Diversity"),
+        Cell::new(format!("{:.2}", metrics.shannon_index)),
+        Cell::new("H' by TPA"),
+    ]);
+    table.add_row(vec![
+        Cell::new("Simpson Diversity"),
+        Cell::new(format!("{:.2}", metrics.simpson_index)),
+        Cell::new("1 - D by TPA"),
+    ]);
+    let large_trees = match units {
+        UnitSystem::Imperial => format!("{LARGE_TREE_DBH:.0}\""),
+        UnitSystem::Metric => format!("{:.0} cm", units.diameter(LARGE_TREE_DBH)),
+    };
+    table.add_row(vec![
+        Cell::new("Structural Stage"),
+        Cell::new(StructuralStage::classify(metrics).to_string()),
+        Cell::new(format!(
+            "{:.0}% BA in {large_trees}+ trees",
+            metrics.ba_in_large_trees_percent
+        )),
+    ]);
+    if let Some(age) = &metrics.age {
+        table.add_row(vec![
+            Cell::new("Mean Age"),
+            Cell::new(format!("{:.0} ({}-{})", age.mean, age.min, age.max)),
+            Cell::new("years"),
+        ]);
+        table.add_row(vec![
+            Cell::new("Age Structure"),
+            Cell::new(age.structure.to_string()),
+            Cell::new(format!("{} trees cored", age.count)),
+        ]);
+    }
+    if let Some(si) = metrics.site_index {
+        table.add_row(vec![
+            Cell::new("Site Index"),
+            Cell::new(format!("{:.0}", si)),
+            Cell::new(format!(
+                "{} at age {DEFAULT_SITE_INDEX_BASE_AGE} (King DF)",
+                units.height_unit()
+            )),
+        ]);
+    }
 
     output.push_str(&table.to_string());
+
+    let snags = &metrics.snags;
+    if snags.snag_tpa > 0.0 {
+        output.push_str(&format!("\n\n{}\n", "Snags".bold().green()));
+        output.push_str(&format!("{}\n", "=".repeat(50)));
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(vec!["Metric", "Value", "Unit"]);
+        table.add_row(vec![
+            Cell::new("Snag Density"),
+            Cell::new(format!("{:.1}", snags.snag_tpa)),
+            Cell::new(units.density_unit()),
+        ]);
+        table.add_row(vec![
+            Cell::new("Snag Basal Area"),
+            Cell::new(format!("{:.1}", snags.snag_basal_area)),
+            Cell::new(format!("{}/{per_area}", units.basal_area_unit())),
+        ]);
+        table.add_row(vec![
+            Cell::new("Snag Volume"),
+            Cell::new(format!("{:.1}", snags.snag_volume_cuft)),
+            Cell::new(format!("{}/{per_area}", units.volume_unit())),
+        ]);
+        table.add_row(vec![
+            Cell::new("Mean Snag DBH"),
+            Cell::new(format!("{:.1}", snags.mean_snag_dbh)),
+            Cell::new(units.diameter_unit()),
+        ]);
+        output.push_str(&table.to_string());
+    }
     output
 }
 
@@ -65,8 +255,39 @@ pub fn print_stand_summary(metrics: &StandMetrics) {
     print!("{}", format_stand_summary(metrics));
 }
 
-/// Format species composition table as a string.
+/// Print a stand summary table with board-foot volume shown in `unit`.
+pub fn print_stand_summary_with(metrics: &StandMetrics, unit: &BoardFootUnit) {
+    print!("{}", format_stand_summary_with(metrics, unit));
+}
+
+/// Format species composition table as a string, largest basal area first.
 pub fn format_species_table(metrics: &StandMetrics) -> String {
+    format_species_table_with(metrics, SpeciesOrder::default())
+}
+
+/// Format species composition table as a string with rows in `order`.
+pub fn format_species_table_with(metrics: &StandMetrics, order: SpeciesOrder) -> String {
+    species_table(metrics, order, None)
+}
+
+/// Format species composition table as a string, showing only the `n`
+/// species with the most basal area and rolling the rest into one
+/// "Other" row. The percentage columns still sum to 100.
+pub fn format_species_table_top(metrics: &StandMetrics, n: usize) -> String {
+    format_species_table_top_with(metrics, SpeciesOrder::default(), n)
+}
+
+/// Like [`format_species_table_top`], listing the top `n` species in `order`
+/// with the "Other" row last.
+pub fn format_species_table_top_with(
+    metrics: &StandMetrics,
+    order: SpeciesOrder,
+    n: usize,
+) -> String {
+    species_table(metrics, order, Some(n))
+}
+
+fn species_table(metrics: &StandMetrics, order: SpeciesOrder, top: Option<usize>) -> String {
     let mut output = String::new();
     output.push_str(&format!("\n{}\n", "Species Composition".bold().green()));
     output.push_str(&format!("{}\n", "=".repeat(50)));
@@ -81,11 +302,16 @@ pub fn format_species_table(metrics: &StandMetrics) -> String {
         .load_preset(UTF8_FULL)
         .apply_modifier(UTF8_ROUND_CORNERS)
         .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec![
-            "Species", "Code", "TPA", "% TPA", "BA/ac", "% BA", "Mean DBH",
-        ]);
+        .set_header(species_header(metrics.units));
 
-    for sp in &metrics.species_composition {
+    let mut rows: Vec<&SpeciesComposition> = metrics.species_composition.iter().collect();
+    let mut rest: Vec<&SpeciesComposition> = Vec::new();
+    if let Some(n) = top.filter(|&n| n < rows.len()) {
+        SpeciesOrder::BasalArea.sort(&mut rows);
+        rest = rows.split_off(n);
+    }
+    order.sort(&mut rows);
+    for sp in rows {
         table.add_row(vec![
             Cell::new(&sp.species.common_name),
             Cell::new(&sp.species.code),
@@ -93,7 +319,25 @@ pub fn format_species_table(metrics: &StandMetrics) -> String {
             Cell::new(format!("{:.1}%", sp.percent_tpa)),
             Cell::new(format!("{:.1}", sp.basal_area)),
             Cell::new(format!("{:.1}%", sp.percent_basal_area)),
-            Cell::new(format!("{:.1}\"", sp.mean_dbh)),
+            Cell::new(format_dbh(sp.mean_dbh, metrics.units)),
+        ]);
+    }
+    if !rest.is_empty() {
+        let sum = |f: fn(&SpeciesComposition) -> f64| rest.iter().fold(0.0, |acc, sp| acc + f(sp));
+        let tpa = sum(|sp| sp.tpa);
+        let mean_dbh = if tpa > 0.0 {
+            sum(|sp| sp.tpa * sp.mean_dbh) / tpa
+        } else {
+            0.0
+        };
+        table.add_row(vec![
+            Cell::new(OTHER_GROUP),
+            Cell::new(""),
+            Cell::new(format!("{:.1}", tpa)),
+            Cell::new(format!("{:.1}%", sum(|sp| sp.percent_tpa))),
+            Cell::new(format!("{:.1}", sum(|sp| sp.basal_area))),
+            Cell::new(format!("{:.1}%", sum(|sp| sp.percent_basal_area))),
+            Cell::new(format_dbh(mean_dbh, metrics.units)),
         ]);
     }
 
@@ -101,25 +345,60 @@ pub fn format_species_table(metrics: &StandMetrics) -> String {
     output
 }
 
+/// Species table header with the density and basal area columns in `units`.
+fn species_header(units: UnitSystem) -> Vec<String> {
+    let (density, percent_density, basal_area) = match units {
+        UnitSystem::Imperial => ("TPA", "% TPA", "BA/ac"),
+        UnitSystem::Metric => ("Trees/ha", "% Trees", "BA/ha"),
+    };
+    vec![
+        "Species".to_string(),
+        "Code".to_string(),
+        density.to_string(),
+        percent_density.to_string(),
+        basal_area.to_string(),
+        "% BA".to_string(),
+        "Mean DBH".to_string(),
+    ]
+}
+
+/// A diameter with its unit: inch marks for imperial, "cm" for metric.
+fn format_dbh(dbh: f64, units: UnitSystem) -> String {
+    match units {
+        UnitSystem::Imperial => format!("{dbh:.1}\""),
+        UnitSystem::Metric => format!("{dbh:.1} cm"),
+    }
+}
+
 /// Print species composition table.
 pub fn print_species_table(metrics: &StandMetrics) {
     print!("{}", format_species_table(metrics));
 }
 
-/// Format sampling statistics table as a string.
-pub fn format_statistics_table(stats: &SamplingStatistics) -> String {
+/// Print species composition table with rows in `order`.
+pub fn print_species_table_with(metrics: &StandMetrics, order: SpeciesOrder) {
+    print!("{}", format_species_table_with(metrics, order));
+}
+
+/// Print species composition table with the top `n` species in `order`;
+/// see [`format_species_table_top_with`].
+pub fn print_species_table_top_with(metrics: &StandMetrics, order: SpeciesOrder, n: usize) {
+    print!("{}", format_species_table_top_with(metrics, order, n));
+}
+
+/// Format species group composition table as a string.
+pub fn format_group_table(groups: &[GroupComposition]) -> String {
     let mut output = String::new();
-    output.push_str(&format!("\n{}\n", "Sampling Statistics".bold().green()));
     output.push_str(&format!(
-        "{}\n",
-        format!(
-            "Confidence Level: {:.0}% | Sample Size: {} plots",
-            stats.tpa.confidence_level * 100.0,
-            stats.tpa.sample_size
-        )
-        .dimmed()
+        "\n{}\n",
+        "Species Group Composition".bold().green()
     ));
-    output.push_str(&format!("{}\n", "=".repeat(70)));
+    output.push_str(&format!("{}\n", "=".repeat(50)));
+
+    if groups.is_empty() {
+        output.push_str("  No species data available.\n");
+        return output;
+    }
 
     let mut table = Table::new();
     table
@@ -127,30 +406,137 @@ pub fn format_statistics_table(stats: &SamplingStatistics) -> String {
         .apply_modifier(UTF8_ROUND_CORNERS)
         .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
-            "Metric",
-            "Mean",
-            "Std Error",
-            "Lower CI",
-            "Upper CI",
-            "Samp. Error %",
+            "Group",
+            "Species",
+            "TPA",
+            "% TPA",
+            "BA/ac",
+            "% BA",
+            "Vol (cu ft/ac)",
+            "Vol (bd ft/ac)",
         ]);
 
-    let metrics = [
-        ("TPA", &stats.tpa),
-        ("Basal Area (sq ft/ac)", &stats.basal_area),
-        ("Volume (cu ft/ac)", &stats.volume_cuft),
-        ("Volume (bd ft/ac)", &stats.volume_bdft),
+    for g in groups {
+        table.add_row(vec![
+            Cell::new(&g.group),
+            Cell::new(g.species.join(", ")),
+            Cell::new(format!("{:.1}", g.tpa)),
+            Cell::new(format!("{:.1}%", g.percent_tpa)),
+            Cell::new(format!("{:.1}", g.basal_area)),
+            Cell::new(format!("{:.1}%", g.percent_basal_area)),
+            Cell::new(format!("{:.0}", g.volume_cuft)),
+            Cell::new(format!("{:.0}", g.volume_bdft)),
+        ]);
+    }
+
+    output.push_str(&table.to_string());
+    output
+}
+
+/// Print species group composition table.
+pub fn print_group_table(groups: &[GroupComposition]) {
+    print!("{}", format_group_table(groups));
+}
+
+/// Format sampling statistics table as a string.
+pub fn format_statistics_table(stats: &SamplingStatistics) -> String {
+    format_statistics_table_with(stats, None)
+}
+
+/// Format sampling statistics table, optionally checking each metric against
+/// a target sampling error.
+///
+/// With `Some(target_percent)`, a "Status" column shows `✓ OK` (green) when a
+/// metric's sampling error is within the target and `✗ HIGH` (red) otherwise.
+/// With `None`, the output matches [`format_statistics_table`].
+pub fn format_statistics_table_with(
+    stats: &SamplingStatistics,
+    target_percent: Option<f64>,
+) -> String {
+    format_statistics_table_in(stats, target_percent, UnitSystem::Imperial)
+}
+
+/// Like [`format_statistics_table_with`], with the means, standard errors and
+/// confidence bounds converted to `units`. Sampling errors and CVs are
+/// percentages and unchanged.
+pub fn format_statistics_table_in(
+    stats: &SamplingStatistics,
+    target_percent: Option<f64>,
+    units: UnitSystem,
+) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("\n{}\n", "Sampling Statistics".bold().green()));
+    let mut subtitle = format!(
+        "Confidence Level: {:.0}% | Sample Size: {} plots",
+        stats.tpa.confidence_level * 100.0,
+        stats.tpa.sample_size
+    );
+    if let Some(target) = target_percent {
+        subtitle.push_str(&format!(" | Target Error: {:.1}%", target));
+    }
+    output.push_str(&format!("{}\n", subtitle.dimmed()));
+    output.push_str(&format!("{}\n", "=".repeat(70)));
+
+    let mut header = vec![
+        "Metric",
+        "Mean",
+        "Std Error",
+        "Lower CI",
+        "Upper CI",
+        "Samp. Error %",
+        "CV%",
     ];
+    if target_percent.is_some() {
+        header.push("Status");
+    }
 
-    for (name, ci) in &metrics {
-        table.add_row(vec![
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(header);
+
+    let metrics = match units {
+        UnitSystem::Imperial => [
+            ("TPA", &stats.tpa),
+            ("Basal Area (sq ft/ac)", &stats.basal_area),
+            ("Volume (cu ft/ac)", &stats.volume_cuft),
+            ("Volume (bd ft/ac)", &stats.volume_bdft),
+        ],
+        UnitSystem::Metric => [
+            ("Trees/ha", &stats.tpa),
+            ("Basal Area (m²/ha)", &stats.basal_area),
+            ("Volume (m³/ha)", &stats.volume_cuft),
+            ("Volume (bd ft/ha)", &stats.volume_bdft),
+        ],
+    };
+    // Every statistic but the percentages scales with its metric's mean
+    let factors = [
+        units.per_area(1.0),
+        units.basal_area_per_area(1.0),
+        units.volume_per_area(1.0),
+        units.per_area(1.0),
+    ];
+
+    for ((name, ci), factor) in metrics.iter().zip(factors) {
+        let mut row = vec![
             Cell::new(name),
-            Cell::new(format!("{:.1}", ci.mean)),
-            Cell::new(format!("{:.2}", ci.std_error)),
-            Cell::new(format!("{:.1}", ci.lower)),
-            Cell::new(format!("{:.1}", ci.upper)),
+            Cell::new(format!("{:.1}", ci.mean * factor)),
+            Cell::new(format!("{:.2}", ci.std_error * factor)),
+            Cell::new(format!("{:.1}", ci.lower * factor)),
+            Cell::new(format!("{:.1}", ci.upper * factor)),
             Cell::new(format!("{:.1}%", ci.sampling_error_percent)),
-        ]);
+            Cell::new(format!("{:.1}%", ci.cv_percent)),
+        ];
+        if let Some(target) = target_percent {
+            row.push(if ci.sampling_error_percent.abs() <= target {
+                Cell::new("✓ OK").fg(Color::Green)
+            } else {
+                Cell::new("✗ HIGH").fg(Color::Red)
+            });
+        }
+        table.add_row(row);
     }
 
     output.push_str(&table.to_string());
@@ -162,33 +548,70 @@ pub fn print_statistics_table(stats: &SamplingStatistics) {
     print!("{}", format_statistics_table(stats));
 }
 
+/// Print sampling statistics table with a pass/fail status against a target error.
+pub fn print_statistics_table_with(stats: &SamplingStatistics, target_percent: Option<f64>) {
+    print!("{}", format_statistics_table_with(stats, target_percent));
+}
+
+/// Print sampling statistics table in `units`; see [`format_statistics_table_in`].
+pub fn print_statistics_table_in(
+    stats: &SamplingStatistics,
+    target_percent: Option<f64>,
+    units: UnitSystem,
+) {
+    print!(
+        "{}",
+        format_statistics_table_in(stats, target_percent, units)
+    );
+}
+
 /// Format growth projection table as a string.
 pub fn format_growth_table(projections: &[GrowthProjection]) -> String {
+    format_growth_table_with(projections, &BoardFootUnit::BoardFeet)
+}
+
+/// Format growth projection table with board-foot volume shown in `unit`.
+pub fn format_growth_table_with(projections: &[GrowthProjection], unit: &BoardFootUnit) -> String {
     let mut output = String::new();
     output.push_str(&format!("\n{}\n", "Growth Projections".bold().green()));
     output.push_str(&format!("{}\n", "=".repeat(60)));
 
+    // Only projections with ingrowth get a recruitment column
+    let recruited = projections.iter().any(|p| p.recruited_tpa > 0.0);
+    let bdft_header = match unit {
+        BoardFootUnit::BoardFeet => "Vol (bdft/ac)".to_string(),
+        BoardFootUnit::Mbf(label) => format!("Vol ({label}/ac)"),
+    };
+    let mut header = vec![
+        "Year".to_string(),
+        "TPA".to_string(),
+        "BA/ac".to_string(),
+        "Vol (cuft/ac)".to_string(),
+        bdft_header,
+    ];
+    if recruited {
+        header.push("Recruited TPA".to_string());
+    }
+
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
         .apply_modifier(UTF8_ROUND_CORNERS)
         .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec![
-            "Year",
-            "TPA",
-            "BA/ac",
-            "Vol (cuft/ac)",
-            "Vol (bdft/ac)",
-        ]);
+        .set_header(header);
 
     for proj in projections {
-        table.add_row(vec![
+        let mut row = vec![
             Cell::new(format!("{}", proj.year)),
             Cell::new(format!("{:.1}", proj.tpa)),
             Cell::new(format!("{:.1}", proj.basal_area)),
             Cell::new(format!("{:.1}", proj.volume_cuft)),
-            Cell::new(format!("{:.0}", proj.volume_bdft)),
-        ]);
+            Cell::new(unit.format(proj.volume_bdft)),
+        ];
+        if recruited {
+            row.push(Cell::new(format!("{:.1}", proj.recruited_tpa)));
+        }
+        table.add_row(row);
     }
 
     output.push_str(&table.to_string());
@@ -200,6 +623,164 @@ pub fn print_growth_table(projections: &[GrowthProjection]) {
     print!("{}", format_growth_table(projections));
 }
 
+/// Print growth projection table with board-foot volume shown in `unit`.
+pub fn print_growth_table_with(projections: &[GrowthProjection], unit: &BoardFootUnit) {
+    print!("{}", format_growth_table_with(projections, unit));
+}
+
+/// Format a harvest (cut tree) summary as a string.
+pub fn format_harvest_table(summary: &HarvestSummary) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("\n{}\n", "Harvest Summary (Cut Trees)".bold().green()));
+    output.push_str(&format!("{}\n", "=".repeat(50)));
+
+    if summary.species.is_empty() {
+        output.push_str("  No cut trees found.\n");
+        return output;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Species",
+            "Code",
+            "TPA",
+            "BA/ac",
+            "Vol/ac (cu ft)",
+            "Vol/ac (bd ft)",
+        ]);
+
+    for sp in &summary.species {
+        table.add_row(vec![
+            Cell::new(&sp.species.common_name),
+            Cell::new(&sp.species.code),
+            Cell::new(format!("{:.1}", sp.tpa)),
+            Cell::new(format!("{:.1}", sp.basal_area)),
+            Cell::new(format!("{:.1}", sp.volume_cuft)),
+            Cell::new(format!("{:.0}", sp.volume_bdft)),
+        ]);
+    }
+    table.add_row(vec![
+        Cell::new("Total"),
+        Cell::new(""),
+        Cell::new(format!("{:.1}", summary.tpa)),
+        Cell::new(format!("{:.1}", summary.basal_area)),
+        Cell::new(format!("{:.1}", summary.volume_cuft)),
+        Cell::new(format!("{:.0}", summary.volume_bdft)),
+    ]);
+
+    output.push_str(&table.to_string());
+    if let Some(pct) = summary.percent_basal_area_removed {
+        output.push_str(&format!("\n  Basal area removed: {pct:.1}% of pre-harvest\n"));
+    }
+    output
+}
+
+/// Print a harvest (cut tree) summary.
+pub fn print_harvest_table(summary: &HarvestSummary) {
+    print!("{}", format_harvest_table(summary));
+}
+
+/// Format plot QA flags as a string.
+pub fn format_plot_qa_table(flags: &[PlotQaFlag]) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("\n{}\n", "Plot QA".bold().green()));
+    output.push_str(&format!("{}\n", "=".repeat(50)));
+
+    if flags.is_empty() {
+        output.push_str(&format!("{}\n", "No problem plots found".dimmed()));
+        return output;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Plot", "Issue", "Value", "Std Devs"]);
+
+    for flag in flags {
+        table.add_row(vec![
+            Cell::new(flag.plot_id),
+            Cell::new(flag.reason.to_string()),
+            Cell::new(format!("{:.1}", flag.value)),
+            Cell::new(
+                flag.z_score
+                    .map(|z| format!("{:+.1}", z))
+                    .unwrap_or_default(),
+            ),
+        ]);
+    }
+
+    output.push_str(&table.to_string());
+    output
+}
+
+/// Print plot QA flags.
+pub fn print_plot_qa_table(flags: &[PlotQaFlag]) {
+    print!("{}", format_plot_qa_table(flags));
+}
+
+/// Format the issues found by [`crate::io::validate_file`] as a table.
+///
+/// Rows are numbered from 1, counting data rows only (not the header).
+pub fn format_validation_table(report: &ValidationReport) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\n{}\n",
+        format!("Validation: {}", report.name).bold().green()
+    ));
+    output.push_str(&format!("{}\n", "=".repeat(50)));
+
+    if report.issues.is_empty() {
+        output.push_str(&format!(
+            "{}\n",
+            format!("No issues found in {} rows", report.num_rows).dimmed()
+        ));
+        return output;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Row", "Plot", "Tree", "Field", "Severity", "Message"]);
+
+    for file_issue in &report.issues {
+        let issue = &file_issue.issue;
+        let severity = Cell::new(file_issue.severity.to_string());
+        table.add_row(vec![
+            Cell::new(issue.row_index + 1),
+            Cell::new(issue.plot_id),
+            Cell::new(issue.tree_id),
+            Cell::new(&issue.field),
+            match file_issue.severity {
+                Severity::Error => severity.fg(Color::Red),
+                Severity::Warning => severity.fg(Color::Yellow),
+            },
+            Cell::new(&issue.message),
+        ]);
+    }
+
+    output.push_str(&table.to_string());
+    output.push_str(&format!(
+        "\n{} errors, {} warnings in {} rows\n",
+        report.error_count(),
+        report.warning_count(),
+        report.num_rows
+    ));
+    output
+}
+
+/// Print the issues found by [`crate::io::validate_file`].
+pub fn print_validation_table(report: &ValidationReport) {
+    print!("{}", format_validation_table(report));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,6 +804,7 @@ mod tests {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }
     }
 
@@ -235,6 +817,11 @@ mod tests {
             elevation_ft: None,
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         }
     }
 
@@ -256,6 +843,7 @@ mod tests {
             confidence_level: 0.95,
             sample_size: 5,
             sampling_error_percent: 20.0,
+            cv_percent: 22.4,
         }
     }
 
@@ -270,6 +858,18 @@ mod tests {
         assert!(output.contains("Number of Species"));
     }
 
+    #[test]
+    fn test_format_stand_summary_snag_section() {
+        let mut inv = sample_inventory();
+        assert!(!format_stand_summary(&compute_stand_metrics(&inv)).contains("Snags"));
+
+        inv.plots[0].trees[0].status = TreeStatus::Dead;
+        let output = format_stand_summary(&compute_stand_metrics(&inv));
+        assert!(output.contains("Snags"));
+        assert!(output.contains("Snag Density"));
+        assert!(output.contains("Mean Snag DBH"));
+    }
+
     #[test]
     fn test_format_stand_summary_with_height() {
         let inv = sample_inventory();
@@ -278,6 +878,39 @@ mod tests {
         assert!(output.contains("Mean Height"));
     }
 
+    #[test]
+    fn test_format_stand_summary_with_ages() {
+        let mut inv = sample_inventory();
+        let output = format_stand_summary(&compute_stand_metrics(&inv));
+        assert!(!output.contains("Age Structure"));
+        assert!(!output.contains("Site Index"));
+
+        let trees = inv.plots.iter_mut().flat_map(|p| p.trees.iter_mut());
+        for (tree, age) in trees.zip([30, 95, 60, 140]) {
+            tree.age = Some(age);
+        }
+        let output = format_stand_summary(&compute_stand_metrics(&inv));
+        assert!(output.contains("Mean Age"));
+        assert!(output.contains("81 (30-140)"));
+        assert!(output.contains("Uneven-aged"));
+        assert!(output.contains("Site Index"));
+        assert!(output.contains("feet at age 50"));
+    }
+
+    #[test]
+    fn test_format_stand_summary_in_mbf() {
+        let metrics = compute_stand_metrics(&sample_inventory());
+        let raw = format_stand_summary(&metrics);
+        assert!(raw.contains(&format!("{:.0}", metrics.total_volume_bdft)));
+        assert!(raw.contains("bd ft/acre"));
+        assert!(!raw.contains("MBF"));
+
+        let output = format_stand_summary_with(&metrics, &BoardFootUnit::mbf());
+        assert!(output.contains("MBF/acre"));
+        assert!(output.contains(&format!("{:.1}", metrics.total_volume_bdft / 1000.0)));
+        assert!(!output.contains("bd ft/acre"));
+    }
+
     #[test]
     fn test_format_species_table_contains_headers() {
         let inv = sample_inventory();
@@ -298,6 +931,105 @@ mod tests {
         assert!(output.contains("DF"));
     }
 
+    #[test]
+    fn test_format_species_table_orders() {
+        let wrc = |plot_id, dbh| Tree {
+            species: Species {
+                common_name: "Western Red Cedar".to_string(),
+                code: "WRC".to_string(),
+            },
+            ..make_tree(plot_id, dbh)
+        };
+        let mut inv = sample_inventory();
+        // Cedar has more stems but less basal area than Douglas fir
+        inv.plots
+            .push(make_plot(3, vec![wrc(3, 6.0), wrc(3, 6.0), wrc(3, 6.0)]));
+        inv.plots[0].trees.push(Tree {
+            species: Species {
+                common_name: "bigleaf maple".to_string(),
+                code: "BM".to_string(),
+            },
+            ..make_tree(1, 4.0)
+        });
+        let metrics = compute_stand_metrics(&inv);
+        let order = |order: SpeciesOrder| {
+            let output = format_species_table_with(&metrics, order);
+            let mut codes: Vec<(usize, &str)> = ["DF", "WRC", "BM"]
+                .into_iter()
+                .map(|code| (output.find(&format!(" {code} ")).unwrap(), code))
+                .collect();
+            codes.sort();
+            codes.into_iter().map(|(_, code)| code).collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(SpeciesOrder::BasalArea), ["DF", "WRC", "BM"]);
+        assert_eq!(
+            format_species_table(&metrics),
+            format_species_table_with(&metrics, SpeciesOrder::BasalArea)
+        );
+        assert_eq!(order(SpeciesOrder::Tpa), ["DF", "WRC", "BM"]);
+        // Case-insensitive, so "bigleaf" sorts before "Douglas"
+        assert_eq!(order(SpeciesOrder::Name), ["BM", "DF", "WRC"]);
+        assert_eq!(order(SpeciesOrder::Importance), ["DF", "WRC", "BM"]);
+    }
+
+    #[test]
+    fn test_format_species_table_top_rolls_up_other() {
+        let species = |plot_id, dbh, code: &str| Tree {
+            species: Species {
+                common_name: format!("Species {code}"),
+                code: code.to_string(),
+            },
+            ..make_tree(plot_id, dbh)
+        };
+        let inv = ForestInventory {
+            plots: vec![make_plot(
+                1,
+                vec![
+                    species(1, 20.0, "AA"),
+                    species(1, 16.0, "BB"),
+                    species(1, 12.0, "CC"),
+                    species(1, 8.0, "DD"),
+                ],
+            )],
+            ..ForestInventory::new("Four")
+        };
+        let metrics = compute_stand_metrics(&inv);
+        let output = format_species_table_top(&metrics, 2);
+
+        // Data rows are those whose % TPA cell parses as a percentage
+        let pct = |cell: &str| cell.strip_suffix('%').and_then(|p| p.parse::<f64>().ok());
+        let rows: Vec<Vec<&str>> = output
+            .lines()
+            .map(|l| l.split(['│', '┆']).map(str::trim).collect::<Vec<_>>())
+            .filter(|cells| cells.len() > 4 && pct(cells[4]).is_some())
+            .collect();
+        let names: Vec<&str> = rows.iter().map(|r| r[1]).collect();
+        assert_eq!(names, ["Species AA", "Species BB", OTHER_GROUP]);
+        for col in [4, 6] {
+            let total: f64 = rows.iter().map(|r| pct(r[col]).unwrap()).sum();
+            assert!((total - 100.0).abs() < 0.2, "column {col} sums to {total}");
+        }
+
+        // Asking for every species adds no "Other" row
+        let all = format_species_table_top(&metrics, 4);
+        assert_eq!(all, format_species_table(&metrics));
+    }
+
+    #[test]
+    fn test_species_order_from_str() {
+        assert_eq!(
+            "BA".parse::<SpeciesOrder>().unwrap(),
+            SpeciesOrder::BasalArea
+        );
+        assert_eq!("name".parse::<SpeciesOrder>().unwrap(), SpeciesOrder::Name);
+        assert_eq!(
+            "Importance".parse::<SpeciesOrder>().unwrap(),
+            SpeciesOrder::Importance
+        );
+        assert!("volume".parse::<SpeciesOrder>().is_err());
+    }
+
     #[test]
     fn test_format_statistics_table_contains_fields() {
         let stats = SamplingStatistics {
@@ -313,6 +1045,153 @@ mod tests {
         assert!(output.contains("Std Error"));
         assert!(output.contains("Lower CI"));
         assert!(output.contains("Upper CI"));
+        assert!(output.contains("CV%"));
+        assert!(output.contains("22.4%"));
+    }
+
+    #[test]
+    fn test_format_statistics_table_in_metric_units() {
+        let stats = SamplingStatistics {
+            tpa: sample_ci(),
+            basal_area: sample_ci(),
+            volume_cuft: sample_ci(),
+            volume_bdft: sample_ci(),
+        };
+        let output = format_statistics_table_in(&stats, None, UnitSystem::Metric);
+        assert!(output.contains("Trees/ha"));
+        assert!(output.contains("Basal Area (m²/ha)"));
+        assert!(output.contains("Volume (m³/ha)"));
+        assert!(!output.contains("sq ft/ac"));
+        // Means convert; sampling errors and CVs stay percentages
+        assert!(output.contains("24.7"));
+        assert!(output.contains("2.3"));
+        assert!(output.contains("20.0%"));
+        assert!(output.contains("22.4%"));
+    }
+
+    #[test]
+    fn test_format_statistics_table_with_target_status() {
+        let stats = SamplingStatistics {
+            tpa: ConfidenceInterval {
+                sampling_error_percent: 8.0,
+                ..sample_ci()
+            },
+            basal_area: ConfidenceInterval {
+                sampling_error_percent: 10.0,
+                ..sample_ci()
+            },
+            volume_cuft: ConfidenceInterval {
+                sampling_error_percent: 25.0,
+                ..sample_ci()
+            },
+            volume_bdft: ConfidenceInterval {
+                sampling_error_percent: 40.0,
+                ..sample_ci()
+            },
+        };
+        let output = format_statistics_table_with(&stats, Some(10.0));
+        assert!(output.contains("Status"));
+        assert!(output.contains("Target Error: 10.0%"));
+
+        let status_of = |metric: &str| {
+            let line = output.lines().find(|l| l.contains(metric)).unwrap();
+            if line.contains("✓ OK") {
+                "OK"
+            } else if line.contains("✗ HIGH") {
+                "HIGH"
+            } else {
+                panic!("no status in row: {line}")
+            }
+        };
+        assert_eq!(status_of("TPA"), "OK");
+        assert_eq!(status_of("Basal Area"), "OK");
+        assert_eq!(status_of("cu ft"), "HIGH");
+        assert_eq!(status_of("bd ft"), "HIGH");
+    }
+
+    #[test]
+    fn test_format_statistics_table_without_target_unchanged() {
+        let stats = SamplingStatistics {
+            tpa: sample_ci(),
+            basal_area: sample_ci(),
+            volume_cuft: sample_ci(),
+            volume_bdft: sample_ci(),
+        };
+        let output = format_statistics_table_with(&stats, None);
+        assert_eq!(output, format_statistics_table(&stats));
+        assert!(!output.contains("Status"));
+    }
+
+    #[test]
+    fn test_format_group_table() {
+        let groups = vec![GroupComposition {
+            group: "Conifer".to_string(),
+            species: vec!["DF".to_string(), "WH".to_string()],
+            tpa: 40.0,
+            basal_area: 120.0,
+            volume_cuft: 3000.0,
+            volume_bdft: 15000.0,
+            percent_tpa: 80.0,
+            percent_basal_area: 90.0,
+        }];
+        let output = format_group_table(&groups);
+        assert!(output.contains("Species Group Composition"));
+        assert!(output.contains("DF, WH"));
+        assert!(output.contains("90.0%"));
+        assert!(output.contains("15000"));
+        assert!(format_group_table(&[]).contains("No species data available"));
+    }
+
+    #[test]
+    fn test_format_harvest_table() {
+        use crate::analysis::{HarvestSummary, SpeciesHarvest};
+
+        let summary = HarvestSummary {
+            tpa: 12.5,
+            basal_area: 20.0,
+            volume_cuft: 500.0,
+            volume_bdft: 2400.0,
+            percent_basal_area_removed: Some(25.0),
+            species: vec![SpeciesHarvest {
+                species: Species {
+                    common_name: "Douglas Fir".to_string(),
+                    code: "DF".to_string(),
+                },
+                tpa: 12.5,
+                basal_area: 20.0,
+                volume_cuft: 500.0,
+                volume_bdft: 2400.0,
+            }],
+        };
+        let output = format_harvest_table(&summary);
+        assert!(output.contains("Douglas Fir"));
+        assert!(output.contains("Total"));
+        assert!(output.contains("2400"));
+        assert!(output.contains("25.0% of pre-harvest"));
+
+        let none = HarvestSummary {
+            species: vec![],
+            percent_basal_area_removed: None,
+            ..summary
+        };
+        assert!(format_harvest_table(&none).contains("No cut trees found"));
+    }
+
+    #[test]
+    fn test_format_plot_qa_table() {
+        use crate::analysis::PlotQaReason;
+
+        let flags = vec![PlotQaFlag {
+            plot_id: 42,
+            reason: PlotQaReason::BasalAreaOutlier,
+            value: 310.0,
+            z_score: Some(2.8),
+        }];
+        let output = format_plot_qa_table(&flags);
+        assert!(output.contains("42"));
+        assert!(output.contains("Basal area outlier"));
+        assert!(output.contains("+2.8"));
+        assert!(format_plot_qa_table(&[]).contains("No problem plots"));
     }
 
     #[test]
@@ -324,6 +1203,7 @@ mod tests {
                 basal_area: 50.0,
                 volume_cuft: 1000.0,
                 volume_bdft: 5000.0,
+                recruited_tpa: 0.0,
             },
             GrowthProjection {
                 year: 5,
@@ -331,6 +1211,7 @@ mod tests {
                 basal_area: 55.0,
                 volume_cuft: 1100.0,
                 volume_bdft: 5500.0,
+                recruited_tpa: 0.0,
             },
         ];
         let output = format_growth_table(&projections);
@@ -341,6 +1222,24 @@ mod tests {
         assert!(output.contains("Vol (bdft/ac)"));
     }
 
+    #[test]
+    fn test_format_growth_table_recruitment_column() {
+        let mut projections = vec![GrowthProjection {
+            year: 10,
+            tpa: 95.0,
+            basal_area: 60.0,
+            volume_cuft: 1200.0,
+            volume_bdft: 6000.0,
+            recruited_tpa: 0.0,
+        }];
+        assert!(!format_growth_table(&projections).contains("Recruited TPA"));
+
+        projections[0].recruited_tpa = 12.5;
+        let output = format_growth_table(&projections);
+        assert!(output.contains("Recruited TPA"));
+        assert!(output.contains("12.5"));
+    }
+
     #[test]
     fn test_format_growth_table_contains_data() {
         let projections = vec![GrowthProjection {
@@ -349,12 +1248,32 @@ mod tests {
             basal_area: 60.0,
             volume_cuft: 1200.0,
             volume_bdft: 6000.0,
+            recruited_tpa: 0.0,
         }];
         let output = format_growth_table(&projections);
         assert!(output.contains("10"));
         assert!(output.contains("95.0"));
     }
 
+    #[test]
+    fn test_format_growth_table_in_mbf() {
+        let projections = vec![GrowthProjection {
+            year: 10,
+            tpa: 95.0,
+            basal_area: 60.0,
+            volume_cuft: 1200.0,
+            volume_bdft: 6450.0,
+            recruited_tpa: 0.0,
+        }];
+        let output = format_growth_table_with(&projections, &BoardFootUnit::mbf());
+        assert!(output.contains("Vol (MBF/ac)"));
+        assert!(output.contains("6.5"));
+        assert!(!output.contains("6450"));
+
+        let custom = BoardFootUnit::Mbf("Mbf".to_string());
+        assert!(format_growth_table_with(&projections, &custom).contains("Vol (Mbf/ac)"));
+    }
+
     #[test]
     fn test_format_growth_table_empty() {
         let output = format_growth_table(&[]);
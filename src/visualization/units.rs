@@ -0,0 +1,74 @@
+use std::fmt;
+
+/// Unit for displaying board-foot volumes in tables and summaries.
+///
+/// Analysis results are always in board feet; this only scales and labels
+/// them for display. Timber sales and appraisals usually quote thousand
+/// board feet (MBF), so `Mbf` divides by 1000 under the given label.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BoardFootUnit {
+    /// Raw board feet, shown as whole numbers
+    #[default]
+    BoardFeet,
+    /// Thousand board feet, shown with one decimal under the given label
+    Mbf(String),
+}
+
+impl BoardFootUnit {
+    /// Thousand board feet labelled "MBF".
+    pub fn mbf() -> Self {
+        BoardFootUnit::Mbf("MBF".to_string())
+    }
+
+    /// Convert a volume in board feet to this unit.
+    pub fn convert(&self, bdft: f64) -> f64 {
+        match self {
+            BoardFootUnit::BoardFeet => bdft,
+            BoardFootUnit::Mbf(_) => bdft / 1000.0,
+        }
+    }
+
+    /// Unit label, e.g. "bd ft" or "MBF".
+    pub fn label(&self) -> &str {
+        match self {
+            BoardFootUnit::BoardFeet => "bd ft",
+            BoardFootUnit::Mbf(label) => label,
+        }
+    }
+
+    /// Format a volume given in board feet as a number in this unit.
+    pub fn format(&self, bdft: f64) -> String {
+        match self {
+            BoardFootUnit::BoardFeet => format!("{bdft:.0}"),
+            BoardFootUnit::Mbf(_) => format!("{:.1}", self.convert(bdft)),
+        }
+    }
+}
+
+impl fmt::Display for BoardFootUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_feet_is_default_and_unscaled() {
+        let unit = BoardFootUnit::default();
+        assert_eq!(unit, BoardFootUnit::BoardFeet);
+        assert_eq!(unit.format(51234.4), "51234");
+        assert_eq!(unit.to_string(), "bd ft");
+    }
+
+    #[test]
+    fn test_mbf_scales_and_labels() {
+        let unit = BoardFootUnit::mbf();
+        assert!((unit.convert(51234.0) - 51.234).abs() < 1e-9);
+        assert_eq!(unit.format(51234.0), "51.2");
+        assert_eq!(unit.label(), "MBF");
+        assert_eq!(BoardFootUnit::Mbf("Mbf".into()).label(), "Mbf");
+    }
+}
@@ -0,0 +1,102 @@
+//! Client-side analysis for browser-only deployments, via `wasm-bindgen`.
+//!
+//! Enabled by the `wasm` feature. Build the module with:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown \
+//!     --no-default-features --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/forest_inventory_analyzer.wasm
+//! ```
+//!
+//! then call `analyze_csv(new Uint8Array(await file.arrayBuffer()))` from JavaScript.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::analysis::{Analyzer, DiameterClassWidth, DiameterDistribution, StandMetrics};
+use crate::error::ForestError;
+use crate::io::{csv_expansion_kind, parse_csv_lenient, rows_to_inventory, CsvEncoding, FileIssue};
+
+/// Metrics and diameter distribution for one CSV file.
+#[derive(Debug, Clone, Serialize)]
+pub struct CsvAnalysis {
+    pub name: String,
+    pub metrics: StandMetrics,
+    pub distribution: DiameterDistribution,
+    /// Row-level validation errors and warnings; flagged rows are still
+    /// included in the analysis
+    pub issues: Vec<FileIssue>,
+}
+
+/// Parse CSV bytes and compute stand metrics and a diameter distribution
+/// with the default class width.
+///
+/// This is the native function behind [`analyze_csv`]. Only CSV format
+/// errors (missing columns, unparseable values) fail; row validation errors
+/// and warnings are returned in [`CsvAnalysis::issues`]. A
+/// `# expansion_kind: count` line above the header is honoured as in
+/// [`crate::io::read_csv`].
+pub fn analyze_csv_bytes(bytes: &[u8]) -> Result<CsvAnalysis, ForestError> {
+    let (name, rows, issues) = parse_csv_lenient(bytes, "inventory")?;
+    let mut inventory = rows_to_inventory(&name, &rows);
+    inventory.expansion_kind = csv_expansion_kind(bytes, CsvEncoding::Utf8)?;
+    let analyzer = Analyzer::new(&inventory);
+    Ok(CsvAnalysis {
+        name,
+        metrics: analyzer.stand_metrics(),
+        distribution: analyzer.diameter_distribution(DiameterClassWidth::default().get()),
+        issues,
+    })
+}
+
+/// JavaScript binding for [`analyze_csv_bytes`]: returns a plain object with
+/// `name`, `metrics`, `distribution` and `issues`, or throws on a CSV error.
+#[wasm_bindgen]
+pub fn analyze_csv(bytes: &[u8]) -> Result<JsValue, JsError> {
+    let analysis = analyze_csv_bytes(bytes)?;
+    Ok(analysis.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Severity;
+    use crate::testing::SAMPLE_CSV as CSV;
+
+    #[test]
+    fn test_analyze_csv_bytes_matches_analyzer() {
+        let analysis = analyze_csv_bytes(CSV.as_bytes()).unwrap();
+        assert!(analysis.issues.is_empty());
+        assert!((analysis.metrics.total_tpa - 20.0 / 3.0).abs() < 1e-9);
+        assert_eq!(analysis.metrics.species_composition.len(), 2);
+
+        let class_tpa: f64 = analysis.distribution.classes.iter().map(|c| c.tpa).sum();
+        assert!((class_tpa - analysis.metrics.total_tpa).abs() < 1e-9);
+
+        let json = serde_json::to_value(&analysis).unwrap();
+        assert!(json["metrics"]["total_basal_area"].as_f64().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_analyze_csv_bytes_reports_row_issues() {
+        let csv = CSV.replace("9.0,60.0", "-9.0,60.0");
+        let analysis = analyze_csv_bytes(csv.as_bytes()).unwrap();
+        assert_eq!(analysis.issues.len(), 1);
+        assert_eq!(analysis.issues[0].issue.field, "dbh");
+        assert_eq!(analysis.issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_analyze_csv_bytes_honours_count_expansion_kind() {
+        let csv = format!("# expansion_kind: count\n{CSV}");
+        let analysis = analyze_csv_bytes(csv.as_bytes()).unwrap();
+        // Counts of 5 on 0.2-acre plots: 50, 25 and 25 TPA
+        assert!((analysis.metrics.total_tpa - 100.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_analyze_csv_bytes_rejects_malformed_csv() {
+        assert!(analyze_csv_bytes(b"plot_id,dbh\nx,1\n").is_err());
+    }
+}
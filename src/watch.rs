@@ -0,0 +1,166 @@
+//! Re-run work whenever an input file changes, for the CLI `--watch` flag.
+//!
+//! [`watch_file`] watches the file's directory rather than the file itself,
+//! so editors that save by writing a new file and renaming it over the old
+//! one are still seen.
+
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{Event, RecursiveMode, Watcher};
+
+use crate::error::ForestError;
+
+/// How long the file must stay quiet before [`watch_file`] reports a change.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Block until `path` changes, call `on_change`, and repeat until it returns
+/// [`ControlFlow::Break`].
+///
+/// A burst of writes is reported once, after no further event has arrived for
+/// `debounce`. `on_change` is not called for the file as it is when watching
+/// starts; run the first analysis before calling this. Fails if the file's
+/// directory cannot be watched or the watcher reports an error.
+pub fn watch_file(
+    path: &Path,
+    debounce: Duration,
+    mut on_change: impl FnMut() -> ControlFlow<()>,
+) -> Result<(), ForestError> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| ForestError::ValidationError(format!("{} is not a file", path.display())))?;
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(watch_error)?;
+
+    let is_change = |event: notify::Result<Event>| -> Result<bool, ForestError> {
+        let event = event.map_err(watch_error)?;
+        Ok((event.kind.is_create() || event.kind.is_modify())
+            && event.paths.iter().any(|p| p.file_name() == Some(file_name)))
+    };
+
+    // The channel only closes if the watcher is dropped, which it is not
+    // while this loop runs.
+    while let Ok(event) = rx.recv() {
+        if !is_change(event)? {
+            continue;
+        }
+        loop {
+            match rx.recv_timeout(debounce) {
+                Ok(event) => {
+                    is_change(event)?;
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+        if on_change().is_break() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn watch_error(e: notify::Error) -> ForestError {
+    ForestError::Io(std::io::Error::other(format!("file watch failed: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modification_triggers_callback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stand.csv");
+        std::fs::write(&path, "plot_id,tree_id\n").unwrap();
+
+        let (done_tx, done_rx) = mpsc::channel();
+        let watched = path.clone();
+        std::thread::spawn(move || {
+            let mut calls = 0;
+            let result = watch_file(&watched, Duration::from_millis(100), || {
+                calls += 1;
+                ControlFlow::Break(())
+            });
+            done_tx.send((result, calls)).unwrap();
+        });
+
+        // Keep writing until the watcher, which starts asynchronously, has
+        // seen a change; several quick writes must still give one call.
+        for _ in 0..100 {
+            for row in 0..3 {
+                std::fs::write(&path, format!("plot_id,tree_id\n1,{row}\n")).unwrap();
+            }
+            if let Ok((result, calls)) = done_rx.recv_timeout(Duration::from_millis(500)) {
+                result.unwrap();
+                assert_eq!(calls, 1);
+                return;
+            }
+        }
+        panic!("no change reported for {}", path.display());
+    }
+
+    #[test]
+    fn test_burst_of_writes_calls_continuing_callback_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stand.csv");
+        std::fs::write(&path, "plot_id,tree_id\n").unwrap();
+
+        let debounce = Duration::from_millis(200);
+        let (call_tx, call_rx) = mpsc::channel();
+        let watched = path.clone();
+        std::thread::spawn(move || {
+            let _ = watch_file(&watched, debounce, || {
+                call_tx.send(()).ok();
+                ControlFlow::Continue(())
+            });
+        });
+
+        // Wait for the watcher, which starts asynchronously, to see a write,
+        // then let that change settle before the burst.
+        let started = (0..100).any(|_| {
+            std::fs::write(&path, "plot_id,tree_id\n").unwrap();
+            call_rx.recv_timeout(Duration::from_millis(500)).is_ok()
+        });
+        assert!(started, "no change reported for {}", path.display());
+        std::thread::sleep(debounce * 3);
+        while call_rx.try_recv().is_ok() {}
+
+        for row in 0..5 {
+            std::fs::write(&path, format!("plot_id,tree_id\n1,{row}\n")).unwrap();
+        }
+        assert!(call_rx.recv_timeout(Duration::from_secs(5)).is_ok());
+        assert!(call_rx.recv_timeout(debounce * 3).is_err());
+    }
+
+    #[test]
+    fn test_other_files_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stand.csv");
+        std::fs::write(&path, "").unwrap();
+
+        let (done_tx, done_rx) = mpsc::channel();
+        let watched = path.clone();
+        std::thread::spawn(move || {
+            let _ = watch_file(&watched, Duration::from_millis(50), || {
+                done_tx.send(()).ok();
+                ControlFlow::Break(())
+            });
+        });
+        for i in 0..5 {
+            std::fs::write(dir.path().join(format!("other{i}.csv")), "x").unwrap();
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        assert!(done_rx.recv_timeout(Duration::from_millis(300)).is_err());
+    }
+}
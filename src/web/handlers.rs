@@ -1,17 +1,24 @@
 use std::borrow::Cow;
 
 use actix_multipart::Multipart;
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::analysis::{Analyzer, GrowthModel};
+use crate::analysis::{
+    Analyzer, DiameterClassWidth, DistributionOptions, GrowthModel, DEFAULT_OUTLIER_SD,
+};
 use crate::error::ForestError;
 use crate::io::{self, rows_to_inventory, EditableTreeRow};
-use crate::models::{Species, Tree, TreeStatus, ValidationIssue};
+use crate::models::{
+    ExpansionKind, ForestInventory, RenumberPolicy, Species, Tree, TreeStatus, ValidationIssue,
+    VolumeEquation,
+};
+use crate::visualization::SpeciesPalette;
 
 use super::state::AppState;
+use super::{MaxGrowthYears, MaxSdi};
 
 // ---------------------------------------------------------------------------
 // Error wrapper
@@ -40,7 +47,7 @@ impl std::fmt::Display for WebError {
 
 impl actix_web::ResponseError for WebError {
     fn error_response(&self) -> HttpResponse {
-        let (status, error_type) = match &self.0 {
+        let (status, error_type) = match self.0.without_context() {
             ForestError::ValidationError(_) | ForestError::ParseError(_) => {
                 (actix_web::http::StatusCode::BAD_REQUEST, "Bad Request")
             }
@@ -83,8 +90,24 @@ struct UploadResponse {
     num_trees: usize,
     has_errors: bool,
     errors: Vec<ValidationIssue>,
+    /// Plausible but unusual values, e.g. slender trees; these never block
+    /// the upload.
+    warnings: Vec<ValidationIssue>,
     trees: Vec<EditableTreeRow>,
     species: Vec<String>,
+    /// Per-plot totals from the rows without validation errors.
+    plot_summary: Vec<PlotSummary>,
+    /// Rows left out of `plot_summary` because they have errors.
+    skipped_rows: usize,
+}
+
+/// Live-tree totals for one plot, shown in the editor to spot bad plots.
+#[derive(Debug, Serialize, Deserialize)]
+struct PlotSummary {
+    plot_id: u32,
+    live_trees: usize,
+    tpa: f64,
+    basal_area: f64,
 }
 
 // ---------------------------------------------------------------------------
@@ -111,6 +134,44 @@ fn num_plots_from_rows(rows: &[EditableTreeRow]) -> usize {
         .len()
 }
 
+/// Summarize each plot of an inventory, ordered by plot ID.
+fn plot_summary(inventory: &ForestInventory) -> Vec<PlotSummary> {
+    let mut summary: Vec<PlotSummary> = inventory
+        .plots
+        .iter()
+        .map(|plot| {
+            let per_acre = plot.per_acre_factor(inventory.expansion_kind);
+            PlotSummary {
+                plot_id: plot.plot_id,
+                live_trees: plot.live_trees().len(),
+                tpa: plot.trees_per_acre() * per_acre,
+                basal_area: plot.basal_area_per_acre() * per_acre,
+            }
+        })
+        .collect();
+    summary.sort_by_key(|p| p.plot_id);
+    summary
+}
+
+/// Summarize plots from the editable rows that have no validation issues.
+/// Returns the summary and the number of rows skipped.
+fn plot_summary_from_rows(
+    rows: &[EditableTreeRow],
+    issues: &[ValidationIssue],
+    expansion_kind: ExpansionKind,
+) -> (Vec<PlotSummary>, usize) {
+    let bad: std::collections::HashSet<usize> = issues.iter().map(|i| i.row_index).collect();
+    let valid: Vec<EditableTreeRow> = rows
+        .iter()
+        .filter(|r| !bad.contains(&r.row_index))
+        .cloned()
+        .collect();
+    let skipped = rows.len() - valid.len();
+    let mut inventory = rows_to_inventory("", &valid);
+    inventory.expansion_kind = expansion_kind;
+    (plot_summary(&inventory), skipped)
+}
+
 /// Sanitize a filename for use in Content-Disposition headers.
 /// Removes characters that could enable header injection or path traversal.
 fn sanitize_filename(name: &str) -> String {
@@ -163,7 +224,7 @@ pub async fn upload(
             .unwrap_or(&filename)
             .to_string();
 
-        let (inv_name, rows, issues) = match ext.as_str() {
+        let (inv_name, rows, file_issues) = match ext.as_str() {
             "csv" => io::parse_csv_lenient(&bytes, &name)?,
             "json" => io::parse_json_lenient(&bytes, &name)?,
             "xlsx" | "xls" => io::parse_excel_lenient(&bytes, &name)?,
@@ -174,27 +235,44 @@ pub async fn upload(
                 }));
             }
         };
+        let expansion_kind = match ext.as_str() {
+            "csv" => io::csv_expansion_kind(&bytes, io::CsvEncoding::Utf8)?,
+            "json" => io::json_expansion_kind(&bytes)?,
+            _ => io::excel_expansion_kind(&bytes)?,
+        };
+
+        let (errors, warnings): (Vec<_>, Vec<_>) = file_issues
+            .into_iter()
+            .partition(|f| f.severity == io::Severity::Error);
+        let errors: Vec<ValidationIssue> = errors.into_iter().map(|f| f.issue).collect();
+        let warnings = warnings.into_iter().map(|f| f.issue).collect();
 
         let id = Uuid::new_v4();
-        let has_errors = !issues.is_empty();
+        let has_errors = !errors.is_empty();
 
         if has_errors {
             // Store pending rows for later revalidation
+            let (plot_summary, skipped_rows) =
+                plot_summary_from_rows(&rows, &errors, expansion_kind);
             let resp = UploadResponse {
                 id,
                 name: inv_name.clone(),
                 num_plots: num_plots_from_rows(&rows),
                 num_trees: rows.len(),
                 has_errors: true,
-                errors: issues,
+                errors,
+                warnings,
                 trees: rows.clone(),
                 species: species_from_rows(&rows),
+                plot_summary,
+                skipped_rows,
             };
-            state.insert_pending(id, inv_name, rows)?;
+            state.insert_pending(id, inv_name, expansion_kind, rows)?;
             return Ok(HttpResponse::Ok().json(resp));
         } else {
             // No errors — build inventory and store it
-            let inventory = rows_to_inventory(&inv_name, &rows);
+            let mut inventory = rows_to_inventory(&inv_name, &rows);
+            inventory.expansion_kind = expansion_kind;
             let resp = UploadResponse {
                 id,
                 name: inventory.name.clone(),
@@ -202,12 +280,15 @@ pub async fn upload(
                 num_trees: inventory.num_trees(),
                 has_errors: false,
                 errors: vec![],
+                warnings,
                 trees: vec![],
                 species: inventory
                     .species_list()
                     .into_iter()
                     .map(|s| s.common_name)
                     .collect(),
+                plot_summary: plot_summary(&inventory),
+                skipped_rows: 0,
             };
             state.insert_inventory(id, inventory)?;
             return Ok(HttpResponse::Ok().json(resp));
@@ -224,6 +305,56 @@ pub async fn upload(
 // Validate & submit endpoint
 // ---------------------------------------------------------------------------
 
+/// Build the [`Tree`] an editable row describes; an unknown status reads as live.
+fn row_tree(row: &EditableTreeRow) -> Tree {
+    let status: TreeStatus = row.status.parse().unwrap_or(TreeStatus::Live);
+    Tree {
+        tree_id: row.tree_id,
+        plot_id: row.plot_id,
+        species: Species {
+            code: row.species_code.clone(),
+            common_name: row.species_name.clone(),
+        },
+        dbh: row.dbh,
+        height: row.height,
+        crown_ratio: row.crown_ratio,
+        status,
+        expansion_factor: row.expansion_factor,
+        age: row.age,
+        defect: row.defect,
+        decay_class: row.decay_class,
+    }
+}
+
+/// Collect [`Tree::warnings`] for each row.
+fn row_warnings(rows: &[EditableTreeRow]) -> Vec<ValidationIssue> {
+    rows.iter()
+        .flat_map(|row| row_tree(row).warnings(row.row_index))
+        .collect()
+}
+
+/// Check each row's status and tree values, as the validate endpoint does.
+fn validate_rows(rows: &[EditableTreeRow]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for row in rows {
+        // Check status validity
+        if row.status.parse::<TreeStatus>().is_err() {
+            issues.push(ValidationIssue {
+                plot_id: row.plot_id,
+                tree_id: row.tree_id,
+                row_index: row.row_index,
+                field: Cow::Borrowed("status"),
+                message: Cow::Owned(format!("Unknown tree status '{}'", row.status)),
+            });
+        }
+
+        issues.extend(row_tree(row).validate_all(row.row_index));
+    }
+
+    issues
+}
+
 #[derive(Deserialize)]
 pub struct ValidateRequest {
     id: Uuid,
@@ -242,50 +373,20 @@ pub async fn validate_and_submit(
         }));
     }
 
-    let mut all_issues = Vec::new();
-
-    for row in &body.trees {
-        // Check status validity
-        if row.status.parse::<TreeStatus>().is_err() {
-            all_issues.push(ValidationIssue {
-                plot_id: row.plot_id,
-                tree_id: row.tree_id,
-                row_index: row.row_index,
-                field: Cow::Borrowed("status"),
-                message: Cow::Owned(format!("Unknown tree status '{}'", row.status)),
-            });
-        }
-
-        // Build a Tree to validate
-        let status: TreeStatus = row.status.parse().unwrap_or(TreeStatus::Live);
-        let tree = Tree {
-            tree_id: row.tree_id,
-            plot_id: row.plot_id,
-            species: Species {
-                code: row.species_code.clone(),
-                common_name: row.species_name.clone(),
-            },
-            dbh: row.dbh,
-            height: row.height,
-            crown_ratio: row.crown_ratio,
-            status,
-            expansion_factor: row.expansion_factor,
-            age: row.age,
-            defect: row.defect,
-        };
-
-        all_issues.extend(tree.validate_all(row.row_index));
-    }
+    let all_issues = validate_rows(&body.trees);
+    let warnings = row_warnings(&body.trees);
 
     let has_errors = !all_issues.is_empty();
 
     if has_errors {
-        // Update pending rows, preserving the original name
-        let name = state
-            .get_pending_name(&body.id)?
-            .unwrap_or_else(|| "Unknown".to_string());
-        state.insert_pending(body.id, name.clone(), body.trees.clone())?;
-
+        // Update pending rows, preserving the original name and expansion kind
+        let (name, expansion_kind) = state
+            .get_pending_header(&body.id)?
+            .unwrap_or_else(|| ("Unknown".to_string(), ExpansionKind::default()));
+        state.insert_pending(body.id, name.clone(), expansion_kind, body.trees.clone())?;
+
+        let (plot_summary, skipped_rows) =
+            plot_summary_from_rows(&body.trees, &all_issues, expansion_kind);
         let resp = UploadResponse {
             id: body.id,
             name,
@@ -293,17 +394,21 @@ pub async fn validate_and_submit(
             num_trees: body.trees.len(),
             has_errors: true,
             errors: all_issues,
+            warnings,
             trees: body.trees.clone(),
             species: species_from_rows(&body.trees),
+            plot_summary,
+            skipped_rows,
         };
         Ok(HttpResponse::Ok().json(resp))
     } else {
         // Clean — build inventory, move from pending to inventories
-        let name = state
+        let (name, expansion_kind) = state
             .remove_pending(&body.id)?
-            .map(|(n, _)| n)
-            .unwrap_or_else(|| "Unknown".to_string());
-        let inventory = rows_to_inventory(&name, &body.trees);
+            .map(|(n, kind, _)| (n, kind))
+            .unwrap_or_else(|| ("Unknown".to_string(), ExpansionKind::default()));
+        let mut inventory = rows_to_inventory(&name, &body.trees);
+        inventory.expansion_kind = expansion_kind;
         let resp = UploadResponse {
             id: body.id,
             name: inventory.name.clone(),
@@ -311,12 +416,15 @@ pub async fn validate_and_submit(
             num_trees: inventory.num_trees(),
             has_errors: false,
             errors: vec![],
+            warnings,
             trees: vec![],
             species: inventory
                 .species_list()
                 .into_iter()
                 .map(|s| s.common_name)
                 .collect(),
+            plot_summary: plot_summary(&inventory),
+            skipped_rows: 0,
         };
         state.insert_inventory(body.id, inventory)?;
         Ok(HttpResponse::Ok().json(resp))
@@ -752,10 +860,10 @@ pub async fn autofix(
 
     // Persist the fixed rows back to pending storage so the latest state
     // survives browser refresh or network retries.
-    let name = state
-        .get_pending_name(&body.id)?
-        .unwrap_or_else(|| "Unknown".to_string());
-    state.insert_pending(body.id, name, rows.clone())?;
+    let (name, expansion_kind) = state
+        .get_pending_header(&body.id)?
+        .unwrap_or_else(|| ("Unknown".to_string(), ExpansionKind::default()));
+    state.insert_pending(body.id, name, expansion_kind, rows.clone())?;
 
     Ok(HttpResponse::Ok().json(AutofixResponse {
         trees: rows,
@@ -784,18 +892,59 @@ struct MetricsResponse {
     metrics: crate::analysis::StandMetrics,
     #[serde(skip_serializing_if = "Option::is_none")]
     stands: Option<Vec<StandSummary>>,
+    /// Chart color for each species code present in the composition
+    species_colors: std::collections::BTreeMap<String, String>,
+}
+
+/// Volume equation coefficients accepted as query parameters; any that are
+/// omitted keep their default value.
+#[derive(Deserialize)]
+pub struct MetricsQuery {
+    cuft_b1: Option<f64>,
+    bdft_b1: Option<f64>,
+    bdft_b2: Option<f64>,
+    bdft_min_dbh: Option<f64>,
+}
+
+impl MetricsQuery {
+    fn volume_equation(&self) -> Result<VolumeEquation, ForestError> {
+        let default = VolumeEquation::default();
+        let eq = VolumeEquation {
+            cuft_b1: self.cuft_b1.unwrap_or(default.cuft_b1),
+            bdft_b1: self.bdft_b1.unwrap_or(default.bdft_b1),
+            bdft_b2: self.bdft_b2.unwrap_or(default.bdft_b2),
+            bdft_min_dbh: self.bdft_min_dbh.unwrap_or(default.bdft_min_dbh),
+        };
+        eq.validate()?;
+        Ok(eq)
+    }
+}
+
+/// The maximum SDI registered as `web::Data<`[`MaxSdi`]`>`, or its default.
+fn registered_max_sdi(req: &HttpRequest) -> f64 {
+    req.app_data::<web::Data<MaxSdi>>()
+        .map(|m| *m.get_ref())
+        .unwrap_or_default()
+        .0
 }
 
+/// Metrics for an inventory. Species colors come from a registered
+/// `web::Data<SpeciesPalette>` if present, otherwise the default palette, and
+/// relative density from a registered `web::Data<`[`MaxSdi`]`>` or its default.
 pub async fn metrics(
+    req: HttpRequest,
     state: web::Data<AppState>,
     path: web::Path<Uuid>,
+    query: web::Query<MetricsQuery>,
 ) -> Result<HttpResponse, WebError> {
     let id = path.into_inner();
     let inventory = state
         .get_inventory(&id)?
         .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
-    let analyzer = Analyzer::new(&inventory);
-    let metrics = analyzer.stand_metrics();
+    let volume_equation = query.volume_equation()?;
+    let max_sdi = registered_max_sdi(&req);
+    let analyzer = Analyzer::new(&inventory).with_volume_equation(volume_equation.clone());
+    let metrics = analyzer.stand_metrics().with_max_sdi(max_sdi);
 
     let stand_list = inventory.stands();
     let stands = if stand_list.is_empty() {
@@ -805,7 +954,8 @@ pub async fn metrics(
             stand_list
                 .iter()
                 .map(|(sid, sub_inv)| {
-                    let sm = crate::analysis::compute_stand_metrics(sub_inv);
+                    let sm = crate::analysis::compute_stand_metrics_with(sub_inv, &volume_equation)
+                        .with_max_sdi(max_sdi);
                     StandSummary {
                         stand_id: *sid,
                         num_plots: sub_inv.num_plots(),
@@ -820,7 +970,26 @@ pub async fn metrics(
         )
     };
 
-    Ok(HttpResponse::Ok().json(MetricsResponse { metrics, stands }))
+    let default_palette = SpeciesPalette::default();
+    let palette = req
+        .app_data::<web::Data<SpeciesPalette>>()
+        .map(|p| p.get_ref())
+        .unwrap_or(&default_palette);
+    let species_colors = metrics
+        .species_composition
+        .iter()
+        .map(|sc| {
+            let code = sc.species.code.clone();
+            let color = palette.color_for(&code).to_string();
+            (code, color)
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(MetricsResponse {
+        metrics,
+        stands,
+        species_colors,
+    }))
 }
 
 #[derive(Deserialize)]
@@ -838,6 +1007,12 @@ pub async fn statistics(
         .get_inventory(&id)?
         .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
     let confidence = query.confidence.unwrap_or(0.95);
+    // Out-of-range confidence is a client error, not an analysis failure.
+    if !(0.0 < confidence && confidence < 1.0) {
+        return Err(WebError(ForestError::ValidationError(format!(
+            "confidence must be strictly between 0 and 1, got {confidence}"
+        ))));
+    }
     let analyzer = Analyzer::new(&inventory);
     let stats = analyzer.sampling_statistics(confidence)?;
     Ok(HttpResponse::Ok().json(stats))
@@ -846,6 +1021,9 @@ pub async fn statistics(
 #[derive(Deserialize)]
 pub struct DistQuery {
     class_width: Option<f64>,
+    min_dbh: Option<f64>,
+    status: Option<String>,
+    ingrowth: Option<bool>,
 }
 
 pub async fn distribution(
@@ -857,37 +1035,118 @@ pub async fn distribution(
     let inventory = state
         .get_inventory(&id)?
         .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
-    let class_width = query.class_width.unwrap_or(2.0);
+    let options = DistributionOptions {
+        class_width: DiameterClassWidth::new(query.class_width.unwrap_or(2.0))?,
+        min_dbh: query.min_dbh,
+        status_filter: match &query.status {
+            Some(status) => status.parse::<TreeStatus>()?,
+            None => TreeStatus::Live,
+        },
+        include_ingrowth_class: query.ingrowth.unwrap_or(false),
+    };
+    let dist = Analyzer::new(&inventory).diameter_distribution_with(&options)?;
+    Ok(HttpResponse::Ok().json(dist))
+}
+
+#[derive(Deserialize)]
+pub struct QaQuery {
+    k: Option<f64>,
+}
+
+pub async fn plot_qa(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<QaQuery>,
+) -> Result<HttpResponse, WebError> {
+    let id = path.into_inner();
+    let inventory = state
+        .get_inventory(&id)?
+        .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
+    let k = query.k.unwrap_or(DEFAULT_OUTLIER_SD);
+    if k.is_nan() || k <= 0.0 {
+        return Err(WebError(ForestError::ValidationError(format!(
+            "k must be positive, got {k}"
+        ))));
+    }
     let analyzer = Analyzer::new(&inventory);
-    Ok(HttpResponse::Ok().json(analyzer.diameter_distribution(class_width)))
+    Ok(HttpResponse::Ok().json(analyzer.plot_qa(k)))
 }
 
 #[derive(Deserialize)]
 pub struct GrowthRequest {
     model: GrowthModel,
     years: u32,
+    /// Volume coefficients for the starting volumes; defaults if absent
+    #[serde(default)]
+    volume_equation: Option<VolumeEquation>,
 }
 
+/// Growth projection for an inventory. `years` above the registered
+/// `web::Data<`[`MaxGrowthYears`]`>` (or its default) is rejected with 400.
 pub async fn growth(
+    req: HttpRequest,
     state: web::Data<AppState>,
     path: web::Path<Uuid>,
     body: web::Json<GrowthRequest>,
 ) -> Result<HttpResponse, WebError> {
+    let max_years = req
+        .app_data::<web::Data<MaxGrowthYears>>()
+        .map(|m| *m.get_ref())
+        .unwrap_or_default();
+    if body.years > max_years.0 {
+        return Err(WebError(ForestError::ValidationError(format!(
+            "years must be at most {max_years}, got {}",
+            body.years
+        ))));
+    }
     let id = path.into_inner();
     let inventory = state
         .get_inventory(&id)?
         .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
-    let analyzer = Analyzer::new(&inventory);
-    let projections = analyzer.project_growth(&body.model, body.years)?;
+    let GrowthRequest {
+        model,
+        years,
+        volume_equation,
+    } = body.into_inner();
+    let projections = run_blocking(move || {
+        let mut analyzer = Analyzer::new(&inventory);
+        if let Some(eq) = volume_equation {
+            eq.validate()?;
+            analyzer = analyzer.with_volume_equation(eq);
+        }
+        analyzer.project_growth(&model, years)
+    })
+    .await?;
     Ok(HttpResponse::Ok().json(projections))
 }
 
+/// Run CPU-heavy analysis on actix's blocking thread pool so long
+/// computations don't stall the async worker serving other requests.
+async fn run_blocking<T, F>(f: F) -> Result<T, WebError>
+where
+    F: FnOnce() -> Result<T, ForestError> + Send + 'static,
+    T: Send + 'static,
+{
+    web::block(f)
+        .await
+        .map_err(|e| WebError(ForestError::AnalysisError(format!("analysis failed: {e}"))))?
+        .map_err(WebError)
+}
+
 #[derive(Deserialize)]
 pub struct ExportQuery {
     format: Option<String>,
+    /// Class width in inches for `format=distribution`; defaults to 2
+    class_width: Option<f64>,
 }
 
+/// Export the inventory data (`csv`, `json`, `geojson`) or computed analysis:
+/// `metrics` (stand and species metrics as a one-row CSV whose columns are
+/// the keys of
+/// [`StandMetrics::to_flat_map`](crate::analysis::StandMetrics::to_flat_map)),
+/// `metrics-json`, or `distribution` (live-tree diameter classes as CSV).
 pub async fn export(
+    req: HttpRequest,
     state: web::Data<AppState>,
     path: web::Path<Uuid>,
     query: web::Query<ExportQuery>,
@@ -897,6 +1156,9 @@ pub async fn export(
         .get_inventory(&id)?
         .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
     let fmt = query.format.as_deref().unwrap_or("csv");
+    let safe_name = sanitize_filename(&inventory.name);
+    let analyzer = Analyzer::new(&inventory);
+    let max_sdi = registered_max_sdi(&req);
 
     match fmt {
         "csv" => {
@@ -907,50 +1169,97 @@ pub async fn export(
                         .map_err(|e| WebError(ForestError::Csv(e)))?;
                 }
             }
-            let data = wtr
-                .into_inner()
-                .map_err(|e| WebError(ForestError::Io(std::io::Error::other(e.to_string()))))?;
-            let safe_name = sanitize_filename(&inventory.name);
-            Ok(HttpResponse::Ok()
-                .content_type("text/csv")
-                .insert_header((
-                    "Content-Disposition",
-                    format!("attachment; filename=\"{}.csv\"", safe_name),
-                ))
-                .body(data))
+            let data = finish_csv(wtr)?;
+            Ok(attachment("text/csv", &format!("{safe_name}.csv"), data))
         }
         "json" => {
-            let data = serde_json::to_string_pretty(&inventory)
-                .map_err(|e| WebError(ForestError::Json(e)))?;
-            let safe_name = sanitize_filename(&inventory.name);
-            Ok(HttpResponse::Ok()
-                .content_type("application/json")
-                .insert_header((
-                    "Content-Disposition",
-                    format!("attachment; filename=\"{}.json\"", safe_name),
-                ))
-                .body(data))
+            let mut data = Vec::new();
+            io::write_json_to(&inventory, &mut data, true)?;
+            Ok(attachment(
+                "application/json",
+                &format!("{safe_name}.json"),
+                data,
+            ))
         }
         "geojson" => {
-            let collection = io::build_geojson_value(&inventory);
+            let collection = io::build_geojson_value(&inventory)?;
             let data = serde_json::to_string_pretty(&collection)
                 .map_err(|e| WebError(ForestError::Json(e)))?;
-            let safe_name = sanitize_filename(&inventory.name);
-            Ok(HttpResponse::Ok()
-                .content_type("application/geo+json")
-                .insert_header((
-                    "Content-Disposition",
-                    format!("attachment; filename=\"{}.geojson\"", safe_name),
-                ))
-                .body(data))
+            Ok(attachment(
+                "application/geo+json",
+                &format!("{safe_name}.geojson"),
+                data,
+            ))
+        }
+        "metrics" => {
+            let flat = analyzer.stand_metrics().with_max_sdi(max_sdi).to_flat_map();
+            let mut wtr = csv::Writer::from_writer(Vec::new());
+            wtr.write_record(flat.keys())
+                .map_err(|e| WebError(ForestError::Csv(e)))?;
+            wtr.write_record(flat.values().map(|v| v.to_string()))
+                .map_err(|e| WebError(ForestError::Csv(e)))?;
+            let data = finish_csv(wtr)?;
+            Ok(attachment(
+                "text/csv",
+                &format!("{safe_name}_metrics.csv"),
+                data,
+            ))
+        }
+        "metrics-json" => {
+            let metrics = analyzer.stand_metrics().with_max_sdi(max_sdi);
+            let data = serde_json::to_string_pretty(&metrics)
+                .map_err(|e| WebError(ForestError::Json(e)))?;
+            Ok(attachment(
+                "application/json",
+                &format!("{safe_name}_metrics.json"),
+                data,
+            ))
+        }
+        "distribution" => {
+            let options = DistributionOptions {
+                class_width: DiameterClassWidth::new(query.class_width.unwrap_or(2.0))?,
+                ..Default::default()
+            };
+            let dist = analyzer.diameter_distribution_with(&options)?;
+            let mut wtr = csv::Writer::from_writer(Vec::new());
+            for class in &dist.classes {
+                wtr.serialize(class)
+                    .map_err(|e| WebError(ForestError::Csv(e)))?;
+            }
+            let data = finish_csv(wtr)?;
+            Ok(attachment(
+                "text/csv",
+                &format!("{safe_name}_distribution.csv"),
+                data,
+            ))
         }
         _ => Ok(HttpResponse::BadRequest().json(ErrorBody {
             error: "Bad Request".to_string(),
-            details: format!("Unsupported export format: {fmt}. Use csv, json, or geojson."),
+            details: format!(
+                "Unsupported export format: {fmt}. \
+                 Use csv, json, geojson, metrics, metrics-json, or distribution."
+            ),
         })),
     }
 }
 
+/// Flush an in-memory CSV writer and return its bytes.
+fn finish_csv(wtr: csv::Writer<Vec<u8>>) -> Result<Vec<u8>, WebError> {
+    wtr.into_inner()
+        .map_err(|e| WebError(ForestError::Io(std::io::Error::other(e.to_string()))))
+}
+
+/// A download response with the given content type and file name.
+fn attachment(content_type: &str, file_name: &str, body: impl Into<Vec<u8>>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(content_type.to_string())
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"{file_name}\""),
+        ))
+        .body(body.into())
+}
+
 #[derive(serde::Serialize)]
 struct CsvExportRow {
     plot_id: u32,
@@ -1003,6 +1312,110 @@ pub async fn inventory_json(
     Ok(HttpResponse::Ok().json(inventory))
 }
 
+#[derive(Deserialize)]
+pub struct RenumberQuery {
+    /// `keep_order` (default) or `sort_by_id`
+    #[serde(default)]
+    policy: RenumberPolicy,
+}
+
+/// Renumber an inventory's plots and trees sequentially, store the result
+/// under the same id and return it.
+pub async fn renumber(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    query: web::Query<RenumberQuery>,
+) -> Result<HttpResponse, WebError> {
+    let id = path.into_inner();
+    let mut inventory = state
+        .get_inventory(&id)?
+        .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
+    inventory.renumber(query.policy);
+    state.insert_inventory(id, inventory.clone())?;
+    Ok(HttpResponse::Ok().json(inventory))
+}
+
+#[derive(Deserialize)]
+pub struct AppendRequest {
+    trees: Vec<EditableTreeRow>,
+}
+
+#[derive(Debug, Serialize)]
+struct AppendResponse {
+    id: Uuid,
+    num_plots: usize,
+    num_trees: usize,
+    plots_added: usize,
+    trees_added: usize,
+}
+
+/// Validate rows and merge them into a stored inventory: trees on existing
+/// plot ids are appended to those plots and other plot ids become new plots.
+///
+/// Rejects the whole request with 400 if any row fails validation or would
+/// duplicate a `(plot_id, tree_id)` already in the inventory or the request.
+pub async fn append(
+    state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    body: web::Json<AppendRequest>,
+) -> Result<HttpResponse, WebError> {
+    let id = path.into_inner();
+    let issues = validate_rows(&body.trees);
+    if !issues.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Bad Request",
+            "details": format!("{} validation issue(s) in appended rows", issues.len()),
+            "errors": issues,
+        })));
+    }
+
+    let added = state.update_inventory(&id, |inventory| {
+        let mut seen: std::collections::HashSet<(u32, u32)> = inventory
+            .plots
+            .iter()
+            .flat_map(|p| p.trees.iter().map(|t| (t.plot_id, t.tree_id)))
+            .collect();
+        for row in &body.trees {
+            if !seen.insert((row.plot_id, row.tree_id)) {
+                return Err(ForestError::ValidationError(format!(
+                    "Tree {} on plot {} already exists",
+                    row.tree_id, row.plot_id
+                )));
+            }
+        }
+
+        // Appended expansion factors are read in the stored inventory's kind
+        let mut appended = rows_to_inventory("", &body.trees);
+        appended.expansion_kind = inventory.expansion_kind;
+
+        let mut plots_added = 0;
+        for plot in appended.plots {
+            let existing = inventory
+                .plots
+                .iter_mut()
+                .find(|p| p.plot_id == plot.plot_id);
+            match existing {
+                Some(existing) => existing.trees.extend(plot.trees),
+                None => {
+                    inventory.plots.push(plot);
+                    plots_added += 1;
+                }
+            }
+        }
+        Ok((plots_added, inventory.num_plots(), inventory.num_trees()))
+    })?;
+    let (plots_added, num_plots, num_trees) = added
+        .ok_or_else(|| WebError(ForestError::NotFound(format!("Inventory {id} not found"))))?;
+
+    Ok(HttpResponse::Ok().json(AppendResponse {
+        id,
+        num_plots,
+        num_trees,
+        plots_added,
+        trees_added: body.trees.len(),
+    }))
+}
+
 // ---------------------------------------------------------------------------
 // Health check
 // ---------------------------------------------------------------------------
@@ -1046,7 +1459,7 @@ mod tests {
     use actix_web::App;
 
     use crate::io::EditableTreeRow;
-    use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus};
+    use crate::models::{ExpansionKind, ForestInventory, Plot, Species, Tree, TreeStatus};
 
     fn sample_inventory(name: &str) -> ForestInventory {
         let mut inv = ForestInventory::new(name);
@@ -1071,6 +1484,7 @@ mod tests {
                     expansion_factor: 5.0,
                     age: Some(60),
                     defect: None,
+                    decay_class: None,
                 },
                 Tree {
                     tree_id: 2,
@@ -1086,9 +1500,15 @@ mod tests {
                     expansion_factor: 5.0,
                     age: Some(80),
                     defect: None,
+                    decay_class: None,
                 },
             ],
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         });
         inv.plots.push(Plot {
             plot_id: 2,
@@ -1110,8 +1530,14 @@ mod tests {
                 expansion_factor: 5.0,
                 age: Some(70),
                 defect: None,
+                decay_class: None,
             }],
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         });
         inv
     }
@@ -1134,6 +1560,12 @@ mod tests {
             slope_percent: None,
             aspect_degrees: None,
             elevation_ft: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+            decay_class: None,
         }]
     }
 
@@ -1161,9 +1593,12 @@ mod tests {
             .route("/api/{id}/metrics", web::get().to(metrics))
             .route("/api/{id}/statistics", web::get().to(statistics))
             .route("/api/{id}/distribution", web::get().to(distribution))
+            .route("/api/{id}/qa", web::get().to(plot_qa))
             .route("/api/{id}/growth", web::post().to(growth))
             .route("/api/{id}/export", web::get().to(export))
             .route("/api/{id}/inventory", web::get().to(inventory_json))
+            .route("/api/{id}/renumber", web::post().to(renumber))
+            .route("/api/{id}/append", web::post().to(append))
     }
 
     // -----------------------------------------------------------------------
@@ -1188,6 +1623,94 @@ mod tests {
         let body: serde_json::Value = actix_test::read_body_json(resp).await;
         assert!(body["total_tpa"].as_f64().unwrap() > 0.0);
         assert!(body["total_basal_area"].as_f64().unwrap() > 0.0);
+        assert!(body["stand_density_index"].as_f64().unwrap() > 0.0);
+        assert!(body["relative_density"].as_f64().unwrap() > 0.0);
+        assert!(body["shannon_index"].as_f64().unwrap() > 0.0);
+        assert!(body["simpson_index"].as_f64().unwrap() > 0.0);
+        assert!(body["total_carbon_tons_per_acre"].as_f64().unwrap() > 0.0);
+    }
+
+    #[actix_web::test]
+    async fn test_metrics_and_export_use_registered_max_sdi() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Test"))
+            .unwrap();
+
+        let max_sdi = web::Data::new(MaxSdi(300.0));
+        let app = actix_test::init_service(make_app(state).app_data(max_sdi)).await;
+        for uri in [
+            format!("/api/{id}/metrics"),
+            format!("/api/{id}/export?format=metrics-json"),
+        ] {
+            let req = actix_test::TestRequest::get().uri(&uri).to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), 200);
+            let body: serde_json::Value = actix_test::read_body_json(resp).await;
+            let sdi = body["stand_density_index"].as_f64().unwrap();
+            let relative = body["relative_density"].as_f64().unwrap();
+            assert!((relative - sdi / 300.0 * 100.0).abs() < 1e-9, "{uri}");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_metrics_custom_volume_equation() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Regional"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/metrics"))
+            .to_request();
+        let default: serde_json::Value =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/metrics?cuft_b1=0.003&bdft_b1=0.015"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let custom: serde_json::Value = actix_test::read_body_json(resp).await;
+
+        assert_ne!(custom["total_volume_cuft"], default["total_volume_cuft"]);
+        assert_ne!(custom["total_volume_bdft"], default["total_volume_bdft"]);
+        assert_eq!(custom["total_basal_area"], default["total_basal_area"]);
+
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/metrics?cuft_b1=-1"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+    }
+
+    #[actix_web::test]
+    async fn test_metrics_species_colors_from_palette() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Colors"))
+            .unwrap();
+        let palette = SpeciesPalette::new().with_color("DF", "#123456").unwrap();
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .app_data(web::Data::new(palette))
+                .route("/api/{id}/metrics", web::get().to(metrics)),
+        )
+        .await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/metrics"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["species_colors"]["DF"], "#123456");
     }
 
     #[actix_web::test]
@@ -1227,6 +1750,23 @@ mod tests {
         assert!(body["tpa"]["mean"].as_f64().is_some());
     }
 
+    #[actix_web::test]
+    async fn test_statistics_confidence_out_of_range() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Stats"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/statistics?confidence=1.5"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 400);
+    }
+
     #[actix_web::test]
     async fn test_statistics_not_found() {
         let state = super::super::state::AppState::new_in_memory().unwrap();
@@ -1263,66 +1803,248 @@ mod tests {
         assert!(body["classes"].as_array().is_some());
     }
 
-    // -----------------------------------------------------------------------
-    // Growth endpoint
-    // -----------------------------------------------------------------------
-
     #[actix_web::test]
-    async fn test_growth_success() {
+    async fn test_distribution_status_and_min_dbh() {
         let state = super::super::state::AppState::new_in_memory().unwrap();
         let id = Uuid::new_v4();
-        state
-            .insert_inventory(id, sample_inventory("Growth"))
-            .unwrap();
+        let mut inv = sample_inventory("Snags");
+        inv.plots[0].trees[1].status = TreeStatus::Dead;
+        state.insert_inventory(id, inv).unwrap();
 
         let app = actix_test::init_service(make_app(state)).await;
-        let req = actix_test::TestRequest::post()
-            .uri(&format!("/api/{id}/growth"))
-            .set_json(serde_json::json!({
-                "model": {"Logistic": {"annual_rate": 0.03, "carrying_capacity": 300.0, "mortality_rate": 0.005}},
-                "years": 10
-            }))
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/distribution?status=dead&min_dbh=11"))
             .to_request();
         let resp = actix_test::call_service(&app, req).await;
 
         assert_eq!(resp.status(), 200);
         let body: serde_json::Value = actix_test::read_body_json(resp).await;
-        let arr = body.as_array().unwrap();
-        assert_eq!(arr.len(), 11); // year 0 through 10
+        let classes = body["classes"].as_array().unwrap();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0]["lower"], 17.0);
+
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/distribution?status=standing"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
     }
 
     #[actix_web::test]
-    async fn test_growth_not_found() {
+    async fn test_distribution_zero_class_width_rejected() {
         let state = super::super::state::AppState::new_in_memory().unwrap();
-        let app = actix_test::init_service(make_app(state)).await;
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Dist"))
+            .unwrap();
 
-        let req = actix_test::TestRequest::post()
-            .uri(&format!("/api/{}/growth", Uuid::new_v4()))
-            .set_json(serde_json::json!({
-                "model": {"Exponential": {"annual_rate": 0.03, "mortality_rate": 0.005}},
-                "years": 5
-            }))
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/distribution?class_width=0"))
             .to_request();
         let resp = actix_test::call_service(&app, req).await;
 
-        assert_eq!(resp.status(), 404);
+        assert_eq!(resp.status(), 400);
     }
 
     // -----------------------------------------------------------------------
-    // Export endpoint
+    // Growth endpoint
     // -----------------------------------------------------------------------
 
     #[actix_web::test]
-    async fn test_export_csv() {
+    async fn test_plot_qa_flags_empty_plot() {
         let state = super::super::state::AppState::new_in_memory().unwrap();
         let id = Uuid::new_v4();
-        state
-            .insert_inventory(id, sample_inventory("Export"))
-            .unwrap();
+        let mut inv = sample_inventory("QA");
+        inv.plots[0].trees.clear();
+        state.insert_inventory(id, inv).unwrap();
 
         let app = actix_test::init_service(make_app(state)).await;
         let req = actix_test::TestRequest::get()
-            .uri(&format!("/api/{id}/export?format=csv"))
+            .uri(&format!("/api/{id}/qa"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        let flags = body.as_array().unwrap();
+        assert!(flags.iter().any(|f| f["reason"] == "NoLiveTrees"));
+    }
+
+    #[actix_web::test]
+    async fn test_growth_success() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Growth"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/growth"))
+            .set_json(serde_json::json!({
+                "model": {"Logistic": {"annual_rate": 0.03, "carrying_capacity": 300.0, "mortality_rate": 0.005}},
+                "years": 10
+            }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        let arr = body.as_array().unwrap();
+        assert_eq!(arr.len(), 11); // year 0 through 10
+    }
+
+    #[actix_web::test]
+    async fn test_growth_chapman_richards() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Growth"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/growth"))
+            .set_json(serde_json::json!({
+                "model": {"ChapmanRichards": {"asymptote": 250.0, "rate": 0.04, "shape": 2.5, "mortality_rate": 0.005}},
+                "years": 50
+            }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        let arr = body.as_array().unwrap();
+        assert_eq!(arr.len(), 51);
+        assert!(arr
+            .iter()
+            .all(|p| p["basal_area"].as_f64().unwrap() <= 250.0));
+    }
+
+    #[actix_web::test]
+    async fn test_growth_custom_volume_equation() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Growth"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let model = serde_json::json!({"Linear": {"annual_increment": 1.0, "mortality_rate": 0.0}});
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/growth"))
+            .set_json(serde_json::json!({"model": model, "years": 5}))
+            .to_request();
+        let default: serde_json::Value =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/growth"))
+            .set_json(serde_json::json!({
+                "model": model,
+                "years": 5,
+                "volume_equation": {
+                    "cuft_b1": 0.003,
+                    "bdft_b1": 0.015,
+                    "bdft_b2": 4.0,
+                    "bdft_min_dbh": 6.0
+                }
+            }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let custom: serde_json::Value = actix_test::read_body_json(resp).await;
+
+        let cuft = |body: &serde_json::Value| body[0]["volume_cuft"].as_f64().unwrap();
+        assert!(cuft(&custom) > cuft(&default));
+        assert_eq!(custom[0]["basal_area"], default[0]["basal_area"]);
+    }
+
+    #[actix_web::test]
+    async fn test_growth_not_found() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let app = actix_test::init_service(make_app(state)).await;
+
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{}/growth", Uuid::new_v4()))
+            .set_json(serde_json::json!({
+                "model": {"Exponential": {"annual_rate": 0.03, "mortality_rate": 0.005}},
+                "years": 5
+            }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[actix_web::test]
+    async fn test_growth_long_projection_within_cap() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Growth"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let years = MaxGrowthYears::default().0;
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/growth"))
+            .set_json(serde_json::json!({
+                "model": {"Logistic": {"annual_rate": 0.03, "carrying_capacity": 300.0, "mortality_rate": 0.005}},
+                "years": years
+            }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body.as_array().unwrap().len(), years as usize + 1);
+    }
+
+    #[actix_web::test]
+    async fn test_growth_over_cap_rejected() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Growth"))
+            .unwrap();
+
+        let cap = web::Data::new(MaxGrowthYears(50));
+        let app = actix_test::init_service(make_app(state).app_data(cap)).await;
+        let model =
+            serde_json::json!({"Exponential": {"annual_rate": 0.03, "mortality_rate": 0.005}});
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/growth"))
+            .set_json(serde_json::json!({"model": model, "years": 51}))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert!(body["details"].as_str().unwrap().contains("at most 50"));
+
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/growth"))
+            .set_json(serde_json::json!({"model": model, "years": 50}))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), 200);
+    }
+
+    // -----------------------------------------------------------------------
+    // Export endpoint
+    // -----------------------------------------------------------------------
+
+    #[actix_web::test]
+    async fn test_export_csv() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Export"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/export?format=csv"))
             .to_request();
         let resp = actix_test::call_service(&app, req).await;
 
@@ -1358,6 +2080,120 @@ mod tests {
         );
     }
 
+    #[actix_web::test]
+    async fn test_export_metrics_csv() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        let inventory = sample_inventory("Metrics");
+        let expected = crate::analysis::compute_stand_metrics(&inventory).to_flat_map();
+        state.insert_inventory(id, inventory).unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/export?format=metrics"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/csv");
+        let disposition = resp.headers().get("content-disposition").unwrap();
+        assert!(disposition
+            .to_str()
+            .unwrap()
+            .contains("Metrics_metrics.csv"));
+        let body = actix_test::read_body(resp).await;
+        let mut rdr = csv::Reader::from_reader(body.as_ref());
+        let headers: Vec<String> = rdr.headers().unwrap().iter().map(String::from).collect();
+        let keys: Vec<String> = expected.keys().cloned().collect();
+        assert_eq!(headers, keys);
+        assert!(headers.contains(&"species.DF.percent_basal_area".to_string()));
+        let row = rdr.records().next().unwrap().unwrap();
+        let tpa: f64 = row[headers.iter().position(|h| h == "total_tpa").unwrap()]
+            .parse()
+            .unwrap();
+        assert_eq!(tpa, expected["total_tpa"]);
+    }
+
+    #[actix_web::test]
+    async fn test_export_metrics_json() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Metrics"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/export?format=metrics-json"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert!(body["total_tpa"].as_f64().unwrap() > 0.0);
+        assert!(body["species_composition"].is_array());
+    }
+
+    #[actix_web::test]
+    async fn test_export_distribution_csv() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Dist"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!(
+                "/api/{id}/export?format=distribution&class_width=4"
+            ))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/csv");
+        let disposition = resp.headers().get("content-disposition").unwrap();
+        assert!(disposition
+            .to_str()
+            .unwrap()
+            .contains("Dist_distribution.csv"));
+        let body = actix_test::read_body(resp).await;
+        let text = std::str::from_utf8(&body).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "lower,upper,midpoint,tpa,basal_area,tree_count"
+        );
+        // Every class is 4 inches wide
+        for line in lines {
+            let fields: Vec<f64> = line.split(',').map(|f| f.parse().unwrap()).collect();
+            assert_eq!(fields[1] - fields[0], 4.0);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_export_distribution_rejects_bad_width() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Dist"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::get()
+            .uri(&format!(
+                "/api/{id}/export?format=distribution&class_width=0"
+            ))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 400);
+    }
+
     #[actix_web::test]
     async fn test_export_unsupported_format() {
         let state = super::super::state::AppState::new_in_memory().unwrap();
@@ -1411,6 +2247,240 @@ mod tests {
         assert_eq!(body["name"], "InvJson");
     }
 
+    #[actix_web::test]
+    async fn test_renumber_endpoint() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        let mut inv = sample_inventory("Renumber");
+        inv.plots[0].plot_id = 10;
+        inv.plots[0].trees[1].tree_id = 8;
+        state.insert_inventory(id, inv).unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/renumber?policy=sort_by_id"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let body: ForestInventory = actix_test::read_body_json(resp).await;
+        let ids: Vec<(u32, u32, u32)> = body
+            .plots
+            .iter()
+            .flat_map(|p| p.trees.iter().map(move |t| (p.plot_id, t.plot_id, t.tree_id)))
+            .collect();
+        assert_eq!(ids, vec![(1, 1, 1), (2, 2, 1), (2, 2, 2)]);
+
+        // The stored inventory is renumbered too
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/inventory"))
+            .to_request();
+        let stored: serde_json::Value =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+        assert_eq!(stored["plots"][1]["plot_id"], 2);
+
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{}/renumber", Uuid::new_v4()))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), 404);
+    }
+
+    #[actix_web::test]
+    async fn test_append_adds_plot_and_updates_metrics() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Append"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let metrics_req = || {
+            actix_test::TestRequest::get()
+                .uri(&format!("/api/{id}/metrics"))
+                .to_request()
+        };
+        let before: serde_json::Value =
+            actix_test::read_body_json(actix_test::call_service(&app, metrics_req()).await).await;
+
+        let new_plot: Vec<EditableTreeRow> = (1..=3)
+            .map(|tree_id| EditableTreeRow {
+                plot_id: 3,
+                tree_id,
+                dbh: 20.0,
+                ..valid_rows()[0].clone()
+            })
+            .collect();
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/append"))
+            .set_json(serde_json::json!({ "trees": new_plot }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["num_plots"], 3);
+        assert_eq!(body["num_trees"], 6);
+        assert_eq!(body["plots_added"], 1);
+        assert_eq!(body["trees_added"], 3);
+
+        let after: serde_json::Value =
+            actix_test::read_body_json(actix_test::call_service(&app, metrics_req()).await).await;
+        assert!(
+            after["quadratic_mean_diameter"].as_f64().unwrap()
+                > before["quadratic_mean_diameter"].as_f64().unwrap()
+        );
+        assert!(
+            after["total_basal_area"].as_f64().unwrap()
+                > before["total_basal_area"].as_f64().unwrap()
+        );
+
+        // A tree on an existing plot is appended to that plot
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/{id}/append"))
+            .set_json(serde_json::json!({ "trees": [EditableTreeRow {
+                plot_id: 2,
+                tree_id: 2,
+                ..valid_rows()[0].clone()
+            }] }))
+            .to_request();
+        let body: serde_json::Value =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+        assert_eq!(body["num_plots"], 3);
+        assert_eq!(body["plots_added"], 0);
+        assert_eq!(body["num_trees"], 7);
+    }
+
+    #[actix_web::test]
+    async fn test_append_rejects_duplicates_and_invalid_rows() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Append"))
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let post = |trees: Vec<EditableTreeRow>, id: Uuid| {
+            actix_test::TestRequest::post()
+                .uri(&format!("/api/{id}/append"))
+                .set_json(serde_json::json!({ "trees": trees }))
+                .to_request()
+        };
+
+        // Plot 1 tree 1 is already stored
+        let resp = actix_test::call_service(&app, post(valid_rows(), id)).await;
+        assert_eq!(resp.status(), 400);
+
+        // Duplicates within the request are rejected as well
+        let mut twice = valid_rows();
+        twice[0].plot_id = 5;
+        twice.push(twice[0].clone());
+        let resp = actix_test::call_service(&app, post(twice, id)).await;
+        assert_eq!(resp.status(), 400);
+
+        let mut invalid = valid_rows();
+        invalid[0].plot_id = 5;
+        invalid[0].dbh = -1.0;
+        let resp = actix_test::call_service(&app, post(invalid, id)).await;
+        assert_eq!(resp.status(), 400);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert!(!body["errors"].as_array().unwrap().is_empty());
+
+        // Nothing was stored by the rejected requests
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{id}/inventory"))
+            .to_request();
+        let stored: ForestInventory =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+        assert_eq!(stored.num_plots(), 2);
+        assert_eq!(stored.num_trees(), 3);
+
+        let resp = actix_test::call_service(&app, post(valid_rows(), Uuid::new_v4())).await;
+        assert_eq!(resp.status(), 404);
+    }
+
+    // -----------------------------------------------------------------------
+    // Upload endpoint
+    // -----------------------------------------------------------------------
+
+    #[actix_web::test]
+    async fn test_upload_reports_slenderness_warning_without_blocking() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let app = actix_test::init_service(make_app(state)).await;
+
+        let csv = "plot_id,tree_id,species_code,species_name,dbh,height,crown_ratio,status,expansion_factor,age,defect\n\
+                   1,1,DF,Douglas Fir,14.0,90.0,0.5,Live,5.0,,\n\
+                   1,2,DF,Douglas Fir,8.0,150.0,0.3,Live,5.0,,\n";
+        let body = format!(
+            "--XBOUNDARY\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"slender.csv\"\r\n\
+             Content-Type: text/csv\r\n\r\n\
+             {csv}\r\n\
+             --XBOUNDARY--\r\n"
+        );
+        let req = actix_test::TestRequest::post()
+            .uri("/api/upload")
+            .insert_header(("content-type", "multipart/form-data; boundary=XBOUNDARY"))
+            .set_payload(body)
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let body: UploadResponse = actix_test::read_body_json(resp).await;
+        assert!(!body.has_errors);
+        assert!(body.errors.is_empty());
+        assert_eq!(body.num_trees, 2);
+        assert_eq!(body.warnings.len(), 1);
+        assert_eq!(body.warnings[0].tree_id, 2);
+        assert_eq!(body.warnings[0].field, "height");
+        assert!(body.warnings[0].message.contains("slenderness"));
+    }
+
+    #[actix_web::test]
+    async fn test_upload_count_based_csv_reports_per_acre_tpa() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let app = actix_test::init_service(make_app(state)).await;
+
+        // Two trees counted once each on a 0.2-acre plot: 10 trees per acre
+        let csv = "# expansion_kind: count\n\
+                   plot_id,tree_id,species_code,species_name,dbh,height,crown_ratio,status,expansion_factor,age,defect,plot_size_acres\n\
+                   1,1,DF,Douglas Fir,14.0,90.0,0.5,Live,1.0,,,0.2\n\
+                   1,2,DF,Douglas Fir,-12.0,80.0,0.5,Live,1.0,,,0.2\n";
+        let body = format!(
+            "--XBOUNDARY\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"counts.csv\"\r\n\
+             Content-Type: text/csv\r\n\r\n\
+             {csv}\r\n\
+             --XBOUNDARY--\r\n"
+        );
+        let req = actix_test::TestRequest::post()
+            .uri("/api/upload")
+            .insert_header(("content-type", "multipart/form-data; boundary=XBOUNDARY"))
+            .set_payload(body)
+            .to_request();
+        let upload: UploadResponse =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+        assert!(upload.has_errors);
+        // Only the valid tree is summarized while the upload is pending
+        assert!((upload.plot_summary[0].tpa - 5.0).abs() < 1e-9);
+
+        let mut trees = upload.trees;
+        trees[1].dbh = 12.0;
+        let req = actix_test::TestRequest::post()
+            .uri("/api/validate")
+            .set_json(serde_json::json!({ "id": upload.id, "trees": trees }))
+            .to_request();
+        let validated: UploadResponse =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+        assert!(!validated.has_errors);
+        assert!((validated.plot_summary[0].tpa - 10.0).abs() < 1e-9);
+
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/{}/inventory", upload.id))
+            .to_request();
+        let stored: ForestInventory =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+        assert_eq!(stored.expansion_kind, ExpansionKind::CountOnPlot);
+        assert!((stored.mean_tpa() - 10.0).abs() < 1e-9);
+    }
+
     // -----------------------------------------------------------------------
     // Validate endpoint
     // -----------------------------------------------------------------------
@@ -1440,7 +2510,12 @@ mod tests {
 
         // Seed pending rows (simulates a prior upload with errors)
         state
-            .insert_pending(id, "test.csv".to_string(), rows.clone())
+            .insert_pending(
+                id,
+                "test.csv".to_string(),
+                ExpansionKind::PerAcre,
+                rows.clone(),
+            )
             .unwrap();
 
         let app = actix_test::init_service(make_app(state)).await;
@@ -1468,7 +2543,12 @@ mod tests {
         rows[0].dbh = -5.0; // Invalid DBH
 
         state
-            .insert_pending(id, "bad.csv".to_string(), rows.clone())
+            .insert_pending(
+                id,
+                "bad.csv".to_string(),
+                ExpansionKind::PerAcre,
+                rows.clone(),
+            )
             .unwrap();
 
         let app = actix_test::init_service(make_app(state)).await;
@@ -1495,7 +2575,12 @@ mod tests {
         rows[0].status = "Unknown".to_string();
 
         state
-            .insert_pending(id, "status.csv".to_string(), rows.clone())
+            .insert_pending(
+                id,
+                "status.csv".to_string(),
+                ExpansionKind::PerAcre,
+                rows.clone(),
+            )
             .unwrap();
 
         let app = actix_test::init_service(make_app(state)).await;
@@ -1514,6 +2599,85 @@ mod tests {
         assert!(body.errors.iter().any(|e| e.field == "status"));
     }
 
+    #[actix_web::test]
+    async fn test_validate_errors_include_plot_summary_of_valid_rows() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        let row = |row_index, plot_id, tree_id, dbh, status: &str| EditableTreeRow {
+            row_index,
+            plot_id,
+            tree_id,
+            dbh,
+            status: status.to_string(),
+            ..valid_rows().remove(0)
+        };
+        let rows = vec![
+            row(0, 1, 1, 14.0, "Live"),
+            row(1, 1, 2, -5.0, "Live"),
+            row(2, 2, 1, 10.0, "Live"),
+            row(3, 2, 2, 12.0, "Dead"),
+            row(4, 3, 1, 8.0, "Unknown"),
+        ];
+        state
+            .insert_pending(
+                id,
+                "plots.csv".to_string(),
+                ExpansionKind::PerAcre,
+                rows.clone(),
+            )
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::post()
+            .uri("/api/validate")
+            .set_json(serde_json::json!({ "id": id, "trees": rows }))
+            .to_request();
+        let body: UploadResponse =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+
+        assert!(body.has_errors);
+        // The negative DBH and the unknown status are left out; plot 3 had
+        // only the bad-status tree, so it disappears
+        assert_eq!(body.skipped_rows, 2);
+        let plots: Vec<(u32, usize)> = body
+            .plot_summary
+            .iter()
+            .map(|p| (p.plot_id, p.live_trees))
+            .collect();
+        assert_eq!(plots, vec![(1, 1), (2, 1)]);
+        let plot1 = &body.plot_summary[0];
+        assert!((plot1.tpa - 5.0).abs() < 1e-9);
+        assert!((plot1.basal_area - 0.005454 * 14.0 * 14.0 * 5.0).abs() < 0.01);
+    }
+
+    #[actix_web::test]
+    async fn test_validate_clean_rows_summarize_every_plot() {
+        let state = super::super::state::AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        let rows = valid_rows();
+        state
+            .insert_pending(
+                id,
+                "clean.csv".to_string(),
+                ExpansionKind::PerAcre,
+                rows.clone(),
+            )
+            .unwrap();
+
+        let app = actix_test::init_service(make_app(state)).await;
+        let req = actix_test::TestRequest::post()
+            .uri("/api/validate")
+            .set_json(serde_json::json!({ "id": id, "trees": rows }))
+            .to_request();
+        let body: UploadResponse =
+            actix_test::read_body_json(actix_test::call_service(&app, req).await).await;
+
+        assert!(!body.has_errors);
+        assert_eq!(body.skipped_rows, 0);
+        assert_eq!(body.plot_summary.len(), 1);
+        assert_eq!(body.plot_summary[0].live_trees, 1);
+    }
+
     // -----------------------------------------------------------------------
     // Export filename sanitization
     // -----------------------------------------------------------------------
@@ -1607,6 +2771,29 @@ mod tests {
         assert_eq!(body["status"], "ok");
     }
 
+    #[actix_web::test]
+    async fn test_configure_api_embedded_under_scope() {
+        let state = web::Data::new(super::super::state::AppState::ephemeral().unwrap());
+        let id = Uuid::new_v4();
+        state
+            .insert_inventory(id, sample_inventory("Embedded"))
+            .unwrap();
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state)
+                .app_data(web::Data::new(10 * 1024 * 1024_usize))
+                .service(web::scope("/forest").configure(super::super::configure_api)),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/forest/api/{id}/metrics"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+    }
+
     // -----------------------------------------------------------------------
     // Auto-fix endpoint
     // -----------------------------------------------------------------------
@@ -1629,6 +2816,12 @@ mod tests {
             slope_percent: None,
             aspect_degrees: None,
             elevation_ft: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+            decay_class: None,
         };
         overrides(&mut row);
         row
@@ -1638,7 +2831,12 @@ mod tests {
         let state = super::super::state::AppState::new_in_memory().unwrap();
         let id = Uuid::new_v4();
         state
-            .insert_pending(id, "test.csv".to_string(), rows.clone())
+            .insert_pending(
+                id,
+                "test.csv".to_string(),
+                ExpansionKind::PerAcre,
+                rows.clone(),
+            )
             .unwrap();
 
         let app = actix_test::init_service(make_app(state)).await;
@@ -6,22 +6,118 @@
 mod handlers;
 mod state;
 
+use std::future::Future;
+
 use actix_cors::Cors;
 use actix_web::{http::header, web, App, HttpServer};
-use state::AppState;
 use tracing_actix_web::TracingLogger;
 
+pub use state::{AppState, IN_MEMORY_DB_PATH};
+
+use crate::analysis::DEFAULT_MAX_SDI;
 use crate::config::AppConfig;
+use crate::visualization::SpeciesPalette;
+
+/// Longest projection, in years, the growth endpoint accepts.
+///
+/// Register as `web::Data<MaxGrowthYears>` to override the default of 500
+/// years; requests beyond the limit get 400 Bad Request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxGrowthYears(pub u32);
+
+impl Default for MaxGrowthYears {
+    fn default() -> Self {
+        Self(500)
+    }
+}
 
+impl std::fmt::Display for MaxGrowthYears {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Maximum stand density index that relative density is computed against.
+///
+/// Register as `web::Data<MaxSdi>` to override the default of
+/// [`DEFAULT_MAX_SDI`] in the metrics and export endpoints.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxSdi(pub f64);
+
+impl Default for MaxSdi {
+    fn default() -> Self {
+        Self(DEFAULT_MAX_SDI)
+    }
+}
+
+/// Register the health check and `/api` routes on an actix service config.
+///
+/// The caller must provide `web::Data<AppState>` and `web::Data<usize>` (the
+/// maximum upload size in bytes) as app data. A `web::Data<`[`SpeciesPalette`]`>`
+/// is optional and sets chart colors, a `web::Data<`[`MaxGrowthYears`]`>`
+/// optionally caps growth projections, and a `web::Data<`[`MaxSdi`]`>`
+/// optionally sets the maximum SDI for relative density. See [`AppState::ephemeral`] for an
+/// embedding example.
+pub fn configure_api(cfg: &mut web::ServiceConfig) {
+    cfg.route("/health", web::get().to(handlers::health))
+        .route("/api/upload", web::post().to(handlers::upload))
+        .route(
+            "/api/validate",
+            web::post().to(handlers::validate_and_submit),
+        )
+        .route("/api/autofix", web::post().to(handlers::autofix))
+        .route("/api/{id}/metrics", web::get().to(handlers::metrics))
+        .route("/api/{id}/statistics", web::get().to(handlers::statistics))
+        .route(
+            "/api/{id}/distribution",
+            web::get().to(handlers::distribution),
+        )
+        .route("/api/{id}/qa", web::get().to(handlers::plot_qa))
+        .route("/api/{id}/growth", web::post().to(handlers::growth))
+        .route("/api/{id}/export", web::get().to(handlers::export))
+        .route(
+            "/api/{id}/inventory",
+            web::get().to(handlers::inventory_json),
+        )
+        .route("/api/{id}/renumber", web::post().to(handlers::renumber))
+        .route("/api/{id}/append", web::post().to(handlers::append));
+}
+
+/// Run the web server until SIGINT (Ctrl-C) or SIGTERM, then shut down
+/// gracefully; see [`start_server_with_shutdown`].
 pub async fn start_server(config: AppConfig) -> std::io::Result<()> {
+    start_server_with_shutdown(config, shutdown_signal()).await
+}
+
+/// Run the web server until `shutdown` completes.
+///
+/// On shutdown the server stops accepting connections, waits for in-flight
+/// requests to finish, then checkpoints the database before closing it.
+/// Embedders can pass any future, e.g. a oneshot receiver, to control the
+/// server's lifetime; the server installs no signal handlers of its own.
+pub async fn start_server_with_shutdown(
+    config: AppConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> std::io::Result<()> {
     let port = config.server.port;
     let max_upload = config.server.max_upload_bytes;
     let bind_addr = config.server.bind_address.clone();
 
-    let state =
-        AppState::new(&config.database.path).map_err(|e| std::io::Error::other(e.to_string()))?;
+    let state = if config.database.path == IN_MEMORY_DB_PATH {
+        tracing::info!("Using in-memory database; data will not persist across restarts");
+        AppState::ephemeral()
+    } else {
+        AppState::new(&config.database.path)
+    }
+    .map_err(|e| std::io::Error::other(e.to_string()))?;
     let data = web::Data::new(state);
+    let db = data.clone();
     let upload_limit = web::Data::new(max_upload);
+    let palette = SpeciesPalette::from_colors(config.species_colors.clone())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    let palette = web::Data::new(palette);
+    let max_growth_years = web::Data::new(MaxGrowthYears(config.server.max_growth_years));
+    let max_sdi = web::Data::new(MaxSdi(config.analysis.max_sdi));
 
     tracing::info!("Starting Forest Inventory Analyzer web server on http://{bind_addr}:{port}");
 
@@ -56,35 +152,19 @@ pub async fn start_server(config: AppConfig) -> std::io::Result<()> {
             .wrap(cors)
             .app_data(data.clone())
             .app_data(upload_limit.clone())
+            .app_data(palette.clone())
+            .app_data(max_growth_years.clone())
+            .app_data(max_sdi.clone())
             .app_data(multipart_cfg)
             .app_data(payload_cfg)
             .app_data(json_cfg)
-            // Health check
-            .route("/health", web::get().to(handlers::health))
             // Static files
             .route("/", web::get().to(handlers::index_html))
             .route("/app.js", web::get().to(handlers::app_js))
             .route("/style.css", web::get().to(handlers::style_css))
             .route("/chart.min.js", web::get().to(handlers::chart_js))
-            // API routes
-            .route("/api/upload", web::post().to(handlers::upload))
-            .route(
-                "/api/validate",
-                web::post().to(handlers::validate_and_submit),
-            )
-            .route("/api/autofix", web::post().to(handlers::autofix))
-            .route("/api/{id}/metrics", web::get().to(handlers::metrics))
-            .route("/api/{id}/statistics", web::get().to(handlers::statistics))
-            .route(
-                "/api/{id}/distribution",
-                web::get().to(handlers::distribution),
-            )
-            .route("/api/{id}/growth", web::post().to(handlers::growth))
-            .route("/api/{id}/export", web::get().to(handlers::export))
-            .route(
-                "/api/{id}/inventory",
-                web::get().to(handlers::inventory_json),
-            )
+            // Health check and API routes
+            .configure(configure_api)
     })
     .bind((&*bind_addr, port))
     .map_err(|e| {
@@ -97,17 +177,105 @@ pub async fn start_server(config: AppConfig) -> std::io::Result<()> {
             e
         }
     })?
+    .disable_signals()
     .run();
 
-    // Graceful shutdown: spawn a task that listens for ctrl-c
     let handle = server.handle();
     tokio::spawn(async move {
-        tokio::signal::ctrl_c().await.ok();
+        shutdown.await;
         tracing::info!("Shutdown signal received, draining connections...");
         handle.stop(true).await;
     });
 
     server.await?;
+    if let Err(e) = db.checkpoint() {
+        tracing::warn!("{e}");
+    }
     tracing::info!("Server has shut down");
     Ok(())
 }
+
+/// Resolve on the first SIGINT or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Failed to listen for Ctrl-C: {e}");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn test_config(db_path: &str) -> AppConfig {
+        let mut config = AppConfig::default();
+        config.server.port = 0;
+        config.database.path = db_path.to_string();
+        config
+    }
+
+    #[actix_web::test]
+    async fn test_server_stops_when_shutdown_resolves() {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(start_server_with_shutdown(
+            test_config(IN_MEMORY_DB_PATH),
+            async move {
+                rx.await.ok();
+            },
+        ));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!server.is_finished());
+        tx.send(()).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(10), server)
+            .await
+            .expect("server did not stop")
+            .unwrap();
+        assert!(result.is_ok());
+    }
+
+    #[actix_web::test]
+    async fn test_shutdown_checkpoints_file_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("forest.db");
+        let path = path.to_str().unwrap();
+
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            start_server_with_shutdown(test_config(path), async {}),
+        )
+        .await
+        .expect("server did not stop")
+        .unwrap();
+
+        // The store was closed cleanly and reopens with its tables intact.
+        let state = AppState::new(path).unwrap();
+        assert!(state.get_inventory(&uuid::Uuid::new_v4()).unwrap().is_none());
+    }
+}
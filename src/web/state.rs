@@ -7,7 +7,7 @@ use uuid::Uuid;
 
 use crate::error::ForestError;
 use crate::io::EditableTreeRow;
-use crate::models::ForestInventory;
+use crate::models::{ExpansionKind, ForestInventory};
 
 /// Maximum number of inventories before oldest is evicted.
 const MAX_INVENTORIES: usize = 100;
@@ -27,6 +27,10 @@ fn unix_now() -> u64 {
         .as_secs()
 }
 
+/// Database path that selects an ephemeral in-memory store instead of a file.
+pub const IN_MEMORY_DB_PATH: &str = ":memory:";
+
+/// Shared server state: uploaded inventories and pending editor rows, stored in SQLite.
 pub struct AppState {
     db: Mutex<Connection>,
     last_evict_inventories: AtomicU64,
@@ -34,21 +38,55 @@ pub struct AppState {
 }
 
 impl AppState {
+    /// Open (or create) a file-backed store at `db_path`.
     pub fn new(db_path: &str) -> Result<Self, ForestError> {
         let conn = Connection::open(db_path)
             .map_err(|e| ForestError::Database(format!("failed to open database: {e}")))?;
         Self::init_with_connection(conn)
     }
 
-    /// Create an AppState backed by an in-memory SQLite database (for testing).
-    #[cfg(test)]
-    pub fn new_in_memory() -> Result<Self, ForestError> {
+    /// Create an AppState backed by an in-memory SQLite database.
+    ///
+    /// Nothing is written to disk and all data is lost when the state is
+    /// dropped, which suits embedding the API in another application or
+    /// short-lived servers.
+    ///
+    /// # Examples
+    ///
+    /// Mount the API under a scope of your own actix `App`:
+    ///
+    /// ```no_run
+    /// use actix_web::{web, App, HttpServer};
+    /// use forest_inventory_analyzer::web::{configure_api, AppState};
+    ///
+    /// # async fn run() -> std::io::Result<()> {
+    /// let state = web::Data::new(AppState::ephemeral().expect("in-memory database"));
+    /// let upload_limit = web::Data::new(50 * 1024 * 1024_usize);
+    ///
+    /// HttpServer::new(move || {
+    ///     App::new()
+    ///         .app_data(state.clone())
+    ///         .app_data(upload_limit.clone())
+    ///         .service(web::scope("/forest").configure(configure_api))
+    /// })
+    /// .bind(("127.0.0.1", 8080))?
+    /// .run()
+    /// .await
+    /// # }
+    /// ```
+    pub fn ephemeral() -> Result<Self, ForestError> {
         let conn = Connection::open_in_memory().map_err(|e| {
             ForestError::Database(format!("failed to open in-memory database: {e}"))
         })?;
         Self::init_with_connection(conn)
     }
 
+    /// Create an AppState backed by an in-memory SQLite database (for testing).
+    #[cfg(test)]
+    pub fn new_in_memory() -> Result<Self, ForestError> {
+        Self::ephemeral()
+    }
+
     fn init_with_connection(conn: Connection) -> Result<Self, ForestError> {
         conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS inventories (
@@ -61,11 +99,28 @@ impl AppState {
                 id TEXT PRIMARY KEY,
                 name TEXT NOT NULL,
                 rows TEXT NOT NULL,
-                created_at INTEGER NOT NULL
+                created_at INTEGER NOT NULL,
+                expansion_kind TEXT NOT NULL DEFAULT 'per-acre'
             );",
         )
         .map_err(|e| ForestError::Database(format!("failed to create tables: {e}")))?;
 
+        // Databases created before pending rows recorded their expansion kind
+        let has_kind: bool = conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('pending_rows') WHERE name = 'expansion_kind')",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| ForestError::Database(format!("failed to inspect tables: {e}")))?;
+        if !has_kind {
+            conn.execute(
+                "ALTER TABLE pending_rows ADD COLUMN expansion_kind TEXT NOT NULL DEFAULT 'per-acre'",
+                [],
+            )
+            .map_err(|e| ForestError::Database(format!("failed to migrate pending_rows: {e}")))?;
+        }
+
         Ok(Self {
             db: Mutex::new(conn),
             last_evict_inventories: AtomicU64::new(0),
@@ -83,6 +138,17 @@ impl AppState {
         }
     }
 
+    /// Flush the database to disk before shutdown.
+    ///
+    /// Checkpoints the write-ahead log, if one is in use, so the database file
+    /// is complete on its own once the connection closes. A no-op for
+    /// in-memory and rollback-journal databases.
+    pub fn checkpoint(&self) -> Result<(), ForestError> {
+        let conn = self.lock_db()?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| ForestError::Database(format!("failed to checkpoint database: {e}")))
+    }
+
     fn lock_db(&self) -> Result<std::sync::MutexGuard<'_, Connection>, ForestError> {
         self.db
             .lock()
@@ -113,6 +179,41 @@ impl AppState {
         }
     }
 
+    /// Read, modify and write back a stored inventory under one lock, so
+    /// concurrent updates to the same inventory cannot interleave.
+    ///
+    /// Returns `Ok(None)` if no inventory is stored under `id`. If `update`
+    /// fails the stored inventory is left unchanged and its error returned.
+    pub fn update_inventory<T>(
+        &self,
+        id: &Uuid,
+        update: impl FnOnce(&mut ForestInventory) -> Result<T, ForestError>,
+    ) -> Result<Option<T>, ForestError> {
+        let conn = self.lock_db()?;
+        self.maybe_evict(&conn, "inventories", INVENTORY_TTL_SECS, &self.last_evict_inventories);
+
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT data FROM inventories WHERE id = ?1",
+                [id.to_string()],
+                |row| row.get(0),
+            )
+            .ok();
+        let Some(json) = json else {
+            return Ok(None);
+        };
+        let mut inventory: ForestInventory = serde_json::from_str(&json)?;
+        let result = update(&mut inventory)?;
+
+        let json = serde_json::to_string(&inventory)?;
+        conn.execute(
+            "UPDATE inventories SET name = ?1, data = ?2 WHERE id = ?3",
+            (&inventory.name, &json, id.to_string()),
+        )
+        .map_err(|e| ForestError::Database(format!("failed to update inventory: {e}")))?;
+        Ok(Some(result))
+    }
+
     pub fn insert_inventory(
         &self,
         id: Uuid,
@@ -131,18 +232,29 @@ impl AppState {
         Ok(())
     }
 
-    pub fn get_pending_name(&self, id: &Uuid) -> Result<Option<String>, ForestError> {
+    /// Name and expansion kind of a pending upload.
+    pub(crate) fn get_pending_header(
+        &self,
+        id: &Uuid,
+    ) -> Result<Option<(String, ExpansionKind)>, ForestError> {
         let conn = self.lock_db()?;
         self.maybe_evict(&conn, "pending_rows", PENDING_TTL_SECS, &self.last_evict_pending);
 
         let mut stmt = conn
-            .prepare("SELECT name FROM pending_rows WHERE id = ?1")
+            .prepare("SELECT name, expansion_kind FROM pending_rows WHERE id = ?1")
             .map_err(|e| ForestError::Database(format!("failed to prepare query: {e}")))?;
 
-        Ok(stmt.query_row([id.to_string()], |row| row.get(0)).ok())
+        let result = stmt
+            .query_row([id.to_string()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .ok();
+        result
+            .map(|(name, kind)| Ok((name, kind.parse()?)))
+            .transpose()
     }
 
-    pub fn has_pending(&self, id: &Uuid) -> Result<bool, ForestError> {
+    pub(crate) fn has_pending(&self, id: &Uuid) -> Result<bool, ForestError> {
         let conn = self.lock_db()?;
         self.maybe_evict(&conn, "pending_rows", PENDING_TTL_SECS, &self.last_evict_pending);
 
@@ -155,10 +267,11 @@ impl AppState {
             .unwrap_or(false))
     }
 
-    pub fn insert_pending(
+    pub(crate) fn insert_pending(
         &self,
         id: Uuid,
         name: String,
+        expansion_kind: ExpansionKind,
         rows: Vec<EditableTreeRow>,
     ) -> Result<(), ForestError> {
         let conn = self.lock_db()?;
@@ -167,40 +280,48 @@ impl AppState {
 
         let json = serde_json::to_string(&rows)?;
         conn.execute(
-            "INSERT OR REPLACE INTO pending_rows (id, name, rows, created_at) VALUES (?1, ?2, ?3, ?4)",
-            (id.to_string(), &name, &json, unix_now()),
+            "INSERT OR REPLACE INTO pending_rows (id, name, rows, created_at, expansion_kind) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            (
+                id.to_string(),
+                &name,
+                &json,
+                unix_now(),
+                expansion_kind.to_string(),
+            ),
         )
         .map_err(|e| ForestError::Database(format!("failed to insert pending rows: {e}")))?;
         Ok(())
     }
 
-    pub fn remove_pending(
+    pub(crate) fn remove_pending(
         &self,
         id: &Uuid,
-    ) -> Result<Option<(String, Vec<EditableTreeRow>)>, ForestError> {
+    ) -> Result<Option<(String, ExpansionKind, Vec<EditableTreeRow>)>, ForestError> {
         let conn = self.lock_db()?;
         self.maybe_evict(&conn, "pending_rows", PENDING_TTL_SECS, &self.last_evict_pending);
 
         let mut stmt = conn
-            .prepare("SELECT name, rows FROM pending_rows WHERE id = ?1")
+            .prepare("SELECT name, rows, expansion_kind FROM pending_rows WHERE id = ?1")
             .map_err(|e| ForestError::Database(format!("failed to prepare query: {e}")))?;
 
         let result = stmt
             .query_row([id.to_string()], |row| {
                 let name: String = row.get(0)?;
                 let json: String = row.get(1)?;
-                Ok((name, json))
+                let kind: String = row.get(2)?;
+                Ok((name, json, kind))
             })
             .ok();
 
         match result {
-            Some((name, json)) => {
+            Some((name, json, kind)) => {
                 conn.execute("DELETE FROM pending_rows WHERE id = ?1", [id.to_string()])
                     .map_err(|e| {
                         ForestError::Database(format!("failed to delete pending rows: {e}"))
                     })?;
                 let rows: Vec<EditableTreeRow> = serde_json::from_str(&json)?;
-                Ok(Some((name, rows)))
+                Ok(Some((name, kind.parse()?, rows)))
             }
             None => Ok(None),
         }
@@ -280,7 +401,7 @@ impl AppState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{ForestInventory, Plot, Species, Tree, TreeStatus};
+    use crate::models::{ExpansionKind, ForestInventory, Plot, Species, Tree, TreeStatus};
 
     fn sample_inventory(name: &str) -> ForestInventory {
         let mut inv = ForestInventory::new(name);
@@ -304,8 +425,14 @@ mod tests {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             }],
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         });
         inv
     }
@@ -328,6 +455,12 @@ mod tests {
             slope_percent: None,
             aspect_degrees: None,
             elevation_ft: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
+            decay_class: None,
         }]
     }
 
@@ -379,6 +512,35 @@ mod tests {
         assert!(state.get_inventory(&Uuid::new_v4()).unwrap().is_none());
     }
 
+    #[test]
+    fn test_update_inventory() {
+        let state = AppState::new_in_memory().unwrap();
+        let id = Uuid::new_v4();
+        state.insert_inventory(id, sample_inventory("Old")).unwrap();
+
+        let renamed = state
+            .update_inventory(&id, |inv| {
+                inv.name = "New".to_string();
+                Ok(inv.num_plots())
+            })
+            .unwrap();
+        assert_eq!(renamed, Some(1));
+        assert_eq!(state.get_inventory(&id).unwrap().unwrap().name, "New");
+
+        // A failed update leaves the stored inventory unchanged
+        let result = state.update_inventory(&id, |inv| {
+            inv.plots.clear();
+            Err::<(), _>(ForestError::ValidationError("rejected".to_string()))
+        });
+        assert!(matches!(result, Err(ForestError::ValidationError(_))));
+        assert_eq!(state.get_inventory(&id).unwrap().unwrap().num_plots(), 1);
+
+        assert!(state
+            .update_inventory(&Uuid::new_v4(), |_| Ok(()))
+            .unwrap()
+            .is_none());
+    }
+
     // -----------------------------------------------------------------------
     // Pending rows round-trip tests
     // -----------------------------------------------------------------------
@@ -391,7 +553,12 @@ mod tests {
         assert!(!state.has_pending(&id).unwrap());
 
         state
-            .insert_pending(id, "test.csv".to_string(), sample_rows())
+            .insert_pending(
+                id,
+                "test.csv".to_string(),
+                ExpansionKind::PerAcre,
+                sample_rows(),
+            )
             .unwrap();
 
         assert!(state.has_pending(&id).unwrap());
@@ -402,18 +569,51 @@ mod tests {
         let state = AppState::new_in_memory().unwrap();
         let id = Uuid::new_v4();
 
-        assert!(state.get_pending_name(&id).unwrap().is_none());
+        assert!(state.get_pending_header(&id).unwrap().is_none());
 
         state
-            .insert_pending(id, "my_file.csv".to_string(), sample_rows())
+            .insert_pending(
+                id,
+                "my_file.csv".to_string(),
+                ExpansionKind::PerAcre,
+                sample_rows(),
+            )
             .unwrap();
 
         assert_eq!(
-            state.get_pending_name(&id).unwrap(),
-            Some("my_file.csv".to_string())
+            state.get_pending_header(&id).unwrap(),
+            Some(("my_file.csv".to_string(), ExpansionKind::PerAcre))
         );
     }
 
+    #[test]
+    fn test_pending_keeps_expansion_kind_after_migration() {
+        // A database from before pending rows recorded their expansion kind
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE pending_rows (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                rows TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );",
+        )
+        .unwrap();
+        let state = AppState::init_with_connection(conn).unwrap();
+        let id = Uuid::new_v4();
+
+        state
+            .insert_pending(
+                id,
+                "counts.csv".to_string(),
+                ExpansionKind::CountOnPlot,
+                sample_rows(),
+            )
+            .unwrap();
+        let (_, kind, _) = state.remove_pending(&id).unwrap().unwrap();
+        assert_eq!(kind, ExpansionKind::CountOnPlot);
+    }
+
     #[test]
     fn test_pending_remove() {
         let state = AppState::new_in_memory().unwrap();
@@ -421,11 +621,16 @@ mod tests {
         let rows = sample_rows();
 
         state
-            .insert_pending(id, "test.csv".to_string(), rows.clone())
+            .insert_pending(
+                id,
+                "test.csv".to_string(),
+                ExpansionKind::PerAcre,
+                rows.clone(),
+            )
             .unwrap();
         assert!(state.has_pending(&id).unwrap());
 
-        let (name, returned_rows) = state
+        let (name, _, returned_rows) = state
             .remove_pending(&id)
             .unwrap()
             .expect("should find pending");
@@ -484,7 +689,12 @@ mod tests {
         let state = AppState::new_in_memory().unwrap();
         let id = Uuid::new_v4();
         state
-            .insert_pending(id, "expired.csv".to_string(), sample_rows())
+            .insert_pending(
+                id,
+                "expired.csv".to_string(),
+                ExpansionKind::PerAcre,
+                sample_rows(),
+            )
             .unwrap();
 
         // Backdate beyond the 30-minute TTL and reset eviction timer
@@ -493,7 +703,7 @@ mod tests {
 
         // Next access should evict it
         assert!(!state.has_pending(&id).unwrap());
-        assert!(state.get_pending_name(&id).unwrap().is_none());
+        assert!(state.get_pending_header(&id).unwrap().is_none());
     }
 
     #[test]
@@ -501,7 +711,12 @@ mod tests {
         let state = AppState::new_in_memory().unwrap();
         let id = Uuid::new_v4();
         state
-            .insert_pending(id, "fresh.csv".to_string(), sample_rows())
+            .insert_pending(
+                id,
+                "fresh.csv".to_string(),
+                ExpansionKind::PerAcre,
+                sample_rows(),
+            )
             .unwrap();
 
         state.backdate_pending(&id, PENDING_TTL_SECS - 60);
@@ -544,7 +759,12 @@ mod tests {
         // Fill to MAX_PENDING
         for _ in 0..MAX_PENDING {
             state
-                .insert_pending(Uuid::new_v4(), "file.csv".to_string(), rows.clone())
+                .insert_pending(
+                    Uuid::new_v4(),
+                    "file.csv".to_string(),
+                    ExpansionKind::PerAcre,
+                    rows.clone(),
+                )
                 .unwrap();
         }
         assert_eq!(state.count_rows("pending_rows"), MAX_PENDING);
@@ -552,7 +772,7 @@ mod tests {
         // Insert one more — should evict oldest
         let new_id = Uuid::new_v4();
         state
-            .insert_pending(new_id, "new.csv".to_string(), rows)
+            .insert_pending(new_id, "new.csv".to_string(), ExpansionKind::PerAcre, rows)
             .unwrap();
 
         assert!(state.has_pending(&new_id).unwrap());
@@ -586,9 +806,9 @@ mod tests {
         let rows = sample_rows();
 
         state
-            .insert_pending(id, "data.csv".to_string(), rows)
+            .insert_pending(id, "data.csv".to_string(), ExpansionKind::PerAcre, rows)
             .unwrap();
-        let (name, loaded) = state.remove_pending(&id).unwrap().unwrap();
+        let (name, _, loaded) = state.remove_pending(&id).unwrap().unwrap();
 
         assert_eq!(name, "data.csv");
         assert_eq!(loaded[0].species_code, "DF");
@@ -127,7 +127,7 @@ async function loadMetrics() {
         document.getElementById('metric-vol-bdft').textContent = fmtNum(m.total_volume_bdft, 0);
         document.getElementById('metric-qmd').textContent = fmtNum(m.quadratic_mean_diameter, 1);
 
-        renderSpeciesChart(m.species_composition);
+        renderSpeciesChart(m.species_composition, m.species_colors);
 
         // Show per-stand summary if cruise data has multiple stands
         const warning = document.getElementById('stand-summary-banner');
@@ -156,13 +156,15 @@ const CHART_COLORS = [
     '#95d5b2', '#b7e4c7', '#d8f3dc', '#a7c957', '#6a994e',
 ];
 
-function renderSpeciesChart(composition) {
+function renderSpeciesChart(composition, speciesColors = {}) {
     const ctx = document.getElementById('species-chart').getContext('2d');
     if (speciesChart) speciesChart.destroy();
 
     const labels = composition.map(s => s.species.common_name);
     const baData = composition.map(s => s.basal_area);
     const tpaData = composition.map(s => s.tpa);
+    const colors = composition.map((s, i) =>
+        speciesColors[s.species.code] || CHART_COLORS[i % CHART_COLORS.length]);
 
     speciesChart = new Chart(ctx, {
         type: 'bar',
@@ -172,13 +174,13 @@ function renderSpeciesChart(composition) {
                 {
                     label: 'Basal Area (ft\u00b2/ac)',
                     data: baData,
-                    backgroundColor: CHART_COLORS.slice(0, labels.length),
+                    backgroundColor: colors,
                     borderRadius: 4,
                 },
                 {
                     label: 'TPA',
                     data: tpaData,
-                    backgroundColor: CHART_COLORS.slice(0, labels.length).map(c => c + '66'),
+                    backgroundColor: colors.map(c => c + '66'),
                     borderRadius: 4,
                 }
             ]
@@ -474,6 +476,7 @@ function showErrorEditor(data) {
     document.getElementById('autofix-banner').hidden = true;
 
     renderErrorList(data.errors);
+    renderPlotSummary(data);
     renderEditTable(data.trees);
     highlightErrorCells(data.errors);
 
@@ -495,6 +498,25 @@ function showErrorEditor(data) {
     }
 }
 
+function renderPlotSummary(data) {
+    const el = document.getElementById('plot-summary');
+    const plots = data.plot_summary || [];
+    if (plots.length === 0) {
+        el.hidden = true;
+        return;
+    }
+    const lines = plots.map(p =>
+        'Plot ' + p.plot_id + ': ' + p.live_trees + ' live trees, ' +
+        p.tpa.toFixed(1) + ' TPA, ' + p.basal_area.toFixed(1) + ' BA/ac'
+    );
+    if (data.skipped_rows > 0) {
+        lines.push('(' + data.skipped_rows + ' row' + (data.skipped_rows !== 1 ? 's' : '') +
+            ' with errors not counted)');
+    }
+    el.textContent = lines.join(' \u2022 ');
+    el.hidden = false;
+}
+
 function renderErrorList(errors) {
     const el = document.getElementById('error-list');
     document.getElementById('error-count').textContent = errors.length + ' error' + (errors.length !== 1 ? 's' : '') + ' found';
@@ -557,6 +579,12 @@ function renderEditTable(trees) {
             slope_percent: tree.slope_percent,
             aspect_degrees: tree.aspect_degrees,
             elevation_ft: tree.elevation_ft,
+            source: tree.source,
+            measurement_date: tree.measurement_date,
+            latitude: tree.latitude,
+            longitude: tree.longitude,
+            stratum: tree.stratum,
+            decay_class: tree.decay_class,
         };
 
         for (const f of EDIT_FIELDS) {
@@ -636,6 +664,12 @@ function collectTableData() {
         row.slope_percent = tr._hiddenFields ? tr._hiddenFields.slope_percent : null;
         row.aspect_degrees = tr._hiddenFields ? tr._hiddenFields.aspect_degrees : null;
         row.elevation_ft = tr._hiddenFields ? tr._hiddenFields.elevation_ft : null;
+        row.source = tr._hiddenFields ? tr._hiddenFields.source : null;
+        row.measurement_date = tr._hiddenFields ? tr._hiddenFields.measurement_date : null;
+        row.latitude = tr._hiddenFields ? tr._hiddenFields.latitude : null;
+        row.longitude = tr._hiddenFields ? tr._hiddenFields.longitude : null;
+        row.stratum = tr._hiddenFields ? tr._hiddenFields.stratum : null;
+        row.decay_class = tr._hiddenFields ? tr._hiddenFields.decay_class : null;
         rows.push(row);
     }
     return rows;
@@ -990,6 +1024,7 @@ async function revalidateData() {
 
         if (data.has_errors) {
             renderErrorList(data.errors);
+            renderPlotSummary(data);
             highlightErrorCells(data.errors);
         } else {
             showDashboard(data);
@@ -6,7 +6,7 @@ use tempfile::TempDir;
 
 use forest_inventory_analyzer::{
     io::write_csv,
-    models::{ForestInventory, Plot, Species, Tree, TreeStatus},
+    models::{ExpansionKind, ForestInventory, Plot, Species, Tree, TreeStatus},
 };
 
 /// Create a test inventory and write it to a CSV file in the given directory.
@@ -35,6 +35,11 @@ fn sample_inventory() -> ForestInventory {
         aspect_degrees: Some(180.0),
         elevation_ft: Some(1200.0),
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
         trees: vec![
             Tree {
                 tree_id: 1,
@@ -47,6 +52,7 @@ fn sample_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
             Tree {
                 tree_id: 2,
@@ -59,6 +65,7 @@ fn sample_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
         ],
     });
@@ -80,6 +87,7 @@ fn sample_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
             Tree {
                 tree_id: 4,
@@ -92,13 +100,24 @@ fn sample_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
         ],
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
     });
     inv
 }
 
+/// Exit status of an analysis whose sampling errors exceed
+/// `--max-sampling-error`. Both the two-plot test stand and the bundled
+/// sample exceed the default 30%.
+const EXIT_UNRELIABLE: i32 = 3;
+
 fn cmd() -> Command {
     Command::cargo_bin("forest-analyzer").unwrap()
 }
@@ -111,15 +130,530 @@ fn test_analyze_success() {
     let csv_path = create_test_csv(&dir);
 
     cmd()
-        .args(["analyze", "--input", csv_path.to_str().unwrap()])
+        .args(["analyze", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("Trees per Acre"))
+        .stdout(predicate::str::contains("Basal Area"));
+}
+
+#[test]
+fn test_analyze_custom_confidence() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--confidence",
+            "0.90",
+        ])
+        .assert()
+        .code(EXIT_UNRELIABLE);
+}
+
+#[test]
+fn test_analyze_rejects_out_of_range_confidence() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    for confidence in ["0", "1", "1.5"] {
+        cmd()
+            .args([
+                "analyze",
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--confidence",
+                confidence,
+            ])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("between 0.0 and 1.0"));
+    }
+}
+
+#[test]
+fn test_analyze_rejects_nan_confidence() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--confidence",
+            "NaN",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("between 0.0 and 1.0"));
+}
+
+#[test]
+fn test_analyze_target_error_status_column() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--target-error",
+            "10",
+        ])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("Status"))
+        .stdout(predicate::str::contains("Target Error: 10.0%"));
+}
+
+#[test]
+fn test_analyze_warns_on_unreliable_statistics() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    // Two plots with equal TPA but different basal area: only the BA and
+    // volume estimates exceed the default 30% sampling error
+    cmd()
+        .args(["analyze", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stderr(predicate::str::contains("sampling error exceeds 30.0%"))
+        .stderr(predicate::str::contains("basal_area:"))
+        .stderr(predicate::str::contains("tpa:").not());
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--max-sampling-error",
+            "1000",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("unreliable").not());
+}
+
+#[test]
+fn test_analyze_exit_codes() {
+    let dir = TempDir::new().unwrap();
+    let analyze = |path: &std::path::Path, extra: &[&str]| {
+        cmd()
+            .args(["analyze", "--input", path.to_str().unwrap()])
+            .args(extra)
+            .assert()
+    };
+    let write = |name: &str, inv: &ForestInventory| {
+        let path = dir.path().join(name);
+        write_csv(inv, &path).unwrap();
+        path
+    };
+
+    // Four identical plots: no sampling error, no validation issues
+    let mut inv = sample_inventory();
+    inv.plots.truncate(1);
+    for plot_id in 2..=4 {
+        let mut plot = inv.plots[0].clone();
+        plot.plot_id = plot_id;
+        for tree in &mut plot.trees {
+            tree.plot_id = plot_id;
+        }
+        inv.plots.push(plot);
+    }
+    let clean = write("clean.csv", &inv);
+    analyze(&clean, &[]).code(0);
+    analyze(&clean, &["--lenient"]).code(0);
+
+    // A slender tree on every plot is only a warning, reported under --lenient
+    for plot in &mut inv.plots {
+        plot.trees[0].height = Some(150.0);
+    }
+    let slender = write("slender.csv", &inv);
+    analyze(&slender, &[]).code(0);
+    analyze(&slender, &["--lenient"])
+        .code(2)
+        .stdout(predicate::str::contains("slenderness"));
+
+    // An invalid row fails outright, but is skipped under --lenient
+    inv.plots[3].trees[1].crown_ratio = Some(1.5);
+    let bad = write("bad.csv", &inv);
+    analyze(&bad, &[]).code(1);
+    analyze(&bad, &["--lenient", "--max-sampling-error", "1000"])
+        .code(2)
+        .stdout(predicate::str::contains("crown_ratio"))
+        .stdout(predicate::str::contains("Loaded 4 plots with 7 trees"))
+        .stderr(predicate::str::contains("skipped rows with 1 errors"));
+
+    // Unreliable statistics outrank warnings
+    analyze(&bad, &["--lenient"]).code(EXIT_UNRELIABLE);
+    analyze(&create_test_csv(&dir), &[]).code(EXIT_UNRELIABLE);
+}
+
+#[test]
+fn test_usage_errors_exit_1_not_warnings() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let input = csv_path.to_str().unwrap();
+
+    cmd()
+        .args(["analyze", "--input", input, "--bogus"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("--bogus"));
+    cmd()
+        .args(["analyze", "--input", input, "--confidence", "2"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("--confidence"));
+
+    // Help and version are not errors
+    cmd().arg("--help").assert().code(0);
+    cmd().arg("--version").assert().code(0);
+}
+
+#[test]
+fn test_analyze_windows_1252_csv() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("cp1252.csv");
+    let mut content = b"plot_id,tree_id,species_code,species_name,dbh,height,crown_ratio,status,expansion_factor,age,defect,plot_size_acres,slope_percent,aspect_degrees,elevation_ft\n".to_vec();
+    for plot in 1..=2 {
+        content.extend_from_slice(format!("{plot},1,EP,").as_bytes());
+        content.extend_from_slice(b"\xC9pinette");
+        content.extend_from_slice(b",14.0,90.0,0.5,Live,5.0,60,,0.2,15,180,3200\n");
+    }
+    std::fs::write(&csv_path, content).unwrap();
+
+    cmd()
+        .args(["analyze", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .failure();
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--encoding",
+            "windows-1252",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Épinette"));
+}
+
+#[test]
+fn test_analyze_svg_dir_uses_configured_colors() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let config_path = dir.path().join("config.toml");
+    std::fs::write(&config_path, "[species_colors]\nDF = \"#123456\"\n").unwrap();
+    let svg_dir = dir.path().join("charts");
+
+    cmd()
+        .args([
+            "--config",
+            config_path.to_str().unwrap(),
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--svg-dir",
+            svg_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .code(EXIT_UNRELIABLE);
+
+    for name in ["species_composition.svg", "diameter_distribution.svg"] {
+        let svg = std::fs::read_to_string(svg_dir.join(name)).unwrap();
+        assert!(svg.contains(r##"fill="#123456" data-species="DF""##), "{name}");
+    }
+}
+
+#[test]
+fn test_analyze_cumulative_distribution() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args(["analyze", "--input", csv_path.to_str().unwrap(), "--cumulative"])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("Cumulative Diameter Distribution"))
+        .stdout(predicate::str::contains("100.0%"));
+}
+
+#[test]
+fn test_analyze_distribution_status_and_min_dbh() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("snags.csv");
+    let mut inv = sample_inventory();
+    inv.plots[0].trees[0].status = TreeStatus::Dead;
+    write_csv(&inv, &csv_path).unwrap();
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--distribution-status",
+            "dead",
+            "--distribution-min-dbh",
+            "5",
+        ])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("Diameter Distribution"));
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--distribution-status",
+            "standing",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown tree status"));
+}
+
+#[test]
+fn test_analyze_ingrowth_class() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--distribution-min-dbh",
+            "15",
+            "--ingrowth-class",
+        ])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("   0-15  \""));
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--ingrowth-class",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--distribution-min-dbh"));
+}
+
+#[test]
+fn test_analyze_compact() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    for flag in ["--compact", "--summary-only"] {
+        cmd()
+            .args(["analyze", "--input", csv_path.to_str().unwrap(), flag])
+            .assert()
+            .code(EXIT_UNRELIABLE)
+            .stdout(predicate::str::is_match(r"(?m)^TPA: \d+\.\d ± \d+\.\d \(±\d+\.\d% at 95%\)$").unwrap())
+            .stdout(predicate::str::contains("QMD (in): "))
+            .stdout(predicate::str::contains("│").not())
+            .stdout(predicate::str::contains("╭").not());
+    }
+}
+
+#[test]
+fn test_analyze_group_by_file() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let groups_path = dir.path().join("groups.toml");
+    std::fs::write(&groups_path, "DF = \"Conifer\"\nWRC = \"Conifer\"\n").unwrap();
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--group-by",
+            groups_path.to_str().unwrap(),
+        ])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("Species Group Composition"))
+        .stdout(predicate::str::contains("DF, WRC"))
+        .stdout(predicate::str::contains("100.0%"));
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--group-by",
+            dir.path().join("missing.toml").to_str().unwrap(),
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_validate_clean_file() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args(["validate", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found in 4 rows"));
+}
+
+#[test]
+fn test_validate_warnings_only_exits_2() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("slender.csv");
+    let mut inv = sample_inventory();
+    inv.plots[0].trees[0].height = Some(150.0);
+    write_csv(&inv, &csv_path).unwrap();
+
+    cmd()
+        .args(["validate", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("warning"))
+        .stdout(predicate::str::contains("slenderness"))
+        .stdout(predicate::str::contains("0 errors, 1 warnings in 4 rows"));
+}
+
+#[test]
+fn test_validate_hard_error_fails() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("bad.csv");
+    let mut inv = sample_inventory();
+    inv.plots[0].trees[0].height = Some(150.0);
+    inv.plots[0].trees[1].crown_ratio = Some(1.5);
+    write_csv(&inv, &csv_path).unwrap();
+
+    cmd()
+        .args(["validate", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("crown_ratio"))
+        .stdout(predicate::str::contains("1 errors, 1 warnings in 4 rows"))
+        .stderr(predicate::str::contains("1 validation errors"));
+}
+
+#[test]
+fn test_template_csv_reads_back_empty() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("template.csv");
+
+    cmd()
+        .args(["template", "--output", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template written"));
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(text.starts_with("plot_id,tree_id,species_code"));
+    assert!(text.contains("dbh: diameter at breast height, inches"));
+    let inv = forest_inventory_analyzer::io::read_csv(&path).unwrap();
+    assert_eq!(inv.num_trees(), 0);
+
+    cmd()
+        .args(["template", "--output", "template.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported template format"));
+}
+
+#[test]
+fn test_summary_tract_totals() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args(["summary", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tract Totals").not());
+
+    let inv = sample_inventory();
+    let expected = format!("Total Stems:    {:.0}", inv.mean_tpa() * 40.0);
+    cmd()
+        .args([
+            "summary",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--acres",
+            "40",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tract Totals (40.0 acres)"))
+        .stdout(predicate::str::contains(expected))
+        .stdout(predicate::str::contains("Total Volume:"));
+}
+
+#[test]
+fn test_summary_and_analyze_in_metric_units() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let input = csv_path.to_str().unwrap();
+    let expected = format!("Mean Trees/ha:  {:.1}", sample_inventory().mean_tpa() * 2.471);
+
+    cmd()
+        .args(["summary", "--input", input, "--units", "metric"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(expected))
+        .stdout(predicate::str::contains("m²"))
+        .stdout(predicate::str::contains("bd ft").not());
+
+    cmd()
+        .args(["analyze", "--input", input, "--units", "metric"])
+        .assert()
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("Trees per Hectare"))
+        .stdout(predicate::str::contains("m³/ha"))
+        .stdout(predicate::str::contains("Trees per Acre").not());
+
+    cmd()
+        .args(["summary", "--input", input, "--units", "furlongs"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_analyze_distribution_and_statistics_in_metric_units() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--units",
+            "metric",
+            "--cumulative",
+        ])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("Trees per Acre"))
-        .stdout(predicate::str::contains("Basal Area"));
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("BA/ha"))
+        .stdout(predicate::str::contains("Cumulative Trees/ha"))
+        .stdout(predicate::str::contains("Basal Area (m²/ha)"))
+        .stdout(predicate::str::contains("BA/ac").not())
+        .stdout(predicate::str::contains("sq ft/ac").not());
 }
 
 #[test]
-fn test_analyze_custom_confidence() {
+fn test_analyze_histogram_basal_area_mode() {
     let dir = TempDir::new().unwrap();
     let csv_path = create_test_csv(&dir);
 
@@ -128,11 +662,126 @@ fn test_analyze_custom_confidence() {
             "analyze",
             "--input",
             csv_path.to_str().unwrap(),
-            "--confidence",
-            "0.90",
+            "--histogram-metric",
+            "ba",
+            "--histogram-width",
+            "60",
         ])
         .assert()
-        .success();
+        .code(EXIT_UNRELIABLE)
+        .stdout(predicate::str::contains("Distribution (BA)"))
+        .stdout(predicate::str::contains("\u{2588}".repeat(60)));
+}
+
+#[test]
+fn test_analyze_sort_species_by_name() {
+    let input = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/samples/sample_inventory.csv");
+    let output = cmd()
+        .args([
+            "analyze",
+            "--input",
+            input.to_str().unwrap(),
+            "--sort-species",
+            "name",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(EXIT_UNRELIABLE));
+
+    // Western Red Cedar has the second-largest basal area but sorts last by name
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let table = &stdout[stdout.find("Species Composition").unwrap()..];
+    let position = |name: &str| table.find(name).unwrap();
+    assert!(position("Douglas Fir") < position("Ponderosa Pine"));
+    assert!(position("Ponderosa Pine") < position("Western Hemlock"));
+    assert!(position("Western Hemlock") < position("Western Red Cedar"));
+}
+
+#[test]
+fn test_analyze_top_species_rolls_up_other() {
+    let input = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/samples/sample_inventory.csv");
+    let output = cmd()
+        .args([
+            "analyze",
+            "--input",
+            input.to_str().unwrap(),
+            "--top-species",
+            "2",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(EXIT_UNRELIABLE));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let table = &stdout[stdout.find("Species Composition").unwrap()..];
+    assert!(table.contains("Douglas Fir"));
+    assert!(table.contains("Western Red Cedar"));
+    assert!(!table.contains("Western Hemlock"));
+    assert!(table.contains("Other"));
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            input.to_str().unwrap(),
+            "--top-species",
+            "0",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--top-species must be positive"));
+}
+
+#[test]
+fn test_harvest_command() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("thinned.csv");
+    let mut inv = sample_inventory();
+    inv.plots[0].trees[0].status = TreeStatus::Cut;
+    write_csv(&inv, &csv_path).unwrap();
+
+    cmd()
+        .args(["harvest", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Harvest Summary"))
+        .stdout(predicate::str::contains(
+            inv.plots[0].trees[0].species.common_name.as_str(),
+        ))
+        .stdout(predicate::str::contains("of pre-harvest"));
+}
+
+#[test]
+fn test_qa_plots_command() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args(["qa-plots", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Plot QA"))
+        .stdout(predicate::str::contains("plots flagged"));
+}
+
+#[test]
+fn test_analyze_rejects_non_positive_diameter_width() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    for width in ["0", "-2"] {
+        cmd()
+            .args([
+                "analyze",
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--diameter-class-width",
+                width,
+            ])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("diameter-class-width"));
+    }
 }
 
 #[test]
@@ -149,7 +798,7 @@ fn test_analyze_custom_diameter_width() {
             "4.0",
         ])
         .assert()
-        .success();
+        .code(EXIT_UNRELIABLE);
 }
 
 // --- Growth subcommand ---
@@ -212,6 +861,37 @@ fn test_growth_linear() {
         .success();
 }
 
+#[test]
+fn test_growth_thinning() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args([
+            "growth",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--years",
+            "10",
+            "--thin",
+            "5:0.6",
+        ])
+        .assert()
+        .success();
+
+    cmd()
+        .args([
+            "growth",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--thin",
+            "5:1.5",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--thin"));
+}
+
 #[test]
 fn test_growth_invalid_model() {
     let dir = TempDir::new().unwrap();
@@ -249,6 +929,61 @@ fn test_growth_with_mortality() {
         .success();
 }
 
+#[test]
+fn test_growth_with_recruitment() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+
+    cmd()
+        .args([
+            "growth",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--years",
+            "10",
+            "--recruitment",
+            "5",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Recruited TPA"))
+        .stdout(predicate::str::contains("50.0"));
+}
+
+#[test]
+fn test_fit_reports_model_errors() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let fit = |rate: &str| {
+        cmd()
+            .args([
+                "fit",
+                "--before",
+                csv_path.to_str().unwrap(),
+                "--after",
+                csv_path.to_str().unwrap(),
+                "--years",
+                "5",
+                "--model",
+                "exponential",
+                "--rate",
+                rate,
+                "--mortality",
+                "0",
+            ])
+            .assert()
+            .success()
+    };
+
+    // An unchanged stand is fit exactly by zero growth
+    fit("0")
+        .stdout(predicate::str::contains("Plots compared: 2"))
+        .stdout(predicate::str::contains("RMSE BA/ac:     0.00 sq ft"));
+    fit("0.05")
+        .stdout(predicate::str::contains("RMSE BA/ac:     0.00").not())
+        .stdout(predicate::str::contains("Bias BA/ac:     +"));
+}
+
 // --- Convert subcommand ---
 
 #[test]
@@ -272,6 +1007,209 @@ fn test_convert_csv_to_json() {
     assert!(json_path.exists());
 }
 
+#[test]
+fn test_convert_with_expansion_kind_records_it_in_output() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let counts_path = dir.path().join("counts.csv");
+    let xlsx_path = dir.path().join("counts.xlsx");
+
+    let convert = |input: &std::path::Path, output: &std::path::Path, extra: &[&str]| {
+        cmd()
+            .args(extra)
+            .args(["convert", "--input", input.to_str().unwrap()])
+            .args(["--output", output.to_str().unwrap()])
+            .assert()
+            .success();
+    };
+    convert(&csv_path, &counts_path, &["--expansion-kind", "count"]);
+    // The kind is read back from the CSV without the flag
+    convert(&counts_path, &xlsx_path, &[]);
+
+    let csv = std::fs::read_to_string(&counts_path).unwrap();
+    assert!(csv.starts_with("# expansion_kind: count\n"), "{csv}");
+    let back = forest_inventory_analyzer::io::read_excel(&xlsx_path).unwrap();
+    assert_eq!(back.expansion_kind, ExpansionKind::CountOnPlot);
+
+    cmd()
+        .args(["--expansion-kind", "hectare", "analyze", "--input"])
+        .arg(&csv_path)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("per-acre or count"));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_convert_csv_to_parquet_and_back() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let parquet_path = dir.path().join("output.parquet");
+    let back_path = dir.path().join("back.csv");
+
+    for (input, output) in [(&csv_path, &parquet_path), (&parquet_path, &back_path)] {
+        cmd()
+            .args([
+                "convert",
+                "--input",
+                input.to_str().unwrap(),
+                "--output",
+                output.to_str().unwrap(),
+            ])
+            .assert()
+            .success();
+    }
+
+    let original = forest_inventory_analyzer::io::read_csv(&csv_path).unwrap();
+    let back = forest_inventory_analyzer::io::read_csv(&back_path).unwrap();
+    assert_eq!(back.num_trees(), original.num_trees());
+    assert_eq!(
+        back.content_hash().unwrap(),
+        original.content_hash().unwrap()
+    );
+}
+
+#[test]
+fn test_convert_jsonl_to_csv() {
+    let dir = TempDir::new().unwrap();
+    let jsonl_path = dir.path().join("trees.jsonl");
+    std::fs::write(
+        &jsonl_path,
+        concat!(
+            r#"{"plot_id":1,"tree_id":1,"species_code":"DF","species_name":"Douglas Fir","dbh":14.0,"height":90.0,"crown_ratio":0.5,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null}"#,
+            "\n",
+            r#"{"plot_id":2,"tree_id":1,"species_code":"WRC","species_name":"Western Red Cedar","dbh":20.0,"height":110.0,"crown_ratio":0.6,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+    let csv_path = dir.path().join("trees.csv");
+
+    cmd()
+        .args([
+            "convert",
+            "--input",
+            jsonl_path.to_str().unwrap(),
+            "--output",
+            csv_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let inv = forest_inventory_analyzer::io::read_csv(&csv_path).unwrap();
+    assert_eq!(inv.num_plots(), 2);
+    assert_eq!(inv.num_trees(), 2);
+}
+
+#[test]
+fn test_validate_and_lenient_analyze_jsonl() {
+    let dir = TempDir::new().unwrap();
+    let jsonl_path = dir.path().join("trees.jsonl");
+    std::fs::write(
+        &jsonl_path,
+        concat!(
+            r#"{"plot_id":1,"tree_id":1,"species_code":"DF","species_name":"Douglas Fir","dbh":14.0,"height":90.0,"crown_ratio":0.5,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null}"#,
+            "\n",
+            r#"{"plot_id":1,"tree_id":2,"species_code":"DF","species_name":"Douglas Fir","dbh":12.0,"height":80.0,"crown_ratio":1.5,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null}"#,
+            "\n",
+            r#"{"plot_id":2,"tree_id":1,"species_code":"WRC","species_name":"Western Red Cedar","dbh":20.0,"height":110.0,"crown_ratio":0.6,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["validate", "--input", jsonl_path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("crown_ratio"))
+        .stdout(predicate::str::contains("1 errors, 0 warnings in 3 rows"));
+
+    cmd()
+        .args([
+            "analyze",
+            "--input",
+            jsonl_path.to_str().unwrap(),
+            "--lenient",
+            "--max-sampling-error",
+            "1000",
+        ])
+        .assert()
+        .stdout(predicate::str::contains("Loaded 2 plots with 2 trees"))
+        .stderr(predicate::str::contains("skipped rows with 1 errors"));
+}
+
+#[test]
+fn test_convert_csv_to_geojson_keeps_plots_without_coordinates() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("located.csv");
+    let mut inv = sample_inventory();
+    inv.plots[0].latitude = Some(45.52);
+    inv.plots[0].longitude = Some(-122.68);
+    write_csv(&inv, &csv_path).unwrap();
+    let geojson_path = dir.path().join("output.geojson");
+
+    cmd()
+        .args([
+            "convert",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--output",
+            geojson_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(&geojson_path).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let features = parsed["features"].as_array().unwrap();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features[0]["geometry"]["type"], "Point");
+    assert!(features[1]["geometry"].is_null());
+}
+
+#[test]
+fn test_map_renamed_csv_columns() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = dir.path().join("crew.csv");
+    std::fs::write(
+        &csv_path,
+        "plot_id,tree_id,species_code,species_name,DBH_in,status,expansion_factor\n\
+         1,1,DF,Douglas Fir,14.5,Live,5\n\
+         2,1,DF,Douglas Fir,12.0,Live,5\n",
+    )
+    .unwrap();
+
+    cmd()
+        .args(["summary", "--input", csv_path.to_str().unwrap()])
+        .assert()
+        .failure();
+
+    cmd()
+        .args([
+            "summary",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--map",
+            "dbh=DBH_in",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total Trees:    2"));
+
+    cmd()
+        .args([
+            "summary",
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--map",
+            "diameter=DBH_in",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown column"));
+}
+
 #[test]
 fn test_convert_csv_to_excel() {
     let dir = TempDir::new().unwrap();
@@ -305,7 +1243,39 @@ fn test_summary_success() {
         .success()
         .stdout(predicate::str::contains("Quick Summary"))
         .stdout(predicate::str::contains("Plots"))
-        .stdout(predicate::str::contains("Species"));
+        .stdout(predicate::str::contains("Species"))
+        .stdout(predicate::str::contains("Sampling Intensity"))
+        .stdout(predicate::str::contains("Trees Measured:"));
+}
+
+#[test]
+fn test_summary_and_growth_in_mbf() {
+    let dir = TempDir::new().unwrap();
+    let csv_path = create_test_csv(&dir);
+    let input = csv_path.to_str().unwrap();
+    let mean_bdft = sample_inventory().mean_volume_bdft();
+
+    cmd()
+        .args(["summary", "--input", input, "--mbf"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{:.1} MBF",
+            mean_bdft / 1000.0
+        )))
+        .stdout(predicate::str::contains("bd ft").not());
+
+    cmd()
+        .args(["growth", "--input", input, "--years", "10", "--mbf"])
+        .args(["--mbf-label", "Mbf"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Vol (Mbf/ac)"));
+
+    cmd()
+        .args(["summary", "--input", input, "--mbf-label", "Mbf"])
+        .assert()
+        .failure();
 }
 
 // --- Error cases ---
@@ -386,6 +1356,63 @@ fn test_analyze_batch_success() {
     }
 }
 
+#[test]
+fn test_convert_directory() {
+    let input_dir = TempDir::new().unwrap();
+    let output_dir = TempDir::new().unwrap();
+
+    for name in ["stand_a", "stand_b"] {
+        let path = input_dir.path().join(format!("{name}.csv"));
+        write_csv(&sample_inventory(), &path).unwrap();
+    }
+    // Unsupported files are ignored
+    std::fs::write(input_dir.path().join("notes.txt"), "not inventory").unwrap();
+
+    cmd()
+        .args([
+            "convert",
+            "--input",
+            input_dir.path().to_str().unwrap(),
+            "--output",
+            output_dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Batch Convert"))
+        .stdout(predicate::str::contains("Converted 2 files, 0 failed"));
+
+    for name in ["stand_a", "stand_b"] {
+        let out = output_dir.path().join(format!("{name}.json"));
+        let inv = forest_inventory_analyzer::io::read_json(&out).unwrap();
+        assert_eq!(inv.num_plots(), 2);
+    }
+}
+
+#[test]
+fn test_convert_directory_continues_past_failures() {
+    let input_dir = TempDir::new().unwrap();
+    let output_dir = TempDir::new().unwrap();
+
+    write_csv(&sample_inventory(), input_dir.path().join("good.csv")).unwrap();
+    std::fs::write(input_dir.path().join("bad.csv"), "not,a,valid\ninventory\n").unwrap();
+
+    cmd()
+        .args([
+            "convert",
+            "--input",
+            input_dir.path().to_str().unwrap(),
+            "--output",
+            output_dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Converted 1 files, 1 failed"))
+        .stderr(predicate::str::contains("FAIL"));
+
+    assert!(output_dir.path().join("good.json").exists());
+    assert!(!output_dir.path().join("bad.json").exists());
+}
+
 #[test]
 fn test_analyze_batch_empty_dir() {
     let input_dir = TempDir::new().unwrap();
@@ -0,0 +1,59 @@
+use std::process::Command;
+
+/// Type-check the library with a feature set, without the defaults.
+fn check_lib(features: &str) {
+    let output = Command::new(env!("CARGO"))
+        .args(["check", "--lib", "--quiet", "--no-default-features"])
+        .args(["--features", features])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("failed to run cargo");
+    assert!(
+        output.status.success(),
+        "cargo check --no-default-features --features {features} failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_builds_with_analysis_only() {
+    check_lib("analysis");
+}
+
+#[test]
+fn test_builds_with_config_without_visualization() {
+    check_lib("config");
+}
+
+#[test]
+fn test_analysis_only_pulls_in_no_io_dependencies() {
+    let output = Command::new(env!("CARGO"))
+        .args(["tree", "--no-default-features", "--features", "analysis"])
+        .args(["--edges", "normal", "--depth", "1", "--prefix", "none"])
+        .args(["--format", "{lib}"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("failed to run cargo");
+    assert!(
+        output.status.success(),
+        "cargo tree failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mut deps: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(1)
+        .map(str::to_string)
+        .collect();
+    deps.sort();
+    let expected = [
+        "rand",
+        "serde",
+        "serde_json",
+        "sha2",
+        "statrs",
+        "thiserror",
+        "tracing",
+    ];
+    assert_eq!(deps, expected);
+}
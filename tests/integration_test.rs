@@ -5,7 +5,7 @@ use forest_inventory_analyzer::{
     },
     error::ForestError,
     io,
-    models::{ForestInventory, Plot, Species, Tree, TreeStatus},
+    models::{ExpansionKind, ForestInventory, Plot, Species, Tree, TreeStatus},
 };
 
 fn create_test_inventory() -> ForestInventory {
@@ -20,6 +20,11 @@ fn create_test_inventory() -> ForestInventory {
             elevation_ft: Some(3000.0),
             trees: Vec::new(),
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         };
 
         // Add trees to each plot with varying expansion factors to create
@@ -40,6 +45,7 @@ fn create_test_inventory() -> ForestInventory {
             expansion_factor: ef,
             age: Some(80),
             defect: None,
+            decay_class: None,
         });
 
         plot.trees.push(Tree {
@@ -56,6 +62,7 @@ fn create_test_inventory() -> ForestInventory {
             expansion_factor: ef,
             age: Some(90),
             defect: Some(0.05),
+            decay_class: None,
         });
 
         plot.trees.push(Tree {
@@ -72,6 +79,7 @@ fn create_test_inventory() -> ForestInventory {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         });
 
         inventory.plots.push(plot);
@@ -110,6 +118,7 @@ fn test_tree_basal_area() {
         expansion_factor: 5.0,
         age: Some(60),
         defect: None,
+        decay_class: None,
     };
 
     let ba = tree.basal_area_sqft();
@@ -133,6 +142,7 @@ fn test_tree_volume() {
         expansion_factor: 5.0,
         age: Some(75),
         defect: None,
+        decay_class: None,
     };
 
     let vol = tree.volume_cuft().unwrap();
@@ -405,6 +415,300 @@ fn test_csv_roundtrip() {
     assert_eq!(loaded.num_trees(), inventory.num_trees());
 }
 
+#[cfg(feature = "parquet")]
+#[test]
+fn test_parquet_roundtrip_preserves_trees_and_basal_area() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].stand_id = Some(3);
+    inventory.plots[1].latitude = Some(45.5);
+    inventory.plots[1].longitude = Some(-122.7);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("stand.parquet");
+    io::write_parquet(&inventory, &path).unwrap();
+    let loaded = io::read_parquet(&path).unwrap();
+
+    assert_eq!(loaded.num_plots(), inventory.num_plots());
+    assert_eq!(loaded.num_trees(), inventory.num_trees());
+    assert_eq!(loaded.mean_basal_area(), inventory.mean_basal_area());
+    // Plot fields repeat on every tree row and are read back once per plot
+    for (orig, back) in inventory.plots.iter().zip(&loaded.plots) {
+        assert_eq!(back.plot_size_acres, orig.plot_size_acres);
+        assert_eq!(back.slope_percent, orig.slope_percent);
+        assert_eq!(back.stand_id, orig.stand_id);
+        assert_eq!(back.latitude, orig.latitude);
+    }
+    assert_eq!(
+        loaded.content_hash().unwrap(),
+        inventory.content_hash().unwrap()
+    );
+
+    // read_file picks the Parquet reader by extension
+    assert_eq!(
+        io::read_file(&path).unwrap().num_trees(),
+        inventory.num_trees()
+    );
+}
+
+#[test]
+fn test_merged_sources_survive_csv_json_and_excel() {
+    let north = create_test_inventory();
+    let mut south = create_test_inventory();
+    south.name = "South".to_string();
+    let merged = ForestInventory::merge("Merged", [&north, &south]);
+    assert_eq!(merged.num_plots(), 2 * north.num_plots());
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("merged.csv");
+    io::write_csv(&merged, &csv_path).unwrap();
+    let json_path = dir.path().join("merged.json");
+    io::write_json_long(&merged, &json_path).unwrap();
+    let xlsx_path = dir.path().join("merged.xlsx");
+    io::write_excel(&merged, &xlsx_path).unwrap();
+
+    for loaded in [
+        io::read_csv(&csv_path).unwrap(),
+        io::read_json_long(&json_path).unwrap(),
+        io::read_excel(&xlsx_path).unwrap(),
+    ] {
+        assert_eq!(loaded.num_plots(), merged.num_plots());
+        for (back, orig) in loaded.plots.iter().zip(&merged.plots) {
+            assert_eq!(back.plot_id, orig.plot_id);
+            assert_eq!(back.source, orig.source);
+        }
+    }
+}
+
+#[test]
+fn test_decay_class_survives_csv_json_and_excel() {
+    let mut inventory = create_test_inventory();
+    let snag = &mut inventory.plots[1].trees[0];
+    snag.status = TreeStatus::Dead;
+    snag.decay_class = Some(3);
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("snags.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+    let json_path = dir.path().join("snags.json");
+    io::write_json(&inventory, &json_path, false).unwrap();
+    let long_path = dir.path().join("snags_long.json");
+    io::write_json_long(&inventory, &long_path).unwrap();
+    let xlsx_path = dir.path().join("snags.xlsx");
+    io::write_excel(&inventory, &xlsx_path).unwrap();
+
+    for loaded in [
+        io::read_csv(&csv_path).unwrap(),
+        io::read_json(&json_path).unwrap(),
+        io::read_json_long(&long_path).unwrap(),
+        io::read_excel(&xlsx_path).unwrap(),
+    ] {
+        let classes: Vec<Option<u8>> = loaded
+            .plots
+            .iter()
+            .flat_map(|p| p.trees.iter().map(|t| t.decay_class))
+            .collect();
+        assert_eq!(classes.iter().flatten().collect::<Vec<_>>(), vec![&3]);
+        assert_eq!(loaded.plots[1].trees[0].decay_class, Some(3));
+    }
+}
+
+#[test]
+fn test_measurement_date_survives_csv_json_and_excel() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].measurement_date = Some("2024-06-12".to_string());
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("dated.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+    let json_path = dir.path().join("dated.json");
+    io::write_json(&inventory, &json_path, false).unwrap();
+    let long_path = dir.path().join("dated_long.json");
+    io::write_json_long(&inventory, &long_path).unwrap();
+    let xlsx_path = dir.path().join("dated.xlsx");
+    io::write_excel(&inventory, &xlsx_path).unwrap();
+
+    for loaded in [
+        io::read_csv(&csv_path).unwrap(),
+        io::read_json(&json_path).unwrap(),
+        io::read_json_long(&long_path).unwrap(),
+        io::read_excel(&xlsx_path).unwrap(),
+    ] {
+        for (back, orig) in loaded.plots.iter().zip(&inventory.plots) {
+            assert_eq!(back.measurement_date, orig.measurement_date);
+        }
+        assert_eq!(
+            loaded.measurement_date_range(),
+            Some(("2024-06-12", "2024-06-12"))
+        );
+    }
+}
+
+#[test]
+fn test_plot_coordinates_survive_csv_json_and_excel() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].latitude = Some(45.52);
+    inventory.plots[0].longitude = Some(-122.68);
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("located.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+    let json_path = dir.path().join("located.json");
+    io::write_json(&inventory, &json_path, false).unwrap();
+    let long_path = dir.path().join("located_long.json");
+    io::write_json_long(&inventory, &long_path).unwrap();
+    let xlsx_path = dir.path().join("located.xlsx");
+    io::write_excel(&inventory, &xlsx_path).unwrap();
+
+    for loaded in [
+        io::read_csv(&csv_path).unwrap(),
+        io::read_json(&json_path).unwrap(),
+        io::read_json_long(&long_path).unwrap(),
+        io::read_excel(&xlsx_path).unwrap(),
+    ] {
+        for (back, orig) in loaded.plots.iter().zip(&inventory.plots) {
+            assert_eq!(back.latitude, orig.latitude);
+            assert_eq!(back.longitude, orig.longitude);
+        }
+    }
+}
+
+#[test]
+fn test_expansion_kind_survives_csv_excel_and_parquet() {
+    let mut inventory = create_test_inventory();
+    inventory.expansion_kind = ExpansionKind::CountOnPlot;
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("counts.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+    let xlsx_path = dir.path().join("counts.xlsx");
+    io::write_excel(&inventory, &xlsx_path).unwrap();
+
+    for loaded in [
+        io::read_csv(&csv_path).unwrap(),
+        io::read_excel(&xlsx_path).unwrap(),
+        io::read_file_lenient(&csv_path, io::CsvEncoding::Utf8)
+            .unwrap()
+            .0,
+        io::read_file_lenient(&xlsx_path, io::CsvEncoding::Utf8)
+            .unwrap()
+            .0,
+    ] {
+        assert_eq!(loaded.expansion_kind, ExpansionKind::CountOnPlot);
+        assert!((loaded.mean_tpa() - inventory.mean_tpa()).abs() < 1e-9);
+    }
+    #[cfg(feature = "parquet")]
+    {
+        let parquet_path = dir.path().join("counts.parquet");
+        io::write_parquet(&inventory, &parquet_path).unwrap();
+        let loaded = io::read_parquet(&parquet_path).unwrap();
+        assert_eq!(loaded.expansion_kind, ExpansionKind::CountOnPlot);
+    }
+    // No provenance was written, only the expansion kind
+    assert!(io::read_excel_metadata(&xlsx_path).unwrap().is_none());
+
+    // Per-acre files are unchanged and read back as per-acre
+    inventory.expansion_kind = ExpansionKind::PerAcre;
+    io::write_csv(&inventory, &csv_path).unwrap();
+    assert!(std::fs::read_to_string(&csv_path)
+        .unwrap()
+        .starts_with("plot_id,"));
+    assert_eq!(
+        io::read_csv(&csv_path).unwrap().expansion_kind,
+        ExpansionKind::PerAcre
+    );
+}
+
+#[test]
+fn test_plot_stratum_survives_csv_json_and_excel() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].stratum = Some("north".to_string());
+    inventory.plots[1].stratum = Some("north".to_string());
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("strata.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+    let long_path = dir.path().join("strata_long.json");
+    io::write_json_long(&inventory, &long_path).unwrap();
+    let xlsx_path = dir.path().join("strata.xlsx");
+    io::write_excel(&inventory, &xlsx_path).unwrap();
+
+    for loaded in [
+        io::read_csv(&csv_path).unwrap(),
+        io::read_json_long(&long_path).unwrap(),
+        io::read_excel(&xlsx_path).unwrap(),
+    ] {
+        let strata: Vec<Option<&str>> = loaded.plots.iter().map(|p| p.stratum.as_deref()).collect();
+        assert_eq!(strata, [Some("north"), Some("north"), None]);
+    }
+
+    // The third plot alone is too few for its own stratum
+    let err = SamplingStatistics::compute_stratified(&inventory, 0.95).unwrap_err();
+    assert!(matches!(err, ForestError::InsufficientData(_)));
+    inventory.plots[2].stratum = Some("north".to_string());
+    let stratified = SamplingStatistics::compute_stratified(&inventory, 0.95).unwrap();
+    assert_eq!(stratified.strata["north"].num_plots, 3);
+}
+
+#[test]
+fn test_validate_file_warns_on_malformed_measurement_date() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].measurement_date = Some("06/12/2024".to_string());
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("bad_date.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+
+    let report = io::validate_file(&csv_path, io::CsvEncoding::Utf8).unwrap();
+    assert!(!report.has_errors());
+    assert_eq!(report.warning_count(), inventory.plots[0].trees.len());
+    assert!(report
+        .issues
+        .iter()
+        .all(|i| i.issue.field == "measurement_date"
+            && i.severity == io::Severity::Warning
+            && i.issue.message.contains("06/12/2024")));
+    // The strict reader still loads the file
+    assert!(io::read_csv(&csv_path)
+        .unwrap()
+        .measurement_date_range()
+        .is_none());
+}
+
+#[test]
+fn test_validate_file_warns_on_expansion_mismatch() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].plot_size_acres = 0.5;
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("mismatch.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+
+    let report = io::validate_file(&csv_path, io::CsvEncoding::Utf8).unwrap();
+    assert!(!report.has_errors());
+    assert_eq!(report.warning_count(), 1);
+    let issue = &report.issues[0].issue;
+    assert_eq!(issue.field, "expansion_factor");
+    assert_eq!(issue.plot_id, inventory.plots[0].plot_id);
+}
+
+#[test]
+fn test_read_file_lenient_skips_invalid_rows() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].trees[0].dbh = -1.0;
+    let total = inventory.num_trees();
+
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("one_bad.csv");
+    io::write_csv(&inventory, &csv_path).unwrap();
+    assert!(io::read_csv(&csv_path).is_err());
+
+    let (loaded, report) = io::read_file_lenient(&csv_path, io::CsvEncoding::Utf8).unwrap();
+    assert_eq!(report.error_count(), 1);
+    assert_eq!(report.num_rows, total);
+    assert_eq!(loaded.num_trees(), total - 1);
+    assert!(loaded.plots.iter().flat_map(|p| &p.trees).all(|t| t.dbh > 0.0));
+}
+
 #[test]
 fn test_csv_preserves_tree_data() {
     let inventory = create_test_inventory();
@@ -425,6 +729,108 @@ fn test_csv_preserves_tree_data() {
     assert!((orig_tpa - loaded_tpa).abs() < 0.01);
 }
 
+#[test]
+fn test_csv_column_subset() {
+    let inventory = create_test_inventory();
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("slim.csv");
+
+    let columns = [
+        io::TreeColumn::SpeciesCode,
+        io::TreeColumn::PlotId,
+        io::TreeColumn::Dbh,
+    ];
+    io::write_csv_columns(&inventory, &csv_path, &columns).unwrap();
+
+    let content = std::fs::read_to_string(&csv_path).unwrap();
+    let mut lines = content.lines();
+    assert_eq!(lines.next().unwrap(), "species_code,plot_id,dbh");
+    let first = &inventory.plots[0].trees[0];
+    assert_eq!(
+        lines.next().unwrap(),
+        format!("{},{},{:?}", first.species.code, first.plot_id, first.dbh)
+    );
+    assert_eq!(content.lines().count(), inventory.num_trees() + 1);
+    for absent in ["tree_id", "height", "status", "expansion_factor"] {
+        assert!(!content.contains(absent), "{absent}");
+    }
+}
+
+#[test]
+fn test_read_csv_mapped_renames_crew_headers() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("crew.csv");
+    std::fs::write(
+        &csv_path,
+        "plot_id,tree_id,species_code,species_name,DBH_in,height,TreeStatus,expansion_factor\n\
+         1,1,DF,Douglas Fir,14.5,90,Live,5\n\
+         1,2,WH,Western Hemlock,10.0,70,Dead,5\n",
+    )
+    .unwrap();
+
+    // The fixed headers don't match, so a plain read fails
+    assert!(io::read_csv(&csv_path).is_err());
+
+    let mapping = io::ColumnMapping::new()
+        .with(io::TreeColumn::Dbh, "dbh_in")
+        .with(io::TreeColumn::Status, "TreeStatus");
+    let inventory = io::read_csv_mapped(&csv_path, &mapping).unwrap();
+    assert_eq!(inventory.num_trees(), 2);
+    let trees = &inventory.plots[0].trees;
+    assert_eq!(trees[0].dbh, 14.5);
+    assert_eq!(trees[1].status, TreeStatus::Dead);
+    assert_eq!(trees[0].height, Some(90.0));
+}
+
+#[test]
+fn test_read_csv_mapped_names_missing_required_column() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("crew.csv");
+    std::fs::write(
+        &csv_path,
+        "plot_id,tree_id,species_code,species_name,DBH_in,status\n\
+         1,1,DF,Douglas Fir,14.5,Live\n",
+    )
+    .unwrap();
+
+    let mapping = io::ColumnMapping::new().with(io::TreeColumn::Dbh, "DBH_in");
+    let err = io::read_csv_mapped(&csv_path, &mapping).unwrap_err();
+    assert!(matches!(err.without_context(), ForestError::ParseError(_)));
+    assert!(err.to_string().contains("'expansion_factor'"), "{err}");
+
+    let mapping = io::ColumnMapping::new().with(io::TreeColumn::Dbh, "Diameter");
+    let err = io::read_csv_mapped(&csv_path, &mapping).unwrap_err();
+    assert!(err.to_string().contains("'dbh'"), "{err}");
+    assert!(err.to_string().contains("'Diameter'"), "{err}");
+}
+
+#[test]
+fn test_csv_column_subset_rejects_bad_requests() {
+    let inventory = create_test_inventory();
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("bad.csv");
+
+    let duplicate = [
+        io::TreeColumn::Dbh,
+        io::TreeColumn::PlotId,
+        io::TreeColumn::Dbh,
+    ];
+    let err = io::write_csv_columns(&inventory, &csv_path, &duplicate).unwrap_err();
+    assert!(matches!(err, ForestError::ValidationError(_)));
+    assert!(err.to_string().contains("'dbh'"));
+    assert!(io::write_csv_columns(&inventory, &csv_path, &[]).is_err());
+    assert!(!csv_path.exists());
+
+    assert_eq!(
+        "Species_Code".parse::<io::TreeColumn>().unwrap(),
+        io::TreeColumn::SpeciesCode
+    );
+    assert!(matches!(
+        "diameter".parse::<io::TreeColumn>(),
+        Err(ForestError::ParseError(_))
+    ));
+}
+
 #[test]
 fn test_csv_species_preserved() {
     let inventory = create_test_inventory();
@@ -459,6 +865,145 @@ fn test_json_roundtrip() {
     assert_eq!(loaded.name, inventory.name);
 }
 
+#[test]
+fn test_json_long_roundtrip_regroups_plots() {
+    let mut inventory = create_test_inventory();
+    inventory.plots[0].stand_id = Some(7);
+
+    let dir = tempfile::tempdir().unwrap();
+    let json_path = dir.path().join("long.json");
+
+    io::write_json_long(&inventory, &json_path).unwrap();
+
+    let value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    let rows = value.as_array().unwrap();
+    assert_eq!(rows.len(), inventory.num_trees());
+    assert_eq!(rows[0]["plot_size_acres"], inventory.plots[0].plot_size_acres);
+    assert_eq!(rows[0]["stand_id"], 7);
+
+    let loaded = io::read_json_long(&json_path).unwrap();
+    assert_eq!(loaded.name, "long");
+    assert_eq!(loaded.num_plots(), inventory.num_plots());
+    for (orig, back) in inventory.plots.iter().zip(&loaded.plots) {
+        assert_eq!(back.plot_id, orig.plot_id);
+        assert_eq!(back.trees.len(), orig.trees.len());
+        assert_eq!(back.slope_percent, orig.slope_percent);
+        assert_eq!(back.stand_id, orig.stand_id);
+    }
+    assert_eq!(
+        loaded.content_hash().unwrap(),
+        inventory.content_hash().unwrap()
+    );
+}
+
+#[test]
+fn test_jsonl_groups_trees_like_monolithic_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let jsonl_path = dir.path().join("stream.jsonl");
+    // Plots arrive interleaved; plot attributes come from each plot's first line
+    let lines = [
+        r#"{"plot_id":2,"tree_id":1,"species_code":"DF","species_name":"Douglas Fir","dbh":14.0,"height":90.0,"crown_ratio":0.5,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.1,"slope_percent":20.0,"aspect_degrees":null,"elevation_ft":null}"#,
+        r#"{"plot_id":1,"tree_id":1,"species_code":"WRC","species_name":"Western Red Cedar","dbh":22.0,"height":null,"crown_ratio":null,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":1200.0}"#,
+        "",
+        r#"{"plot_id":2,"tree_id":2,"species_code":"DF","species_name":"Douglas Fir","dbh":9.5,"height":60.0,"crown_ratio":0.4,"status":"Dead","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":0.1,"slope_percent":20.0,"aspect_degrees":null,"elevation_ft":null}"#,
+        r#"{"plot_id":1,"tree_id":2,"species_code":"DF","species_name":"Douglas Fir","dbh":16.0,"height":100.0,"crown_ratio":0.6,"status":"Live","expansion_factor":5.0,"age":40,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":1200.0}"#,
+    ];
+    std::fs::write(&jsonl_path, lines.join("\n")).unwrap();
+
+    let json_path = dir.path().join("whole.json");
+    std::fs::write(
+        &json_path,
+        r#"{"name":"whole","plots":[
+            {"plot_id":1,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":1200.0,"trees":[
+                {"tree_id":1,"plot_id":1,"species":{"common_name":"Western Red Cedar","code":"WRC"},"dbh":22.0,"height":null,"crown_ratio":null,"status":"Live","expansion_factor":5.0,"age":null,"defect":null},
+                {"tree_id":2,"plot_id":1,"species":{"common_name":"Douglas Fir","code":"DF"},"dbh":16.0,"height":100.0,"crown_ratio":0.6,"status":"Live","expansion_factor":5.0,"age":40,"defect":null}]},
+            {"plot_id":2,"plot_size_acres":0.1,"slope_percent":20.0,"aspect_degrees":null,"elevation_ft":null,"trees":[
+                {"tree_id":1,"plot_id":2,"species":{"common_name":"Douglas Fir","code":"DF"},"dbh":14.0,"height":90.0,"crown_ratio":0.5,"status":"Live","expansion_factor":5.0,"age":null,"defect":null},
+                {"tree_id":2,"plot_id":2,"species":{"common_name":"Douglas Fir","code":"DF"},"dbh":9.5,"height":60.0,"crown_ratio":0.4,"status":"Dead","expansion_factor":5.0,"age":null,"defect":null}]}
+        ]}"#,
+    )
+    .unwrap();
+
+    let streamed = io::read_jsonl(&jsonl_path).unwrap();
+    let whole = io::read_json(&json_path).unwrap();
+    assert_eq!(streamed.name, "stream");
+    assert_eq!(streamed.num_plots(), 2);
+    assert_eq!(streamed.num_trees(), 4);
+    let ids = |inv: &ForestInventory| -> Vec<(u32, Vec<u32>)> {
+        inv.plots
+            .iter()
+            .map(|p| (p.plot_id, p.trees.iter().map(|t| t.tree_id).collect()))
+            .collect()
+    };
+    assert_eq!(ids(&streamed), ids(&whole));
+    assert_eq!(
+        streamed.content_hash().unwrap(),
+        whole.content_hash().unwrap()
+    );
+
+    // read_file picks the reader by extension
+    assert_eq!(
+        io::read_file(&jsonl_path).unwrap().content_hash().unwrap(),
+        whole.content_hash().unwrap()
+    );
+}
+
+#[test]
+fn test_jsonl_reports_every_malformed_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bad.jsonl");
+    std::fs::write(
+        &path,
+        concat!(
+            r#"{"plot_id":1,"tree_id":1,"species_code":"DF","species_name":"Douglas Fir","dbh":14.0,"height":null,"crown_ratio":null,"status":"Live","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":null,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null}"#,
+            "\n{not json\n",
+            r#"{"plot_id":1,"tree_id":2,"species_code":"DF","species_name":"Douglas Fir","dbh":14.0,"height":null,"crown_ratio":null,"status":"Sleeping","expansion_factor":5.0,"age":null,"defect":null,"plot_size_acres":null,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    let err = io::read_jsonl(&path).unwrap_err();
+    assert!(matches!(err, ForestError::ParseError(_)), "{err:?}");
+    let msg = err.to_string();
+    assert!(msg.contains("2 malformed line(s)"), "{msg}");
+    assert!(msg.contains("line 2:"), "{msg}");
+    assert!(msg.contains("line 3:"), "{msg}");
+}
+
+#[test]
+fn test_expansion_kind_survives_json_long_and_jsonl() {
+    let mut inventory = create_test_inventory();
+    inventory.expansion_kind = ExpansionKind::CountOnPlot;
+
+    let dir = tempfile::tempdir().unwrap();
+    let json_path = dir.path().join("long.json");
+    io::write_json_long(&inventory, &json_path).unwrap();
+    let loaded = io::read_json_long(&json_path).unwrap();
+    assert_eq!(loaded.expansion_kind, ExpansionKind::CountOnPlot);
+    assert!((loaded.mean_tpa() - inventory.mean_tpa()).abs() < 1e-9);
+
+    let row = |tree_id: u32, kind: &str| {
+        format!(
+            r#"{{"plot_id":1,"tree_id":{tree_id},"species_code":"DF","species_name":"Douglas Fir","dbh":14.0,"height":null,"crown_ratio":null,"status":"Live","expansion_factor":1.0,"age":null,"defect":null,"plot_size_acres":0.2,"slope_percent":null,"aspect_degrees":null,"elevation_ft":null,"expansion_kind":"{kind}"}}"#
+        )
+    };
+    let jsonl_path = dir.path().join("counts.jsonl");
+    let lines = [row(1, "count_on_plot"), row(2, "count_on_plot")];
+    std::fs::write(&jsonl_path, lines.join("\n")).unwrap();
+    let loaded = io::read_jsonl(&jsonl_path).unwrap();
+    assert_eq!(loaded.expansion_kind, ExpansionKind::CountOnPlot);
+    // Two trees counted on a 0.2-acre plot
+    assert!((loaded.mean_tpa() - 10.0).abs() < 1e-9);
+
+    // Rows that disagree are rejected rather than guessed at
+    let lines = [row(1, "count_on_plot"), row(2, "per_acre")];
+    std::fs::write(&jsonl_path, lines.join("\n")).unwrap();
+    let err = io::read_jsonl(&jsonl_path).unwrap_err();
+    assert!(err.to_string().contains("row 2"), "{err}");
+}
+
 #[test]
 fn test_json_compact_roundtrip() {
     let inventory = create_test_inventory();
@@ -473,6 +1018,28 @@ fn test_json_compact_roundtrip() {
     assert_eq!(loaded.num_trees(), inventory.num_trees());
 }
 
+#[test]
+fn test_json_to_writer_roundtrip() {
+    let inventory = create_test_inventory();
+
+    let mut buf: Vec<u8> = Vec::new();
+    io::write_json_to(&inventory, &mut buf, false).unwrap();
+    let loaded = io::read_json_from_bytes(&buf, &inventory.name).unwrap();
+    assert_eq!(loaded.num_trees(), inventory.num_trees());
+    assert_eq!(
+        loaded.content_hash().unwrap(),
+        inventory.content_hash().unwrap()
+    );
+
+    // Same bytes as the file writer
+    let dir = tempfile::tempdir().unwrap();
+    let json_path = dir.path().join("pretty.json");
+    io::write_json(&inventory, &json_path, true).unwrap();
+    let mut pretty: Vec<u8> = Vec::new();
+    io::write_json_to(&inventory, &mut pretty, true).unwrap();
+    assert_eq!(std::fs::read(&json_path).unwrap(), pretty);
+}
+
 #[test]
 fn test_json_preserves_volumes() {
     let inventory = create_test_inventory();
@@ -487,6 +1054,31 @@ fn test_json_preserves_volumes() {
     assert!((loaded.mean_volume_bdft() - inventory.mean_volume_bdft()).abs() < 0.001);
 }
 
+#[test]
+fn test_json_metadata_roundtrip() {
+    let inventory = create_test_inventory();
+    let dir = tempfile::tempdir().unwrap();
+    let json_path = dir.path().join("meta.json");
+
+    let meta = io::ExportMetadata::new(&inventory, None).unwrap();
+    io::write_json_with_metadata(&inventory, &json_path, true, &meta).unwrap();
+
+    let content = std::fs::read_to_string(&json_path).unwrap();
+    assert!(content.contains("\"_meta\""));
+
+    // "_meta" is ignored when loading the inventory
+    let loaded = io::read_json(&json_path).unwrap();
+    assert_eq!(loaded.num_trees(), inventory.num_trees());
+
+    let read_meta = io::read_json_metadata(&json_path).unwrap().unwrap();
+    assert_eq!(read_meta, meta);
+    assert!(read_meta.matches(&loaded).unwrap());
+
+    let mut edited = loaded.clone();
+    edited.plots[0].trees[0].dbh += 1.0;
+    assert!(!read_meta.matches(&edited).unwrap());
+}
+
 // ============================================================================
 // Excel I/O integration tests
 // ============================================================================
@@ -505,6 +1097,39 @@ fn test_excel_roundtrip() {
     assert_eq!(loaded.num_trees(), inventory.num_trees());
 }
 
+#[test]
+fn test_writers_replace_existing_files_without_leftovers() {
+    let inventory = create_test_inventory();
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("out.csv");
+    let json_path = dir.path().join("out.json");
+    let xlsx_path = dir.path().join("out.xlsx");
+    for path in [&csv_path, &json_path, &xlsx_path] {
+        std::fs::write(path, "stale contents that are longer than nothing").unwrap();
+    }
+
+    io::write_csv(&inventory, &csv_path).unwrap();
+    io::write_json(&inventory, &json_path, false).unwrap();
+    io::write_excel(&inventory, &xlsx_path).unwrap();
+
+    assert_eq!(io::read_csv(&csv_path).unwrap().num_trees(), inventory.num_trees());
+    assert_eq!(io::read_json(&json_path).unwrap().num_trees(), inventory.num_trees());
+    assert_eq!(io::read_excel(&xlsx_path).unwrap().num_trees(), inventory.num_trees());
+
+    // Only the three outputs remain; no temp files are left behind
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
+}
+
+#[test]
+fn test_write_into_missing_directory_fails_cleanly() {
+    let inventory = create_test_inventory();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("no_such_dir").join("out.csv");
+
+    assert!(io::write_csv(&inventory, &path).is_err());
+    assert!(!path.exists());
+}
+
 #[test]
 fn test_excel_preserves_metrics() {
     let inventory = create_test_inventory();
@@ -520,6 +1145,233 @@ fn test_excel_preserves_metrics() {
     assert!((orig_ba - loaded_ba).abs() < 0.1);
 }
 
+#[test]
+fn test_excel_shuffled_columns() {
+    use rust_xlsxwriter::Workbook;
+
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("shuffled.xlsx");
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    let headers = [
+        " DBH ",
+        "Status",
+        "species_name",
+        "expansion_factor",
+        "height",
+        "TREE_ID",
+        "species_code",
+        "plot_size_acres",
+        "plot_id",
+    ];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col as u16, *header).unwrap();
+    }
+    sheet.write_number(1, 0, 14.5).unwrap();
+    sheet.write_string(1, 1, "Live").unwrap();
+    sheet.write_string(1, 2, "Douglas Fir").unwrap();
+    sheet.write_number(1, 3, 5.0).unwrap();
+    sheet.write_number(1, 4, 95.0).unwrap();
+    sheet.write_number(1, 5, 7.0).unwrap();
+    sheet.write_string(1, 6, "DF").unwrap();
+    sheet.write_number(1, 7, 0.25).unwrap();
+    sheet.write_number(1, 8, 3.0).unwrap();
+    workbook.save(&xlsx_path).unwrap();
+
+    let loaded = io::read_excel(&xlsx_path).unwrap();
+    assert_eq!(loaded.num_plots(), 1);
+    let plot = &loaded.plots[0];
+    assert_eq!(plot.plot_id, 3);
+    assert!((plot.plot_size_acres - 0.25).abs() < 1e-9);
+    assert!(plot.slope_percent.is_none());
+
+    let tree = &plot.trees[0];
+    assert_eq!(tree.tree_id, 7);
+    assert_eq!(tree.species.code, "DF");
+    assert_eq!(tree.species.common_name, "Douglas Fir");
+    assert!((tree.dbh - 14.5).abs() < 1e-9);
+    assert_eq!(tree.height, Some(95.0));
+    assert_eq!(tree.crown_ratio, None);
+    assert_eq!(tree.status, TreeStatus::Live);
+    assert!((tree.expansion_factor - 5.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_excel_missing_required_column() {
+    use rust_xlsxwriter::Workbook;
+
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("missing_col.xlsx");
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    for (col, header) in ["plot_id", "tree_id", "species_code", "dbh"]
+        .iter()
+        .enumerate()
+    {
+        sheet.write_string(0, col as u16, *header).unwrap();
+    }
+    workbook.save(&xlsx_path).unwrap();
+
+    let err = io::read_excel(&xlsx_path).unwrap_err();
+    assert!(err.to_string().contains("species_name"));
+}
+
+#[test]
+fn test_excel_date_cell_measurement_date() {
+    use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("dated.xlsx");
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    let headers = [
+        "plot_id",
+        "tree_id",
+        "species_code",
+        "species_name",
+        "dbh",
+        "status",
+        "expansion_factor",
+        "measurement_date",
+    ];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col as u16, *header).unwrap();
+    }
+    sheet.write_number(1, 0, 1.0).unwrap();
+    sheet.write_number(1, 1, 1.0).unwrap();
+    sheet.write_string(1, 2, "DF").unwrap();
+    sheet.write_string(1, 3, "Douglas Fir").unwrap();
+    sheet.write_number(1, 4, 14.0).unwrap();
+    sheet.write_string(1, 5, "Live").unwrap();
+    sheet.write_number(1, 6, 5.0).unwrap();
+    let date = ExcelDateTime::from_ymd(2024, 6, 1).unwrap();
+    let format = Format::new().set_num_format("yyyy-mm-dd");
+    sheet
+        .write_datetime_with_format(1, 7, &date, &format)
+        .unwrap();
+    workbook.save(&xlsx_path).unwrap();
+
+    let loaded = io::read_excel(&xlsx_path).unwrap();
+    assert_eq!(
+        loaded.plots[0].measurement_date.as_deref(),
+        Some("2024-06-01")
+    );
+
+    // The lenient reader converts it too, so no malformed-date warning
+    let report = io::validate_file(&xlsx_path, io::CsvEncoding::Utf8).unwrap();
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+}
+
+/// Write a one-tree sheet whose DBH and expansion factor cells are text.
+fn write_text_number_sheet(path: &std::path::Path, dbh: &str) {
+    use rust_xlsxwriter::Workbook;
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    let headers = [
+        "plot_id",
+        "tree_id",
+        "species_code",
+        "species_name",
+        "dbh",
+        "status",
+        "expansion_factor",
+    ];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col as u16, *header).unwrap();
+    }
+    sheet.write_number(1, 0, 1.0).unwrap();
+    sheet.write_number(1, 1, 1.0).unwrap();
+    sheet.write_string(1, 2, "DF").unwrap();
+    sheet.write_string(1, 3, "Douglas Fir").unwrap();
+    sheet.write_string(1, 4, dbh).unwrap();
+    sheet.write_string(1, 5, "Live").unwrap();
+    sheet.write_string(1, 6, " 5 ").unwrap();
+    workbook.save(path).unwrap();
+}
+
+#[test]
+fn test_excel_numbers_stored_as_text() {
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("text_numbers.xlsx");
+    write_text_number_sheet(&xlsx_path, "14.0");
+
+    let loaded = io::read_excel(&xlsx_path).unwrap();
+    let tree = &loaded.plots[0].trees[0];
+    assert_eq!(tree.dbh, 14.0);
+    assert_eq!(tree.expansion_factor, 5.0);
+
+    let report = io::validate_file(&xlsx_path, io::CsvEncoding::Utf8).unwrap();
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn test_excel_numbers_stored_as_text_are_warnings() {
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("text_numbers.xlsx");
+    write_text_number_sheet(&xlsx_path, "14.0");
+
+    let report = io::validate_file(&xlsx_path, io::CsvEncoding::Utf8).unwrap();
+    let mut fields: Vec<&str> = report
+        .issues
+        .iter()
+        .filter(|i| i.severity == io::Severity::Warning)
+        .map(|i| i.issue.field.as_ref())
+        .collect();
+    fields.sort_unstable();
+    assert_eq!(fields, ["dbh", "expansion_factor"]);
+    assert!(report.issues[0].issue.message.contains("stored as text"));
+}
+
+#[test]
+fn test_excel_non_numeric_text_names_row_and_column() {
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("bad_number.xlsx");
+    write_text_number_sheet(&xlsx_path, "14 in");
+
+    let err = io::read_excel(&xlsx_path).unwrap_err().to_string();
+    assert!(err.contains("bad_number.xlsx:2: "), "{err}");
+    assert!(err.contains("Row 2, column 'dbh'"), "{err}");
+    assert!(err.contains("'14 in' is not a number"), "{err}");
+
+    let report = io::validate_file(&xlsx_path, io::CsvEncoding::Utf8).unwrap();
+    assert!(report
+        .issues
+        .iter()
+        .any(|i| i.issue.field == "dbh" && i.issue.message.contains("'14 in'")));
+}
+
+#[test]
+fn test_excel_metadata_roundtrip() {
+    let inventory = create_test_inventory();
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("meta.xlsx");
+
+    let meta = io::ExportMetadata::new(&inventory, Some("source.csv")).unwrap();
+    io::write_excel_with_metadata(&inventory, &xlsx_path, &meta).unwrap();
+
+    // Metadata sheet is ignored by the data reader
+    let loaded = io::read_excel(&xlsx_path).unwrap();
+    assert_eq!(loaded.num_trees(), inventory.num_trees());
+
+    let read_meta = io::read_excel_metadata(&xlsx_path).unwrap().unwrap();
+    assert_eq!(read_meta, meta);
+    assert!(read_meta.matches(&loaded).unwrap());
+}
+
+#[test]
+fn test_excel_metadata_absent() {
+    let inventory = create_test_inventory();
+    let dir = tempfile::tempdir().unwrap();
+    let xlsx_path = dir.path().join("plain.xlsx");
+
+    io::write_excel(&inventory, &xlsx_path).unwrap();
+    assert!(io::read_excel_metadata(&xlsx_path).unwrap().is_none());
+}
+
 // ============================================================================
 // Format conversion integration tests
 // ============================================================================
@@ -676,8 +1528,14 @@ fn test_single_plot_inventory() {
             expansion_factor: 5.0,
             age: Some(60),
             defect: None,
+            decay_class: None,
         }],
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
     });
 
     let metrics = compute_stand_metrics(&inventory);
@@ -711,8 +1569,14 @@ fn test_inventory_all_optional_fields_none() {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }],
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
     });
 
     let metrics = compute_stand_metrics(&inventory);
@@ -753,6 +1617,7 @@ fn test_large_inventory() {
                 expansion_factor: 4.0 + plot_id as f64 * 0.1,
                 age: Some(50 + tree_id),
                 defect: None,
+                decay_class: None,
             });
         }
         inventory.plots.push(Plot {
@@ -763,6 +1628,11 @@ fn test_large_inventory() {
             elevation_ft: Some(2500.0),
             trees,
             stand_id: None,
+            source: None,
+            measurement_date: None,
+            latitude: None,
+            longitude: None,
+            stratum: None,
         });
     }
 
@@ -828,6 +1698,56 @@ fn write_and_read_csv(
     io::read_csv(&csv_path)
 }
 
+#[test]
+fn test_read_errors_name_file_and_line() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // Header on line 1, a comment on line 2, the bad row on line 4
+    let csv_path = dir.path().join("cruise.csv");
+    std::fs::write(
+        &csv_path,
+        "plot_id,tree_id,species_code,species_name,dbh,status,expansion_factor\n\
+         # crew B\n\
+         1,1,DF,Douglas Fir,14,Live,5\n\
+         1,2,DF,Douglas Fir,-3,Live,5\n",
+    )
+    .unwrap();
+    let err = io::read_csv(&csv_path).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.starts_with("error in "), "{msg}");
+    assert!(msg.contains("cruise.csv:4: "), "{msg}");
+    assert!(msg.contains("DBH must be positive"), "{msg}");
+    assert!(matches!(
+        err,
+        ForestError::WithContext { line: Some(4), .. }
+    ));
+    assert!(matches!(
+        err.without_context(),
+        ForestError::ValidationError(_)
+    ));
+
+    // A field that fails to parse is located too
+    std::fs::write(
+        &csv_path,
+        "plot_id,tree_id,species_code,species_name,dbh,status,expansion_factor\n\
+         1,1,DF,Douglas Fir,14 in,Live,5\n",
+    )
+    .unwrap();
+    let msg = io::read_csv(&csv_path).unwrap_err().to_string();
+    assert!(msg.contains("cruise.csv:2: CSV error"), "{msg}");
+
+    // A missing file names the path without a line
+    let missing = dir.path().join("missing.csv");
+    let msg = io::read_csv(&missing).unwrap_err().to_string();
+    assert!(msg.contains("missing.csv: IO error"), "{msg}");
+
+    let json_path = dir.path().join("cruise.json");
+    let json = "{\n  \"name\": \"Cruise\",\n  \"plots\": [,]\n}\n";
+    std::fs::write(&json_path, json).unwrap();
+    let msg = io::read_json(&json_path).unwrap_err().to_string();
+    assert!(msg.contains("cruise.json:3: JSON error"), "{msg}");
+}
+
 #[test]
 fn test_csv_rejects_negative_dbh() {
     let result = write_and_read_csv(-5.0, "80", "0.5", 5.0, "");
@@ -928,8 +1848,14 @@ fn test_json_rejects_invalid_data() {
             expansion_factor: 5.0,
             age: Some(60),
             defect: None,
+            decay_class: None,
         }],
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
     });
 
     let dir = tempfile::tempdir().unwrap();
@@ -959,6 +1885,49 @@ fn test_csv_with_utf8_bom() {
     assert_eq!(inv.plots[0].trees[0].dbh, 14.0);
 }
 
+#[test]
+fn test_csv_file_with_utf8_bom_header() {
+    let csv_content = "\u{FEFF}plot_id,tree_id,species_code,species_name,dbh,height,crown_ratio,status,expansion_factor,age,defect,plot_size_acres,slope_percent,aspect_degrees,elevation_ft\n\
+                        7,1,DF,Douglas Fir,14.0,90.0,0.5,Live,5.0,60,,0.2,15,180,3200";
+    let dir = tempfile::tempdir().unwrap();
+    let csv_path = dir.path().join("bom.csv");
+    std::fs::write(&csv_path, csv_content).unwrap();
+
+    let inv = io::read_csv(&csv_path).unwrap();
+    assert_eq!(inv.name, "bom");
+    assert_eq!(inv.plots[0].plot_id, 7);
+}
+
+#[test]
+fn test_csv_windows_1252_species_name() {
+    // "Épinette" (spruce) encoded in Windows-1252: 0xC9 = 'É'
+    let mut csv_content = b"plot_id,tree_id,species_code,species_name,dbh,height,crown_ratio,status,expansion_factor,age,defect,plot_size_acres,slope_percent,aspect_degrees,elevation_ft\n1,1,EP,".to_vec();
+    csv_content.extend_from_slice(b"\xC9pinette blanche");
+    csv_content.extend_from_slice(b",14.0,90.0,0.5,Live,5.0,60,,0.2,15,180,3200\n");
+
+    // Strict UTF-8 (default) rejects it with a hint
+    let err = io::read_csv_from_bytes(&csv_content, "cp1252").unwrap_err();
+    assert!(err.to_string().contains("windows-1252"));
+
+    let inv =
+        io::read_csv_from_bytes_with_encoding(&csv_content, "cp1252", io::CsvEncoding::Windows1252)
+            .unwrap();
+    assert_eq!(inv.plots[0].trees[0].species.common_name, "Épinette blanche");
+}
+
+#[test]
+fn test_csv_encoding_from_str() {
+    assert_eq!(
+        "UTF-8".parse::<io::CsvEncoding>().unwrap(),
+        io::CsvEncoding::Utf8
+    );
+    assert_eq!(
+        "latin1".parse::<io::CsvEncoding>().unwrap(),
+        io::CsvEncoding::Windows1252
+    );
+    assert!("ebcdic".parse::<io::CsvEncoding>().is_err());
+}
+
 #[test]
 fn test_csv_with_windows_line_endings() {
     let csv_content = "plot_id,tree_id,species_code,species_name,dbh,height,crown_ratio,status,expansion_factor,age,defect,plot_size_acres,slope_percent,aspect_degrees,elevation_ft\r\n\
@@ -1155,8 +2124,14 @@ fn test_csv_roundtrip_preserves_optional_none_values() {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         }],
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
     });
 
     let dir = tempfile::tempdir().unwrap();
@@ -6,6 +6,7 @@ use forest_inventory_analyzer::{
         SamplingStatistics,
     },
     models::{ForestInventory, Plot, Species, Tree, TreeStatus},
+    testing::{generate_stand, GenConfig},
 };
 
 /// Strategy to generate a valid Species.
@@ -49,6 +50,7 @@ fn arb_tree(plot_id: u32) -> impl Strategy<Value = Tree> {
             expansion_factor: ef,
             age: None,
             defect: None,
+            decay_class: None,
         })
 }
 
@@ -62,6 +64,11 @@ fn arb_plot(plot_id: u32) -> impl Strategy<Value = Plot> {
         elevation_ft: None,
         trees,
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
     })
 }
 
@@ -92,6 +99,7 @@ proptest! {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         };
         let ba = tree.basal_area_sqft();
         prop_assert!(ba >= 0.0, "basal area was negative: {} for dbh {}", ba, dbh);
@@ -115,6 +123,7 @@ proptest! {
             expansion_factor: 5.0,
             age: None,
             defect: None,
+            decay_class: None,
         };
         if let Some(vol) = tree.volume_cuft() {
             prop_assert!(vol >= 0.0, "cubic ft volume was negative: {} for dbh={}, ht={}", vol, dbh, height);
@@ -218,3 +227,68 @@ proptest! {
         }
     }
 }
+
+/// Strategy for realistic generated stands of varying size and structure.
+fn arb_generated_stand() -> impl Strategy<Value = ForestInventory> {
+    (
+        2u32..=12,
+        1u32..=15,
+        4.0f64..30.0,
+        0.5f64..10.0,
+        any::<u64>(),
+    )
+        .prop_map(|(plots, trees_per_plot, dbh_mean, dbh_sd, seed)| {
+            generate_stand(GenConfig {
+                plots,
+                trees_per_plot,
+                dbh_mean,
+                dbh_sd,
+                seed: Some(seed.into()),
+                ..GenConfig::default()
+            })
+        })
+}
+
+proptest! {
+    // --- Stand metrics on generated stands are finite and non-negative ---
+    #[test]
+    fn generated_stand_metrics_non_negative(ref inv in arb_generated_stand()) {
+        let metrics = compute_stand_metrics(inv);
+        for (key, value) in metrics.to_flat_map() {
+            prop_assert!(value.is_finite() && value >= 0.0, "{} was {}", key, value);
+        }
+    }
+
+    // --- Species percentages on generated stands sum to 100% ---
+    #[test]
+    fn generated_stand_species_percentages_sum_to_100(ref inv in arb_generated_stand()) {
+        let metrics = compute_stand_metrics(inv);
+        prop_assume!(!metrics.species_composition.is_empty());
+        let tpa_pct: f64 = metrics.species_composition.iter().map(|s| s.percent_tpa).sum();
+        let ba_pct: f64 = metrics.species_composition.iter().map(|s| s.percent_basal_area).sum();
+        prop_assert!((tpa_pct - 100.0).abs() < 1e-6, "TPA percentages sum to {}", tpa_pct);
+        prop_assert!((ba_pct - 100.0).abs() < 1e-6, "BA percentages sum to {}", ba_pct);
+    }
+
+    // --- Diameter classes on generated stands account for all live TPA ---
+    #[test]
+    fn generated_stand_distribution_matches_total_tpa(ref inv in arb_generated_stand()) {
+        let dist = DiameterDistribution::from_inventory(inv, 2.0);
+        let class_tpa: f64 = dist.classes.iter().map(|c| c.tpa).sum();
+        let total_tpa = compute_stand_metrics(inv).total_tpa;
+        prop_assert!(
+            (class_tpa - total_tpa).abs() < 1e-6,
+            "classes sum to {} but total TPA is {}", class_tpa, total_tpa
+        );
+    }
+
+    // --- Sampling statistics on generated stands bracket the mean ---
+    #[test]
+    fn generated_stand_confidence_interval_contains_mean(ref inv in arb_generated_stand()) {
+        let stats = SamplingStatistics::compute(inv, 0.95).unwrap();
+        for ci in [&stats.tpa, &stats.basal_area, &stats.volume_cuft, &stats.volume_bdft] {
+            prop_assert!(ci.lower <= ci.mean && ci.mean <= ci.upper);
+        }
+        prop_assert!((stats.tpa.mean - inv.mean_tpa()).abs() < 1e-9);
+    }
+}
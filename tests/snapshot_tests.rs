@@ -29,6 +29,11 @@ fn deterministic_inventory() -> ForestInventory {
         aspect_degrees: Some(180.0),
         elevation_ft: Some(3000.0),
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
         trees: vec![
             Tree {
                 tree_id: 1,
@@ -41,6 +46,7 @@ fn deterministic_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
             Tree {
                 tree_id: 2,
@@ -53,6 +59,7 @@ fn deterministic_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
         ],
     });
@@ -74,6 +81,7 @@ fn deterministic_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
             Tree {
                 tree_id: 4,
@@ -86,9 +94,15 @@ fn deterministic_inventory() -> ForestInventory {
                 expansion_factor: 5.0,
                 age: None,
                 defect: None,
+                decay_class: None,
             },
         ],
         stand_id: None,
+        source: None,
+        measurement_date: None,
+        latitude: None,
+        longitude: None,
+        stratum: None,
     });
     inv
 }
@@ -110,6 +124,7 @@ fn deterministic_statistics() -> SamplingStatistics {
             confidence_level: 0.95,
             sample_size: 2,
             sampling_error_percent: 63.7,
+            cv_percent: 7.1,
         },
         basal_area: ConfidenceInterval {
             mean: 7.1,
@@ -119,6 +134,7 @@ fn deterministic_statistics() -> SamplingStatistics {
             confidence_level: 0.95,
             sample_size: 2,
             sampling_error_percent: 133.8,
+            cv_percent: 14.9,
         },
         volume_cuft: ConfidenceInterval {
             mean: 250.0,
@@ -128,6 +144,7 @@ fn deterministic_statistics() -> SamplingStatistics {
             confidence_level: 0.95,
             sample_size: 2,
             sampling_error_percent: 127.0,
+            cv_percent: 14.1,
         },
         volume_bdft: ConfidenceInterval {
             mean: 1200.0,
@@ -137,6 +154,7 @@ fn deterministic_statistics() -> SamplingStatistics {
             confidence_level: 0.95,
             sample_size: 2,
             sampling_error_percent: 127.0,
+            cv_percent: 14.1,
         },
     }
 }
@@ -150,6 +168,7 @@ fn deterministic_projections() -> Vec<GrowthProjection> {
             basal_area: 7.1,
             volume_cuft: 250.0,
             volume_bdft: 1200.0,
+            recruited_tpa: 0.0,
         },
         GrowthProjection {
             year: 5,
@@ -157,6 +176,7 @@ fn deterministic_projections() -> Vec<GrowthProjection> {
             basal_area: 8.2,
             volume_cuft: 289.5,
             volume_bdft: 1389.7,
+            recruited_tpa: 0.0,
         },
         GrowthProjection {
             year: 10,
@@ -164,6 +184,7 @@ fn deterministic_projections() -> Vec<GrowthProjection> {
             basal_area: 9.5,
             volume_cuft: 335.2,
             volume_bdft: 1609.4,
+            recruited_tpa: 0.0,
         },
         GrowthProjection {
             year: 15,
@@ -171,6 +192,7 @@ fn deterministic_projections() -> Vec<GrowthProjection> {
             basal_area: 11.0,
             volume_cuft: 387.8,
             volume_bdft: 1862.3,
+            recruited_tpa: 0.0,
         },
         GrowthProjection {
             year: 20,
@@ -178,6 +200,7 @@ fn deterministic_projections() -> Vec<GrowthProjection> {
             basal_area: 12.7,
             volume_cuft: 448.7,
             volume_bdft: 2155.0,
+            recruited_tpa: 0.0,
         },
     ]
 }
@@ -194,6 +217,7 @@ fn deterministic_distribution() -> DiameterDistribution {
                 tpa: 5.0,
                 basal_area: 3.9,
                 tree_count: 2,
+                tpa_ci: None,
             },
             DiameterClass {
                 lower: 14.0,
@@ -202,6 +226,7 @@ fn deterministic_distribution() -> DiameterDistribution {
                 tpa: 2.5,
                 basal_area: 2.7,
                 tree_count: 1,
+                tpa_ci: None,
             },
             DiameterClass {
                 lower: 16.0,
@@ -210,6 +235,7 @@ fn deterministic_distribution() -> DiameterDistribution {
                 tpa: 2.5,
                 basal_area: 3.5,
                 tree_count: 1,
+                tpa_ci: None,
             },
             DiameterClass {
                 lower: 18.0,
@@ -218,6 +244,7 @@ fn deterministic_distribution() -> DiameterDistribution {
                 tpa: 2.5,
                 basal_area: 4.4,
                 tree_count: 1,
+                tpa_ci: None,
             },
         ],
     }